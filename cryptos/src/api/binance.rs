@@ -1,2 +1,3 @@
 pub mod spot;
-pub mod futures;
\ No newline at end of file
+pub mod futures;
+pub mod delivery;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use axum::Router;
+
+use crate::common::*;
+use crate::api::jwt::*;
+use crate::api::jwe::*;
+use crate::api::binance::delivery::v1::symbols::*;
+use crate::api::binance::delivery::v1::tickers::*;
+use crate::api::binance::delivery::v1::klines::*;
+use crate::api::binance::delivery::v1::positions::*;
+
+mod symbols;
+mod tickers;
+mod klines;
+mod positions;
+
+pub struct V1Router {
+  ctx: Ctx,
+}
+
+impl V1Router {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub fn routes(&self) -> Router {
+    return Router::new()
+      .nest("/symbols", SymbolsRouter::new(self.ctx.clone()).routes())
+      .nest("/tickers", TickersRouter::new(self.ctx.clone()).routes())
+      .nest("/klines", KlinesRouter::new(self.ctx.clone()).routes())
+      .nest("/positions", PositionsRouter::new(self.ctx.clone()).routes())
+      .layer(AuthenticatorLayer::new())
+      .layer(EncryptionLayer::new());
+  }
+}
\ No newline at end of file
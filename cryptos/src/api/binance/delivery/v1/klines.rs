@@ -0,0 +1,81 @@
+use axum::{
+  extract::{State, Query},
+  routing::get,
+  http::StatusCode,
+  Json,
+  Router,
+};
+use serde::{Serialize, Deserialize};
+
+use crate::common::*;
+use crate::api::response::*;
+use crate::repositories::binance::delivery::klines::*;
+
+#[derive(Deserialize)]
+struct GetsRequest {
+  symbol: String,
+  interval: String,
+  limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct KlineInfo {
+  open: f64,
+  high: f64,
+  low: f64,
+  close: f64,
+  timestamp: i64,
+}
+
+pub struct KlinesRouter {
+  ctx: Ctx,
+}
+
+impl KlinesRouter {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  async fn gets(
+    State(ctx): State<Ctx>,
+    request: Query<GetsRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if Interval::parse(&request.interval).is_err() {
+      let message = ErrorMessage::new(false, "1004", "interval not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+    let limit = request.limit.unwrap_or(100);
+    if limit < 1 || limit > 1000 {
+      let message = ErrorMessage::new(false, "1004", "limit not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+
+    let series = match KlinesRepository::series(ctx.clone(), request.symbol.as_str(), request.interval.as_str(), limit).await {
+      Ok(result) => result,
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+    let response = RankingResponse{
+      success: true,
+      data: series.into_iter().rev().map(|(open, high, low, close, timestamp)| -> Box<dyn erased_serde::Serialize> { Box::new(KlineInfo{
+        open,
+        high,
+        low,
+        close,
+        timestamp,
+      })}).collect(),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  pub fn routes(&self) -> Router {
+    let ctx = self.ctx.clone();
+    return Router::new()
+      .route("/", get(Self::gets))
+      .with_state(ctx)
+  }
+}
\ No newline at end of file
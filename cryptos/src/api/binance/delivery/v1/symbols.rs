@@ -0,0 +1,134 @@
+use axum::{
+  extract::{State, Query},
+  routing::get,
+  http::StatusCode,
+  Json,
+  Router,
+};
+use chrono::prelude::Utc;
+use serde::{Serialize, Deserialize};
+
+use crate::common::*;
+use crate::api::response::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::symbols::*;
+
+#[derive(Deserialize)]
+struct ExpiringRequest {
+  days: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct ContractInfo {
+  symbol: String,
+  pair: String,
+  contract_type: String,
+  contract_size: f64,
+  delivery_date: i64,
+  rollover: String,
+}
+
+pub struct SymbolsRouter {
+  ctx: Ctx,
+}
+
+impl SymbolsRouter {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  async fn gets(
+    State(ctx): State<Ctx>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let symbols = match SymbolsRepository::listings(ctx.clone()).await {
+      Ok(result) => result,
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+
+    let mut contracts = Vec::new();
+    for symbol in symbols.iter() {
+      let entity = match SymbolsRepository::get(ctx.clone(), symbol).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => continue,
+        Err(_) => {
+          let message = ErrorMessage::new(false, "500", "database error");
+          return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+        }
+      };
+      contracts.push(ContractInfo{
+        symbol: entity.symbol,
+        pair: entity.pair,
+        contract_type: entity.contract_type,
+        contract_size: entity.contract_size,
+        delivery_date: entity.delivery_date,
+        rollover: "".to_string(),
+      });
+    }
+
+    let response = RankingResponse{
+      success: true,
+      data: contracts.into_iter().map(|x| -> Box<dyn erased_serde::Serialize> { Box::new(x) }).collect(),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  async fn expiring(
+    State(ctx): State<Ctx>,
+    request: Query<ExpiringRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let days = request.days.unwrap_or(Config::EXPIRY_ROLLOVER_DAYS);
+    if days < 0 || days > 180 {
+      let message = ErrorMessage::new(false, "1004", "days not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+
+    let timestamp = Utc::now().timestamp_millis() + days * 86400000;
+    let expiring = match SymbolsRepository::expiring(ctx.clone(), timestamp).await {
+      Ok(result) => result,
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+
+    let mut contracts = Vec::new();
+    for (symbol, pair, delivery_date) in expiring.into_iter() {
+      let entity = match SymbolsRepository::get(ctx.clone(), &symbol).await {
+        Ok(Some(entity)) => entity,
+        Ok(None) => continue,
+        Err(_) => {
+          let message = ErrorMessage::new(false, "500", "database error");
+          return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+        }
+      };
+      let rollover = SymbolsRepository::rollover(ctx.clone(), &symbol).await.unwrap_or(None).unwrap_or_default();
+      contracts.push(ContractInfo{
+        symbol,
+        pair,
+        contract_type: entity.contract_type,
+        contract_size: entity.contract_size,
+        delivery_date,
+        rollover,
+      });
+    }
+
+    let response = RankingResponse{
+      success: true,
+      data: contracts.into_iter().map(|x| -> Box<dyn erased_serde::Serialize> { Box::new(x) }).collect(),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  pub fn routes(&self) -> Router {
+    let ctx = self.ctx.clone();
+    return Router::new()
+      .route("/", get(Self::gets))
+      .route("/expiring", get(Self::expiring))
+      .with_state(ctx)
+  }
+}
\ No newline at end of file
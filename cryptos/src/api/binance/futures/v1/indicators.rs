@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::{
   extract::{State, Query},
   routing::get,
@@ -5,12 +7,15 @@ use axum::{
   Json,
   Router,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::*;
 use crate::api::response::*;
+use crate::models::binance::param::*;
+use crate::repositories::binance::params::*;
 use crate::repositories::binance::futures::scalping::*;
 use crate::repositories::binance::futures::indicators::*;
+use crate::repositories::binance::futures::indicators::history::*;
 
 #[derive(Deserialize)]
 struct GetsRequest {
@@ -29,6 +34,40 @@ struct RankingRequest {
   page_size: u32,
 }
 
+#[derive(Deserialize)]
+struct HistoryRequest {
+  symbol: String,
+  interval: String,
+  start_time: i64,
+  end_time: i64,
+  fields: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct HistoryInfo {
+  timestamp: i64,
+  values: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ParamsRequest {
+  symbol: Option<String>,
+  interval: String,
+}
+
+#[derive(Deserialize)]
+struct ParamsUpdateRequest {
+  symbol: Option<String>,
+  interval: String,
+  values: Values,
+}
+
+#[derive(Serialize)]
+pub struct ParamsInfo {
+  params: IndicatorParams,
+  values: Values,
+}
+
 pub struct IndicatorsRouter {
   ctx: Ctx,
 }
@@ -138,11 +177,160 @@ impl IndicatorsRouter {
     Ok(Json(serde_json::json!(response)))
   }
 
+  async fn history(
+    State(ctx): State<Ctx>,
+    request: Query<HistoryRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if request.symbol == "" {
+      let message = ErrorMessage::new(false, "1004", "symbol is empty");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+    if let Err(_) = Interval::parse(&request.interval) {
+      let message = ErrorMessage::new(false, "1004", "interval not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+    if request.start_time > request.end_time {
+      let message = ErrorMessage::new(false, "1004", "time range not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+
+    let fields = match &request.fields {
+      Some(value) if value != "" => value.split(',').map(|s| s.to_string()).collect(),
+      _ => vec![],
+    };
+
+    let items = match HistoryRepository::range(
+      ctx.clone(),
+      request.symbol.as_str(),
+      request.interval.as_str(),
+      request.start_time,
+      request.end_time,
+      1000,
+    ).await {
+      Ok(result) => result,
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+
+    let response = RankingResponse{
+      success: true,
+      data: items.into_iter().map(|(timestamp, mut item)| -> Box<dyn erased_serde::Serialize> { Box::new({
+        if !fields.is_empty() {
+          item.values.retain(|field, _| fields.contains(field));
+        }
+        HistoryInfo{
+          timestamp,
+          values: item.values,
+        }
+      }) }).collect(),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  async fn params(
+    State(ctx): State<Ctx>,
+    request: Query<ParamsRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if let Err(_) = Interval::parse(&request.interval) {
+      let message = ErrorMessage::new(false, "1004", "interval not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+    let symbol = match &request.symbol {
+      Some(value) => value.clone(),
+      None => "".to_owned(),
+    };
+
+    let params = match ParamsRepository::get(ctx.clone(), "futures", symbol.as_str(), request.interval.as_str()).await {
+      Ok(result) => result,
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+    let values = match ParamsRepository::find(ctx.clone(), "futures", symbol.as_str(), request.interval.as_str()).await {
+      Ok(Some(result)) => result.values,
+      Ok(None) => Values::default(),
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+
+    let response = SuccessResponse{
+      success: true,
+      data: Box::new(ParamsInfo{
+        params,
+        values,
+      }),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  async fn update_params(
+    State(ctx): State<Ctx>,
+    Json(request): Json<ParamsUpdateRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    if let Err(_) = Interval::parse(&request.interval) {
+      let message = ErrorMessage::new(false, "1004", "interval not valid");
+      return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+    }
+    let symbol = match &request.symbol {
+      Some(value) => value.clone(),
+      None => "".to_owned(),
+    };
+
+    let values = match ParamsRepository::set(ctx.clone(), "futures", symbol.as_str(), request.interval.as_str(), request.values).await {
+      Ok(result) => result,
+      Err(err) => {
+        let message = ErrorMessage::new(false, "1004".to_string(), err.to_string());
+        return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+      }
+    };
+
+    let response = SuccessResponse{
+      success: true,
+      data: Box::new(values),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
+  async fn delete_params(
+    State(ctx): State<Ctx>,
+    request: Query<ParamsRequest>,
+  ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let symbol = match &request.symbol {
+      Some(value) => value.clone(),
+      None => "".to_owned(),
+    };
+
+    match ParamsRepository::delete(ctx.clone(), "futures", symbol.as_str(), request.interval.as_str()).await {
+      Ok(true) => (),
+      Ok(false) => {
+        let message = ErrorMessage::new(false, "1004", "params not exists");
+        return Err((StatusCode::FORBIDDEN, Json(serde_json::json!(message))))
+      }
+      Err(_) => {
+        let message = ErrorMessage::new(false, "500", "database error");
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!(message))))
+      }
+    };
+
+    let response = SuccessResponse{
+      success: true,
+      data: Box::new(true),
+    };
+    Ok(Json(serde_json::json!(response)))
+  }
+
   pub fn routes(&self) -> Router {
     let ctx = self.ctx.clone();
     return Router::new()
       .route("/", get(Self::gets))
       .route("/ranking", get(Self::ranking))
+      .route("/history", get(Self::history))
+      .route("/params", get(Self::params).post(Self::update_params).delete(Self::delete_params))
       .with_state(ctx)
   }
 }
@@ -2,9 +2,11 @@ use clap::{Parser, Subcommand};
 
 use crate::commands::api::binance::spot::*;
 use crate::commands::api::binance::futures::*;
+use crate::commands::api::binance::delivery::*;
 
 pub mod spot;
 pub mod futures;
+pub mod delivery;
 pub mod margin;
 
 #[derive(Parser)]
@@ -17,6 +19,7 @@ pub struct BinanceCommand {
 enum Commands {
   Spot(SpotCommand),
   Futures(FuturesCommand),
+  Delivery(DeliveryCommand),
 }
 
 impl BinanceCommand {
@@ -24,6 +27,7 @@ impl BinanceCommand {
     match &self.commands {
       Commands::Spot(spot) => spot.run().await,
       Commands::Futures(futures) => futures.run().await,
+      Commands::Delivery(delivery) => delivery.run().await,
     }
   }
 }
\ No newline at end of file
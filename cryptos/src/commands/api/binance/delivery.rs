@@ -0,0 +1,46 @@
+use axum::{
+  routing::get,
+  Router,
+};
+use clap::Parser;
+
+use crate::common::*;
+use crate::api::binance::delivery::v1::*;
+
+#[derive(Parser)]
+pub struct DeliveryCommand {}
+
+impl Default for DeliveryCommand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DeliveryCommand {
+  pub fn new() -> Self {
+    Self {
+      ..Default::default()
+    }
+  }
+
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("api binance delivery");
+    let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", Env::int("CRYPTOS_API_BINANCE_DELIVERY_PORT")))
+      .await
+      .unwrap();
+
+    let rdb = Rdb::new(3).await?;
+    let rmq = Rmq::new(3).await?;
+    let pool = Pool::new(3)?;
+    let nats = Nats::new().await?;
+    let ctx = Ctx::new(rdb, rmq, pool, nats);
+
+    let router = Router::new()
+      .route("/foo", get(|| async { "Hi! delivery api" }))
+      .nest("/v1", V1Router::new(ctx).routes());
+
+    axum::serve(listener, router).await.unwrap();
+
+    Ok(())
+  }
+}
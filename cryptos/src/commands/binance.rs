@@ -2,10 +2,15 @@ use clap::{Parser, Subcommand};
 
 use crate::commands::binance::spot::*;
 use crate::commands::binance::futures::*;
+use crate::commands::binance::delivery::*;
+use crate::commands::binance::margin::*;
+use crate::commands::binance::simulator::*;
 
 pub mod spot;
 pub mod futures;
+pub mod delivery;
 pub mod margin;
+pub mod simulator;
 
 #[derive(Parser)]
 pub struct BinanceCommand {
@@ -17,6 +22,9 @@ pub struct BinanceCommand {
 enum Commands {
   Spot(SpotCommand),
   Futures(FuturesCommand),
+  Delivery(DeliveryCommand),
+  Margin(MarginCommand),
+  Simulator(SimulatorCommand),
 }
 
 impl BinanceCommand {
@@ -24,6 +32,9 @@ impl BinanceCommand {
     match &self.commands {
       Commands::Spot(spot) => spot.run().await,
       Commands::Futures(futures) => futures.run().await,
+      Commands::Delivery(delivery) => delivery.run().await,
+      Commands::Margin(margin) => margin.run().await,
+      Commands::Simulator(simulator) => simulator.run().await,
     }
   }
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use clap::{Parser, Subcommand};
+
+use crate::common::*;
+use crate::commands::binance::delivery::account::*;
+use crate::commands::binance::delivery::symbols::*;
+use crate::commands::binance::delivery::tickers::*;
+use crate::commands::binance::delivery::klines::*;
+use crate::commands::binance::delivery::indicators::*;
+use crate::commands::binance::delivery::orders::*;
+
+pub mod account;
+pub mod symbols;
+pub mod tickers;
+pub mod klines;
+pub mod indicators;
+pub mod orders;
+
+#[derive(Parser)]
+pub struct DeliveryCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Account(AccountCommand),
+  Symbols(SymbolsCommand),
+  Tickers(TickersCommand),
+  Klines(KlinesCommand),
+  Indicators(IndicatorsCommand),
+  Orders(OrdersCommand),
+}
+
+impl DeliveryCommand {
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    let rdb = Rdb::new(3).await?;
+    let rmq = Rmq::new(3).await?;
+    let pool = Pool::new(3)?;
+    let nats = Nats::new().await?;
+    let ctx = Ctx::new(rdb, rmq, pool, nats);
+    match &self.commands {
+      Commands::Account(account) => account.run(ctx.clone()).await,
+      Commands::Symbols(symbols) => symbols.run(ctx.clone()).await,
+      Commands::Tickers(tickers) => tickers.run(ctx.clone()).await,
+      Commands::Klines(klines) => klines.run(ctx.clone()).await,
+      Commands::Indicators(indicators) => indicators.run(ctx.clone()).await,
+      Commands::Orders(orders) => orders.run(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::account::*;
+
+#[derive(Parser)]
+pub struct AccountCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// account flush
+  Flush,
+  /// account balance
+  Balance(BalanceArgs),
+}
+
+#[derive(Args)]
+struct BalanceArgs {
+  /// margin asset, BTC ETH ...
+  asset: String,
+}
+
+impl AccountCommand {
+  async fn flush(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery account flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ACCOUNT_FLUSH,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(600)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ACCOUNT_FLUSH)));
+    }
+    match AccountRepository::flush(ctx.clone()).await {
+      Ok(_) => (),
+      Err(err) => {
+        mutex.unlock().await.unwrap();
+        return Err(err)
+      }
+    }
+    mutex.unlock().await.unwrap();
+    Ok(())
+  }
+
+  async fn balance(&self, ctx: Ctx, asset: String) -> Result<(), Box<dyn std::error::Error>> {
+    let (balance, free, unrealized_profit, margin, initial_margin, maint_margin) = AccountRepository::balance(ctx.clone(), &asset).await?;
+    println!("balance {asset:} {balance:} free {free:} unrealized profit {unrealized_profit:} margin {margin:} {initial_margin:} {maint_margin:}");
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Flush => self.flush(ctx.clone()).await,
+      Commands::Balance(args) => self.balance(ctx.clone(), args.asset.clone()).await,
+    }
+  }
+}
\ No newline at end of file
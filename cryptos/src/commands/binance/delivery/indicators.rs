@@ -0,0 +1,51 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::delivery::exchange::*;
+use crate::repositories::binance::params::*;
+use crate::repositories::binance::delivery::indicators::*;
+use crate::repositories::binance::futures::strategies::StrategiesRepository;
+
+#[derive(Parser)]
+pub struct IndicatorsCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// indicators pivot
+  Pivot(IndicatorArgs),
+  /// indicators atr, with the atr strategy levels
+  Atr(IndicatorArgs),
+}
+
+#[derive(Args)]
+struct IndicatorArgs {
+  /// symbol
+  symbol: String,
+  /// interval
+  #[arg(default_value = "1d")]
+  interval: String,
+}
+
+impl IndicatorsCommand {
+  async fn pivot(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators pivot {symbol:} {interval:}");
+    IndicatorsRepository::pivot(ctx.clone(), &symbol, &interval).await
+  }
+
+  async fn atr(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators atr {symbol:} {interval:}");
+    let params = ParamsRepository::get(ctx.clone(), "delivery", symbol.as_str(), interval.as_str()).await?;
+    IndicatorsRepository::atr(ctx.clone(), &symbol, &interval, params.atr_period, params.limit).await?;
+    StrategiesRepository::atr(ctx.clone(), &DeliveryExchange::new(ctx.clone()), &symbol, &interval).await
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Pivot(args) => self.pivot(ctx.clone(), args.symbol.clone(), args.interval.clone()).await,
+      Commands::Atr(args) => self.atr(ctx.clone(), args.symbol.clone(), args.interval.clone()).await,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::delivery::klines::*;
+
+#[derive(Parser)]
+pub struct KlinesCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// klines flush from the api, backfills the lookback of the indicators
+  Flush(FlushArgs),
+}
+
+#[derive(Args)]
+struct FlushArgs {
+  /// symbol
+  symbol: String,
+  /// interval 15m 4h 1d
+  interval: String,
+  /// limit
+  #[arg(default_value_t = 100)]
+  limit: i64,
+}
+
+impl KlinesCommand {
+  async fn flush(&self, ctx: Ctx, symbol: String, interval: String, limit: i64) -> Result<(), Box<dyn std::error::Error>> {
+    println!("klines flush {symbol:} {interval:} {limit:}");
+    Interval::parse(&interval)?;
+    KlinesRepository::flush(ctx.clone(), &symbol, &interval, 0, limit).await
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Flush(args) => self.flush(ctx.clone(), args.symbol.clone(), args.interval.clone(), args.limit).await,
+    }
+  }
+}
\ No newline at end of file
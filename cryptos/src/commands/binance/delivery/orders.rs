@@ -0,0 +1,76 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::delivery::orders::*;
+
+#[derive(Parser)]
+pub struct OrdersCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// orders submit
+  Submit(SubmitArgs),
+  /// orders sync
+  Sync(SyncArgs),
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+  /// symbol
+  symbol: String,
+  /// position side
+  position_side: String,
+  /// side
+  side: String,
+  /// price
+  price: f64,
+  /// contracts
+  quantity: f64,
+}
+
+#[derive(Args)]
+struct SyncArgs {
+  /// symbol
+  symbol: String,
+  /// limit
+  limit: i64,
+}
+
+impl OrdersCommand {
+  async fn submit(
+    &self,
+    ctx: Ctx,
+    symbol: String,
+    position_side: String,
+    side: String,
+    price: f64,
+    quantity: f64,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    println!("orders submit");
+    let order_id = OrdersRepository::submit(ctx, &symbol, &position_side, &side, price, quantity).await?;
+    println!("orders submit success {order_id:}");
+    Ok(())
+  }
+
+  async fn sync(&self, ctx: Ctx, symbol: String, limit: i64) -> Result<(), Box<dyn std::error::Error>> {
+    println!("orders sync");
+    OrdersRepository::sync(ctx.clone(), &symbol, 0, limit).await
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Submit(args) => self.submit(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.position_side.clone(),
+        args.side.clone(),
+        args.price,
+        args.quantity,
+      ).await,
+      Commands::Sync(args) => self.sync(ctx.clone(), args.symbol.clone(), args.limit).await,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::cron::binance::delivery::expiry::*;
+use crate::repositories::binance::delivery::symbols::*;
+
+#[derive(Parser)]
+pub struct SymbolsCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// symbols flush
+  Flush,
+  /// symbols count
+  Count,
+  /// quarterly contracts delivering within the given days
+  Expiring(ExpiringArgs),
+  /// publish expiring contracts and settle delivered positions
+  Expiry,
+}
+
+#[derive(Args)]
+struct ExpiringArgs {
+  /// days
+  #[arg(default_value_t = Config::EXPIRY_ROLLOVER_DAYS)]
+  days: i64,
+}
+
+impl SymbolsCommand {
+  async fn flush(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("symbols flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_SYMBOLS_FLUSH,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(600)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_SYMBOLS_FLUSH)));
+    }
+
+    let result = SymbolsRepository::flush(ctx.clone()).await;
+
+    mutex.unlock().await.unwrap();
+    result
+  }
+
+  async fn count(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let count = SymbolsRepository::count(ctx.clone()).await?;
+    println!("symbols count {}", count);
+    Ok(())
+  }
+
+  async fn expiring(&self, ctx: Ctx, days: i64) -> Result<(), Box<dyn std::error::Error>> {
+    let timestamp = Utc::now().timestamp_millis() + days * 86400000;
+    for (symbol, pair, delivery_date) in SymbolsRepository::expiring(ctx.clone(), timestamp).await?.iter() {
+      let rollover = SymbolsRepository::rollover(ctx.clone(), symbol).await?.unwrap_or_default();
+      println!("{symbol:} {pair:} delivery {delivery_date:} rollover {rollover:}");
+    }
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Flush => self.flush(ctx.clone()).await,
+      Commands::Count => self.count(ctx.clone()).await,
+      Commands::Expiring(args) => self.expiring(ctx.clone(), args.days).await,
+      Commands::Expiry => ExpiryScheduler::check(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
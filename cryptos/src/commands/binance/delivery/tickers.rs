@@ -0,0 +1,46 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::delivery::tickers::*;
+
+#[derive(Parser)]
+pub struct TickersCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// tickers price
+  Price(PriceArgs),
+  /// tickers flush
+  Flush,
+}
+
+#[derive(Args)]
+struct PriceArgs {
+  /// symbol
+  symbol: String,
+}
+
+impl TickersCommand {
+  async fn price(&self, ctx: Ctx, symbol: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("tickers price {symbol:}");
+    let price = TickersRepository::price(ctx.clone(), &symbol).await?;
+    println!("price {}", price);
+    Ok(())
+  }
+
+  async fn flush(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let count = TickersRepository::flush(ctx.clone()).await?;
+    println!("tickers flush {}", count);
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Price(args) => self.price(ctx.clone(), args.symbol.clone()).await,
+      Commands::Flush => self.flush(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
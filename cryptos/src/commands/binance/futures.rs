@@ -12,6 +12,7 @@ use crate::commands::binance::futures::orders::*;
 use crate::commands::binance::futures::positions::*;
 use crate::commands::binance::futures::gambling::*;
 use crate::commands::binance::futures::scalping::*;
+use crate::commands::binance::futures::triggers::*;
 use crate::commands::binance::futures::tradings::*;
 use crate::commands::binance::futures::streams::*;
 
@@ -26,6 +27,7 @@ pub mod orders;
 pub mod positions;
 pub mod gambling;
 pub mod scalping;
+pub mod triggers;
 pub mod tradings;
 pub mod streams;
 
@@ -48,6 +50,7 @@ enum Commands {
   Positions(PositionsCommand),
   Gambling(GamblingCommand),
   Scalping(ScalpingCommand),
+  Triggers(TriggersCommand),
   Tradings(TradingsCommand),
   Streams(StreamsCommand),
 }
@@ -71,6 +74,7 @@ impl FuturesCommand {
       Commands::Positions(positions) => positions.run(ctx.clone()).await,
       Commands::Gambling(gambling) => gambling.run(ctx.clone()).await,
       Commands::Scalping(scalping) => scalping.run(ctx.clone()).await,
+      Commands::Triggers(triggers) => triggers.run(ctx.clone()).await,
       Commands::Tradings(tradings) => tradings.run(ctx.clone()).await,
       Commands::Streams(streams) => streams.run(ctx.clone()).await,
     }
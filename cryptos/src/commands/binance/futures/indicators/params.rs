@@ -0,0 +1,138 @@
+use clap::{Args, Parser, Subcommand};
+
+use crate::common::*;
+use crate::models::binance::param::*;
+use crate::repositories::binance::params::*;
+
+#[derive(Parser)]
+pub struct ParamsCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// params resolved for a symbol
+  Get(CmdArgs),
+  /// params overrides of the market
+  List(ListArgs),
+  /// params set key=value
+  Set(SetArgs),
+  /// params unset keys
+  Unset(SetArgs),
+  /// params delete overrides
+  Delete(CmdArgs),
+}
+
+#[derive(Args)]
+struct CmdArgs {
+  /// interval
+  interval: String,
+  /// symbol, the market default when omitted
+  #[arg(long, default_value = "")]
+  symbol: String,
+}
+
+#[derive(Args)]
+struct ListArgs {
+  /// symbol
+  #[arg(long, default_value = "")]
+  symbol: String,
+}
+
+#[derive(Args)]
+struct SetArgs {
+  /// interval
+  interval: String,
+  /// key=value for set, key for unset
+  values: Vec<String>,
+  /// symbol, the market default when omitted
+  #[arg(long, default_value = "")]
+  symbol: String,
+}
+
+impl ParamsCommand {
+  async fn get(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators params get {symbol:} {interval:}");
+    let params = ParamsRepository::get(ctx.clone(), "futures", symbol.as_str(), interval.as_str()).await?;
+    println!("{}", serde_json::to_string_pretty(&params)?);
+    Ok(())
+  }
+
+  async fn list(&self, ctx: Ctx, symbol: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators params list {symbol:}");
+    let items = ParamsRepository::listings(ctx.clone(), "futures", symbol.as_str()).await?;
+    for item in items.iter() {
+      let symbol = if item.symbol == "" { "default" } else { item.symbol.as_str() };
+      println!("{} {} {}", symbol, item.interval, serde_json::to_string(&item.values)?);
+    }
+    Ok(())
+  }
+
+  async fn set(&self, ctx: Ctx, symbol: String, interval: String, values: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators params set {symbol:} {interval:}");
+    let mut data = serde_json::Map::new();
+    for value in values.iter() {
+      let (key, value) = match value.split_once("=") {
+        Some(result) => result,
+        None => return Err(Box::from(format!("{value:} is not key=value"))),
+      };
+      let value = match serde_json::from_str::<serde_json::Number>(value) {
+        Ok(result) => result,
+        Err(_) => return Err(Box::from(format!("{key:} is not a number"))),
+      };
+      data.insert(key.to_string(), serde_json::Value::Number(value));
+    }
+    let values: Values = serde_json::from_value(serde_json::Value::Object(data))?;
+    let values = ParamsRepository::set(ctx.clone(), "futures", symbol.as_str(), interval.as_str(), values).await?;
+    println!("{}", serde_json::to_string(&values)?);
+    Ok(())
+  }
+
+  async fn unset(&self, ctx: Ctx, symbol: String, interval: String, keys: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators params unset {symbol:} {interval:}");
+    let keys = keys.iter().map(|key| key.as_str()).collect();
+    let values = ParamsRepository::unset(ctx.clone(), "futures", symbol.as_str(), interval.as_str(), keys).await?;
+    println!("{}", serde_json::to_string(&values)?);
+    Ok(())
+  }
+
+  async fn delete(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators params delete {symbol:} {interval:}");
+    if !ParamsRepository::delete(ctx.clone(), "futures", symbol.as_str(), interval.as_str()).await? {
+      return Err(Box::from(format!("params of {symbol:} {interval:} not exists")))
+    }
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Get(args) => self.get(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::List(args) => self.list(
+        ctx.clone(),
+        args.symbol.clone(),
+      ).await,
+      Commands::Set(args) => self.set(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+        args.values.clone(),
+      ).await,
+      Commands::Unset(args) => self.unset(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+        args.values.clone(),
+      ).await,
+      Commands::Delete(args) => self.delete(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+    }
+  }
+}
\ No newline at end of file
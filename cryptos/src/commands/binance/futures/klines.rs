@@ -42,7 +42,7 @@ impl KlinesCommand {
 
   async fn gets(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     let interval = "4h";
-    let timestamp = KlinesRepository::timestamp(interval);
+    let timestamp = Interval::parse(interval)?.timestamp();
     println!("klines gets {} {}", interval, timestamp);
     let values = KlinesRepository::gets(
       ctx.clone(),
@@ -57,10 +57,7 @@ impl KlinesCommand {
 
   async fn timestamp(&self, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("klines timestamp");
-    if !["1m", "15m", "4h", "1d"].iter().any(|&s| s == interval) {
-      return Err(Box::from("interval not valid"))
-    }
-    let timestamp = KlinesRepository::timestamp(interval);
+    let timestamp = Interval::parse(interval)?.timestamp();
     println!("klines timestamp {}", timestamp);
     Ok(())
   }
@@ -2,6 +2,7 @@ use clap::{Args, Parser, Subcommand};
 
 use crate::common::*;
 use crate::repositories::binance::futures::plans::*;
+use crate::repositories::binance::futures::exchange::*;
 
 #[derive(Parser)]
 pub struct PlansCommand {
@@ -38,7 +39,7 @@ impl PlansCommand {
 
   async fn flush(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("plans flush");
-    match PlansRepository::flush(ctx.clone(), &symbol, &interval).await {
+    match PlansRepository::flush(ctx.clone(), &FuturesExchange::new(ctx.clone()), &symbol, &interval).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
     }
@@ -3,6 +3,7 @@ use clap::{Args, Parser, Subcommand};
 use crate::common::*;
 use crate::commands::binance::futures::strategies::nats::*;
 use crate::repositories::binance::futures::strategies::*;
+use crate::repositories::binance::futures::exchange::*;
 
 pub mod nats;
 
@@ -26,6 +27,14 @@ enum Commands {
   Bbands(CmdArgs),
   /// strategies ichimoku cloud
   IchimokuCloud(CmdArgs),
+  /// strategies rsi
+  Rsi(CmdArgs),
+  /// strategies macd
+  Macd(CmdArgs),
+  /// strategies adx
+  Adx(CmdArgs),
+  /// strategies supertrend
+  Supertrend(CmdArgs),
   Nats(NatsCommand),
 }
 
@@ -42,6 +51,7 @@ impl StrategiesCommand {
     println!("strategies atr");
     match StrategiesRepository::atr(
       ctx.clone(),
+      &FuturesExchange::new(ctx.clone()),
       &symbol,
       &interval,
     ).await {
@@ -110,6 +120,54 @@ impl StrategiesCommand {
     }
   }
 
+  async fn rsi(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("strategies rsi");
+    match StrategiesRepository::rsi(
+      ctx.clone(),
+      &symbol,
+      &interval,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn macd(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("strategies macd");
+    match StrategiesRepository::macd(
+      ctx.clone(),
+      &symbol,
+      &interval,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn adx(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("strategies adx");
+    match StrategiesRepository::adx(
+      ctx.clone(),
+      &symbol,
+      &interval,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn supertrend(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("strategies supertrend");
+    match StrategiesRepository::supertrend(
+      ctx.clone(),
+      &symbol,
+      &interval,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
   pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     match &self.commands {
       Commands::Atr(args) => self.atr(
@@ -142,6 +200,26 @@ impl StrategiesCommand {
         args.symbol.clone(),
         args.interval.clone(),
       ).await,
+      Commands::Rsi(args) => self.rsi(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Macd(args) => self.macd(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Adx(args) => self.adx(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Supertrend(args) => self.supertrend(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
       Commands::Nats(nats) => nats.run(ctx).await,
     }
   }
@@ -45,6 +45,9 @@ impl SymbolsCommand {
     if !mutex.lock(Duration::from_secs(600)).await.unwrap() {
       panic!("mutex failed {}", Config::LOCKS_SYMBOLS_FLUSH);
     }
+
+    SymbolsRepository::flush(ctx.clone()).await?;
+
     mutex.unlock().await.unwrap();
     Ok(())
   }
@@ -1,9 +1,11 @@
 use clap::{Parser, Subcommand};
 
 use crate::common::*;
+use crate::commands::binance::futures::tradings::launchpad::*;
 use crate::commands::binance::futures::tradings::scalping::*;
 use crate::commands::binance::futures::tradings::triggers::*;
 
+pub mod launchpad;
 pub mod scalping;
 pub mod triggers;
 
@@ -15,6 +17,7 @@ pub struct TradingsCommand {
 
 #[derive(Subcommand)]
 enum Commands {
+  Launchpad(LaunchpadCommand),
   Scalping(ScalpingCommand),
   Triggers(TriggersCommand),
 }
@@ -22,6 +25,7 @@ enum Commands {
 impl TradingsCommand {
   pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     match &self.commands {
+      Commands::Launchpad(launchpad) => launchpad.run(ctx.clone()).await,
       Commands::Scalping(scalping) => scalping.run(ctx.clone()).await,
       Commands::Triggers(triggers) => triggers.run(ctx.clone()).await,
     }
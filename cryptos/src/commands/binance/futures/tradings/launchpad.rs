@@ -0,0 +1,60 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::futures::exchange::*;
+use crate::repositories::binance::futures::tradings::launchpad::*;
+
+#[derive(Parser)]
+pub struct LaunchpadCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// launchpad ladder
+  Ladder,
+  /// launchpad place
+  Place(PlaceArgs),
+  /// launchpad flush
+  Flush(FlushArgs),
+}
+
+#[derive(Args)]
+struct PlaceArgs {
+  /// symbol
+  symbol: String,
+}
+
+#[derive(Args)]
+struct FlushArgs {
+  /// id
+  id: String,
+}
+
+impl LaunchpadCommand {
+  async fn ladder(&self) -> Result<(), Box<dyn std::error::Error>> {
+    for (level, (ratio, amount)) in LaunchpadRepository::ladder().iter().enumerate() {
+      println!("level {level:} ratio {ratio:} amount {amount:}");
+    }
+    Ok(())
+  }
+
+  async fn place(&self, ctx: Ctx, symbol: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures tradings launchpad place {symbol:}");
+    LaunchpadRepository::place(ctx.clone(), &FuturesExchange::new(ctx.clone()), &symbol).await
+  }
+
+  async fn flush(&self, ctx: Ctx, id: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures tradings launchpad flush {id:}");
+    LaunchpadRepository::flush(ctx.clone(), &FuturesExchange::new(ctx.clone()), &id).await
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Ladder => self.ladder().await,
+      Commands::Place(args) => self.place(ctx.clone(), args.symbol.clone()).await,
+      Commands::Flush(args) => self.flush(ctx.clone(), args.id.clone()).await,
+    }
+  }
+}
\ No newline at end of file
@@ -1,6 +1,8 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Args, Subcommand};
 
 use crate::common::*;
+use crate::repositories::binance::futures::exchange::*;
+use crate::repositories::binance::futures::tradings::triggers::*;
 
 #[derive(Parser)]
 pub struct TriggersCommand {
@@ -8,41 +10,32 @@ pub struct TriggersCommand {
   commands: Commands,
 }
 
-impl Default for TriggersCommand {
-  fn default() -> Self {
-    Self::new()
-  }
-}
-
 #[derive(Subcommand)]
 enum Commands {
   /// triggers place
-  Place,
+  Place(PlaceArgs),
   /// triggers flush
-  Flush,
+  Flush(FlushArgs),
 }
 
-impl TriggersCommand {
-  pub fn new() -> Self {
-    Self {
-      ..Default::default()
-    }
-  }
-
-  async fn place(&self, _: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    println!("binance futures tradings triggers place");
-    Ok(())
-  }
+#[derive(Args)]
+struct PlaceArgs {
+  /// trigger id
+  trigger_id: String,
+}
 
-  async fn flush(&self, _: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    println!("binance futures tradings triggers flush");
-    Ok(())
-  }
+#[derive(Args)]
+struct FlushArgs {
+  /// trading id
+  id: String,
+}
 
+impl TriggersCommand {
   pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let exchange = FuturesExchange::new(ctx.clone());
     match &self.commands {
-      Commands::Place => self.place(ctx.clone()).await,
-      Commands::Flush => self.flush(ctx.clone()).await,
+      Commands::Place(args) => TriggersRepository::place(ctx.clone(), &exchange, &args.trigger_id).await,
+      Commands::Flush(args) => TriggersRepository::flush(ctx.clone(), &exchange, &args.id).await,
     }
   }
-}
+}
\ No newline at end of file
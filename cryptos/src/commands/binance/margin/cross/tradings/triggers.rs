@@ -0,0 +1,41 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::margin::cross::exchange::*;
+use crate::repositories::binance::margin::cross::tradings::triggers::*;
+
+#[derive(Parser)]
+pub struct TriggersCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// triggers place
+  Place(PlaceArgs),
+  /// triggers flush
+  Flush(FlushArgs),
+}
+
+#[derive(Args)]
+struct PlaceArgs {
+  /// trigger id
+  trigger_id: String,
+}
+
+#[derive(Args)]
+struct FlushArgs {
+  /// trading id
+  id: String,
+}
+
+impl TriggersCommand {
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let exchange = CrossExchange::new(ctx.clone());
+    match &self.commands {
+      Commands::Place(args) => TriggersRepository::place(ctx.clone(), &exchange, &args.trigger_id).await,
+      Commands::Flush(args) => TriggersRepository::flush(ctx.clone(), &exchange, &args.id).await,
+    }
+  }
+}
\ No newline at end of file
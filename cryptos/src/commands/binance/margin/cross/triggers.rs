@@ -0,0 +1,81 @@
+use chrono::prelude::Utc;
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::margin::cross::triggers::*;
+
+#[derive(Parser)]
+pub struct TriggersCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// triggers create
+  Create(CreateArgs),
+  /// triggers listings
+  Listings,
+}
+
+#[derive(Args)]
+struct CreateArgs {
+  /// symbol
+  symbol: String,
+  /// side, 1 long 2 short
+  side: i32,
+  /// capital
+  capital: f64,
+  /// price
+  price: f64,
+  /// take price
+  take_price: f64,
+  /// stop price
+  stop_price: f64,
+  /// hours before expired
+  #[arg(default_value_t = 24)]
+  hours: i64,
+}
+
+impl TriggersCommand {
+  async fn create(&self, ctx: Ctx, args: &CreateArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let is_valid = match args.side {
+      1 => args.stop_price < args.price && args.price < args.take_price,
+      2 => args.take_price < args.price && args.price < args.stop_price,
+      _ => false,
+    };
+    if !is_valid {
+      return Err(Box::from("take and stop price not around the trigger price"))
+    }
+    let id = xid::new().to_string();
+    let expired_at = Utc::now() + chrono::Duration::hours(args.hours);
+    TriggersRepository::create(
+      ctx.clone(),
+      id.clone(),
+      args.symbol.clone(),
+      args.side,
+      args.capital,
+      args.price,
+      args.take_price,
+      args.stop_price,
+      expired_at,
+    ).await?;
+    println!("trigger {id:} of {0:} created", args.symbol);
+    Ok(())
+  }
+
+  async fn listings(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let items = TriggersRepository::listings(ctx.clone(), 1, 50).await?;
+    for (id, symbol, side, capital, price, take_price, stop_price, profit, status) in items.iter() {
+      println!("trigger {id:} {symbol:} side {side:} capital {capital:} price {price:} take {take_price:} stop {stop_price:} profit {profit:} status {status:}");
+    }
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Create(args) => self.create(ctx.clone(), args).await,
+      Commands::Listings => self.listings(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
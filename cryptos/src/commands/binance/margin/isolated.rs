@@ -0,0 +1,54 @@
+use clap::{Parser, Subcommand};
+
+use crate::common::*;
+use crate::commands::binance::margin::isolated::account::*;
+use crate::commands::binance::margin::isolated::orders::*;
+use crate::commands::binance::margin::isolated::positions::*;
+use crate::commands::binance::margin::isolated::scalping::*;
+use crate::commands::binance::margin::isolated::triggers::*;
+use crate::commands::binance::margin::isolated::tradings::TradingsCommand;
+use crate::commands::binance::margin::isolated::analysis::*;
+
+pub mod account;
+pub mod analysis;
+pub mod orders;
+pub mod positions;
+pub mod scalping;
+pub mod triggers;
+pub mod tradings;
+
+#[derive(Parser)]
+pub struct IsolatedCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Account(AccountCommand),
+  Orders(OrdersCommand),
+  Positions(PositionsCommand),
+  Scalping(ScalpingCommand),
+  Triggers(TriggersCommand),
+  Tradings(TradingsCommand),
+  Analysis(AnalysisCommand),
+}
+
+impl IsolatedCommand {
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    let rdb = Rdb::new(1).await?;
+    let rmq = Rmq::new(1).await?;
+    let pool = Pool::new(1)?;
+    let nats = Nats::new().await?;
+    let ctx = Ctx::new(rdb, rmq, pool, nats);
+    match &self.commands {
+      Commands::Account(account) => account.run(ctx.clone()).await,
+      Commands::Orders(orders) => orders.run(ctx.clone()).await,
+      Commands::Positions(positions) => positions.run(ctx.clone()).await,
+      Commands::Scalping(scalping) => scalping.run(ctx.clone()).await,
+      Commands::Triggers(triggers) => triggers.run(ctx.clone()).await,
+      Commands::Tradings(tradings) => tradings.run(ctx.clone()).await,
+      Commands::Analysis(analysis) => analysis.run(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::config::binance::margin::isolated::config as Config;
+use crate::repositories::binance::margin::isolated::account::*;
+use crate::repositories::binance::margin::isolated::interests::*;
+
+#[derive(Parser)]
+pub struct AccountCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// account flush
+  Flush,
+  /// account balance
+  Balance(AssetArgs),
+  /// margin level
+  Level(SymbolArgs),
+  /// max borrowable
+  MaxBorrowable(AssetArgs),
+  /// borrow
+  Borrow(AmountArgs),
+  /// repay
+  Repay(AmountArgs),
+  /// interests flush
+  Interests(InterestsArgs),
+}
+
+#[derive(Args)]
+struct SymbolArgs {
+  /// symbol
+  symbol: String,
+}
+
+#[derive(Args)]
+struct AssetArgs {
+  /// symbol
+  symbol: String,
+  /// asset
+  asset: String,
+}
+
+#[derive(Args)]
+struct AmountArgs {
+  /// symbol
+  symbol: String,
+  /// asset
+  asset: String,
+  /// amount
+  amount: f64,
+}
+
+#[derive(Args)]
+struct InterestsArgs {
+  /// symbol
+  symbol: String,
+  /// asset
+  asset: String,
+  /// days
+  #[arg(default_value_t = 30)]
+  days: i64,
+}
+
+impl AccountCommand {
+  async fn flush(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated account flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ACCOUNT_FLUSH,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(600)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ACCOUNT_FLUSH)));
+    }
+    match AccountRepository::flush(ctx.clone()).await {
+      Ok(_) => (),
+      Err(err) => {
+        mutex.unlock().await.unwrap();
+        return Err(err)
+      }
+    }
+    mutex.unlock().await.unwrap();
+    Ok(())
+  }
+
+  async fn balance(&self, ctx: Ctx, symbol: String, asset: String) -> Result<(), Box<dyn std::error::Error>> {
+    let (free, locked, borrowed, interest) = AccountRepository::balance(ctx.clone(), &symbol, &asset).await?;
+    println!("balance {symbol:} {asset:} free {free:} locked {locked:} borrowed {borrowed:} interest {interest:}");
+    Ok(())
+  }
+
+  async fn level(&self, ctx: Ctx, symbol: String) -> Result<(), Box<dyn std::error::Error>> {
+    let (margin_level, liquidate_price, liability) = AccountRepository::level(ctx.clone(), &symbol).await?;
+    println!("margin level {symbol:} {margin_level:} liquidate price {liquidate_price:} liability {liability:}");
+    Ok(())
+  }
+
+  async fn max_borrowable(&self, ctx: Ctx, symbol: String, asset: String) -> Result<(), Box<dyn std::error::Error>> {
+    let (amount, borrow_limit) = AccountRepository::max_borrowable(ctx.clone(), &symbol, &asset).await?;
+    println!("max borrowable {symbol:} {asset:} {amount:} limit {borrow_limit:}");
+    Ok(())
+  }
+
+  async fn interests(&self, ctx: Ctx, symbol: String, asset: String, days: i64) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Utc::now().timestamp_millis() - days * 86400000;
+    let count = InterestsRepository::flush(ctx.clone(), &symbol, &asset, start_time).await?;
+    let total = InterestsRepository::total(ctx.clone(), &symbol, &asset, start_time).await?;
+    println!("interests {symbol:} {asset:} {count:} saved, {total:} accrued in {days:} days");
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Flush => self.flush(ctx.clone()).await,
+      Commands::Balance(args) => self.balance(ctx.clone(), args.symbol.clone(), args.asset.clone()).await,
+      Commands::Level(args) => self.level(ctx.clone(), args.symbol.clone()).await,
+      Commands::MaxBorrowable(args) => self.max_borrowable(ctx.clone(), args.symbol.clone(), args.asset.clone()).await,
+      Commands::Borrow(args) => AccountRepository::borrow(ctx.clone(), &args.symbol, &args.asset, args.amount).await.map(|_| ()),
+      Commands::Repay(args) => AccountRepository::repay(ctx.clone(), &args.symbol, &args.asset, args.amount).await.map(|_| ()),
+      Commands::Interests(args) => self.interests(ctx.clone(), args.symbol.clone(), args.asset.clone(), args.days).await,
+    }
+  }
+}
\ No newline at end of file
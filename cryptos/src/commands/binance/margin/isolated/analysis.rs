@@ -0,0 +1,25 @@
+use clap::{Parser, Subcommand};
+
+use crate::common::*;
+use crate::commands::binance::margin::isolated::analysis::tradings::*;
+
+pub mod tradings;
+
+#[derive(Parser)]
+pub struct AnalysisCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Tradings(TradingsCommand),
+}
+
+impl AnalysisCommand {
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Tradings(tradings) => tradings.run(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
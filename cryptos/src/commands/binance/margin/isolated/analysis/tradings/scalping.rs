@@ -0,0 +1,50 @@
+use chrono::{prelude::Utc, NaiveDate};
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::margin::isolated::analysis::scalping::*;
+
+#[derive(Parser)]
+pub struct ScalpingCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// analysis flush
+  Flush(FlushArgs),
+  /// analysis listings
+  Listings,
+}
+
+#[derive(Args)]
+struct FlushArgs {
+  /// day, defaults to today
+  day: Option<String>,
+}
+
+impl ScalpingCommand {
+  async fn flush(&self, ctx: Ctx, day: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let day = match day {
+      Some(day) => NaiveDate::parse_from_str(&day, "%Y-%m-%d")?,
+      None => Utc::now().date_naive(),
+    };
+    ScalpingRepository::flush(ctx.clone(), day).await
+  }
+
+  async fn listings(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let items = ScalpingRepository::listings(ctx.clone(), 1, 30).await?;
+    for (_, side, day, buys_count, sells_count, buys_amount, sells_amount, profit, additive_profit) in items.iter() {
+      println!("{day:} side {side:} buys {buys_count:} {buys_amount:} sells {sells_count:} {sells_amount:} profit {profit:} additive {additive_profit:}");
+    }
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Flush(args) => self.flush(ctx.clone(), args.day.clone()).await,
+      Commands::Listings => self.listings(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
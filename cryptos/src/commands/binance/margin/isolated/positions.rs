@@ -0,0 +1,32 @@
+use clap::{Parser, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::margin::isolated::positions::*;
+
+#[derive(Parser)]
+pub struct PositionsCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// positions gets
+  Gets,
+}
+
+impl PositionsCommand {
+  async fn gets(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let positions = PositionsRepository::gets(ctx.clone()).await?;
+    for (id, symbol, side, notional, entry_price, entry_quantity, entry_amount, timestamp) in positions.iter() {
+      println!("position {id:} {symbol:} side {side:} notional {notional:} entry {entry_price:} {entry_quantity:} {entry_amount:} {timestamp:}");
+    }
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Gets => self.gets(ctx.clone()).await,
+    }
+  }
+}
\ No newline at end of file
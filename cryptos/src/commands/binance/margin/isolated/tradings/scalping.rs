@@ -0,0 +1,41 @@
+use clap::{Parser, Args, Subcommand};
+
+use crate::common::*;
+use crate::repositories::binance::margin::isolated::exchange::*;
+use crate::repositories::binance::margin::isolated::tradings::scalping::*;
+
+#[derive(Parser)]
+pub struct ScalpingCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// scalping place
+  Place(PlaceArgs),
+  /// scalping flush
+  Flush(FlushArgs),
+}
+
+#[derive(Args)]
+struct PlaceArgs {
+  /// plan id
+  plan_id: String,
+}
+
+#[derive(Args)]
+struct FlushArgs {
+  /// trading id
+  id: String,
+}
+
+impl ScalpingCommand {
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let exchange = IsolatedExchange::new(ctx.clone());
+    match &self.commands {
+      Commands::Place(args) => ScalpingRepository::place(ctx.clone(), &exchange, &args.plan_id).await,
+      Commands::Flush(args) => ScalpingRepository::flush(ctx.clone(), &exchange, &args.id).await,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use chrono::prelude::Utc;
+use clap::Parser;
+
+use crate::simulator::*;
+
+#[derive(Parser)]
+pub struct SimulatorCommand {
+  /// spot or futures
+  market: String,
+  #[arg(long, default_value_t = 8900)]
+  api_port: u16,
+  #[arg(long, default_value_t = 8901)]
+  streams_port: u16,
+  /// comma separated symbols quoted in USDT
+  #[arg(long, default_value = "BTCUSDT")]
+  symbols: String,
+  /// starting USDT balance
+  #[arg(long, default_value_t = 10000.0)]
+  balance: f64,
+  /// starting price of every symbol
+  #[arg(long, default_value_t = 100.0)]
+  price: f64,
+  #[arg(long, default_value_t = 0.01)]
+  tick_size: f64,
+  #[arg(long, default_value_t = 0.001)]
+  step_size: f64,
+  #[arg(long, default_value_t = 5.0)]
+  notional: f64,
+  #[arg(long, default_value_t = 10.0)]
+  leverage: f64,
+  /// milliseconds between price ticks
+  #[arg(long, default_value_t = 1000)]
+  tick_ms: u64,
+  /// csv of symbol,price[,volume] replayed one line per tick, random walk when omitted
+  #[arg(long)]
+  path: Option<String>,
+  #[arg(long, default_value_t = 1)]
+  seed: u64,
+  #[arg(long, default_value_t = 0.002)]
+  volatility: f64,
+}
+
+impl SimulatorCommand {
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    let market: &'static str = match self.market.as_str() {
+      "spot" => "spot",
+      "futures" => "futures",
+      _ => return Err(Box::from(format!("market {} not supported", self.market))),
+    };
+
+    let path = match &self.path {
+      Some(path) => PricePath::load(path)?,
+      None => PricePath::walk(self.seed, self.volatility),
+    };
+
+    let mut engine = Engine::new(market);
+    engine.leverage = self.leverage;
+    engine.deposit("USDT", self.balance);
+    for symbol in self.symbols.split(",").map(|symbol| symbol.trim()).filter(|symbol| !symbol.is_empty()) {
+      let base_asset = match symbol.strip_suffix("USDT") {
+        Some(base_asset) if !base_asset.is_empty() => base_asset,
+        _ => return Err(Box::from(format!("symbol {symbol:} not quoted in USDT"))),
+      };
+      engine.listing(symbol, Listing {
+        base_asset: base_asset.to_string(),
+        quote_asset: "USDT".to_string(),
+        tick_size: self.tick_size,
+        step_size: self.step_size,
+        notional: self.notional,
+      });
+      engine.tick(symbol, self.price, 0.0, Utc::now().timestamp_millis());
+    }
+    let simulator = Simulator::new(market, engine);
+
+    let api = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", self.api_port)).await?;
+    let streams = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", self.streams_port)).await?;
+    println!("simulator binance {} api http://127.0.0.1:{} streams ws://127.0.0.1:{}", market, self.api_port, self.streams_port);
+
+    let feed = simulator.clone();
+    let tick_ms = self.tick_ms;
+    tokio::spawn(async move {
+      feed.feed(path, tick_ms).await;
+    });
+
+    let router = rest::router(simulator.clone());
+    tokio::select! {
+      result = axum::serve(api, router) => result?,
+      result = stream::serve(simulator.clone(), streams) => result?,
+    }
+
+    Ok(())
+  }
+}
@@ -2,9 +2,13 @@ use clap::{Args, Parser, Subcommand};
 
 use crate::common::*;
 use crate::commands::binance::spot::indicators::nats::*;
+use crate::commands::binance::spot::indicators::params::*;
+use crate::repositories::binance::params::*;
 use crate::repositories::binance::spot::indicators::*;
+use crate::repositories::binance::spot::indicators::stream::*;
 
 pub mod nats;
+pub mod params;
 
 #[derive(Parser)]
 pub struct IndicatorsCommand {
@@ -32,7 +36,18 @@ enum Commands {
   VolumeProfile(CmdArgs),
   /// indicators andean oscillator
   AndeanOscillator(CmdArgs),
+  /// indicators rsi
+  Rsi(CmdArgs),
+  /// indicators macd
+  Macd(CmdArgs),
+  /// indicators adx
+  Adx(CmdArgs),
+  /// indicators supertrend
+  Supertrend(CmdArgs),
+  /// indicators incremental engine
+  Stream(CmdArgs),
   Nats(NatsCommand),
+  Params(ParamsCommand),
 }
 
 #[derive(Args)]
@@ -58,12 +73,13 @@ impl IndicatorsCommand {
 
   async fn atr(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators atr");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::atr(
       ctx.clone(),
       &symbol,
       &interval,
-      14,
-      100,
+      params.atr_period,
+      params.limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -72,12 +88,13 @@ impl IndicatorsCommand {
 
   async fn zlema(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators zlema");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::zlema(
       ctx.clone(),
       &symbol,
       &interval,
-      14,
-      100,
+      params.zlema_period,
+      params.limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -86,12 +103,13 @@ impl IndicatorsCommand {
 
   async fn ha_zlema(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators ha zlema");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::ha_zlema(
       ctx.clone(),
       &symbol,
       &interval,
-      14,
-      100,
+      params.zlema_period,
+      params.limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -100,13 +118,14 @@ impl IndicatorsCommand {
 
   async fn kdj(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators kdj");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::kdj(
       ctx.clone(),
       &symbol,
       &interval,
-      9,
-      3,
-      100,
+      params.kdj_long_period,
+      params.kdj_short_period,
+      params.limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -115,12 +134,14 @@ impl IndicatorsCommand {
 
   async fn bbands(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators bbands");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::bbands(
       ctx.clone(),
       &symbol,
       &interval,
-      14,
-      100,
+      params.bbands_period,
+      params.bbands_deviation,
+      params.limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -129,42 +150,15 @@ impl IndicatorsCommand {
 
   async fn ichimoku_cloud(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators ichimoku cloud");
-
-    let tenkan_period: i32;
-    let kijun_period: i32;
-    let senkou_period: i32;
-    let limit: i64;
-
-    if &interval == "1m" {
-      tenkan_period = 129;
-      kijun_period = 374;
-      senkou_period = 748;
-      limit = 1440;
-    } else if &interval == "15m" {
-      tenkan_period = 60;
-      kijun_period = 174;
-      senkou_period = 349;
-      limit = 672;
-    } else if &interval == "4h" {
-      tenkan_period = 11;
-      kijun_period = 32;
-      senkou_period = 65;
-      limit = 126;
-    } else {
-      tenkan_period = 9;
-      kijun_period = 26;
-      senkou_period = 52;
-      limit = 100;
-    }
-
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::ichimoku_cloud(
       ctx.clone(),
       &symbol,
       &interval,
-      tenkan_period,
-      kijun_period,
-      senkou_period,
-      limit,
+      params.tenkan_period,
+      params.kijun_period,
+      params.senkou_period,
+      params.ichimoku_limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -173,23 +167,13 @@ impl IndicatorsCommand {
 
   async fn volume_profile(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators volume profile");
-
-    let limit: i64;
-    if &interval == "1m" {
-      limit = 1440
-    } else if &interval == "15m" {
-      limit = 672
-    } else if &interval == "4h" {
-      limit = 126
-    } else {
-      limit = 100
-    }
-
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::volume_profile(
       ctx.clone(),
       &symbol,
       &interval,
-      limit,
+      params.volume_profile_segments,
+      params.volume_profile_limit,
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -198,13 +182,91 @@ impl IndicatorsCommand {
 
   async fn andean_oscillator(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("indicators andean oscillator");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
     match IndicatorsRepository::andean_oscillator(
       ctx.clone(),
       &symbol,
       &interval,
-      50,
-      9,
-      672,
+      params.andean_period,
+      params.andean_length,
+      params.andean_limit,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn rsi(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators rsi");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
+    match IndicatorsRepository::rsi(
+      ctx.clone(),
+      &symbol,
+      &interval,
+      params.rsi_period,
+      params.limit,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn macd(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators macd");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
+    match IndicatorsRepository::macd(
+      ctx.clone(),
+      &symbol,
+      &interval,
+      params.macd_fast_period,
+      params.macd_slow_period,
+      params.macd_signal_period,
+      params.limit,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn adx(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators adx");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
+    match IndicatorsRepository::adx(
+      ctx.clone(),
+      &symbol,
+      &interval,
+      params.adx_period,
+      params.limit,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn supertrend(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators supertrend");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
+    match IndicatorsRepository::supertrend(
+      ctx.clone(),
+      &symbol,
+      &interval,
+      params.supertrend_period,
+      params.supertrend_multiplier,
+      params.limit,
+    ).await {
+      Ok(_) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn stream(&self, ctx: Ctx, symbol: String, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("indicators stream");
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol.as_str(), interval.as_str()).await?;
+    match StreamRepository::flush(
+      ctx.clone(),
+      &symbol,
+      &interval,
+      params.engine(),
     ).await {
       Ok(_) => Ok(()),
       Err(err) => Err(err.into()),
@@ -258,7 +320,33 @@ impl IndicatorsCommand {
         args.symbol.clone(),
         args.interval.clone(),
       ).await,
+      Commands::Rsi(args) => self.rsi(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Macd(args) => self.macd(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Adx(args) => self.adx(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Supertrend(args) => self.supertrend(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
+      Commands::Stream(args) => self.stream(
+        ctx.clone(),
+        args.symbol.clone(),
+        args.interval.clone(),
+      ).await,
       Commands::Nats(nats) => nats.run(ctx).await,
+      Commands::Params(params) => params.run(ctx).await,
     }
   }
 }
\ No newline at end of file
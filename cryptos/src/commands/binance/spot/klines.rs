@@ -84,10 +84,7 @@ impl KlinesCommand {
 
   async fn timestamp(&self, interval: String) -> Result<(), Box<dyn std::error::Error>> {
     println!("klines timestamp");
-    if !["1m", "15m", "4h", "1d"].iter().any(|&s| s == interval) {
-      return Err(Box::from("interval not valid"))
-    }
-    let timestamp = KlinesRepository::timestamp(interval);
+    let timestamp = Interval::parse(interval)?.timestamp();
     println!("klines timestamp {}", timestamp);
     Ok(())
   }
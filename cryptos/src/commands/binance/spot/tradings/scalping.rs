@@ -6,6 +6,7 @@ use crate::common::*;
 use crate::config::binance::spot::config as Config;
 use crate::repositories::binance::spot::scalping::plans::*;
 use crate::repositories::binance::spot::tradings::scalping::*;
+use crate::repositories::binance::spot::exchange::*;
 
 #[derive(Parser)]
 pub struct ScalpingCommand {
@@ -49,7 +50,7 @@ impl ScalpingCommand {
       if !mutex.lock(Duration::from_secs(600)).await.unwrap() {
         panic!("mutex failed {}", redis_lock_key);
       }
-      match ScalpingRepository::place(ctx.clone(), plan_id).await {
+      match ScalpingRepository::place(ctx.clone(), &SpotExchange::new(ctx.clone()), plan_id).await {
         Ok(_) => (),
         Err(err) => println!("error: {}", err),
       }
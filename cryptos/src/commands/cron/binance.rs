@@ -2,9 +2,13 @@ use clap::{Parser, Subcommand};
 
 use crate::commands::cron::binance::spot::*;
 use crate::commands::cron::binance::futures::*;
+use crate::commands::cron::binance::delivery::*;
+use crate::commands::cron::binance::margin::*;
 
 pub mod spot;
 pub mod futures;
+pub mod delivery;
+pub mod margin;
 
 #[derive(Parser)]
 pub struct BinanceCommand {
@@ -16,6 +20,8 @@ pub struct BinanceCommand {
 enum Commands {
   Spot(SpotCommand),
   Futures(FuturesCommand),
+  Delivery(DeliveryCommand),
+  Margin(MarginCommand),
 }
 
 impl BinanceCommand {
@@ -23,6 +29,8 @@ impl BinanceCommand {
     match &self.commands {
       Commands::Spot(spot) => spot.run().await,
       Commands::Futures(futures) => futures.run().await,
+      Commands::Delivery(delivery) => delivery.run().await,
+      Commands::Margin(margin) => margin.run().await,
     }
   }
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use tokio_cron::Scheduler;
+use clap::Parser;
+
+use crate::common::*;
+use crate::cron::binance::delivery::*;
+
+#[derive(Parser)]
+pub struct DeliveryCommand {}
+
+impl Default for DeliveryCommand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DeliveryCommand {
+  pub fn new() -> Self {
+    Self {
+      ..Default::default()
+    }
+  }
+
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("cron binance delivery");
+    let rdb = Rdb::new(3).await?;
+    let rmq = Rmq::new(3).await?;
+    let pool = Pool::new(3)?;
+    let nats = Nats::new().await?;
+    let ctx = Ctx::new(rdb, rmq, pool, nats);
+    let scheduler = Scheduler::local();
+
+    DeliveryScheduler::new(ctx.clone(), scheduler).dispatch().await?;
+
+    loop {
+      tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+    }
+  }
+}
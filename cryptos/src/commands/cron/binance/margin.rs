@@ -0,0 +1,28 @@
+use clap::{Parser, Subcommand};
+
+use crate::commands::cron::binance::margin::cross::*;
+use crate::commands::cron::binance::margin::isolated::*;
+
+pub mod cross;
+pub mod isolated;
+
+#[derive(Parser)]
+pub struct MarginCommand {
+  #[command(subcommand)]
+  commands: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Cross(CrossCommand),
+  Isolated(IsolatedCommand),
+}
+
+impl MarginCommand {
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    match &self.commands {
+      Commands::Cross(cross) => cross.run().await,
+      Commands::Isolated(isolated) => isolated.run().await,
+    }
+  }
+}
\ No newline at end of file
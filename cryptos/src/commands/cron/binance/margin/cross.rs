@@ -0,0 +1,39 @@
+use tokio_cron::Scheduler;
+use clap::Parser;
+
+use crate::common::*;
+use crate::cron::binance::margin::cross::*;
+
+#[derive(Parser)]
+pub struct CrossCommand {}
+
+impl Default for CrossCommand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CrossCommand {
+  pub fn new() -> Self {
+    Self {
+      ..Default::default()
+    }
+  }
+
+  // margin shares the spot databases, plans and tickers are read from there
+  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("cron binance margin cross");
+    let rdb = Rdb::new(1).await?;
+    let rmq = Rmq::new(1).await?;
+    let pool = Pool::new(1)?;
+    let nats = Nats::new().await?;
+    let ctx = Ctx::new(rdb, rmq, pool, nats);
+    let scheduler = Scheduler::local();
+
+    CrossScheduler::new(ctx.clone(), scheduler).dispatch().await?;
+
+    loop {
+      tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+    }
+  }
+}
\ No newline at end of file
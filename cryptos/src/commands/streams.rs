@@ -1,5 +1,8 @@
-use clap::{Parser, Subcommand};
+use std::sync::Arc;
 
+use clap::{Args, Parser, Subcommand};
+
+use crate::common::*;
 use crate::commands::streams::binance::*;
 
 pub mod binance;
@@ -8,6 +11,24 @@ pub mod binance;
 pub struct StreamsCommand {
   #[command(subcommand)]
   commands: Commands,
+  #[command(flatten)]
+  record: RecordArgs,
+}
+
+#[derive(Args)]
+pub struct RecordArgs {
+  /// directory to record raw frames into
+  #[arg(long, global = true)]
+  pub record: Option<String>,
+  /// recorded file or directory to feed through the stream processors instead of connecting
+  #[arg(long, global = true)]
+  pub replay: Option<String>,
+  /// replay speed multiplier, 0 replays as fast as possible
+  #[arg(long, global = true, default_value_t = 1.0)]
+  pub speed: f64,
+  /// connection index of the scratch database and redis written by replays
+  #[arg(long, global = true, default_value_t = 9)]
+  pub scratch: u8,
 }
 
 #[derive(Subcommand)]
@@ -18,7 +39,35 @@ enum Commands {
 impl StreamsCommand {
   pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
     match &self.commands {
-      Commands::Binance(binance) => binance.run().await,
+      Commands::Binance(binance) => binance.run(&self.record).await,
+    }
+  }
+}
+
+impl RecordArgs {
+  pub fn index(&self, i: u8) -> u8 {
+    match self.replay {
+      Some(_) => self.scratch,
+      None => i,
+    }
+  }
+
+  pub fn recorder<T>(&self, name: T) -> Option<Arc<StreamRecorder>>
+  where
+    T: AsRef<str>
+  {
+    if self.replay.is_some() {
+      return None
     }
+    self.record.as_ref().map(|path| Arc::new(StreamRecorder::new(path.as_str(), name.as_ref())))
+  }
+
+  pub async fn replay(&self, ctx: Ctx, callback: StreamFn) -> Option<Result<(), Box<dyn std::error::Error>>> {
+    let path = self.replay.as_ref()?;
+    let replayer = match StreamReplayer::new(path) {
+      Ok(replayer) => replayer,
+      Err(err) => return Some(Err(err)),
+    };
+    Some(replayer.run(ctx, callback, self.speed).await)
   }
 }
\ No newline at end of file
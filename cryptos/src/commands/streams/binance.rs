@@ -2,6 +2,7 @@ use clap::{Parser, Subcommand};
 
 use crate::commands::streams::binance::spot::*;
 use crate::commands::streams::binance::futures::*;
+use crate::commands::streams::RecordArgs;
 
 pub mod spot;
 pub mod futures;
@@ -19,10 +20,10 @@ enum Commands {
 }
 
 impl BinanceCommand {
-  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+  pub async fn run(&self, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
     match &self.commands {
-      Commands::Spot(spot) => spot.run().await,
-      Commands::Futures(futures) => futures.run().await,
+      Commands::Spot(spot) => spot.run(record).await,
+      Commands::Futures(futures) => futures.run(record).await,
     }
   }
 }
\ No newline at end of file
@@ -1,14 +1,18 @@
 use clap::{Parser, Subcommand};
 
 use crate::common::*;
+use crate::commands::streams::RecordArgs;
 use crate::commands::streams::binance::futures::api::*;
+use crate::commands::streams::binance::futures::account::*;
 use crate::commands::streams::binance::futures::tickers::*;
 use crate::commands::streams::binance::futures::klines::*;
+use crate::commands::streams::binance::futures::depth::*;
 
 mod api;
 mod account;
 mod tickers;
 mod klines;
+mod depth;
 
 #[derive(Parser)]
 pub struct FuturesCommand {
@@ -19,21 +23,25 @@ pub struct FuturesCommand {
 #[derive(Subcommand)]
 enum Commands {
   Api(ApiCommand),
+  Account(AccountCommand),
   Tickers(TickersCommand),
   Klines(KlinesCommand),
+  Depth(DepthCommand),
 }
 
 impl FuturesCommand {
-  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-    let rdb = Rdb::new(2).await.expect("bad rdb connection");
-    let rmq = Rmq::new(2).await.expect("bad rmq connection");
-    let pool = Pool::new(2).expect("bad pool connection");
+  pub async fn run(&self, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let rdb = Rdb::new(record.index(2)).await.expect("bad rdb connection");
+    let rmq = Rmq::new(record.index(2)).await.expect("bad rmq connection");
+    let pool = Pool::new(record.index(2)).expect("bad pool connection");
     let nats = Nats::new().await.expect("bad nats connection");
     let ctx = Ctx::new(rdb, rmq, pool, nats);
     match &self.commands {
       Commands::Api(api) => api.run(ctx).await,
-      Commands::Tickers(tickers) => tickers.run(ctx).await,
-      Commands::Klines(klines) => klines.run(ctx).await,
+      Commands::Account(account) => account.run(ctx, record).await,
+      Commands::Tickers(tickers) => tickers.run(ctx, record).await,
+      Commands::Klines(klines) => klines.run(ctx, record).await,
+      Commands::Depth(depth) => depth.run(ctx, record).await,
     }
   }
 }
\ No newline at end of file
@@ -0,0 +1,383 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use redis::AsyncCommands;
+use serde::{Deserialize, Deserializer};
+use tokio::sync::Mutex;
+use clap::Parser;
+
+use crate::common::*;
+use crate::commands::streams::RecordArgs;
+use crate::config::binance::futures::config as Config;
+use crate::repositories::binance::futures::account::*;
+use crate::repositories::binance::futures::orders::*;
+use crate::repositories::binance::futures::positions::*;
+use crate::schema::binance::futures::orders::*;
+use crate::schema::binance::futures::positions::*;
+use crate::queue::nats::jobs::binance::futures::account::*;
+use crate::queue::nats::jobs::binance::futures::orders::*;
+
+#[derive(Parser)]
+pub struct AccountCommand {}
+
+impl Default for AccountCommand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[derive(Deserialize)]
+struct UserEvent {
+  #[serde(alias = "e")]
+  event: String,
+}
+
+#[derive(Deserialize)]
+struct OrderEvent {
+  #[serde(alias = "o")]
+  message: OrderMessage,
+}
+
+#[derive(Deserialize)]
+struct OrderMessage {
+  #[serde(alias = "s")]
+  symbol: String,
+  #[serde(alias = "i")]
+  order_id: i64,
+  #[serde(alias = "o")]
+  order_type: String,
+  #[serde(alias = "ps")]
+  position_side: String,
+  #[serde(alias = "S")]
+  side: String,
+  #[serde(alias = "p", deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "ap", deserialize_with = "to_f64")]
+  avg_price: f64,
+  #[serde(alias = "sp", deserialize_with = "to_f64")]
+  stop_price: f64,
+  #[serde(alias = "q", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "z", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "T")]
+  update_time: i64,
+  #[serde(alias = "wt")]
+  working_type: String,
+  #[serde(alias = "pP")]
+  price_protect: bool,
+  #[serde(alias = "R")]
+  reduce_only: bool,
+  #[serde(alias = "cp")]
+  close_position: bool,
+  #[serde(alias = "X")]
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct AccountEvent {
+  #[serde(alias = "E")]
+  timestamp: i64,
+  #[serde(alias = "a")]
+  message: AccountMessage,
+}
+
+#[derive(Deserialize)]
+struct AccountMessage {
+  #[serde(alias = "m")]
+  reason: String,
+  #[serde(alias = "B")]
+  balances: Vec<BalanceMessage>,
+  #[serde(alias = "P")]
+  positions: Vec<PositionMessage>,
+}
+
+#[derive(Deserialize)]
+struct BalanceMessage {
+  #[serde(alias = "a")]
+  asset: String,
+  #[serde(alias = "wb", deserialize_with = "to_f64")]
+  balance: f64,
+}
+
+#[derive(Deserialize)]
+struct PositionMessage {
+  #[serde(alias = "s")]
+  symbol: String,
+  #[serde(alias = "ps")]
+  position_side: String,
+  #[serde(alias = "mt")]
+  margin_type: String,
+  #[serde(alias = "ep", deserialize_with = "to_f64")]
+  entry_price: f64,
+  #[serde(alias = "pa", deserialize_with = "to_f64")]
+  entry_quantity: f64,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+impl AccountCommand {
+  pub fn new() -> Self {
+    Self {
+      ..Default::default()
+    }
+  }
+
+  async fn dispatch(ctx: Ctx, content: &str, is_replay: bool) -> bool {
+    let event = match serde_json::from_str::<UserEvent>(content) {
+      Ok(event) => event,
+      Err(err) => {
+        println!("error: {}", err);
+        return true
+      }
+    };
+    match event.event.as_str() {
+      "ORDER_TRADE_UPDATE" => {
+        match serde_json::from_str::<OrderEvent>(content) {
+          Ok(event) => {
+            if let Err(err) = Self::process_order(ctx.clone(), event.message, is_replay).await {
+              println!("process order failed {err:?}");
+            }
+          }
+          Err(err) => println!("error: {}", err)
+        }
+      }
+      "ACCOUNT_UPDATE" => {
+        match serde_json::from_str::<AccountEvent>(content) {
+          Ok(event) => {
+            if let Err(err) = Self::process_account(ctx.clone(), event.timestamp, event.message, is_replay).await {
+              println!("process account failed {err:?}");
+            }
+          }
+          Err(err) => println!("error: {}", err)
+        }
+      }
+      "listenKeyExpired" => {
+        println!("listen key expired");
+        return false
+      }
+      _ => {}
+    }
+    true
+  }
+
+  async fn process_order(ctx: Ctx, message: OrderMessage, is_replay: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("process order {} {} {}", message.symbol, message.order_id, message.status);
+    let entity = match OrdersRepository::get(ctx.clone(), message.symbol.clone(), message.order_id).await {
+      Ok(Some(result)) => Some(result),
+      Ok(None) => None,
+      Err(err) => return Err(err.into()),
+    };
+    if entity.is_none() {
+      let id = xid::new().to_string();
+      match OrdersRepository::create(
+        ctx.clone(),
+        id,
+        message.symbol.to_owned(),
+        message.order_id,
+        message.order_type.to_owned(),
+        message.position_side.to_owned(),
+        message.side.to_owned(),
+        message.price,
+        message.avg_price,
+        message.stop_price,
+        message.quantity,
+        message.executed_quantity,
+        message.update_time,
+        message.update_time,
+        message.working_type.to_owned(),
+        message.price_protect,
+        message.reduce_only,
+        message.close_position,
+        message.status.to_owned(),
+        "".to_owned(),
+      ).await {
+        Ok(result) => {
+          println!("binance futures order {0:} {1:} create success {result:}", message.symbol, message.order_id);
+        }
+        Err(err) => {
+          println!("binance futures order {0:} {1:} create failed {err:?}", message.symbol, message.order_id)
+        }
+      }
+    } else {
+      let entity = entity.unwrap();
+      if entity.update_time > message.update_time {
+        return Ok(())
+      }
+      match OrdersRepository::update(
+        ctx.clone(),
+        entity.id,
+        (
+          orders::avg_price.eq(message.avg_price),
+          orders::executed_quantity.eq(message.executed_quantity),
+          orders::update_time.eq(message.update_time),
+          orders::status.eq(message.status.to_owned()),
+        ),
+      ).await {
+        Ok(result) => {
+          println!("binance futures order {0:} {1:} update success {result:}", message.symbol, message.order_id);
+        }
+        Err(err) => {
+          println!("binance futures order {0:} {1:} update failed {err:?}", message.symbol, message.order_id)
+        }
+      }
+    }
+
+    // replays only rebuild the scratch store, nothing reaches the live subscribers
+    if is_replay {
+      return Ok(())
+    }
+    let job = OrdersJob::new(ctx.clone());
+    job.update(message.symbol.as_str(), message.order_id, message.status.as_str()).await?;
+
+    Ok(())
+  }
+
+  async fn process_account(ctx: Ctx, timestamp: i64, message: AccountMessage, is_replay: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("process account {} {}", message.reason, timestamp);
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    for coin in message.balances.iter() {
+      let redis_key = format!("{}:{}", Config::REDIS_KEY_BALANCE, coin.asset);
+      let is_exists: bool = rdb.exists(&redis_key).await?;
+      if !is_exists {
+        continue
+      }
+      () = rdb.hset(&redis_key, "balance", coin.balance.to_string()).await?;
+      println!("coin balance {} {}", coin.asset, coin.balance);
+    }
+
+    for position in message.positions.iter() {
+      if position.margin_type != "cross" {
+        continue
+      }
+
+      if position.position_side != "LONG" && position.position_side != "SHORT" {
+        continue
+      }
+
+      let mut side: i32 = 1;
+      if position.position_side == "SHORT" {
+        side = 2;
+      }
+      let mut entry_quantity = position.entry_quantity;
+
+      if side == 1 && entry_quantity < 0.0 {
+        entry_quantity = 0.0;
+      }
+      if side == 2 && entry_quantity > 0.0 {
+        entry_quantity = 0.0;
+      }
+      if entry_quantity < 0.0 {
+        entry_quantity = -entry_quantity;
+      }
+
+      let entity = match PositionsRepository::get(ctx.clone(), position.symbol.clone(), side).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        if entry_quantity == 0.0 {
+          continue
+        }
+        // leverage and capital are not part of the event, take them from the account snapshot
+        if is_replay {
+          continue
+        }
+        AccountRepository::flush(ctx.clone()).await?;
+        break
+      }
+
+      let entity = entity.unwrap();
+      if entity.entry_price == position.entry_price && entity.entry_quantity == entry_quantity {
+        continue
+      }
+      match PositionsRepository::update(
+        ctx.clone(),
+        entity.id,
+        entity.version,
+        (
+          positions::entry_price.eq(position.entry_price),
+          positions::entry_quantity.eq(entry_quantity),
+          positions::timestamp.eq(Utc::now().timestamp_micros()),
+          positions::version.eq(positions::version + 1),
+        ),
+      ).await {
+        Ok(result) => {
+          println!("binance futures position {0:} {side:} update success {result:}", position.symbol);
+        }
+        Err(err) => {
+          println!("binance futures position {0:} {side:} update failed {err:?}", position.symbol)
+        }
+      }
+    }
+
+    if is_replay {
+      return Ok(())
+    }
+    let job = AccountJob::new(ctx.clone());
+    job.update(message.reason.as_str(), timestamp).await?;
+
+    Ok(())
+  }
+
+  async fn endpoint(ctx: Ctx, listen_key: Arc<Mutex<String>>) -> Result<String, Box<dyn std::error::Error>> {
+    let key = AccountRepository::listen_key(ctx.clone()).await?;
+    *listen_key.lock().await = key.clone();
+    Ok(format!("{}/ws/{}", Env::var("BINANCE_FUTURES_STREAMS_ENDPOINT"), key))
+  }
+
+  async fn callback(ctx: Ctx, content: String, is_replay: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(Self::dispatch(ctx, &content, is_replay).await)
+  }
+
+  pub async fn run(&self, ctx: Ctx, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    println!("streams account");
+    if let Some(result) = record.replay(ctx.clone(), Box::new(|ctx, content| Box::pin(Self::callback(ctx, content, true)))).await {
+      return result
+    }
+
+    let listen_key = Arc::new(Mutex::new(String::new()));
+    let keepalive = tokio::spawn(Box::pin({
+      let ctx = ctx.clone();
+      let listen_key = listen_key.clone();
+      async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1800));
+        interval.tick().await;
+        loop {
+          interval.tick().await;
+          let listen_key = listen_key.lock().await.clone();
+          if listen_key.is_empty() {
+            continue
+          }
+          match AccountRepository::keepalive(ctx.clone(), listen_key.as_str()).await {
+            Ok(_) => println!("listen key keepalive success"),
+            Err(err) => println!("listen key keepalive failed {err:?}"),
+          }
+        }
+      }
+    }));
+
+    // the user data stream is quiet without trading, binance pings every 3 minutes
+    let supervisor = StreamSupervisor::new("binance futures account", Duration::from_secs(600))
+      .recorder(record.recorder("binance futures account"));
+    let result = supervisor.run(
+      ctx.clone(),
+      Box::new(move |ctx| Box::pin(Self::endpoint(ctx, listen_key.clone()))),
+      Box::new(|ctx, content| Box::pin(Self::callback(ctx, content, false))),
+      None,
+    ).await;
+
+    keepalive.abort();
+    result
+  }
+}
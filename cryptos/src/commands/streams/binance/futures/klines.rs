@@ -1,24 +1,21 @@
-use std::time::Duration;
 use std::sync::Arc;
-use futures_util::stream::StreamExt;
+use std::time::Duration;
 
 use chrono::prelude::Utc;
 use rust_decimal::prelude::*;
 use redis::AsyncCommands;
 use serde::{Deserialize, Deserializer};
-use tokio::sync::Mutex;
-use tokio_tungstenite::{tungstenite::Message, connect_async};
 use clap::Parser;
 
 use crate::common::*;
+use crate::commands::streams::RecordArgs;
 use crate::config::binance::futures::config as Config;
+use crate::repositories::binance::futures::klines::*;
 use crate::repositories::binance::futures::scalping::*;
 
 #[derive(Parser)]
 pub struct KlinesCommand {
   interval: String,
-  #[arg(default_value_t = 1)]
-  current: u8,
 }
 
 impl Default for KlinesCommand {
@@ -83,13 +80,7 @@ impl KlinesCommand {
     let change = ((open - close) / open).round_dp(4).to_f32().unwrap();
     let timestamp = Utc::now().timestamp_millis();
 
-    let ttl: Duration = match message.interval.as_str() {
-      "1m" => Duration::from_secs(30+60),
-      "15m" => Duration::from_secs(30+900),
-      "4h" => Duration::from_secs(30+14400),
-      "1d" => Duration::from_secs(30+86400),
-      _ => panic!("invalid interval {}", message.interval)
-    };
+    let ttl = Duration::from_millis((30000 + Interval::parse(&message.interval)?.timestep()) as u64);
 
     let mut rdb = ctx.rdb.lock().await.clone();
     let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_KLINES, message.interval, message.symbol, message.timestamp);
@@ -114,69 +105,92 @@ impl KlinesCommand {
     Ok(())
   }
 
-  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    if !["1m", "15m", "4h", "1d"].iter().any(|&s| s == self.interval) {
-      return Err(Box::from("interval not valid"))
-    }
+  async fn endpoint() -> Result<String, Box<dyn std::error::Error>> {
+    Ok(format!("{}/stream", Env::var("BINANCE_FUTURES_STREAMS_ENDPOINT")))
+  }
 
-    println!("streams tickers current {}", self.current);
-    let mut symbols = ScalpingRepository::scan(ctx.clone(), 2).await.unwrap();
+  async fn dispatch(ctx: Ctx, content: String) -> Result<bool, Box<dyn std::error::Error>> {
+    if content.starts_with("{\"result\"") {
+      return Ok(true)
+    }
+    match serde_json::from_str::<KlineEvent>(&content) {
+      Ok(event) => {
+        let _ = Self::process(ctx.clone(), event.data.message).await;
+      }
+      Err(err) => println!("error: {}", err)
+    }
+    Ok(true)
+  }
 
-    if self.current < 1 {
-      return Err(Box::from("current less then 1"))
+  async fn connected(ctx: Ctx, shard: Arc<StreamShard>, interval: String, writer: StreamWriter, disconnected_at: i64) -> Result<(), Box<dyn std::error::Error>> {
+    shard.connected(writer).await?;
+    if disconnected_at > 0 {
+      Self::backfill(ctx.clone(), shard.symbols().await, interval, disconnected_at).await?;
     }
+    Ok(())
+  }
 
-    let size = Env::usize("BINANCE_FUTURES_SYMBOLS_SIZE".to_string());
-    let offset = (usize::from(self.current) - 1) * size;
-    if offset >= symbols.len() {
-      return Err(Box::from("symbols out of range"))
+  async fn backfill(ctx: Ctx, symbols: Vec<String>, interval: String, disconnected_at: i64) -> Result<(), Box<dyn std::error::Error>> {
+    let timeframe = Interval::parse(&interval)?;
+    let offset = (Utc::now().timestamp_millis() - disconnected_at) / timeframe.timestep() + 2;
+    println!("binance futures klines backfill {} {} since {}", interval, offset, disconnected_at);
+    for symbol in symbols.iter() {
+      if let Err(err) = KlinesRepository::fix(ctx.clone(), symbol.as_str(), interval.as_str(), offset).await {
+        println!("binance futures klines backfill {} {} failed {:?}", symbol, interval, err);
+      }
     }
+    Ok(())
+  }
 
-    if offset > 1 {
-      let (_, items) = symbols.split_at(offset);
-      symbols = items.to_vec();
+  async fn reshard(ctx: Ctx, shard: Arc<StreamShard>, interval: String) -> Result<(), Box<dyn std::error::Error>> {
+    let symbols = ScalpingRepository::scan(ctx.clone(), 2).await?;
+    let symbols = shard.assign(ctx.clone(), symbols).await?;
+    shard.update(symbols.iter().map(
+      |symbol| format!("{}@kline_{}", symbol.to_lowercase(), interval)
+    ).collect()).await
+  }
+
+  async fn rebalance(ctx: Ctx, shard: Arc<StreamShard>, interval: String) {
+    loop {
+      tokio::time::sleep(Duration::from_secs(10)).await;
+      if let Err(err) = Self::reshard(ctx.clone(), shard.clone(), interval.clone()).await {
+        println!("reshard failed {err:?}");
+      }
     }
+  }
+
+  pub async fn run(&self, ctx: Ctx, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    Interval::parse(&self.interval)?;
 
-    if symbols.len() > size {
-      let (items, _) = symbols.split_at(size);
-      symbols = items.to_vec();
+    println!("streams klines {}", self.interval);
+    if let Some(result) = record.replay(ctx.clone(), Box::new(|ctx, content| Box::pin(Self::dispatch(ctx, content)))).await {
+      return result
     }
 
-    let endpoint = format!(
-      "{}/stream?streams={}",
-      Env::var("BINANCE_FUTURES_STREAMS_ENDPOINT".to_string()),
-      symbols.iter().map(
-        |symbol| format!("{}@kline_{}", symbol.to_lowercase(), self.interval)
-      ).collect::<Vec<_>>().join("/"),
+    let shard = Arc::new(
+      StreamShard::new(format!("{}:klines:{}", Config::REDIS_KEY_STREAMS_SHARDS, self.interval))
+        .capacity(Env::usize("BINANCE_FUTURES_SYMBOLS_SIZE"))
     );
-    println!("endpoint {endpoint:}");
-
-    let (stream, _) = connect_async(&endpoint).await.expect("Failed to connect");
-    let (_, reader) = stream.split();
-    let reader = Arc::new(Mutex::new(reader));
-    println!("stream connected");
-    let handle = tokio::spawn(Box::pin({
-      let ctx = ctx.clone();
-      let mut reader = reader.lock_owned().await;
-      async move {
-        while let Some(message) = reader.next().await {
-          match message.unwrap() {
-            Message::Text(content) => {
-              match serde_json::from_str::<KlineEvent>(&content) {
-                Ok(event) => {
-                  let _ = Self::process(ctx.clone(), event.data.message).await;
-                }
-                Err(err) => println!("error: {}", err)
-              }
-            }
-            Message::Close(_) => break,
-            _ => continue,
-          }
-        }
-      }
-    }));
-    handle.await.expect("stream failed");
+    Self::reshard(ctx.clone(), shard.clone(), self.interval.clone()).await?;
+
+    let name = format!("binance futures klines {}", self.interval);
+    let supervisor = StreamSupervisor::new(&name, Duration::from_secs(60))
+      .recorder(record.recorder(&name));
+    let result = tokio::select! {
+      result = supervisor.run(
+        ctx.clone(),
+        Box::new(|_| Box::pin(Self::endpoint())),
+        Box::new(|ctx, content| Box::pin(Self::dispatch(ctx, content))),
+        Some(Box::new({
+          let shard = shard.clone();
+          let interval = self.interval.clone();
+          move |ctx, writer, disconnected_at| Box::pin(Self::connected(ctx, shard.clone(), interval.clone(), writer, disconnected_at))
+        })),
+      ) => result,
+      _ = Self::rebalance(ctx.clone(), shard.clone(), self.interval.clone()) => Ok(()),
+    };
 
-    Ok(())
+    let _ = shard.leave(ctx.clone()).await;
+    result
   }
 }
@@ -1,14 +1,17 @@
 use clap::{Parser, Subcommand};
 
 use crate::common::*;
+use crate::commands::streams::RecordArgs;
 use crate::commands::streams::binance::spot::api::*;
 use crate::commands::streams::binance::spot::tickers::*;
 use crate::commands::streams::binance::spot::klines::*;
+use crate::commands::streams::binance::spot::depth::*;
 
 mod api;
 mod account;
 mod tickers;
 mod klines;
+mod depth;
 
 #[derive(Parser)]
 pub struct SpotCommand {
@@ -21,19 +24,21 @@ enum Commands {
   Api(ApiCommand),
   Tickers(TickersCommand),
   Klines(KlinesCommand),
+  Depth(DepthCommand),
 }
 
 impl SpotCommand {
-  pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-    let rdb = Rdb::new(1).await.expect("bad rdb connection");
-    let rmq = Rmq::new(1).await.expect("bad rmq connection");
-    let pool = Pool::new(1).expect("bad pool connection");
+  pub async fn run(&self, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let rdb = Rdb::new(record.index(1)).await.expect("bad rdb connection");
+    let rmq = Rmq::new(record.index(1)).await.expect("bad rmq connection");
+    let pool = Pool::new(record.index(1)).expect("bad pool connection");
     let nats = Nats::new().await.expect("bad nats connection");
     let ctx = Ctx::new(rdb, rmq, pool, nats);
     match &self.commands {
       Commands::Api(api) => api.run(ctx).await,
-      Commands::Tickers(tickers) => tickers.run(ctx).await,
-      Commands::Klines(klines) => klines.run(ctx).await,
+      Commands::Tickers(tickers) => tickers.run(ctx, record).await,
+      Commands::Klines(klines) => klines.run(ctx, record).await,
+      Commands::Depth(depth) => depth.run(ctx, record).await,
     }
   }
 }
\ No newline at end of file
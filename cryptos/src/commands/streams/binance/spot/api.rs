@@ -1,12 +1,11 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_nats::Subscriber;
 use futures_util::{stream, StreamExt};
 use redis::AsyncCommands;
 use tokio::sync::Mutex;
-use tokio::task::JoinSet;
-use tokio_tungstenite::{tungstenite::Message, connect_async};
 use clap::Parser;
 
 use crate::common::*;
@@ -31,16 +30,53 @@ impl ApiCommand {
     }
   }
 
-  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    let endpoint = Env::var("BINANCE_SPOT_API_STREAMS_ENDPOINT".to_string());
-    println!("endpoint {endpoint:}");
+  async fn endpoint() -> Result<String, Box<dyn std::error::Error>> {
+    Ok(Env::var("BINANCE_SPOT_API_STREAMS_ENDPOINT"))
+  }
 
-    let (stream, _) = connect_async(&endpoint).await.expect("Failed to connect");
-    let (writer, reader) = stream.split();
-    let writer = Arc::new(Mutex::new(writer));
-    let reader = Arc::new(Mutex::new(reader));
-    println!("stream connected");
+  async fn connected(current: Arc<Mutex<Option<StreamWriter>>>, writer: StreamWriter) -> Result<(), Box<dyn std::error::Error>> {
+    *current.lock().await = Some(writer);
+    Ok(())
+  }
 
+  async fn respond(ctx: Ctx, responses: Arc<HashMap<&str, ResponseFn>>, payload: String) -> Result<bool, Box<dyn std::error::Error>> {
+    let response = match serde_json::from_str::<ApiResponse>(payload.as_ref()) {
+      Ok(response) => response,
+      Err(err) => {
+        println!("error: {}", err);
+        return Ok(true)
+      }
+    };
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    if response.status == 200 {
+      let request: String = match rdb.hget(Config::REDIS_KEY_STREAMS_API, &response.id[..]).await {
+        Ok(Some(request)) => request,
+        Ok(None) => {
+          println!("request {} not exists", response.id);
+          "".to_string()
+        }
+        Err(err) => {
+          println!("error: {}", err);
+          "".to_string()
+        }
+      };
+      if request != "" {
+        let mut values: Vec<String> = request.split(",").map(|s|s.into()).collect();
+        if let Some(method) = responses.get(&values[0][..]) {
+          values.remove(0);
+          let _ = method(ctx.clone(), values, payload.clone()).await;
+        }
+      }
+      println!("request {request}");
+    }
+    let _: bool = rdb.hdel(Config::REDIS_KEY_STREAMS_API, &response.id[..]).await?;
+    println!("response {} {}", response.id, response.status);
+
+    Ok(true)
+  }
+
+  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     let mut requests = HashMap::<&str, RequestFn>::new();
     SpotRequest::new(ctx.clone()).subscribe(&mut requests).await?;
     let client = ctx.nats.clone();
@@ -54,65 +90,38 @@ impl ApiCommand {
 
     let mut responses = HashMap::<&str, ResponseFn>::new();
     SpotResponse::new(ctx.clone()).subscribe(&mut responses).await?;
+    let responses = Arc::new(responses);
 
-    let mut workers = JoinSet::new();
-    workers.spawn(Box::pin({
+    let current: Arc<Mutex<Option<StreamWriter>>> = Arc::new(Mutex::new(None));
+    let worker = tokio::spawn(Box::pin({
       let ctx = ctx.clone();
+      let current = current.clone();
       async move {
         while let Some(message) = messages.next().await {
           if let Some(method) = requests.get(&message.subject[..]) {
-            let payload = std::str::from_utf8(&message.payload).unwrap();
-            let _ = method(ctx.clone(), writer.clone(), payload.into()).await;
-          }
-        }
-      }
-    }));
-    workers.spawn(Box::pin({
-      let ctx = ctx.clone();
-      let mut reader = reader.lock_owned().await;
-      let mut rdb = ctx.rdb.lock().await.clone();
-      async move {
-        while let Some(message) = reader.next().await {
-          match message.unwrap() {
-            Message::Text(payload) => {
-              let payload = std::str::from_utf8(&payload.as_bytes()).unwrap();
-              match serde_json::from_str::<ApiResponse>(payload.as_ref()) {
-                Ok(response) => {
-                  if response.status == 200 {
-                    let request: String = match rdb.hget(Config::REDIS_KEY_STREAMS_API, &response.id[..]).await {
-                      Ok(Some(request)) => request,
-                      Ok(None) => {
-                        println!("request {} not exists", response.id);
-                        "".to_string()
-                      }
-                      Err(err) => {
-                        println!("error: {}", err);
-                        "".to_string()
-                      }
-                    };
-                    if request != "" {
-                      let mut values: Vec<String> = request.split(",").map(|s|s.into()).collect();
-                      if let Some(method) = responses.get(&values[0][..]) {
-                        values.remove(0);
-                        let _ = method(ctx.clone(), values, payload.into()).await;
-                      }
-                    }
-                    println!("request {request}");
-                  }
-                  let _: bool = rdb.hdel(Config::REDIS_KEY_STREAMS_API, &response.id[..]).await.unwrap();
-                  println!("response {} {}", response.id, response.status);
-                }
-                Err(err) => println!("error: {}", err)
+            let writer = match current.lock().await.clone() {
+              Some(writer) => writer,
+              None => {
+                println!("stream not connected, drop request {}", message.subject);
+                continue
               }
-            }
-            Message::Close(_) => break,
-            _ => continue,
+            };
+            let payload = std::str::from_utf8(&message.payload).unwrap();
+            let _ = method(ctx.clone(), writer, payload.into()).await;
           }
         }
       }
     }));
-    let _ = workers.join_next().await;
 
-    Ok(())
+    let supervisor = StreamSupervisor::new("binance spot api", Duration::from_secs(600));
+    let result = supervisor.run(
+      ctx.clone(),
+      Box::new(|_| Box::pin(Self::endpoint())),
+      Box::new(move |ctx, payload| Box::pin(Self::respond(ctx, responses.clone(), payload))),
+      Some(Box::new(move |_, writer, _| Box::pin(Self::connected(current.clone(), writer)))),
+    ).await;
+
+    worker.abort();
+    result
   }
 }
@@ -0,0 +1,270 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::prelude::Utc;
+use rust_decimal::prelude::*;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use clap::Parser;
+
+use crate::common::*;
+use crate::commands::streams::RecordArgs;
+use crate::models::binance::spot::symbol::*;
+use crate::repositories::binance::spot::depth::*;
+use crate::repositories::binance::spot::scalping::*;
+
+#[derive(Parser)]
+pub struct DepthCommand {
+  #[clap(default_value_t = 1)]
+  current: u8,
+}
+
+impl Default for DepthCommand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[derive(Deserialize)]
+struct DepthEvent {
+  #[serde(alias = "data")]
+  message: DepthMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DepthFrame {
+  Snapshot(SnapshotEvent),
+  Update(DepthEvent),
+}
+
+#[derive(Deserialize)]
+struct SnapshotEvent {
+  symbol: String,
+  snapshot: Depth,
+}
+
+#[derive(Deserialize)]
+struct DepthMessage {
+  #[serde(alias = "s")]
+  symbol: String,
+  #[serde(alias = "U")]
+  first_update_id: i64,
+  #[serde(alias = "u")]
+  last_update_id: i64,
+  #[serde(alias = "b")]
+  bids: Vec<Vec<String>>,
+  #[serde(alias = "a")]
+  asks: Vec<Vec<String>>,
+}
+
+#[derive(Default)]
+struct OrderBook {
+  asks: BTreeMap<Decimal, String>,
+  bids: BTreeMap<Decimal, String>,
+  last_update_id: i64,
+  is_synced: bool,
+  cached_at: i64,
+}
+
+impl OrderBook {
+  fn load(&mut self, depth: Depth) {
+    self.reset();
+    Self::merge(&mut self.asks, &depth.asks);
+    Self::merge(&mut self.bids, &depth.bids);
+    self.last_update_id = depth.last_update_id;
+  }
+
+  fn apply(&mut self, message: &DepthMessage) {
+    Self::merge(&mut self.asks, &message.asks);
+    Self::merge(&mut self.bids, &message.bids);
+    self.last_update_id = message.last_update_id;
+  }
+
+  fn merge(side: &mut BTreeMap<Decimal, String>, levels: &Vec<Vec<String>>) {
+    for level in levels.iter() {
+      let price = match Decimal::from_str(&level[0]) {
+        Ok(price) => price,
+        Err(_) => continue,
+      };
+      if level[1].parse::<f64>().unwrap_or(0.0) == 0.0 {
+        side.remove(&price);
+      } else {
+        side.insert(price, level[1].clone());
+      }
+    }
+  }
+
+  fn reset(&mut self) {
+    self.asks.clear();
+    self.bids.clear();
+    self.last_update_id = 0;
+    self.is_synced = false;
+  }
+
+  fn depth(&self, limit: usize) -> Depth {
+    Depth {
+      asks: self.asks.iter().take(limit).map(|(price, quantity)| vec![price.to_string(), quantity.clone()]).collect(),
+      bids: self.bids.iter().rev().take(limit).map(|(price, quantity)| vec![price.to_string(), quantity.clone()]).collect(),
+      last_update_id: self.last_update_id,
+    }
+  }
+}
+
+impl DepthCommand {
+  pub fn new() -> Self {
+    Self {
+      ..Default::default()
+    }
+  }
+
+  async fn snapshot(ctx: Ctx, symbol: &str, recorder: Option<Arc<StreamRecorder>>) -> Result<Depth, Box<dyn std::error::Error>> {
+    let depth = DepthRepository::snapshot(ctx.clone(), symbol, 1000).await?;
+    println!("depth snapshot {} {}", symbol, depth.last_update_id);
+    // replays load the recorded snapshot instead of fetching a current one
+    if let Some(recorder) = &recorder {
+      let content = serde_json::json!({"symbol": symbol, "snapshot": depth}).to_string();
+      if let Err(err) = recorder.write(Utc::now().timestamp_millis(), &content) {
+        println!("depth snapshot {} record failed {err:?}", symbol);
+      }
+    }
+    Ok(depth)
+  }
+
+  async fn process(ctx: Ctx, book: &mut OrderBook, message: DepthMessage) -> Result<(), Box<dyn std::error::Error>> {
+    if book.last_update_id == 0 {
+      return Ok(())
+    }
+
+    if message.last_update_id <= book.last_update_id {
+      return Ok(())
+    }
+
+    if !book.is_synced {
+      if message.first_update_id > book.last_update_id + 1 {
+        book.reset();
+        return Err(Box::from(format!("depth of {} out of sync", message.symbol)))
+      }
+      book.is_synced = true;
+    } else if message.first_update_id != book.last_update_id + 1 {
+      book.reset();
+      return Err(Box::from(format!("depth of {} gap detected", message.symbol)))
+    }
+
+    book.apply(&message);
+
+    let timestamp = Utc::now().timestamp_millis();
+    if timestamp - book.cached_at >= 1000 {
+      DepthRepository::cache(ctx.clone(), &message.symbol, &book.depth(100)).await?;
+      book.cached_at = timestamp;
+    }
+
+    Ok(())
+  }
+
+  async fn endpoint(streams: String) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(format!("{}/stream?streams={}", Env::var("BINANCE_SPOT_STREAMS_ENDPOINT"), streams))
+  }
+
+  async fn dispatch(
+    ctx: Ctx,
+    books: Arc<Mutex<HashMap<String, OrderBook>>>,
+    recorder: Option<Arc<StreamRecorder>>,
+    is_replay: bool,
+    content: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    match serde_json::from_str::<DepthFrame>(&content) {
+      Ok(DepthFrame::Snapshot(event)) => {
+        let mut books = books.lock().await;
+        books.entry(event.symbol).or_default().load(event.snapshot);
+      }
+      Ok(DepthFrame::Update(event)) => {
+        let symbol = event.message.symbol.clone();
+        let is_empty = books.lock().await.get(&symbol).map(|book| book.last_update_id == 0).unwrap_or(true);
+        // replays wait for the recorded snapshot, diffs before it are dropped
+        if is_empty && !is_replay {
+          // the books lock is released while fetching, other symbols keep applying their diffs
+          let depth = match Self::snapshot(ctx.clone(), &symbol, recorder).await {
+            Ok(depth) => depth,
+            Err(err) => {
+              println!("error: {}", err);
+              return Ok(true)
+            }
+          };
+          let mut books = books.lock().await;
+          let book = books.entry(symbol.clone()).or_default();
+          if book.last_update_id == 0 {
+            book.load(depth);
+          }
+        }
+        let mut books = books.lock().await;
+        let book = books.entry(symbol).or_default();
+        if let Err(err) = Self::process(ctx.clone(), book, event.message).await {
+          println!("error: {}", err);
+        }
+      }
+      Err(err) => println!("error: {}", err)
+    }
+    Ok(true)
+  }
+
+  // updates missed while disconnected can not be replayed, start over from a snapshot
+  async fn reset(books: Arc<Mutex<HashMap<String, OrderBook>>>) -> Result<(), Box<dyn std::error::Error>> {
+    books.lock().await.clear();
+    Ok(())
+  }
+
+  pub async fn run(&self, ctx: Ctx, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    println!("streams depth current {}", self.current);
+    let books: Arc<Mutex<HashMap<String, OrderBook>>> = Arc::new(Mutex::new(HashMap::new()));
+    if let Some(result) = record.replay(ctx.clone(), Box::new({
+      let books = books.clone();
+      move |ctx, content| Box::pin(Self::dispatch(ctx, books.clone(), None, true, content))
+    })).await {
+      return result
+    }
+
+    let mut symbols = ScalpingRepository::scan(ctx.clone()).await.unwrap();
+    symbols.sort();
+    symbols.dedup();
+
+    if self.current < 1 {
+      return Err(Box::from("current less then 1"))
+    }
+
+    let size = Env::usize("BINANCE_SPOT_SYMBOLS_SIZE");
+    let offset = (usize::from(self.current) - 1) * size;
+    if offset >= symbols.len() {
+      return Err(Box::from("symbols out of range"))
+    }
+
+    if offset > 1 {
+      let (_, items) = symbols.split_at(offset);
+      symbols = items.to_vec();
+    }
+
+    if symbols.len() > size {
+      let (items, _) = symbols.split_at(size);
+      symbols = items.to_vec();
+    }
+
+    let streams = symbols.iter().map(
+      |symbol| format!("{}@depth@100ms", symbol.to_lowercase())
+    ).collect::<Vec<_>>().join("/");
+
+    let recorder = record.recorder(format!("binance spot depth {}", self.current));
+    let supervisor = StreamSupervisor::new("binance spot depth", Duration::from_secs(30))
+      .recorder(recorder.clone());
+    supervisor.run(
+      ctx.clone(),
+      Box::new(move |_| Box::pin(Self::endpoint(streams.clone()))),
+      Box::new({
+        let books = books.clone();
+        move |ctx, content| Box::pin(Self::dispatch(ctx, books.clone(), recorder.clone(), false, content))
+      }),
+      Some(Box::new(move |_, _, _| Box::pin(Self::reset(books.clone())))),
+    ).await
+  }
+}
@@ -1,23 +1,19 @@
 use std::sync::Arc;
-use futures_util::stream::StreamExt;
+use std::time::Duration;
 
 use chrono::prelude::Utc;
 use rust_decimal::prelude::*;
 use redis::AsyncCommands;
 use serde::{Deserialize, Deserializer};
-use tokio::sync::Mutex;
-use tokio_tungstenite::{tungstenite::Message, connect_async};
 use clap::Parser;
 
 use crate::common::*;
+use crate::commands::streams::RecordArgs;
 use crate::config::binance::spot::config as Config;
 use crate::repositories::binance::spot::scalping::*;
 
 #[derive(Parser)]
-pub struct TickersCommand {
-  #[clap(default_value_t = 1)]
-  current: u8,
-}
+pub struct TickersCommand {}
 
 impl Default for TickersCommand {
   fn default() -> Self {
@@ -91,65 +87,70 @@ impl TickersCommand {
     ).await.unwrap()
   }
 
-  pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    println!("streams tickers current {}", self.current);
-    let mut symbols = ScalpingRepository::scan(ctx.clone()).await.unwrap();
+  async fn endpoint() -> Result<String, Box<dyn std::error::Error>> {
+    Ok(format!("{}/stream", Env::var("BINANCE_SPOT_STREAMS_ENDPOINT")))
+  }
 
-    if self.current < 1 {
-      return Err(Box::from("current less then 1"))
+  async fn dispatch(ctx: Ctx, content: String) -> Result<bool, Box<dyn std::error::Error>> {
+    if content.starts_with("{\"result\"") {
+      return Ok(true)
     }
-
-    let size = Env::usize("BINANCE_SPOT_SYMBOLS_SIZE");
-    let offset = (usize::from(self.current) - 1) * size;
-    if offset >= symbols.len() {
-      return Err(Box::from("symbols out of range"))
+    match serde_json::from_str::<TickerEvent>(&content) {
+      Ok(event) => Self::process(ctx.clone(), event.message).await,
+      Err(err) => println!("error: {}", err),
     }
+    Ok(true)
+  }
+
+  async fn connected(shard: Arc<StreamShard>, writer: StreamWriter) -> Result<(), Box<dyn std::error::Error>> {
+    shard.connected(writer).await
+  }
 
-    if offset > 1 {
-      let (_, items) = symbols.split_at(offset);
-      symbols = items.to_vec();
+  async fn reshard(ctx: Ctx, shard: Arc<StreamShard>) -> Result<(), Box<dyn std::error::Error>> {
+    let symbols = ScalpingRepository::scan(ctx.clone()).await?;
+    let symbols = shard.assign(ctx.clone(), symbols).await?;
+    shard.update(symbols.iter().map(
+      |symbol| format!("{}@miniTicker", symbol.to_lowercase())
+    ).collect()).await
+  }
+
+  async fn rebalance(ctx: Ctx, shard: Arc<StreamShard>) {
+    loop {
+      tokio::time::sleep(Duration::from_secs(10)).await;
+      if let Err(err) = Self::reshard(ctx.clone(), shard.clone()).await {
+        println!("reshard failed {err:?}");
+      }
     }
+  }
 
-    if symbols.len() > size {
-      let (items, _) = symbols.split_at(size);
-      symbols = items.to_vec();
+  pub async fn run(&self, ctx: Ctx, record: &RecordArgs) -> Result<(), Box<dyn std::error::Error>> {
+    println!("streams tickers");
+    if let Some(result) = record.replay(ctx.clone(), Box::new(|ctx, content| Box::pin(Self::dispatch(ctx, content)))).await {
+      return result
     }
 
-    let endpoint = format!(
-      "{}/stream?streams={}",
-      Env::var("BINANCE_SPOT_STREAMS_ENDPOINT"),
-      symbols.iter().map(
-        |symbol| format!("{}@miniTicker", symbol.to_lowercase())
-      ).collect::<Vec<_>>().join("/"),
+    let shard = Arc::new(
+      StreamShard::new(format!("{}:tickers", Config::REDIS_KEY_STREAMS_SHARDS))
+        .capacity(Env::usize("BINANCE_SPOT_SYMBOLS_SIZE"))
     );
-    println!("endpoint {endpoint:}");
-
-    let (stream, _) = connect_async(&endpoint).await.expect("Failed to connect");
-    let (_, reader) = stream.split();
-    let reader = Arc::new(Mutex::new(reader));
-    println!("stream connected");
-    let handle = tokio::spawn(Box::pin({
-      let ctx = ctx.clone();
-      let mut reader = reader.lock_owned().await;
-      async move {
-        while let Some(message) = reader.next().await {
-          match message.unwrap() {
-            Message::Text(content) => {
-              match serde_json::from_str::<TickerEvent>(&content) {
-                Ok(event) => {
-                  let _ = Self::process(ctx.clone(), event.message).await;
-                }
-                Err(err) => println!("error: {}", err)
-              }
-            }
-            Message::Close(_) => break,
-            _ => continue,
-          }
-        }
-      }
-    }));
-    handle.await.expect("stream failed");
-
-    Ok(())
+    Self::reshard(ctx.clone(), shard.clone()).await?;
+
+    let supervisor = StreamSupervisor::new("binance spot tickers", Duration::from_secs(60))
+      .recorder(record.recorder("binance spot tickers"));
+    let result = tokio::select! {
+      result = supervisor.run(
+        ctx.clone(),
+        Box::new(|_| Box::pin(Self::endpoint())),
+        Box::new(|ctx, content| Box::pin(Self::dispatch(ctx, content))),
+        Some(Box::new({
+          let shard = shard.clone();
+          move |_, writer, _| Box::pin(Self::connected(shard.clone(), writer))
+        })),
+      ) => result,
+      _ = Self::rebalance(ctx.clone(), shard.clone()) => Ok(()),
+    };
+
+    let _ = shard.leave(ctx.clone()).await;
+    result
   }
 }
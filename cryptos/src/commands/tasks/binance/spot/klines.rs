@@ -1,11 +1,20 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
 
+use chrono::prelude::Utc;
+use sha2::{Sha256, Digest};
 use clap::{Parser, Args, Subcommand};
 
 use crate::common::*;
 use crate::config::binance::spot::config as Config;
+use crate::models::binance::spot::kline::*;
 use crate::repositories::binance::spot::klines::*;
+use crate::repositories::binance::spot::klines::audits::*;
 use crate::repositories::binance::spot::scalping::*;
+use crate::queue::rsmq::jobs::binance::spot::klines::*;
 
 #[derive(Parser)]
 pub struct KlinesCommand {
@@ -25,6 +34,10 @@ enum Commands {
   Flush(FlushArgs),
   /// klines fix
   Fix(FixArgs),
+  /// klines import
+  Import(ImportArgs),
+  /// klines audit
+  Audit(AuditArgs),
 }
 
 #[derive(Args)]
@@ -43,6 +56,24 @@ struct FixArgs {
   current: u8,
 }
 
+#[derive(Args)]
+struct ImportArgs {
+  /// directory of data.binance.vision zip files
+  path: String,
+}
+
+#[derive(Args)]
+struct AuditArgs {
+  /// interval 1m 15m 4h 1d, all configured intervals when omitted
+  interval: Option<String>,
+  /// days to look back
+  #[arg(long, default_value_t = 30)]
+  days: i64,
+  /// queue flush jobs to repair the findings
+  #[arg(long)]
+  repair: bool,
+}
+
 impl KlinesCommand {
   pub fn new() -> Self {
     Self {
@@ -164,10 +195,221 @@ impl KlinesCommand {
     Ok(())
   }
 
+  async fn import(&self, ctx: Ctx, path: String) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance spot tasks klines import {}", path);
+
+    let mut files = Vec::new();
+    Self::scan(Path::new(&path), &mut files)?;
+    files.sort();
+
+    let mut ranges: HashMap<(String, String), (i64, i64)> = HashMap::new();
+    for file in files.iter() {
+      let name = file.file_stem().unwrap().to_string_lossy().to_string();
+      let parts = name.split("-").collect::<Vec<_>>();
+      if parts.len() < 3 {
+        println!("skip {}", file.display());
+        continue
+      }
+      let symbol = parts[0].to_string();
+      let interval = parts[1].to_string();
+      let timeframe = match Interval::parse(&interval) {
+        Ok(timeframe) => timeframe,
+        Err(err) => {
+          println!("skip {} {}", file.display(), err);
+          continue
+        }
+      };
+
+      if let Err(err) = Self::verify(file) {
+        println!("error: {}", err);
+        continue
+      }
+
+      let klines = match Self::read(file, &symbol, &interval) {
+        Ok(klines) => klines,
+        Err(err) => {
+          println!("error: {} {}", file.display(), err);
+          continue
+        }
+      };
+      if klines.is_empty() {
+        continue
+      }
+
+      let start = klines.first().unwrap().timestamp;
+      let end = klines.last().unwrap().timestamp;
+      let count = klines.len();
+      match KlinesRepository::upsert(ctx.clone(), klines).await {
+        Ok(effective_rows) => println!("binance spot klines import {} {} rows {} upsert {}", symbol, interval, count, effective_rows),
+        Err(err) => {
+          println!("error: {} {}", file.display(), err);
+          continue
+        }
+      }
+
+      let range = ranges.entry((symbol, timeframe.to_string())).or_insert((start, end));
+      range.0 = range.0.min(start);
+      range.1 = range.1.max(end);
+    }
+
+    for ((symbol, interval), (start, end)) in ranges.iter() {
+      let gaps = KlinesRepository::gaps(ctx.clone(), symbol, interval, *start, *end).await?;
+      if gaps.is_empty() {
+        println!("binance spot klines {} {} from {} to {} no gaps", symbol, interval, start, end);
+        continue
+      }
+      for (from, to) in gaps.iter() {
+        println!("binance spot klines {} {} gap from {} to {}", symbol, interval, from, to);
+      }
+    }
+
+    Ok(())
+  }
+
+  fn scan(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    if path.is_file() {
+      if path.extension().is_some_and(|ext| ext == "zip") {
+        files.push(path.to_path_buf());
+      }
+      return Ok(())
+    }
+    for entry in fs::read_dir(path)? {
+      Self::scan(&entry?.path(), files)?;
+    }
+    Ok(())
+  }
+
+  fn verify(file: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let checksum_file = PathBuf::from(format!("{}.CHECKSUM", file.display()));
+    if !checksum_file.exists() {
+      return Err(Box::from(format!("checksum of {} not exists", file.display())))
+    }
+    let content = fs::read_to_string(&checksum_file)?;
+    let expected = match content.split_whitespace().next() {
+      Some(expected) => expected.to_lowercase(),
+      None => return Err(Box::from(format!("checksum of {} is empty", file.display()))),
+    };
+    let digest = hex::encode(Sha256::digest(fs::read(file)?));
+    if digest != expected {
+      return Err(Box::from(format!("checksum of {} mismatch", file.display())))
+    }
+    Ok(())
+  }
+
+  fn read(file: &Path, symbol: &str, interval: &str) -> Result<Vec<Kline>, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(file)?)?;
+    let reader = BufReader::new(archive.by_index(0)?);
+
+    let now = Utc::now();
+    let mut klines = Vec::new();
+    for line in reader.lines() {
+      let line = line?;
+      let fields = line.trim().split(",").collect::<Vec<_>>();
+      if fields.len() < 8 {
+        continue
+      }
+      // newer archives ship a header row
+      let mut timestamp = match fields[0].parse::<i64>() {
+        Ok(timestamp) => timestamp,
+        Err(_) => continue,
+      };
+      // spot archives switched to microseconds since 2025
+      if timestamp > 99999999999999 {
+        timestamp /= 1000;
+      }
+      klines.push(Kline::new(
+        xid::new().to_string(),
+        symbol.to_string(),
+        interval.to_string(),
+        fields[1].parse::<f64>()?,
+        fields[4].parse::<f64>()?,
+        fields[2].parse::<f64>()?,
+        fields[3].parse::<f64>()?,
+        fields[5].parse::<f64>()?,
+        fields[7].parse::<f64>()?,
+        timestamp,
+        now,
+        now,
+      ));
+    }
+    klines.sort_by_key(|kline| kline.timestamp);
+
+    Ok(klines)
+  }
+
+  async fn audit(&self, ctx: Ctx, interval: Option<String>, days: i64, repair: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance spot tasks klines audit {:?} {} {}", interval, days, repair);
+
+    let intervals = match interval {
+      Some(interval) => vec![Interval::parse(interval)?],
+      None => Interval::from_env("BINANCE_SPOT_INTERVALS"),
+    };
+
+    let job = KlinesJob::new(ctx.clone());
+    for timeframe in intervals.iter() {
+      let interval = timeframe.as_str();
+      let end = timeframe.prev(timeframe.timestamp());
+      let start = timeframe.align(end - days * 86400000);
+
+      let symbols = AuditsRepository::symbols(ctx.clone(), interval).await?;
+      for symbol in symbols.iter() {
+        let audits = match AuditsRepository::scan(ctx.clone(), symbol.as_str(), interval, start, end).await {
+          Ok(audits) => audits,
+          Err(err) => {
+            println!("binance spot klines audit {} {} failed {:?}", symbol, interval, err);
+            continue
+          }
+        };
+
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for audit in audits.iter() {
+          *counts.entry(audit.kind.clone()).or_default() += 1;
+        }
+        if counts.is_empty() {
+          println!("binance spot klines audit {} {} clean", symbol, interval);
+        } else {
+          println!(
+            "binance spot klines audit {} {} {}",
+            symbol,
+            interval,
+            counts.iter().map(|(kind, count)| format!("{kind:}={count:}")).collect::<Vec<_>>().join(" "),
+          );
+        }
+
+        if repair {
+          for audit in audits.iter() {
+            match audit.kind.as_str() {
+              "duplicate" | "ohlc" | "outlier" => {
+                job.flush(symbol.as_str(), interval, audit.timestamp, 1).await?;
+              }
+              _ => (),
+            }
+          }
+          for (from, to) in KlinesRepository::gaps(ctx.clone(), symbol.as_str(), interval, start, end).await? {
+            let mut endtime = to;
+            let mut count = (to - from) / timeframe.timestep() + 1;
+            while count > 0 {
+              let limit = count.min(1000);
+              job.flush(symbol.as_str(), interval, endtime, limit).await?;
+              endtime -= limit * timeframe.timestep();
+              count -= limit;
+            }
+          }
+        }
+
+        AuditsRepository::replace(ctx.clone(), symbol.as_str(), interval, audits).await?;
+      }
+    }
+
+    Ok(())
+  }
+
   pub async fn run(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     match &self.commands {
       Commands::Flush(args) => self.flush(ctx.clone(), args.interval.clone(), args.current).await,
       Commands::Fix(args) => self.fix(ctx.clone(), args.interval.clone(), args.current).await,
+      Commands::Import(args) => self.import(ctx.clone(), args.path.clone()).await,
+      Commands::Audit(args) => self.audit(ctx.clone(), args.interval.clone(), args.days, args.repair).await,
     }
   }
 }
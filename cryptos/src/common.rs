@@ -9,6 +9,10 @@ pub mod ctx;
 pub mod mutex;
 pub mod func;
 pub mod value;
+pub mod interval;
+pub mod stream;
+pub mod shard;
+pub mod recorder;
 
 pub use env::*;
 pub use rdb::*;
@@ -20,4 +24,8 @@ pub use rsmq::*;
 pub use ctx::*;
 pub use mutex::*;
 pub use func::*;
-pub use value::*;
\ No newline at end of file
+pub use value::*;
+pub use interval::*;
+pub use stream::*;
+pub use shard::*;
+pub use recorder::*;
\ No newline at end of file
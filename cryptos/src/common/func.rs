@@ -10,5 +10,9 @@ use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
 use crate::common::Ctx;
 
 pub type EventFn = Box<dyn Fn(Ctx, String) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
-pub type RequestFn = Box<dyn Fn(Ctx, Arc<Mutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>, String) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
-pub type ResponseFn = Box<dyn Fn(Ctx, Vec<String>, String) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
\ No newline at end of file
+pub type StreamWriter = Arc<Mutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>;
+pub type RequestFn = Box<dyn Fn(Ctx, StreamWriter, String) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
+pub type ResponseFn = Box<dyn Fn(Ctx, Vec<String>, String) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
+pub type EndpointFn = Box<dyn Fn(Ctx) -> Pin<Box<dyn Future<Output = Result<String, Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
+pub type StreamFn = Box<dyn Fn(Ctx, String) -> Pin<Box<dyn Future<Output = Result<bool, Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
+pub type ConnectFn = Box<dyn Fn(Ctx, StreamWriter, i64) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + Send + 'static >> + Send + Sync + 'static>;
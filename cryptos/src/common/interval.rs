@@ -0,0 +1,190 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{prelude::*, Months};
+
+use crate::common::env::Env;
+
+const MINUTE: i64 = 60000;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Interval {
+  Minute1,
+  Minute3,
+  Minute5,
+  Minute15,
+  Minute30,
+  Hour1,
+  Hour2,
+  Hour4,
+  Hour6,
+  Hour8,
+  Hour12,
+  Day1,
+  Day3,
+  Week1,
+  Month1,
+}
+
+impl Interval {
+  pub const ALL: [Interval; 15] = [
+    Interval::Minute1,
+    Interval::Minute3,
+    Interval::Minute5,
+    Interval::Minute15,
+    Interval::Minute30,
+    Interval::Hour1,
+    Interval::Hour2,
+    Interval::Hour4,
+    Interval::Hour6,
+    Interval::Hour8,
+    Interval::Hour12,
+    Interval::Day1,
+    Interval::Day3,
+    Interval::Week1,
+    Interval::Month1,
+  ];
+
+  pub fn parse<T>(interval: T) -> Result<Self, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    match Self::from_str(interval.as_ref()) {
+      Ok(result) => Ok(result),
+      Err(err) => Err(Box::from(err)),
+    }
+  }
+
+  pub fn from_env<T>(key: T) -> Vec<Self>
+  where
+    T: AsRef<str>
+  {
+    let value = Env::var(key);
+    if value.is_empty() {
+      return vec![Interval::Minute1, Interval::Minute15, Interval::Hour4, Interval::Day1]
+    }
+    value.split(",").filter_map(|s| Self::from_str(s.trim()).ok()).collect()
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Interval::Minute1 => "1m",
+      Interval::Minute3 => "3m",
+      Interval::Minute5 => "5m",
+      Interval::Minute15 => "15m",
+      Interval::Minute30 => "30m",
+      Interval::Hour1 => "1h",
+      Interval::Hour2 => "2h",
+      Interval::Hour4 => "4h",
+      Interval::Hour6 => "6h",
+      Interval::Hour8 => "8h",
+      Interval::Hour12 => "12h",
+      Interval::Day1 => "1d",
+      Interval::Day3 => "3d",
+      Interval::Week1 => "1w",
+      Interval::Month1 => "1M",
+    }
+  }
+
+  // nominal length in milliseconds, a month is counted as 30 days
+  pub fn timestep(&self) -> i64 {
+    match self {
+      Interval::Minute1 => MINUTE,
+      Interval::Minute3 => 3 * MINUTE,
+      Interval::Minute5 => 5 * MINUTE,
+      Interval::Minute15 => 15 * MINUTE,
+      Interval::Minute30 => 30 * MINUTE,
+      Interval::Hour1 => HOUR,
+      Interval::Hour2 => 2 * HOUR,
+      Interval::Hour4 => 4 * HOUR,
+      Interval::Hour6 => 6 * HOUR,
+      Interval::Hour8 => 8 * HOUR,
+      Interval::Hour12 => 12 * HOUR,
+      Interval::Day1 => DAY,
+      Interval::Day3 => 3 * DAY,
+      Interval::Week1 => WEEK,
+      Interval::Month1 => 30 * DAY,
+    }
+  }
+
+  // seconds a plan stays valid before it is dropped
+  pub fn expiry(&self) -> i64 {
+    match self {
+      Interval::Minute1 => 900,
+      Interval::Minute3 => 1200,
+      Interval::Minute5 => 1800,
+      Interval::Minute15 => 2700,
+      Interval::Minute30 => 3600,
+      Interval::Hour1 => 3600,
+      Interval::Hour2 => 4500,
+      Interval::Hour4 => 5400,
+      Interval::Hour6 => 7200,
+      Interval::Hour8 => 10800,
+      Interval::Hour12 => 14400,
+      Interval::Day1 => 21600,
+      Interval::Day3 => 43200,
+      Interval::Week1 => 86400,
+      Interval::Month1 => 172800,
+    }
+  }
+
+  pub fn align(&self, timestamp: i64) -> i64 {
+    match self {
+      // binance weeks open on monday, the epoch was a thursday
+      Interval::Week1 => timestamp - (timestamp - 4 * DAY).rem_euclid(WEEK),
+      Interval::Month1 => {
+        let datetime = DateTime::from_timestamp_millis(timestamp).unwrap();
+        Utc.with_ymd_and_hms(datetime.year(), datetime.month(), 1, 0, 0, 0)
+          .unwrap()
+          .timestamp_millis()
+      }
+      _ => timestamp - timestamp.rem_euclid(self.timestep()),
+    }
+  }
+
+  pub fn next(&self, timestamp: i64) -> i64 {
+    let timestamp = self.align(timestamp);
+    match self {
+      Interval::Month1 => {
+        let datetime = DateTime::from_timestamp_millis(timestamp).unwrap();
+        datetime.checked_add_months(Months::new(1)).unwrap().timestamp_millis()
+      }
+      _ => timestamp + self.timestep(),
+    }
+  }
+
+  pub fn prev(&self, timestamp: i64) -> i64 {
+    let timestamp = self.align(timestamp);
+    match self {
+      Interval::Month1 => {
+        let datetime = DateTime::from_timestamp_millis(timestamp).unwrap();
+        datetime.checked_sub_months(Months::new(1)).unwrap().timestamp_millis()
+      }
+      _ => timestamp - self.timestep(),
+    }
+  }
+
+  pub fn timestamp(&self) -> i64 {
+    self.align(Utc::now().timestamp_millis())
+  }
+}
+
+impl FromStr for Interval {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match Interval::ALL.iter().find(|interval| interval.as_str() == s) {
+      Some(interval) => Ok(*interval),
+      None => Err(format!("interval {s:} not valid")),
+    }
+  }
+}
+
+impl fmt::Display for Interval {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
@@ -0,0 +1,131 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, prelude::Utc};
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+
+use crate::common::{Ctx, StreamFn};
+
+// raw frames as `timestamp\tcontent` lines, one gzip file per stream, hour and process
+pub struct StreamRecorder {
+  path: PathBuf,
+  current: Mutex<Option<(String, GzEncoder<File>, i64)>>,
+}
+
+impl StreamRecorder {
+  pub fn new<T>(path: T, name: T) -> Self
+  where
+    T: AsRef<str>
+  {
+    let name = name.as_ref().replace(" ", "-");
+    Self {
+      path: Path::new(path.as_ref()).join(name),
+      current: Mutex::new(None),
+    }
+  }
+
+  pub fn write(&self, timestamp: i64, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = match DateTime::from_timestamp_millis(timestamp) {
+      Some(datetime) => format!("{}-{}.gz", datetime.format("%Y%m%d%H"), std::process::id()),
+      None => return Err(Box::from(format!("timestamp {timestamp:} not valid"))),
+    };
+
+    let mut current = self.current.lock().unwrap();
+    if current.as_ref().map(|(name, _, _)| name != &filename).unwrap_or(true) {
+      if let Some((_, encoder, _)) = current.take() {
+        encoder.finish()?;
+      }
+      fs::create_dir_all(&self.path)?;
+      // a restart within the hour appends another gzip member to the same file
+      let file = OpenOptions::new().create(true).append(true).open(self.path.join(&filename))?;
+      *current = Some((filename, GzEncoder::new(file, Compression::default()), 0));
+    }
+
+    let (_, encoder, flushed_at) = current.as_mut().unwrap();
+    writeln!(encoder, "{}\t{}", timestamp, content)?;
+    let now = Utc::now().timestamp_millis();
+    if now - *flushed_at >= 1000 {
+      encoder.flush()?;
+      *flushed_at = now;
+    }
+
+    Ok(())
+  }
+}
+
+impl Drop for StreamRecorder {
+  fn drop(&mut self) {
+    if let Ok(mut current) = self.current.lock() {
+      if let Some((_, encoder, _)) = current.take() {
+        let _ = encoder.finish();
+      }
+    }
+  }
+}
+
+pub struct StreamReplayer {
+  files: Vec<PathBuf>,
+}
+
+impl StreamReplayer {
+  pub fn new<T>(path: T) -> Result<Self, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let path = Path::new(path.as_ref());
+    let mut files = Vec::new();
+    if path.is_dir() {
+      for entry in fs::read_dir(path)? {
+        let entry = entry?.path();
+        if entry.extension().map(|extension| extension == "gz").unwrap_or(false) {
+          files.push(entry);
+        }
+      }
+      files.sort();
+    } else {
+      files.push(path.to_path_buf());
+    }
+    if files.is_empty() {
+      return Err(Box::from(format!("no recordings in {}", path.display())))
+    }
+    Ok(Self { files: files })
+  }
+
+  // feeds recorded frames to the stream callback, keeping their spacing divided
+  // by speed, a speed of 0 replays as fast as the callback allows
+  pub async fn run(&self, ctx: Ctx, callback: StreamFn, speed: f64) -> Result<(), Box<dyn std::error::Error>> {
+    let mut count = 0;
+    let mut last_timestamp = 0;
+    for file in self.files.iter() {
+      println!("stream replay {}", file.display());
+      let reader = BufReader::new(MultiGzDecoder::new(File::open(file)?));
+      for line in reader.lines() {
+        // recordings cut short by a crash end in a truncated gzip member
+        let line = match line {
+          Ok(line) => line,
+          Err(err) => {
+            println!("stream replay {} truncated {err:?}", file.display());
+            break
+          }
+        };
+        let (timestamp, content) = match line.split_once("\t") {
+          Some((timestamp, content)) => (timestamp.parse::<i64>()?, content),
+          None => continue,
+        };
+        if speed > 0.0 && last_timestamp > 0 && timestamp > last_timestamp {
+          tokio::time::sleep(Duration::from_millis(((timestamp - last_timestamp) as f64 / speed) as u64)).await;
+        }
+        last_timestamp = last_timestamp.max(timestamp);
+        if let Err(err) = callback(ctx.clone(), content.to_string()).await {
+          println!("error: {}", err);
+        }
+        count += 1;
+      }
+    }
+    println!("stream replay finished {} frames", count);
+    Ok(())
+  }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use futures_util::SinkExt;
+use redis::AsyncCommands;
+use sha2::{Sha256, Digest};
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::common::{Ctx, StreamWriter};
+
+const HEARTBEAT_TIMEOUT: i64 = 30000;
+// binance accepts 5 messages a second on spot and 10 on futures
+const STREAMS_PER_MESSAGE: usize = 100;
+const MESSAGE_INTERVAL: u64 = 250;
+// futures caps a connection at 200 streams, spot at 1024
+const STREAMS_PER_CONNECTION: usize = 200;
+
+pub struct StreamShard {
+  key: String,
+  id: String,
+  requests: AtomicU64,
+  capacity: usize,
+  symbols: Mutex<Vec<String>>,
+  streams: Mutex<Vec<String>>,
+  subscribed: Mutex<Vec<String>>,
+  writer: Mutex<Option<StreamWriter>>,
+}
+
+impl StreamShard {
+  pub fn new<T>(key: T) -> Self
+  where
+    T: AsRef<str>
+  {
+    Self {
+      key: key.as_ref().to_string(),
+      id: xid::new().to_string(),
+      requests: AtomicU64::new(1),
+      capacity: STREAMS_PER_CONNECTION,
+      symbols: Mutex::new(Vec::new()),
+      streams: Mutex::new(Vec::new()),
+      subscribed: Mutex::new(Vec::new()),
+      writer: Mutex::new(None),
+    }
+  }
+
+  pub fn capacity(mut self, capacity: usize) -> Self {
+    if capacity > 0 {
+      self.capacity = capacity;
+    }
+    self
+  }
+
+  // heartbeat and take the symbols whose rendezvous hash ranks this worker first,
+  // so a changed symbol set or worker count only moves the affected symbols
+  pub async fn assign(&self, ctx: Ctx, symbols: Vec<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let timestamp = Utc::now().timestamp_millis();
+    let redis_key = format!("{}:workers", self.key);
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    () = rdb.zadd(&redis_key, &self.id, timestamp).await?;
+    () = rdb.zrembyscore(&redis_key, 0, timestamp - HEARTBEAT_TIMEOUT).await?;
+    let mut workers: Vec<String> = rdb.zrange(&redis_key, 0, -1).await?;
+    if !workers.contains(&self.id) {
+      workers.push(self.id.clone());
+    }
+
+    let mut symbols = symbols;
+    symbols.sort();
+    symbols.dedup();
+
+    // every worker fills the same workers in the same order, a symbol whose first ranked
+    // worker is full moves to the next one, so no connection exceeds its stream limit
+    let mut loads: HashMap<&String, usize> = HashMap::new();
+    let mut assigned = Vec::new();
+    let mut dropped = 0;
+    for symbol in symbols.iter() {
+      let mut ranked = workers.iter().collect::<Vec<_>>();
+      ranked.sort_by_key(|worker| std::cmp::Reverse(Self::weight(worker, symbol)));
+      match ranked.into_iter().find(|worker| loads.get(worker).copied().unwrap_or(0) < self.capacity) {
+        Some(owner) => {
+          *loads.entry(owner).or_default() += 1;
+          if *owner == self.id {
+            assigned.push(symbol.clone());
+          }
+        }
+        None => dropped += 1,
+      }
+    }
+    if dropped > 0 {
+      println!("shard {} {} workers can not hold {} symbols", self.key, workers.len(), dropped);
+    }
+
+    let mut current = self.symbols.lock().await;
+    if *current != assigned {
+      println!("shard {} worker {} of {} assigned {} symbols", self.key, self.id, workers.len(), assigned.len());
+      *current = assigned.clone();
+    }
+
+    Ok(assigned)
+  }
+
+  pub async fn symbols(&self) -> Vec<String> {
+    self.symbols.lock().await.clone()
+  }
+
+  pub async fn connected(&self, writer: StreamWriter) -> Result<(), Box<dyn std::error::Error>> {
+    *self.writer.lock().await = Some(writer);
+    self.subscribed.lock().await.clear();
+    self.sync().await
+  }
+
+  pub async fn update(&self, streams: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    *self.streams.lock().await = streams;
+    self.sync().await
+  }
+
+  pub async fn leave(&self, ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    () = rdb.zrem(format!("{}:workers", self.key), &self.id).await?;
+    Ok(())
+  }
+
+  async fn sync(&self) -> Result<(), Box<dyn std::error::Error>> {
+    let mut subscribed = self.subscribed.lock().await;
+    let writer = match self.writer.lock().await.clone() {
+      Some(writer) => writer,
+      None => return Ok(()),
+    };
+    let streams = self.streams.lock().await.clone();
+
+    let removed = subscribed.iter().filter(|stream| !streams.contains(stream)).cloned().collect::<Vec<_>>();
+    let added = streams.iter().filter(|stream| !subscribed.contains(stream)).cloned().collect::<Vec<_>>();
+    if removed.is_empty() && added.is_empty() {
+      return Ok(())
+    }
+
+    self.send(&writer, "UNSUBSCRIBE", &removed).await?;
+    self.send(&writer, "SUBSCRIBE", &added).await?;
+    println!("shard {} subscribe {} unsubscribe {}", self.key, added.len(), removed.len());
+    *subscribed = streams;
+
+    Ok(())
+  }
+
+  async fn send(&self, writer: &StreamWriter, method: &str, streams: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    for chunk in streams.chunks(STREAMS_PER_MESSAGE) {
+      let content = serde_json::to_string(&serde_json::json!({
+        "method": method,
+        "params": chunk,
+        "id": self.requests.fetch_add(1, Ordering::SeqCst),
+      }))?;
+      writer.lock().await.send(Message::text(content)).await?;
+      tokio::time::sleep(Duration::from_millis(MESSAGE_INTERVAL)).await;
+    }
+    Ok(())
+  }
+
+  fn weight(worker: &str, symbol: &str) -> u64 {
+    let digest = Sha256::digest(format!("{}:{}", worker, symbol));
+    u64::from_be_bytes(digest[..8].try_into().unwrap())
+  }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::Mutex;
+use tokio_tungstenite::{tungstenite::Message, connect_async};
+
+use crate::common::{Ctx, EndpointFn, StreamFn, ConnectFn, StreamRecorder};
+
+// binance closes every connection after 24 hours
+const ROTATE_AFTER: i64 = 23 * 3600000;
+const BACKOFF_MAX: u64 = 60;
+
+pub struct StreamSupervisor {
+  name: String,
+  stale: Duration,
+  recorder: Option<Arc<StreamRecorder>>,
+}
+
+impl StreamSupervisor {
+  pub fn new<T>(name: T, stale: Duration) -> Self
+  where
+    T: AsRef<str>
+  {
+    Self {
+      name: name.as_ref().to_string(),
+      stale: stale,
+      recorder: None,
+    }
+  }
+
+  pub fn recorder(mut self, recorder: Option<Arc<StreamRecorder>>) -> Self {
+    self.recorder = recorder;
+    self
+  }
+
+  pub async fn run(
+    &self,
+    ctx: Ctx,
+    endpoint: EndpointFn,
+    callback: StreamFn,
+    connected: Option<ConnectFn>,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    let mut backoff = 1;
+    let mut disconnected_at = 0;
+    loop {
+      let url = match endpoint(ctx.clone()).await {
+        Ok(url) => url,
+        Err(err) => {
+          println!("stream {} endpoint failed {err:?}", self.name);
+          Self::wait(&mut backoff).await;
+          continue
+        }
+      };
+      println!("stream {} endpoint {url:}", self.name);
+
+      let stream = match connect_async(&url).await {
+        Ok((stream, _)) => stream,
+        Err(err) => {
+          println!("stream {} connect failed {err:?}", self.name);
+          Self::wait(&mut backoff).await;
+          continue
+        }
+      };
+      println!("stream {} connected", self.name);
+      backoff = 1;
+
+      let (writer, mut reader) = stream.split();
+      let writer = Arc::new(Mutex::new(writer));
+      if let Some(connected) = &connected {
+        if let Err(err) = connected(ctx.clone(), writer.clone(), disconnected_at).await {
+          println!("stream {} connected callback failed {err:?}", self.name);
+        }
+      }
+
+      let connected_at = Utc::now().timestamp_millis();
+      loop {
+        if Utc::now().timestamp_millis() - connected_at > ROTATE_AFTER {
+          println!("stream {} rotate", self.name);
+          break
+        }
+        let message = match tokio::time::timeout(self.stale, reader.next()).await {
+          Ok(Some(Ok(message))) => message,
+          Ok(Some(Err(err))) => {
+            println!("stream {} error {err:?}", self.name);
+            break
+          }
+          Ok(None) => break,
+          Err(_) => {
+            println!("stream {} stale for {:?}", self.name, self.stale);
+            break
+          }
+        };
+        match message {
+          Message::Text(content) => {
+            let received_at = Utc::now().timestamp_millis();
+            let result = callback(ctx.clone(), content.to_string()).await;
+            // written after the callback so frames it records, like depth snapshots, replay first
+            if let Some(recorder) = &self.recorder {
+              if let Err(err) = recorder.write(received_at, content.as_str()) {
+                println!("stream {} record failed {err:?}", self.name);
+              }
+            }
+            match result {
+              Ok(true) => (),
+              Ok(false) => break,
+              Err(err) => println!("error: {}", err),
+            }
+          }
+          Message::Ping(payload) => {
+            if let Err(err) = writer.lock().await.send(Message::Pong(payload)).await {
+              println!("stream {} pong failed {err:?}", self.name);
+              break
+            }
+          }
+          Message::Close(_) => break,
+          _ => continue,
+        }
+      }
+
+      let _ = writer.lock().await.close().await;
+      disconnected_at = Utc::now().timestamp_millis();
+      println!("stream {} disconnected", self.name);
+      Self::wait(&mut backoff).await;
+    }
+  }
+
+  async fn wait(backoff: &mut u64) {
+    tokio::time::sleep(Duration::from_secs(*backoff)).await;
+    *backoff = (*backoff * 2).min(BACKOFF_MAX);
+  }
+}
@@ -1,3 +1,4 @@
 pub mod spot;
 pub mod futures;
+pub mod delivery;
 pub mod margin;
\ No newline at end of file
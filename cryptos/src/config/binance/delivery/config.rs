@@ -0,0 +1,21 @@
+pub const REDIS_KEY_BALANCE: &str = "binance:delivery:balance";
+pub const REDIS_KEY_CURRENCIES: &str = "binance:delivery:currencies";
+pub const REDIS_KEY_TICKERS: &str = "binance:delivery:realtime";
+pub const REDIS_KEY_TICKERS_FLUSH: &str = "binance:delivery:tickers:flush";
+pub const REDIS_KEY_KLINES: &str = "binance:delivery:klines";
+pub const REDIS_KEY_INDICATORS: &str = "binance:delivery:indicators";
+pub const REDIS_KEY_EXPIRY: &str = "binance:delivery:expiry";
+pub const REDIS_KEY_API_WEIGHT: &str = "binance:delivery:api:weight";
+pub const REDIS_KEY_API_ORDERS: &str = "binance:delivery:api:orders";
+pub const REDIS_KEY_API_BANNED: &str = "binance:delivery:api:banned";
+pub const REDIS_KEY_TIME_OFFSET: &str = "binance:delivery:time:offset";
+pub const NATS_EVENTS_TIME_DRIFT: &str = "binance.delivery.time.drift";
+// days before delivery a quarterly contract stops taking new positions
+pub const EXPIRY_ROLLOVER_DAYS: i64 = 3;
+pub const LOCKS_ACCOUNT_FLUSH: &str = "locks:binance:delivery:account:flush";
+pub const LOCKS_SYMBOLS_FLUSH: &str = "locks:binance:delivery:symbols:flush";
+pub const LOCKS_TICKERS_FLUSH: &str = "locks:binance:delivery:tickers:flush";
+pub const LOCKS_KLINES_FLUSH: &str = "locks:binance:delivery:klines:flush";
+pub const LOCKS_INDICATORS_FLUSH: &str = "locks:binance:delivery:indicators:flush";
+pub const LOCKS_ORDERS_SYNC: &str = "locks:binance:delivery:orders:sync";
+pub const LOCKS_EXPIRY_CHECK: &str = "locks:binance:delivery:expiry:check";
@@ -3,12 +3,27 @@ pub const REDIS_KEY_CURRENCIES: &str = "binance:futures:currencies";
 pub const REDIS_KEY_TICKERS: &str = "binance:futures:realtime";
 pub const REDIS_KEY_TICKERS_FLUSH: &str = "binance:futures:tickers:flush";
 pub const REDIS_KEY_KLINES: &str = "binance:futures:klines";
+pub const REDIS_KEY_DEPTH: &str = "binance:futures:depth";
 pub const REDIS_KEY_INDICATORS: &str = "binance:futures:indicators";
+pub const REDIS_KEY_INDICATORS_HISTORY: &str = "binance:futures:indicators:history";
 pub const REDIS_KEY_TRADINGS_LAST_PRICE: &str = "binance:futures:tradings:last:price";
 // pub const REDIS_KEY_TRADINGS_TRIGGERS_PLACE: &str = "binance:futures:tradings:triggers:place";
 pub const REDIS_KEY_STREAMS_API: &str = "binance:futures:streams:api";
+pub const REDIS_KEY_STREAMS_SHARDS: &str = "binance:futures:streams:shards";
+pub const REDIS_KEY_API_WEIGHT: &str = "binance:futures:api:weight";
+pub const REDIS_KEY_API_ORDERS: &str = "binance:futures:api:orders";
+pub const REDIS_KEY_API_BANNED: &str = "binance:futures:api:banned";
+pub const REDIS_KEY_TIME_OFFSET: &str = "binance:futures:time:offset";
 pub const SCALPING_MIN_BINANCE: f64 = 50.0;
-// pub const TRIGGERS_MIN_BINANCE: i32 = 50;
+pub const TRIGGERS_MIN_BINANCE: f64 = 50.0;
+pub const LAUNCHPAD_WINDOW: i64 = 600;
+pub const LAUNCHPAD_ENTRY_TIMEOUT: i64 = 1800;
+pub const LAUNCHPAD_TIME_STOP: i64 = 14400;
+pub const LAUNCHPAD_MAX_LEVELS: usize = 5;
+pub const LAUNCHPAD_MAX_LEVEL_AMOUNT: f64 = 50.0;
+pub const LAUNCHPAD_MAX_AMOUNT: f64 = 150.0;
+pub const LAUNCHPAD_TAKE_PROFIT: f64 = 1.12;
+pub const LAUNCHPAD_STOP_LOSS: f64 = 0.92;
 pub const RSMQ_QUEUE_TICKERS: &str  = "binance.futures.tickers";
 pub const RSMQ_QUEUE_KLINES: &str = "binance.futures.klines";
 pub const RSMQ_QUEUE_DEPTH: &str = "binance.futures.depth";
@@ -18,12 +33,15 @@ pub const RSMQ_QUEUE_PLANS: &str = "binance.futures.plans";
 pub const RSMQ_QUEUE_ACCOUNT: &str = "binance.futures.account";
 pub const RSMQ_QUEUE_ORDERS: &str = "binance.futures.orders";
 pub const RSMQ_QUEUE_POSITIONS: &str = "binance.futures.positions";
+pub const RSMQ_QUEUE_TRADINGS_LAUNCHPAD: &str = "binance.futures.tradings.launchpad";
 pub const RSMQ_QUEUE_TRADINGS_SCALPING: &str = "binance.futures.tradings.scalping";
 pub const RSMQ_QUEUE_TRADINGS_TRIGGERS: &str = "binance.futures.tradings.triggers";
 pub const RSMQ_JOBS_TICKERS_FLUSH: &str = "binance:futures:tickers:flush";
 pub const RSMQ_JOBS_TICKERS_UPDATE: &str = "binance:futures:tickers:update";
+pub const RSMQ_JOBS_KLINES_FLUSH: &str = "binance:futures:klines:flush";
 pub const RSMQ_JOBS_KLINES_SYNC: &str = "binance:futures:klines:sync";
 pub const RSMQ_JOBS_KLINES_CLEAN: &str = "binance:futures:klines:clean";
+pub const RSMQ_JOBS_KLINES_AGGREGATE: &str = "binance:futures:klines:aggregate";
 pub const RSMQ_JOBS_INDICATORS_FLUSH: &str = "binance:futures:indicators:flush";
 pub const RSMQ_JOBS_STRATEGIES_FLUSH: &str = "binance:futures:strategies:flush";
 pub const RSMQ_JOBS_PLANS_FLUSH: &str = "binance:futures:plans:flush";
@@ -39,6 +57,8 @@ pub const RSMQ_JOBS_TRADINGS_TRIGGERS_PLACE: &str = "binance:futures:tradings:tr
 pub const RSMQ_JOBS_TRADINGS_TRIGGERS_FLUSH: &str = "binance:futures:tradings:triggers:flush";
 pub const NATS_EVENTS_ACCOUNT_UPDATE: &str = "binance.futures.account.update";
 pub const NATS_EVENTS_TICKERS_UPDATE: &str = "binance.futures.tickers.update";
+pub const NATS_EVENTS_SYMBOLS_HALT: &str = "binance.futures.symbols.halt";
+pub const NATS_EVENTS_TIME_DRIFT: &str = "binance.futures.time.drift";
 pub const NATS_EVENTS_KLINES_UPDATE: &str = "binance.futures.klines.update";
 pub const NATS_EVENTS_INDICATORS_UPDATE: &str = "binance.futures.indicators.update";
 pub const NATS_EVENTS_STRATEGIES_UPDATE: &str = "binance.futures.strategies.update";
@@ -50,14 +70,22 @@ pub const STREAMS_API_KLINES_FLUSH: &str = "klines";
 pub const LOCKS_ACCOUNT_FLUSH: &str = "locks:binance:futures:account:flush";
 pub const LOCKS_SYMBOLS_FLUSH: &str = "locks:binance:futures:symbols:flush";
 pub const LOCKS_KLINES_FLUSH: &str = "locks:binance:futures:klines:flush";
+pub const LOCKS_DEPTH_FLUSH: &str = "locks:binance:futures:depth:flush";
 pub const LOCKS_KLINES_SYNC: &str = "locks:binance:futures:klines:sync";
+pub const LOCKS_KLINES_AGGREGATE: &str = "locks:binance:futures:klines:aggregate";
+pub const LOCKS_KLINES_CLEAN: &str = "locks:binance:futures:klines:clean";
 pub const LOCKS_INDICATORS_FLUSH: &str = "locks:binance:futures:indicators:flush";
 pub const LOCKS_STRATEGIES_FLUSH: &str = "locks:binance:futures:strategies:flush";
 pub const LOCKS_PLANS_FLUSH: &str = "locks:binance:futures:plans:flush";
+pub const LOCKS_TRADINGS_LAUNCHPAD_PLACE: &str = "locks:binance:futures:tradings:launchpad:place";
+pub const LOCKS_TRADINGS_LAUNCHPAD_FLUSH: &str = "locks:binance:futures:tradings:launchpad:flush";
 pub const LOCKS_TRADINGS_SCALPING_PLACE: &str = "locks:binance:futures:tradings:scalping:place";
 pub const LOCKS_TRADINGS_SCALPING_FLUSH: &str = "locks:binance:futures:tradings:scalping:flush";
+pub const LOCKS_TRIGGERS_EXPIRE: &str = "locks:binance:futures:triggers:expire";
 pub const LOCKS_TRADINGS_TRIGGERS_PLACE: &str = "locks:binance:futures:tradings:triggers:place";
 pub const LOCKS_TRADINGS_TRIGGERS_FLUSH: &str = "locks:binance:futures:tradings:triggers:flush";
+pub const LOCKS_ANALYSIS_TRADINGS_LAUNCHPAD_FLUSH: &str = "locks:binance:futures:analysis:tradings:launchpad:flush";
+pub const LOCKS_ANALYSIS_TRADINGS_TRIGGERS_FLUSH: &str = "locks:binance:futures:analysis:tradings:triggers:flush";
 pub const LOCKS_TASKS_KLINES_FLUSH: &str = "locks:tasks:binance:futures:klines:flush";
 pub const LOCKS_TASKS_KLINES_FIX: &str = "locks:tasks:binance:futures:klines:fix";
 pub const LOCKS_STREAMS_API_KLINES_FLUSH: &str = "locks:streams:api:binance:futures:klines:flush";
\ No newline at end of file
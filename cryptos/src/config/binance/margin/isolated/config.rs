@@ -0,0 +1,17 @@
+pub const REDIS_KEY_BALANCE: &str = "binance:margin:isolated:balance";
+pub const REDIS_KEY_SYMBOLS: &str = "binance:margin:isolated:symbols";
+pub const REDIS_KEY_ACCOUNT: &str = "binance:margin:isolated:account";
+pub const REDIS_KEY_TRADINGS_LAST_PRICE: &str = "binance:margin:isolated:tradings:last:price";
+pub const MARGIN_LEVEL_WARNING: f64 = 1.5;
+pub const MARGIN_LEVEL_CRITICAL: f64 = 1.25;
+pub const SCALPING_MIN_BINANCE: f64 = 50.0;
+pub const TRIGGERS_MIN_BINANCE: f64 = 50.0;
+pub const LOCKS_ACCOUNT_FLUSH: &str = "locks:binance:margin:isolated:account:flush";
+pub const LOCKS_INTERESTS_FLUSH: &str = "locks:binance:margin:isolated:interests:flush";
+pub const LOCKS_ORDERS_SYNC: &str = "locks:binance:margin:isolated:orders:sync";
+pub const LOCKS_TRIGGERS_EXPIRE: &str = "locks:binance:margin:isolated:triggers:expire";
+pub const LOCKS_TRADINGS_SCALPING_PLACE: &str = "locks:binance:margin:isolated:tradings:scalping:place";
+pub const LOCKS_TRADINGS_SCALPING_FLUSH: &str = "locks:binance:margin:isolated:tradings:scalping:flush";
+pub const LOCKS_TRADINGS_TRIGGERS_PLACE: &str = "locks:binance:margin:isolated:tradings:triggers:place";
+pub const LOCKS_TRADINGS_TRIGGERS_FLUSH: &str = "locks:binance:margin:isolated:tradings:triggers:flush";
+pub const LOCKS_ANALYSIS_TRADINGS_FLUSH: &str = "locks:binance:margin:isolated:analysis:tradings:flush";
\ No newline at end of file
@@ -3,10 +3,17 @@ pub const REDIS_KEY_CURRENCIES: &str = "binance:spot:currencies";
 pub const REDIS_KEY_TICKERS: &str = "binance:spot:realtime";
 pub const REDIS_KEY_TICKERS_FLUSH: &str = "binance:spot:tickers:flush";
 pub const REDIS_KEY_KLINES: &str = "binance:spot:klines";
+pub const REDIS_KEY_DEPTH: &str = "binance:spot:depth";
 pub const REDIS_KEY_INDICATORS: &str = "binance:spot:indicators";
+pub const REDIS_KEY_INDICATORS_HISTORY: &str = "binance:spot:indicators:history";
 pub const REDIS_KEY_TRADINGS_LAST_PRICE: &str = "binance:spot:tradings:last:price";
 // pub const REDIS_KEY_TRADINGS_TRIGGERS_PLACE: &str = "binance:spot:tradings:triggers:place";
 pub const REDIS_KEY_STREAMS_API: &str = "binance:spot:streams:api";
+pub const REDIS_KEY_STREAMS_SHARDS: &str = "binance:spot:streams:shards";
+pub const REDIS_KEY_API_WEIGHT: &str = "binance:spot:api:weight";
+pub const REDIS_KEY_API_ORDERS: &str = "binance:spot:api:orders";
+pub const REDIS_KEY_API_BANNED: &str = "binance:spot:api:banned";
+pub const REDIS_KEY_TIME_OFFSET: &str = "binance:spot:time:offset";
 pub const SCALPING_MIN_BINANCE: f64 = 50.0;
 // pub const TRIGGERS_MIN_BINANCE: f64 = 50.0;
 pub const RSMQ_QUEUE_TICKERS: &str  = "binance.spot.tickers";
@@ -22,8 +29,10 @@ pub const RSMQ_QUEUE_TRADINGS_SCALPING: &str = "binance.spot.tradings.scalping";
 pub const RSMQ_QUEUE_TRADINGS_TRIGGERS: &str = "binance.spot.tradings.triggers";
 pub const RSMQ_JOBS_TICKERS_FLUSH: &str = "binance:spot:tickers:flush";
 pub const RSMQ_JOBS_TICKERS_UPDATE: &str = "binance:spot:tickers:update";
+pub const RSMQ_JOBS_KLINES_FLUSH: &str = "binance:spot:klines:flush";
 pub const RSMQ_JOBS_KLINES_SYNC: &str = "binance:spot:klines:sync";
 pub const RSMQ_JOBS_KLINES_CLEAN: &str = "binance:spot:klines:clean";
+pub const RSMQ_JOBS_KLINES_AGGREGATE: &str = "binance:spot:klines:aggregate";
 pub const RSMQ_JOBS_INDICATORS_FLUSH: &str = "binance:spot:indicators:flush";
 pub const RSMQ_JOBS_STRATEGIES_FLUSH: &str = "binance:spot:strategies:flush";
 pub const RSMQ_JOBS_PLANS_FLUSH: &str = "binance:spot:plans:flush";
@@ -39,6 +48,8 @@ pub const RSMQ_JOBS_TRADINGS_TRIGGERS_PLACE: &str = "binance:spot:tradings:trigg
 pub const RSMQ_JOBS_TRADINGS_TRIGGERS_FLUSH: &str = "binance:spot:tradings:triggers:flush";
 pub const NATS_EVENTS_ACCOUNT_UPDATE: &str = "binance.spot.account.update";
 pub const NATS_EVENTS_TICKERS_UPDATE: &str = "binance.spot.tickers.update";
+pub const NATS_EVENTS_SYMBOLS_HALT: &str = "binance.spot.symbols.halt";
+pub const NATS_EVENTS_TIME_DRIFT: &str = "binance.spot.time.drift";
 pub const NATS_EVENTS_KLINES_UPDATE: &str = "binance.spot.klines.update";
 pub const NATS_EVENTS_INDICATORS_UPDATE: &str = "binance.spot.indicators.update";
 pub const NATS_EVENTS_STRATEGIES_UPDATE: &str = "binance.spot.strategies.update";
@@ -50,7 +61,10 @@ pub const STREAMS_API_KLINES_FLUSH: &str = "klines";
 pub const LOCKS_ACCOUNT_FLUSH: &str = "locks:binance:spot:account:flush";
 pub const LOCKS_SYMBOLS_FLUSH: &str = "locks:binance:spot:symbols:flush";
 pub const LOCKS_KLINES_FLUSH: &str = "locks:binance:spot:klines:flush";
+pub const LOCKS_DEPTH_FLUSH: &str = "locks:binance:spot:depth:flush";
 pub const LOCKS_KLINES_SYNC: &str = "locks:binance:spot:klines:sync";
+pub const LOCKS_KLINES_AGGREGATE: &str = "locks:binance:spot:klines:aggregate";
+pub const LOCKS_KLINES_CLEAN: &str = "locks:binance:spot:klines:clean";
 pub const LOCKS_INDICATORS_FLUSH: &str = "locks:binance:spot:indicators:flush";
 pub const LOCKS_STRATEGIES_FLUSH: &str = "locks:binance:spot:strategies:flush";
 pub const LOCKS_PLANS_FLUSH: &str = "locks:binance:spot:plans:flush";
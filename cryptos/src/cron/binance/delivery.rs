@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tokio_cron::Scheduler;
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::cron::binance::delivery::account::*;
+use crate::cron::binance::delivery::expiry::*;
+use crate::cron::binance::delivery::indicators::*;
+use crate::cron::binance::delivery::klines::*;
+use crate::cron::binance::delivery::symbols::*;
+use crate::cron::binance::delivery::tickers::*;
+use crate::cron::binance::delivery::time::*;
+
+pub mod account;
+pub mod expiry;
+pub mod indicators;
+pub mod klines;
+pub mod symbols;
+pub mod tickers;
+pub mod time;
+
+#[derive(Clone)]
+pub struct DeliveryScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl DeliveryScheduler {
+  pub fn new(ctx: Ctx, scheduler: Scheduler<Local>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: Arc::new(Mutex::new(scheduler)),
+    }
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery scheduler dispatch");
+    TimeScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    SymbolsScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    ExpiryScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TickersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    KlinesScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    AccountScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    IndicatorsScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    Ok(())
+  }
+}
\ No newline at end of file
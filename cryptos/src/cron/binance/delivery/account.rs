@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::account::*;
+
+pub struct AccountScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl AccountScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery account scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ACCOUNT_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ACCOUNT_FLUSH)));
+    }
+
+    if let Err(err) = AccountRepository::flush(ctx.clone()).await {
+      println!("binance delivery account flush failed {err:?}");
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery account scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("30 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use redis::AsyncCommands;
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::positions::*;
+use crate::repositories::binance::delivery::symbols::*;
+
+pub struct ExpiryScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl ExpiryScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  // quarterlies close to delivery are published with the contract to roll into,
+  // positions left on a delivered contract were settled by the exchange
+  pub async fn check(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let timestamp = Utc::now().timestamp_millis() + Config::EXPIRY_ROLLOVER_DAYS * 86400000;
+    let expiring = SymbolsRepository::expiring(ctx.clone(), timestamp).await?;
+    let holdings = PositionsRepository::symbols(ctx.clone()).await?;
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    () = rdb.del(Config::REDIS_KEY_EXPIRY).await?;
+    for (symbol, _, delivery_date) in expiring.iter() {
+      let rollover = SymbolsRepository::rollover(ctx.clone(), symbol).await?.unwrap_or_default();
+      () = rdb.hset(Config::REDIS_KEY_EXPIRY, symbol, format!("{},{}", delivery_date, rollover)).await?;
+      if holdings.contains(symbol) {
+        println!("alarm: binance delivery {symbol:} delivers at {delivery_date:}, roll the position to {rollover:}");
+      }
+    }
+
+    for symbol in holdings.iter() {
+      let entity = match SymbolsRepository::get(ctx.clone(), symbol).await? {
+        Some(entity) => entity,
+        None => continue,
+      };
+      if entity.status != "DELIVERED" {
+        continue
+      }
+      let count = PositionsRepository::settle(ctx.clone(), symbol).await?;
+      println!("binance delivery {symbol:} delivered, {count:} positions settled");
+    }
+
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery expiry scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_EXPIRY_CHECK,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_EXPIRY_CHECK)));
+    }
+
+    if let Err(err) = Self::check(ctx.clone()).await {
+      println!("binance delivery expiry check failed {err:?}");
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery expiry scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 5 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::exchange::*;
+use crate::repositories::binance::params::*;
+use crate::repositories::binance::delivery::indicators::*;
+use crate::repositories::binance::delivery::symbols::*;
+use crate::repositories::binance::futures::strategies::StrategiesRepository;
+
+pub struct IndicatorsScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl IndicatorsScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery indicators scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_INDICATORS_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_INDICATORS_FLUSH)));
+    }
+
+    let exchange = DeliveryExchange::new(ctx.clone());
+    let symbols = match SymbolsRepository::listings(ctx.clone()).await {
+      Ok(symbols) => symbols,
+      Err(err) => {
+        mutex.unlock().await.unwrap();
+        return Err(err)
+      }
+    };
+    for symbol in symbols.iter() {
+      let symbol = symbol.as_str();
+      if let Err(err) = IndicatorsRepository::pivot(ctx.clone(), symbol, "1d").await {
+        println!("binance delivery indicators {symbol:} pivot failed {err:?}");
+      }
+      let params = match ParamsRepository::get(ctx.clone(), "delivery", symbol, "1d").await {
+        Ok(result) => result,
+        Err(err) => {
+          println!("binance delivery indicators {symbol:} params failed {err:?}");
+          continue
+        }
+      };
+      if let Err(err) = IndicatorsRepository::atr(ctx.clone(), symbol, "1d", params.atr_period, params.limit).await {
+        println!("binance delivery indicators {symbol:} atr failed {err:?}");
+        continue
+      }
+      if let Err(err) = StrategiesRepository::atr(ctx.clone(), &exchange, symbol, "1d").await {
+        println!("binance delivery strategies {symbol:} atr failed {err:?}");
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery indicators scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 */5 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
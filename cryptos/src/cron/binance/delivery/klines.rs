@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::klines::*;
+use crate::repositories::binance::delivery::symbols::*;
+
+pub struct KlinesScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl KlinesScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery klines scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_KLINES_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_KLINES_FLUSH)));
+    }
+
+    // no kline streams for this market, the open and the last closed candle are polled
+    let symbols = match SymbolsRepository::listings(ctx.clone()).await {
+      Ok(symbols) => symbols,
+      Err(err) => {
+        mutex.unlock().await.unwrap();
+        return Err(err)
+      }
+    };
+    for symbol in symbols.iter() {
+      for interval in ["15m", "4h", "1d"] {
+        if let Err(err) = KlinesRepository::flush(ctx.clone(), symbol.as_str(), interval, 0, 2).await {
+          println!("binance delivery klines {symbol:} {interval:} flush failed {err:?}");
+        }
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance delivery klines scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("5 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -6,8 +6,22 @@ use chrono::offset::Local;
 
 use crate::common::*;
 use crate::cron::binance::futures::klines::*;
+use crate::cron::binance::futures::depth::*;
+use crate::cron::binance::futures::time::*;
+use crate::cron::binance::futures::symbols::*;
+use crate::cron::binance::futures::triggers::*;
+use crate::cron::binance::futures::tradings::launchpad::*;
+use crate::cron::binance::futures::tradings::triggers::TriggersScheduler as TradingsTriggersScheduler;
+use crate::cron::binance::futures::analysis::tradings::launchpad::LaunchpadScheduler as AnalysisLaunchpadScheduler;
+use crate::cron::binance::futures::analysis::tradings::triggers::TriggersScheduler as AnalysisTriggersScheduler;
 
 pub mod klines;
+pub mod depth;
+pub mod time;
+pub mod symbols;
+pub mod triggers;
+pub mod tradings;
+pub mod analysis;
 
 #[derive(Clone)]
 pub struct FuturesScheduler {
@@ -26,6 +40,14 @@ impl FuturesScheduler {
   pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
     println!("binance futures scheduler dispatch");
     KlinesScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    DepthScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TimeScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    SymbolsScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    LaunchpadScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TradingsTriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    AnalysisLaunchpadScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    AnalysisTriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
     Ok(())
   }
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
+pub mod launchpad;
 pub mod scalping;
 pub mod triggers;
\ No newline at end of file
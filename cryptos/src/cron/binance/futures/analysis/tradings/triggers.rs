@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::repositories::binance::futures::analysis::tradings::triggers::*;
+
+pub struct TriggersScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl TriggersScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures analysis tradings triggers scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ANALYSIS_TRADINGS_TRIGGERS_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(300)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ANALYSIS_TRADINGS_TRIGGERS_FLUSH)));
+    }
+
+    let today = Utc::now().date_naive();
+    for day in [today.pred_opt().unwrap(), today] {
+      if let Err(err) = TriggersRepository::flush(ctx.clone(), day).await {
+        println!("binance futures analysis tradings triggers {day:} failed {err:?}");
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures analysis tradings triggers scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 */10 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
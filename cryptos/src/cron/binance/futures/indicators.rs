@@ -40,10 +40,9 @@ impl IndicatorsScheduler {
       Box::pin({
         let ctx = ctx.clone();
         async move {
-          let _ = Self::flush(ctx.clone(), "1m").await;
-          let _ = Self::flush(ctx.clone(), "15m").await;
-          let _ = Self::flush(ctx.clone(), "4h").await;
-          let _ = Self::flush(ctx.clone(), "1d").await;
+          for interval in Interval::from_env("BINANCE_FUTURES_INTERVALS") {
+            let _ = Self::flush(ctx.clone(), interval.as_str()).await;
+          }
         }
       })
     }));
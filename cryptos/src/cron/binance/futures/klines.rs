@@ -6,6 +6,7 @@ use chrono::offset::Local;
 
 use crate::common::*;
 use crate::queue::rsmq::jobs::binance::futures::klines::*;
+use crate::repositories::binance::futures::klines::*;
 
 pub struct KlinesScheduler {
   ctx: Ctx,
@@ -27,6 +28,20 @@ impl KlinesScheduler {
     Ok(())
   }
 
+  pub async fn aggregate(ctx: Ctx, interval: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures klines scheduler aggregate {}", interval);
+    let job = KlinesJob::new(ctx.clone());
+    job.aggregate(interval).await?;
+    Ok(())
+  }
+
+  pub async fn clean(ctx: Ctx, interval: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures klines scheduler clean {}", interval);
+    let job = KlinesJob::new(ctx.clone());
+    job.clean(interval).await?;
+    Ok(())
+  }
+
   pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
     println!("binance futures klines scheduler dispatch");
     let mut scheduler = self.scheduler.lock().await;
@@ -35,10 +50,33 @@ impl KlinesScheduler {
       Box::pin({
         let ctx = ctx.clone();
         async move {
-          let _ = Self::sync(ctx.clone(), "1m").await;
-          let _ = Self::sync(ctx.clone(), "15m").await;
-          let _ = Self::sync(ctx.clone(), "4h").await;
-          let _ = Self::sync(ctx.clone(), "1d").await;
+          // higher timeframes are aggregated from the stored 1m bars, rest only covers their gaps
+          let _ = Self::sync(ctx.clone(), Interval::Minute1.as_str()).await;
+        }
+      })
+    }));
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("10 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          for interval in Interval::from_env("BINANCE_FUTURES_INTERVALS") {
+            if interval == Interval::Minute1 {
+              continue
+            }
+            let _ = Self::aggregate(ctx.clone(), interval.as_str()).await;
+          }
+        }
+      })
+    }));
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 30 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          for interval in KlinesRepository::retention("BINANCE_FUTURES_KLINES_RETENTION").keys() {
+            let _ = Self::clean(ctx.clone(), interval.as_str()).await;
+          }
         }
       })
     }));
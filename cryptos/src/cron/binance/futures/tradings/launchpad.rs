@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use chrono::{prelude::Utc, Duration};
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::rsmq::jobs::binance::futures::tradings::launchpad::*;
+use crate::repositories::binance::futures::symbols::*;
+use crate::repositories::binance::futures::tradings::launchpad::*;
+
+pub struct LaunchpadScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl LaunchpadScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  // listings still inside the window are queued again in case the first place missed the price
+  pub async fn place(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Utc::now() - Duration::seconds(Config::LAUNCHPAD_WINDOW);
+    let symbols = SymbolsRepository::listings(ctx.clone(), start_time).await?;
+    for symbol in symbols.iter() {
+      if LaunchpadRepository::count(ctx.clone(), symbol).await? > 0 {
+        continue
+      }
+      let job = LaunchpadJob::new(ctx.clone());
+      job.place(symbol).await?;
+    }
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let ids = LaunchpadRepository::scan(ctx.clone()).await?;
+    for id in ids.iter() {
+      let job = LaunchpadJob::new(ctx.clone());
+      job.flush(id).await?;
+    }
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures tradings launchpad scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("*/15 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::place(ctx.clone()).await;
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
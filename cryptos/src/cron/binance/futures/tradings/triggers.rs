@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::queue::rsmq::jobs::binance::futures::tradings::triggers::*;
+use crate::repositories::binance::futures::triggers::TriggersRepository as ParentRepository;
+use crate::repositories::binance::futures::tradings::triggers::*;
+
+pub struct TriggersScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl TriggersScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn place(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let trigger_ids = ParentRepository::scan(ctx.clone()).await?;
+    for trigger_id in trigger_ids.iter() {
+      let job = TriggersJob::new(ctx.clone());
+      job.place(trigger_id).await?;
+    }
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let ids = TriggersRepository::scan(ctx.clone()).await?;
+    for id in ids.iter() {
+      let job = TriggersJob::new(ctx.clone());
+      job.flush(id).await?;
+    }
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures tradings triggers scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("*/10 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::place(ctx.clone()).await;
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use redis::AsyncCommands;
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::margin::cross::config as Config;
+use crate::repositories::binance::margin::cross::account::*;
+use crate::repositories::binance::margin::cross::interests::*;
+
+pub struct AccountScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl AccountScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn monitor(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let (margin_level, total_asset, total_liability) = AccountRepository::level(ctx.clone()).await?;
+    if total_liability == 0.0 {
+      return Ok(())
+    }
+    if margin_level < Config::MARGIN_LEVEL_CRITICAL {
+      println!("alarm: binance margin cross level {margin_level:} close to liquidation, asset {total_asset:} liability {total_liability:}");
+    } else if margin_level < Config::MARGIN_LEVEL_WARNING {
+      println!("warning: binance margin cross level {margin_level:} below {}, new entries paused", Config::MARGIN_LEVEL_WARNING);
+    }
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross account scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ACCOUNT_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ACCOUNT_FLUSH)));
+    }
+
+    match AccountRepository::flush(ctx.clone()).await {
+      Ok(_) => {
+        if let Err(err) = Self::monitor(ctx.clone()).await {
+          println!("binance margin cross account monitor failed {err:?}");
+        }
+      }
+      Err(err) => println!("binance margin cross account flush failed {err:?}"),
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn interests(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross interests scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_INTERESTS_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(300)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_INTERESTS_FLUSH)));
+    }
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let currencies: Vec<String> = rdb.smembers(Config::REDIS_KEY_CURRENCIES).await.unwrap_or_default();
+    for asset in currencies.iter() {
+      let (_, _, borrowed, interest) = match AccountRepository::balance(ctx.clone(), asset).await {
+        Ok(result) => result,
+        Err(_) => continue,
+      };
+      if borrowed <= 0.0 && interest <= 0.0 {
+        continue
+      }
+      let start_time = match InterestsRepository::last_time(ctx.clone(), asset).await {
+        Ok(timestamp) if timestamp > 0 => timestamp + 1,
+        Ok(_) => 0,
+        Err(err) => {
+          println!("binance margin cross interests {asset:} failed {err:?}");
+          continue
+        }
+      };
+      match InterestsRepository::flush(ctx.clone(), asset, start_time).await {
+        Ok(count) => println!("binance margin cross interests {asset:} {count:} saved"),
+        Err(err) => println!("binance margin cross interests {asset:} failed {err:?}"),
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross account scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("*/30 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 10 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::interests(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod tradings;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::margin::cross::config as Config;
+use crate::repositories::binance::margin::cross::analysis::scalping::*;
+
+pub struct ScalpingScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl ScalpingScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  // yesterday is flushed again so tradings closed around midnight are counted
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross analysis tradings scalping scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_lock_key = format!("{}:scalping", Config::LOCKS_ANALYSIS_TRADINGS_FLUSH);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_lock_key,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(300)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_lock_key)));
+    }
+
+    let today = Utc::now().date_naive();
+    for day in [today.pred_opt().unwrap(), today] {
+      if let Err(err) = ScalpingRepository::flush(ctx.clone(), day).await {
+        println!("binance margin cross analysis tradings scalping {day:} failed {err:?}");
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross analysis tradings scalping scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 */10 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
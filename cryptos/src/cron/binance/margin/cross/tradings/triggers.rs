@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::margin::cross::config as Config;
+use crate::repositories::binance::margin::cross::triggers::TriggersRepository as ParentRepository;
+use crate::repositories::binance::margin::cross::exchange::*;
+use crate::repositories::binance::margin::cross::tradings::triggers::*;
+
+pub struct TriggersScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl TriggersScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn place(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross tradings triggers scheduler place");
+    let exchange = CrossExchange::new(ctx.clone());
+    let trigger_ids = ParentRepository::scan(ctx.clone()).await?;
+    for trigger_id in trigger_ids.iter() {
+      let rdb = ctx.rdb.lock().await.clone();
+      let mutex_id = xid::new().to_string();
+      let redis_lock_key = format!("{}:{}", Config::LOCKS_TRADINGS_TRIGGERS_PLACE, trigger_id);
+      let mut mutex = RedisMutex::new(
+        rdb,
+        &redis_lock_key,
+        &mutex_id,
+      );
+      if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+        continue
+      }
+      if let Err(err) = TriggersRepository::place(ctx.clone(), &exchange, trigger_id).await {
+        println!("binance margin cross tradings triggers {trigger_id:} place failed {err:}");
+      }
+      mutex.unlock().await.unwrap();
+    }
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross tradings triggers scheduler flush");
+    let exchange = CrossExchange::new(ctx.clone());
+    let ids = TriggersRepository::scan(ctx.clone()).await?;
+    for id in ids.iter() {
+      let rdb = ctx.rdb.lock().await.clone();
+      let mutex_id = xid::new().to_string();
+      let redis_lock_key = format!("{}:{}", Config::LOCKS_TRADINGS_TRIGGERS_FLUSH, id);
+      let mut mutex = RedisMutex::new(
+        rdb,
+        &redis_lock_key,
+        &mutex_id,
+      );
+      if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+        continue
+      }
+      if let Err(err) = TriggersRepository::flush(ctx.clone(), &exchange, id).await {
+        println!("binance margin cross tradings triggers {id:} flush failed {err:}");
+      }
+      mutex.unlock().await.unwrap();
+    }
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin cross tradings triggers scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("*/10 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::place(ctx.clone()).await;
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -1,7 +1,49 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tokio_cron::Scheduler;
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::cron::binance::margin::isolated::account::*;
+use crate::cron::binance::margin::isolated::orders::*;
+use crate::cron::binance::margin::isolated::triggers::*;
+use crate::cron::binance::margin::isolated::tradings::scalping::ScalpingScheduler as TradingsScalpingScheduler;
+use crate::cron::binance::margin::isolated::tradings::triggers::TriggersScheduler as TradingsTriggersScheduler;
+use crate::cron::binance::margin::isolated::analysis::tradings::scalping::ScalpingScheduler as AnalysisScalpingScheduler;
+use crate::cron::binance::margin::isolated::analysis::tradings::triggers::TriggersScheduler as AnalysisTriggersScheduler;
+
 pub mod analysis;
 pub mod account;
 pub mod orders;
 pub mod positions;
 pub mod scalping;
 pub mod triggers;
-pub mod tradings;
\ No newline at end of file
+pub mod tradings;
+
+#[derive(Clone)]
+pub struct IsolatedScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl IsolatedScheduler {
+  pub fn new(ctx: Ctx, scheduler: Scheduler<Local>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: Arc::new(Mutex::new(scheduler)),
+    }
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated scheduler dispatch");
+    AccountScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    OrdersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TradingsScalpingScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TradingsTriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    AnalysisScalpingScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    AnalysisTriggersScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    Ok(())
+  }
+}
\ No newline at end of file
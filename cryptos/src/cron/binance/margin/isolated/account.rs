@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::margin::isolated::config as Config;
+use crate::repositories::binance::margin::isolated::account::*;
+use crate::repositories::binance::margin::isolated::interests::*;
+use crate::repositories::binance::spot::symbols::*;
+
+pub struct AccountScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl AccountScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn monitor(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    for symbol in AccountRepository::symbols(ctx.clone()).await?.iter() {
+      let (margin_level, liquidate_price, liability) = match AccountRepository::level(ctx.clone(), symbol).await {
+        Ok(result) => result,
+        Err(_) => continue,
+      };
+      if liability == 0.0 {
+        continue
+      }
+      if margin_level < Config::MARGIN_LEVEL_CRITICAL {
+        println!("alarm: binance margin isolated {symbol:} level {margin_level:} close to liquidation at {liquidate_price:}");
+      } else if margin_level < Config::MARGIN_LEVEL_WARNING {
+        println!("warning: binance margin isolated {symbol:} level {margin_level:} below {}, new entries paused", Config::MARGIN_LEVEL_WARNING);
+      }
+    }
+    Ok(())
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated account scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ACCOUNT_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ACCOUNT_FLUSH)));
+    }
+
+    match AccountRepository::flush(ctx.clone()).await {
+      Ok(_) => {
+        if let Err(err) = Self::monitor(ctx.clone()).await {
+          println!("binance margin isolated account monitor failed {err:?}");
+        }
+      }
+      Err(err) => println!("binance margin isolated account flush failed {err:?}"),
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn interests(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated interests scheduler flush");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_INTERESTS_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(300)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_INTERESTS_FLUSH)));
+    }
+
+    let symbols = AccountRepository::symbols(ctx.clone()).await.unwrap_or_default();
+    for symbol in symbols.iter() {
+      let (base_asset, quote_asset) = match SymbolsRepository::pairs(ctx.clone(), symbol).await {
+        Ok(result) => result,
+        Err(_) => continue,
+      };
+      for asset in [base_asset, quote_asset].iter() {
+        let (_, _, borrowed, interest) = match AccountRepository::balance(ctx.clone(), symbol, asset).await {
+          Ok(result) => result,
+          Err(_) => continue,
+        };
+        if borrowed <= 0.0 && interest <= 0.0 {
+          continue
+        }
+        let start_time = match InterestsRepository::last_time(ctx.clone(), symbol, asset).await {
+          Ok(timestamp) if timestamp > 0 => timestamp + 1,
+          Ok(_) => 0,
+          Err(err) => {
+            println!("binance margin isolated interests {symbol:} {asset:} failed {err:?}");
+            continue
+          }
+        };
+        match InterestsRepository::flush(ctx.clone(), symbol, asset, start_time).await {
+          Ok(count) => println!("binance margin isolated interests {symbol:} {asset:} {count:} saved"),
+          Err(err) => println!("binance margin isolated interests {symbol:} {asset:} failed {err:?}"),
+        }
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated account scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("*/30 * * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::flush(ctx.clone()).await;
+        }
+      })
+    }));
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 10 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::interests(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
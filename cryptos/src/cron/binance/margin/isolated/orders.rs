@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use tokio::sync::Mutex;
+use tokio_cron::{Scheduler, Job};
+use chrono::offset::Local;
+
+use crate::common::*;
+use crate::config::binance::margin::isolated::config as Config;
+use crate::repositories::binance::margin::isolated::orders::*;
+use crate::repositories::binance::margin::isolated::positions::*;
+
+pub struct OrdersScheduler {
+  ctx: Ctx,
+  scheduler: Arc<Mutex<Scheduler<Local>>>,
+}
+
+impl OrdersScheduler {
+  pub fn new(ctx: Ctx, scheduler: Arc<Mutex<Scheduler<Local>>>) -> Self {
+    Self {
+      ctx: ctx,
+      scheduler: scheduler,
+    }
+  }
+
+  pub async fn sync(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated orders scheduler sync");
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_ORDERS_SYNC,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(300)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_ORDERS_SYNC)));
+    }
+
+    let start_time = Utc::now().timestamp_millis() - 86400000;
+    let mut symbols = match PositionsRepository::gets(ctx.clone()).await {
+      Ok(items) => items.into_iter().map(|item| item.1).collect::<Vec<_>>(),
+      Err(err) => {
+        mutex.unlock().await.unwrap();
+        return Err(err)
+      }
+    };
+    symbols.sort();
+    symbols.dedup();
+    for symbol in symbols.iter() {
+      if let Err(err) = OrdersRepository::sync(ctx.clone(), symbol, start_time, 500).await {
+        println!("binance margin isolated orders sync {symbol:} failed {err:?}");
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance margin isolated orders scheduler dispatch");
+    let mut scheduler = self.scheduler.lock().await;
+    let ctx = self.ctx.clone();
+    scheduler.add(Job::new("0 */5 * * * *", move || {
+      Box::pin({
+        let ctx = ctx.clone();
+        async move {
+          let _ = Self::sync(ctx.clone()).await;
+        }
+      })
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
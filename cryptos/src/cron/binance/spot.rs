@@ -6,8 +6,14 @@ use chrono::offset::Local;
 
 use crate::common::*;
 use crate::cron::binance::spot::klines::*;
+use crate::cron::binance::spot::depth::*;
+use crate::cron::binance::spot::time::*;
+use crate::cron::binance::spot::symbols::*;
 
 pub mod klines;
+pub mod depth;
+pub mod time;
+pub mod symbols;
 
 #[derive(Clone)]
 pub struct SpotScheduler {
@@ -26,6 +32,9 @@ impl SpotScheduler {
   pub async fn dispatch(&self) -> Result<(), Box<dyn std::error::Error>> {
     println!("binance spot scheduler dispatch");
     KlinesScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    DepthScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    TimeScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
+    SymbolsScheduler::new(self.ctx.clone(), self.scheduler.clone()).dispatch().await?;
     Ok(())
   }
 }
\ No newline at end of file
@@ -1,9 +1,14 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use tokio::sync::Mutex;
 use tokio_cron::{Scheduler, Job};
 use chrono::offset::Local;
 
 use crate::common::*;
+use crate::config::binance::spot::config as Config;
+use crate::repositories::binance::spot::depth::*;
+use crate::repositories::binance::spot::scalping::*;
 
 pub struct DepthScheduler {
   ctx: Ctx,
@@ -20,7 +25,29 @@ impl DepthScheduler {
 
   pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     println!("binance spot depth scheduler flush");
-    let _ = ctx.clone();
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let mut mutex = RedisMutex::new(
+      rdb,
+      Config::LOCKS_DEPTH_FLUSH,
+      mutex_id.as_str(),
+    );
+    if !mutex.lock(Duration::from_secs(30)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", Config::LOCKS_DEPTH_FLUSH)));
+    }
+
+    let mut symbols = ScalpingRepository::scan(ctx.clone()).await?;
+    symbols.sort();
+    symbols.dedup();
+    for symbol in symbols.iter() {
+      match DepthRepository::flush(ctx.clone(), symbol, 20).await {
+        Ok(result) => println!("binance spot depth {symbol:} flush {result:}"),
+        Err(err) => println!("binance spot depth {symbol:} flush failed {err:?}"),
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+
     Ok(())
   }
 
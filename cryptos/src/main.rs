@@ -11,6 +11,7 @@ pub mod repositories;
 pub mod cron;
 pub mod queue;
 pub mod streams;
+pub mod simulator;
 
 use app::App;
 use common::Env;
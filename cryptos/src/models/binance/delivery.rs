@@ -0,0 +1,4 @@
+pub mod symbol;
+pub mod kline;
+pub mod order;
+pub mod position;
\ No newline at end of file
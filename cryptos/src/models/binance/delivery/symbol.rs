@@ -0,0 +1,67 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::delivery::symbols::*;
+
+pub use crate::models::binance::futures::symbol::{Filters, Depth};
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize)]
+#[diesel(table_name = symbols)]
+pub struct Symbol {
+  pub id: String,
+  pub symbol: String,
+  pub pair: String,
+  pub base_asset: String,
+  pub quote_asset: String,
+  pub margin_asset: String,
+  pub contract_type: String,
+  pub contract_size: f64,
+  pub delivery_date: i64,
+  pub filters: Filters,
+  pub depth: Depth,
+  pub status: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Symbol {
+  pub fn new(
+    id: String,
+    symbol: String,
+    pair: String,
+    base_asset: String,
+    quote_asset: String,
+    margin_asset: String,
+    contract_type: String,
+    contract_size: f64,
+    delivery_date: i64,
+    filters: Filters,
+    depth: Depth,
+    status: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      symbol: symbol,
+      pair: pair,
+      base_asset: base_asset,
+      quote_asset: quote_asset,
+      margin_asset: margin_asset,
+      contract_type: contract_type,
+      contract_size: contract_size,
+      delivery_date: delivery_date,
+      filters: filters,
+      depth: depth,
+      status: status,
+      created_at: created_at,
+      updated_at: updated_at,
+    }
+  }
+
+  // perpetual contracts carry a delivery date far in the future
+  pub fn is_quarterly(&self) -> bool {
+    self.contract_type == "CURRENT_QUARTER" || self.contract_type == "NEXT_QUARTER"
+  }
+}
\ No newline at end of file
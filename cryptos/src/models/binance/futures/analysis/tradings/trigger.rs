@@ -0,0 +1,51 @@
+use chrono::{prelude::Utc, DateTime, NaiveDate};
+use diesel::{Queryable, Selectable, Insertable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::futures::analysis::tradings::triggers::*;
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = triggers)]
+pub struct Trigger {
+  pub id: String,
+  pub side: i32,
+  pub day: NaiveDate,
+  pub buys_count: i32,
+  pub sells_count: i32,
+  pub buys_amount: f64,
+  pub sells_amount: f64,
+  pub profit: f64,
+  pub additive_profit: f64,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Trigger {
+  pub fn new(
+    id: String,
+    side: i32,
+    day: NaiveDate,
+    buys_count: i32,
+    sells_count: i32,
+    buys_amount: f64,
+    sells_amount: f64,
+    profit: f64,
+    additive_profit: f64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      side: side,
+      day: day,
+      buys_count: buys_count,
+      sells_count: sells_count,
+      buys_amount: buys_amount,
+      sells_amount: sells_amount,
+      profit: profit,
+      additive_profit: additive_profit,
+      created_at: created_at,
+      updated_at: updated_at,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use diesel_as_jsonb::AsJsonb;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::futures::indicators::*;
+
+// the redis indicators hash as it was when the candle closed
+#[derive(Debug, Default, Serialize, Deserialize, AsJsonb)]
+pub struct Fields {
+  #[serde(flatten)]
+  pub values: HashMap<String, String>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = indicators)]
+pub struct Indicator {
+  pub id: String,
+  pub symbol: String,
+  pub interval: String,
+  pub fields: Fields,
+  pub timestamp: i64,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Indicator {
+  pub fn new(
+    id: String,
+    symbol: String,
+    interval: String,
+    fields: Fields,
+    timestamp: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      symbol: symbol,
+      interval: interval,
+      fields: fields,
+      timestamp: timestamp,
+      created_at: created_at,
+      updated_at: updated_at,
+    }
+  }
+}
\ No newline at end of file
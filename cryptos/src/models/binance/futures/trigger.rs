@@ -1,14 +1,15 @@
 use chrono::{prelude::Utc, DateTime};
-use diesel::{Queryable, Selectable};
+use diesel::{Queryable, Selectable, Insertable};
 use serde::{Deserialize, Serialize};
 
 use crate::schema::binance::futures::triggers::*;
 
-#[derive(Queryable, Selectable, Deserialize, Serialize)]
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
 #[diesel(table_name = triggers)]
 pub struct Trigger {
   pub id: String,
   pub symbol: String,
+  pub side: i32,
   pub capital: f64,
   pub price: f64,
   pub take_price: f64,
@@ -29,6 +30,7 @@ impl Trigger {
   pub fn new(
     id: String,
     symbol: String,
+    side: i32,
     capital: f64,
     price: f64,
     take_price: f64,
@@ -47,6 +49,7 @@ impl Trigger {
     Self {
       id: id,
       symbol: symbol,
+      side: side,
       capital: capital,
       price: price,
       take_price: take_price,
@@ -1,4 +1,5 @@
 pub mod analysis;
+pub mod interest;
 pub mod order;
 pub mod position;
 pub mod scalping;
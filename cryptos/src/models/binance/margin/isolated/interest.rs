@@ -0,0 +1,51 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::margin::isolated::interests::*;
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = interests)]
+pub struct Interest {
+  pub id: String,
+  pub symbol: String,
+  pub asset: String,
+  pub tx_id: i64,
+  pub principal: f64,
+  pub interest: f64,
+  pub interest_rate: f64,
+  pub interest_type: String,
+  pub timestamp: i64,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Interest {
+  pub fn new(
+    id: String,
+    symbol: String,
+    asset: String,
+    tx_id: i64,
+    principal: f64,
+    interest: f64,
+    interest_rate: f64,
+    interest_type: String,
+    timestamp: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      symbol: symbol,
+      asset: asset,
+      tx_id: tx_id,
+      principal: principal,
+      interest: interest,
+      interest_rate: interest_rate,
+      interest_type: interest_type,
+      timestamp: timestamp,
+      created_at: created_at,
+      updated_at: updated_at,
+    }
+  }
+}
\ No newline at end of file
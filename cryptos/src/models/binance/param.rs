@@ -0,0 +1,114 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use diesel_as_jsonb::AsJsonb;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::params::*;
+
+// only the keys that are set override the default parameter set
+#[derive(Debug, Default, Clone, Serialize, Deserialize, AsJsonb)]
+#[serde(deny_unknown_fields)]
+pub struct Values {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub atr_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub zlema_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kdj_long_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kdj_short_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_deviation: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub tenkan_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kijun_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub senkou_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub ichimoku_limit: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub volume_profile_segments: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub volume_profile_limit: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub andean_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub andean_length: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub andean_limit: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rsi_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub macd_fast_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub macd_slow_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub macd_signal_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub adx_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub supertrend_period: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub supertrend_multiplier: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub atr_profit_ratio: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub atr_stop_ratio: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kdj_oversold: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kdj_overbought: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_middle: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_upper: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_squeeze: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbands_narrow: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rsi_oversold: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rsi_overbought: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub adx_threshold: Option<f64>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = params)]
+pub struct Param {
+  pub id: String,
+  pub market: String,
+  pub symbol: String,
+  pub interval: String,
+  pub values: Values,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Param {
+  pub fn new(
+    id: String,
+    market: String,
+    symbol: String,
+    interval: String,
+    values: Values,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      market: market,
+      symbol: symbol,
+      interval: interval,
+      values: values,
+      created_at: created_at,
+      updated_at: updated_at,
+    }
+  }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 pub mod analysis;
 pub mod symbol;
 pub mod kline;
+pub mod indicator;
 pub mod strategy;
 pub mod plan;
 pub mod order;
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::schema::binance::spot::klines::*;
 
+pub mod audit;
+
 #[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
 #[diesel(table_name = klines)]
 pub struct Kline {
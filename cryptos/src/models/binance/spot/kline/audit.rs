@@ -0,0 +1,39 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::spot::klines::audits::*;
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = audits)]
+pub struct Audit {
+  pub id: String,
+  pub symbol: String,
+  pub interval: String,
+  pub kind: String,
+  pub timestamp: i64,
+  pub detail: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Audit {
+  pub fn new(
+    id: String,
+    symbol: String,
+    interval: String,
+    kind: String,
+    timestamp: i64,
+    detail: String,
+    created_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      symbol: symbol,
+      interval: interval,
+      kind: kind,
+      timestamp: timestamp,
+      detail: detail,
+      created_at: created_at,
+    }
+  }
+}
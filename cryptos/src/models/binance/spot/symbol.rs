@@ -1,14 +1,33 @@
+use std::collections::BTreeMap;
+
 use chrono::{prelude::Utc, DateTime};
-use diesel::{Queryable, Selectable};
+use diesel::{Queryable, Selectable, Insertable};
 use diesel_as_jsonb::AsJsonb;
 use serde::{Deserialize, Serialize};
 
 use crate::schema::binance::spot::symbols::*;
 
+pub mod change;
+
 #[derive(Debug, Serialize, Deserialize, AsJsonb)]
 pub struct Filters {
   pub price: String,
   pub quote: String,
+  #[serde(default)]
+  pub notional: String,
+  #[serde(default)]
+  pub market_quote: String,
+  #[serde(default)]
+  pub orders: String,
+  #[serde(default)]
+  pub percent_price: String,
+  #[serde(default)]
+  pub iceberg_parts: String,
+  #[serde(default)]
+  pub trailing_delta: String,
+  // filter types without a column of their own, kept raw by filterType
+  #[serde(default)]
+  pub others: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, AsJsonb)]
@@ -19,7 +38,7 @@ pub struct Depth {
   pub last_update_id: i64,
 }
 
-#[derive(Queryable, Selectable, Deserialize, Serialize)]
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize)]
 #[diesel(table_name = symbols)]
 pub struct Symbol {
   pub id: String,
@@ -0,0 +1,36 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::{Queryable, Selectable, Insertable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::binance::spot::symbols::changes::*;
+
+#[derive(Queryable, Selectable, Insertable, Deserialize, Serialize, Debug)]
+#[diesel(table_name = changes)]
+pub struct Change {
+  pub id: String,
+  pub symbol: String,
+  pub field: String,
+  pub old_value: String,
+  pub new_value: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Change {
+  pub fn new(
+    id: String,
+    symbol: String,
+    field: String,
+    old_value: String,
+    new_value: String,
+    created_at: DateTime<Utc>,
+  ) -> Self {
+    Self {
+      id: id,
+      symbol: symbol,
+      field: field,
+      old_value: old_value,
+      new_value: new_value,
+      created_at: created_at,
+    }
+  }
+}
@@ -0,0 +1,28 @@
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::nats::payload::binance::futures::account::*;
+
+pub struct AccountJob {
+  ctx: Ctx,
+}
+
+impl AccountJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn update<T>(&self, reason: T, timestamp: i64) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let reason = reason.as_ref();
+    let payload = AccountUpdatePayload::new(reason, timestamp);
+    let message = serde_json::to_string(&payload).unwrap();
+    let client = self.ctx.nats.clone();
+    client.publish(Config::NATS_EVENTS_ACCOUNT_UPDATE, message.into()).await?;
+    client.flush().await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,29 @@
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::nats::payload::binance::futures::orders::*;
+
+pub struct OrdersJob {
+  ctx: Ctx,
+}
+
+impl OrdersJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn update<T>(&self, symbol: T, order_id: i64, status: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let status = status.as_ref();
+    let payload = OrdersUpdatePayload::new(symbol, order_id, status);
+    let message = serde_json::to_string(&payload).unwrap();
+    let client = self.ctx.nats.clone();
+    client.publish(Config::NATS_EVENTS_ORDERS_UPDATE, message.into()).await?;
+    client.flush().await?;
+    Ok(())
+  }
+}
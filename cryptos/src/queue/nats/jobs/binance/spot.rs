@@ -1,3 +1,4 @@
+pub mod symbols;
 pub mod klines;
 pub mod indicators;
 pub mod strategies;
@@ -0,0 +1,29 @@
+use crate::common::*;
+use crate::config::binance::spot::config as Config;
+use crate::queue::nats::payload::binance::spot::symbols::*;
+
+pub struct SymbolsJob {
+  ctx: Ctx,
+}
+
+impl SymbolsJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn halt<T>(&self, symbol: T, status: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let status = status.as_ref();
+    let payload = SymbolsHaltPayload::new(symbol, status);
+    let message = serde_json::to_string(&payload).unwrap();
+    let client = self.ctx.nats.clone();
+    client.publish(Config::NATS_EVENTS_SYMBOLS_HALT, message.into()).await?;
+    client.flush().await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,35 @@
+use crate::common::*;
+use crate::config::binance::futures::config as FuturesConfig;
+use crate::config::binance::spot::config as SpotConfig;
+use crate::config::binance::delivery::config as DeliveryConfig;
+use crate::queue::nats::payload::binance::time::*;
+
+pub struct TimeJob {
+  ctx: Ctx,
+}
+
+impl TimeJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn drift<T>(&self, market: T, offset: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let subject = match market {
+      "spot" => SpotConfig::NATS_EVENTS_TIME_DRIFT,
+      "delivery" => DeliveryConfig::NATS_EVENTS_TIME_DRIFT,
+      _ => FuturesConfig::NATS_EVENTS_TIME_DRIFT,
+    };
+    let payload = TimeDriftPayload::new(market, offset, limit);
+    let message = serde_json::to_string(&payload).unwrap();
+    let client = self.ctx.nats.clone();
+    client.publish(subject, message.into()).await?;
+    client.flush().await?;
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -1,3 +1,4 @@
 pub mod spot;
 pub mod futures;
-pub mod margin;
\ No newline at end of file
+pub mod margin;
+pub mod time;
\ No newline at end of file
@@ -1,3 +1,6 @@
+pub mod account;
+pub mod orders;
+pub mod symbols;
 pub mod klines;
 pub mod indicators;
 pub mod strategies;
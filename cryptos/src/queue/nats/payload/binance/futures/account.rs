@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccountUpdatePayload<T> {
+  pub reason: T,
+  pub timestamp: i64,
+}
+
+impl<T> AccountUpdatePayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(reason: T, timestamp: i64) -> Self {
+    Self {
+      reason: reason,
+      timestamp: timestamp,
+    }
+  }
+}
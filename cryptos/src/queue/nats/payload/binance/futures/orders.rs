@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OrdersUpdatePayload<T> {
+  pub symbol: T,
+  pub order_id: i64,
+  pub status: T,
+}
+
+impl<T> OrdersUpdatePayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(symbol: T, order_id: i64, status: T) -> Self {
+    Self {
+      symbol: symbol,
+      order_id: order_id,
+      status: status,
+    }
+  }
+}
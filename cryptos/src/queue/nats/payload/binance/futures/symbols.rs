@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SymbolsHaltPayload<T> {
+  pub symbol: T,
+  pub status: T,
+}
+
+impl<T> SymbolsHaltPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(symbol: T, status: T) -> Self {
+    Self {
+      symbol: symbol,
+      status: status,
+    }
+  }
+}
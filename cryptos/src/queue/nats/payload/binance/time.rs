@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimeDriftPayload<T> {
+  pub market: T,
+  pub offset: i64,
+  pub limit: i64,
+}
+
+impl<T> TimeDriftPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(market: T, offset: i64, limit: i64) -> Self {
+    Self {
+      market: market,
+      offset: offset,
+      limit: limit,
+    }
+  }
+}
\ No newline at end of file
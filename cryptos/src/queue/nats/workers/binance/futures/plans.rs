@@ -5,6 +5,7 @@ use crate::common::*;
 use crate::config::binance::futures::config as Config;
 use crate::queue::nats::payload::binance::futures::strategies::*;
 use crate::repositories::binance::futures::plans::*;
+use crate::repositories::binance::futures::exchange::*;
 
 pub struct PlansWorker {}
 
@@ -38,7 +39,7 @@ impl PlansWorker {
     }
 
     println!("binance futures plans nats workers process {symbol:} {interval:}");
-    if let Err(err) = PlansRepository::flush(ctx.clone(), symbol, interval).await {
+    if let Err(err) = PlansRepository::flush(ctx.clone(), &FuturesExchange::new(ctx.clone()), symbol, interval).await {
       println!("binance futures plans {symbol:} {interval:} flush failed {err:?}")
     }
 
@@ -6,6 +6,7 @@ use crate::config::binance::futures::config as Config;
 use crate::queue::nats::payload::binance::futures::indicators::*;
 use crate::queue::nats::jobs::binance::futures::strategies::*;
 use crate::repositories::binance::futures::strategies::*;
+use crate::repositories::binance::futures::exchange::*;
 
 pub struct StrategiesWorker {}
 
@@ -21,7 +22,7 @@ impl StrategiesWorker {
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
 
-    if let Err(err) = StrategiesRepository::atr(ctx.clone(), symbol, interval).await {
+    if let Err(err) = StrategiesRepository::atr(ctx.clone(), &FuturesExchange::new(ctx.clone()), symbol, interval).await {
       return Err(err.into())
     }
 
@@ -104,6 +105,62 @@ impl StrategiesWorker {
     Ok(())
   }
 
+  pub async fn rsi<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    if let Err(err) = StrategiesRepository::rsi(ctx.clone(), symbol, interval).await {
+      return Err(err.into())
+    }
+
+    Ok(())
+  }
+
+  pub async fn macd<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    if let Err(err) = StrategiesRepository::macd(ctx.clone(), symbol, interval).await {
+      return Err(err.into())
+    }
+
+    Ok(())
+  }
+
+  pub async fn adx<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    if let Err(err) = StrategiesRepository::adx(ctx.clone(), symbol, interval).await {
+      return Err(err.into())
+    }
+
+    Ok(())
+  }
+
+  pub async fn supertrend<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    if let Err(err) = StrategiesRepository::supertrend(ctx.clone(), symbol, interval).await {
+      return Err(err.into())
+    }
+
+    Ok(())
+  }
+
   pub async fn process<T>(ctx: Ctx, payload: T) -> Result<(), Box<dyn std::error::Error>> 
   where
     T: AsRef<str>
@@ -135,6 +192,10 @@ impl StrategiesWorker {
     Self::kdj(ctx.clone(), symbol, interval).await?;
     Self::bbands(ctx.clone(), symbol, interval).await?;
     Self::ichimoku_cloud(ctx.clone(), symbol, interval).await?;
+    Self::rsi(ctx.clone(), symbol, interval).await?;
+    Self::macd(ctx.clone(), symbol, interval).await?;
+    Self::adx(ctx.clone(), symbol, interval).await?;
+    Self::supertrend(ctx.clone(), symbol, interval).await?;
 
     mutex.unlock().await.unwrap();
     Ok(())
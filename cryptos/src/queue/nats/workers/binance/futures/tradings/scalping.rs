@@ -5,6 +5,7 @@ use crate::common::*;
 use crate::config::binance::futures::config as Config;
 use crate::queue::nats::payload::binance::futures::plans::*;
 use crate::repositories::binance::futures::tradings::scalping::*;
+use crate::repositories::binance::futures::exchange::*;
 
 pub struct ScalpingWorker {}
 
@@ -41,7 +42,7 @@ impl ScalpingWorker {
     }
 
     println!("binance futures tradings scalping nats workers process {plan_id:}");
-    if let Err(err) = ScalpingRepository::place(ctx.clone(), plan_id).await {
+    if let Err(err) = ScalpingRepository::place(ctx.clone(), &FuturesExchange::new(ctx.clone()), plan_id).await {
       println!("binance futures tradings scalping {plan_id:} place failed {err:?}")
     }
 
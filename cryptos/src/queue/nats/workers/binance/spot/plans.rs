@@ -5,6 +5,7 @@ use crate::common::*;
 use crate::config::binance::spot::config as Config;
 use crate::queue::nats::payload::binance::spot::strategies::*;
 use crate::repositories::binance::spot::plans::*;
+use crate::repositories::binance::spot::exchange::*;
 
 pub struct PlansWorker {}
 
@@ -38,7 +39,7 @@ impl PlansWorker {
     }
 
     println!("binance spot plans nats workers process {symbol:} {interval:}");
-    if let Err(err) = PlansRepository::flush(ctx.clone(), symbol, interval).await {
+    if let Err(err) = PlansRepository::flush(ctx.clone(), &SpotExchange::new(ctx.clone()), symbol, interval).await {
       println!("binance spot plans {symbol:} {interval:} flush failed {err:?}")
     }
 
@@ -5,6 +5,7 @@ use crate::common::*;
 use crate::config::binance::spot::config as Config;
 use crate::queue::nats::payload::binance::spot::plans::*;
 use crate::repositories::binance::spot::tradings::scalping::*;
+use crate::repositories::binance::spot::exchange::*;
 
 pub struct ScalpingWorker {}
 
@@ -41,7 +42,7 @@ impl ScalpingWorker {
     }
 
     println!("binance spot tradings scalping nats workers process {plan_id:}");
-    if let Err(err) = ScalpingRepository::place(ctx.clone(), plan_id).await {
+    if let Err(err) = ScalpingRepository::place(ctx.clone(), &SpotExchange::new(ctx.clone()), plan_id).await {
       println!("binance spot tradings scalping {plan_id:} place failed {err:?}")
     }
 
@@ -2,4 +2,5 @@ pub mod account;
 pub mod klines;
 pub mod indicators;
 pub mod strategies;
-pub mod plans;
\ No newline at end of file
+pub mod plans;
+pub mod tradings;
\ No newline at end of file
@@ -15,6 +15,33 @@ impl KlinesJob {
     }
   }
 
+  pub async fn flush<T>(&self, symbol: T, interval: T, endtime: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let payload = KlinesFlushPayload::new(symbol, interval, endtime, limit);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_KLINES_FLUSH,
+      &content,
+    ]).unwrap();
+
+    let rmq = self.ctx.rmq.lock().await.clone();
+    let mut client = Rsmq::new(rmq.clone()).await?;
+    match client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await {
+      Err(RsmqError::QueueNotFound) => {
+        client.create_queue(Config::RSMQ_QUEUE_KLINES, None, None, None).await?;
+        client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await?;
+      }
+      _ => (),
+    };
+
+    Ok(())
+  }
+
   pub async fn sync<T>(&self, interval: T) -> Result<(), Box<dyn std::error::Error>> 
   where
     T: AsRef<str>
@@ -40,4 +67,55 @@ impl KlinesJob {
 
     Ok(())
   }
+
+  pub async fn aggregate<T>(&self, interval: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+
+    let payload = KlinesAggregatePayload::new(interval);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_KLINES_AGGREGATE,
+      &content,
+    ]).unwrap();
+
+    let rmq = self.ctx.rmq.lock().await.clone();
+    let mut client = Rsmq::new(rmq.clone()).await?;
+    match client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await {
+      Err(RsmqError::QueueNotFound) => {
+        client.create_queue(Config::RSMQ_QUEUE_KLINES, None, None, None).await?;
+        client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await?;
+      }
+      _ => (),
+    };
+
+    Ok(())
+  }
+  pub async fn clean<T>(&self, interval: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+
+    let payload = KlinesCleanPayload::new(interval);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_KLINES_CLEAN,
+      &content,
+    ]).unwrap();
+
+    let rmq = self.ctx.rmq.lock().await.clone();
+    let mut client = Rsmq::new(rmq.clone()).await?;
+    match client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await {
+      Err(RsmqError::QueueNotFound) => {
+        client.create_queue(Config::RSMQ_QUEUE_KLINES, None, None, None).await?;
+        client.send_message(Config::RSMQ_QUEUE_KLINES, message.clone(), None).await?;
+      }
+      _ => (),
+    };
+
+    Ok(())
+  }
 }
\ No newline at end of file
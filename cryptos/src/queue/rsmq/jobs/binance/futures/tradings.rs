@@ -0,0 +1,2 @@
+pub mod launchpad;
+pub mod triggers;
\ No newline at end of file
@@ -0,0 +1,63 @@
+use rsmq_async::{RsmqError, RsmqConnection};
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::rsmq::payload::binance::futures::tradings::launchpad::*;
+
+pub struct LaunchpadJob {
+  ctx: Ctx,
+}
+
+impl LaunchpadJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn place<T>(&self, symbol: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let payload = LaunchpadPlacePayload::new(symbol);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_TRADINGS_LAUNCHPAD_PLACE,
+      &content,
+    ]).unwrap();
+
+    self.send(message).await
+  }
+
+  pub async fn flush<T>(&self, id: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let payload = LaunchpadFlushPayload::new(id);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_TRADINGS_LAUNCHPAD_FLUSH,
+      &content,
+    ]).unwrap();
+
+    self.send(message).await
+  }
+
+  async fn send(&self, message: String) -> Result<(), Box<dyn std::error::Error>> {
+    let rmq = self.ctx.rmq.lock().await.clone();
+    let mut client = Rsmq::new(rmq.clone()).await?;
+    match client.send_message(Config::RSMQ_QUEUE_TRADINGS_LAUNCHPAD, message.clone(), None).await {
+      Err(RsmqError::QueueNotFound) => {
+        client.create_queue(Config::RSMQ_QUEUE_TRADINGS_LAUNCHPAD, None, None, None).await?;
+        client.send_message(Config::RSMQ_QUEUE_TRADINGS_LAUNCHPAD, message.clone(), None).await?;
+      }
+      _ => ()
+    };
+
+    Ok(())
+  }
+}
\ No newline at end of file
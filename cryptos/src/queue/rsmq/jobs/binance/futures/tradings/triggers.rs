@@ -0,0 +1,63 @@
+use rsmq_async::{RsmqError, RsmqConnection};
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::rsmq::payload::binance::futures::tradings::triggers::*;
+
+pub struct TriggersJob {
+  ctx: Ctx,
+}
+
+impl TriggersJob {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn place<T>(&self, trigger_id: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let trigger_id = trigger_id.as_ref();
+
+    let payload = TriggersPlacePayload::new(trigger_id);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_TRADINGS_TRIGGERS_PLACE,
+      &content,
+    ]).unwrap();
+
+    self.send(message).await
+  }
+
+  pub async fn flush<T>(&self, id: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let payload = TriggersFlushPayload::new(id);
+    let content = serde_json::to_string(&payload).unwrap();
+    let message = serde_json::to_string(&[
+      Config::RSMQ_JOBS_TRADINGS_TRIGGERS_FLUSH,
+      &content,
+    ]).unwrap();
+
+    self.send(message).await
+  }
+
+  async fn send(&self, message: String) -> Result<(), Box<dyn std::error::Error>> {
+    let rmq = self.ctx.rmq.lock().await.clone();
+    let mut client = Rsmq::new(rmq.clone()).await?;
+    match client.send_message(Config::RSMQ_QUEUE_TRADINGS_TRIGGERS, message.clone(), None).await {
+      Err(RsmqError::QueueNotFound) => {
+        client.create_queue(Config::RSMQ_QUEUE_TRADINGS_TRIGGERS, None, None, None).await?;
+        client.send_message(Config::RSMQ_QUEUE_TRADINGS_TRIGGERS, message.clone(), None).await?;
+      }
+      _ => ()
+    };
+
+    Ok(())
+  }
+}
\ No newline at end of file
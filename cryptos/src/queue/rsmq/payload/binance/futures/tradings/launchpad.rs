@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LaunchpadPlacePayload<T> {
+  pub symbol: T,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LaunchpadFlushPayload<T> {
+  pub id: T,
+}
+
+impl<T> LaunchpadPlacePayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(symbol: T) -> Self {
+    Self {
+      symbol: symbol,
+    }
+  }
+}
+
+impl<T> LaunchpadFlushPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(id: T) -> Self {
+    Self {
+      id: id,
+    }
+  }
+}
\ No newline at end of file
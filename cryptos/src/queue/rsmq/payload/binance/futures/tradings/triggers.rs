@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TriggersPlacePayload<T> {
+  pub trigger_id: T,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TriggersFlushPayload<T> {
+  pub id: T,
+}
+
+impl<T> TriggersPlacePayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(trigger_id: T) -> Self {
+    Self {
+      trigger_id: trigger_id,
+    }
+  }
+}
+
+impl<T> TriggersFlushPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(id: T) -> Self {
+    Self {
+      id: id,
+    }
+  }
+}
\ No newline at end of file
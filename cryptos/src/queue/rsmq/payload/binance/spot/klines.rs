@@ -1,11 +1,65 @@
 use serde::{Deserialize, Serialize};
 
+#[derive(Deserialize, Serialize)]
+pub struct KlinesFlushPayload<T> {
+  pub symbol: T,
+  pub interval: T,
+  pub endtime: i64,
+  pub limit: i64,
+}
+
+impl<T> KlinesFlushPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(symbol: T, interval: T, endtime: i64, limit: i64) -> Self {
+    Self {
+      symbol: symbol,
+      interval: interval,
+      endtime: endtime,
+      limit: limit,
+    }
+  }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct KlinesSyncPayload<T> {
   pub interval: T,
 }
 
 impl<T> KlinesSyncPayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(interval: T) -> Self {
+    Self {
+      interval: interval,
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct KlinesAggregatePayload<T> {
+  pub interval: T,
+}
+
+impl<T> KlinesAggregatePayload<T>
+where
+  T: AsRef<str>
+{
+  pub fn new(interval: T) -> Self {
+    Self {
+      interval: interval,
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct KlinesCleanPayload<T> {
+  pub interval: T,
+}
+
+impl<T> KlinesCleanPayload<T>
 where
   T: AsRef<str>
 {
@@ -2,8 +2,10 @@ use tokio::task::JoinSet;
 
 use crate::common::*;
 use crate::queue::rsmq::workers::binance::futures::klines::*;
+use crate::queue::rsmq::workers::binance::futures::tradings::*;
 
 pub mod klines;
+pub mod tradings;
 
 pub struct FuturesWorkers {
   ctx: Ctx,
@@ -20,6 +22,7 @@ impl FuturesWorkers {
     println!("binance futures rsmq workers subscribe");
     let ctx = self.ctx.clone();
     KlinesWorker::new(ctx.clone()).subscribe(workers).await?;
+    TradingsWorker::new(ctx.clone()).subscribe(workers).await?;
     Ok(())
   }
 }
\ No newline at end of file
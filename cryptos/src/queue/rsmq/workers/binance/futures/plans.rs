@@ -7,6 +7,7 @@ use crate::common::*;
 use crate::config::binance::futures::config as Config;
 use crate::queue::rsmq::payload::binance::futures::plans::*;
 use crate::repositories::binance::futures::plans::*;
+use crate::repositories::binance::futures::exchange::*;
 
 pub struct PlansWorker {
   ctx: Ctx,
@@ -39,7 +40,7 @@ impl PlansWorker {
     }
 
     println!("binance futures plans rsmq workers process {symbol:} {interval:}");
-    if let Err(err) = PlansRepository::flush(ctx.clone(), symbol, interval).await {
+    if let Err(err) = PlansRepository::flush(ctx.clone(), &FuturesExchange::new(ctx.clone()), symbol, interval).await {
       println!("binance futures plans {symbol:} {interval:} flush failed {err:?}")
     }
 
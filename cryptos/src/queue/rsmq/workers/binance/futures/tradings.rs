@@ -0,0 +1,27 @@
+use tokio::task::JoinSet;
+
+use crate::common::*;
+use crate::queue::rsmq::workers::binance::futures::tradings::launchpad::*;
+use crate::queue::rsmq::workers::binance::futures::tradings::triggers::*;
+
+pub mod launchpad;
+pub mod triggers;
+
+pub struct TradingsWorker {
+  ctx: Ctx,
+}
+
+impl TradingsWorker {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn subscribe(&self, workers: &mut JoinSet<()>) -> Result<(), Box<dyn std::error::Error>> {
+    println!("binance futures tradings rsmq workers subscribe");
+    LaunchpadWorker::new(self.ctx.clone()).subscribe(workers).await?;
+    TriggersWorker::new(self.ctx.clone()).subscribe(workers).await?;
+    Ok(())
+  }
+}
\ No newline at end of file
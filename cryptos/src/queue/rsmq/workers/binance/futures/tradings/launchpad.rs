@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use rsmq_async::RsmqConnection;
+use tokio::task::JoinSet;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::queue::rsmq::payload::binance::futures::tradings::launchpad::*;
+use crate::repositories::binance::futures::tradings::launchpad::*;
+use crate::repositories::binance::futures::exchange::*;
+
+pub struct LaunchpadWorker {
+  ctx: Ctx,
+}
+
+impl LaunchpadWorker {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  pub async fn place<T>(ctx: Ctx, symbol: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_lock_key = format!("{}:{}", Config::LOCKS_TRADINGS_LAUNCHPAD_PLACE, symbol);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_lock_key,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(30)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_lock_key)));
+    }
+
+    println!("binance futures tradings launchpad rsmq workers place {symbol:}");
+    if let Err(err) = LaunchpadRepository::place(ctx.clone(), &FuturesExchange::new(ctx.clone()), symbol).await {
+      println!("binance futures tradings launchpad {symbol:} place failed {err:?}")
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn flush<T>(ctx: Ctx, id: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_lock_key = format!("{}:{}", Config::LOCKS_TRADINGS_LAUNCHPAD_FLUSH, id);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_lock_key,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(30)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_lock_key)));
+    }
+
+    if let Err(err) = LaunchpadRepository::flush(ctx.clone(), &FuturesExchange::new(ctx.clone()), id).await {
+      println!("binance futures tradings launchpad {id:} flush failed {err:?}")
+    }
+
+    mutex.unlock().await.unwrap();
+
+    Ok(())
+  }
+
+  pub async fn subscribe(&self, workers: &mut JoinSet<()>) -> Result<(), Box<dyn std::error::Error>> 
+  {
+    println!("binance futures tradings launchpad rsmq workers subscribe");
+    workers.spawn(Box::pin({
+      let ctx = self.ctx.clone();
+      async move {
+        let rmq = ctx.rmq.lock().await.clone();
+        let mut client = Rsmq::new(rmq).await.unwrap();
+        loop {
+          match client.pop_message::<String>(Config::RSMQ_QUEUE_TRADINGS_LAUNCHPAD).await {
+            Ok(Some(message)) => {
+              let (action, content) = serde_json::from_slice::<(String, String)>(message.message.as_bytes()).unwrap();
+              match action.as_str() {
+                Config::RSMQ_JOBS_TRADINGS_LAUNCHPAD_PLACE => {
+                  let payload = serde_json::from_slice::<LaunchpadPlacePayload<&str>>(content.as_bytes()).unwrap();
+                  if let Err(err) = Self::place(ctx.clone(), payload.symbol).await {
+                    println!("{err:?}");
+                  }
+                }
+                Config::RSMQ_JOBS_TRADINGS_LAUNCHPAD_FLUSH => {
+                  let payload = serde_json::from_slice::<LaunchpadFlushPayload<&str>>(content.as_bytes()).unwrap();
+                  if let Err(err) = Self::flush(ctx.clone(), payload.id).await {
+                    println!("{err:?}");
+                  }
+                }
+                _ => (),
+              };
+            }
+            Ok(None) => {
+              tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            }
+            Err(_) => ()
+          }
+        }
+      }
+    }));
+    Ok(())
+  }
+}
\ No newline at end of file
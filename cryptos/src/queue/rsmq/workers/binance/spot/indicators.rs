@@ -6,7 +6,10 @@ use tokio::task::JoinSet;
 use crate::common::*;
 use crate::config::binance::spot::config as Config;
 use crate::queue::rsmq::payload::binance::spot::indicators::*;
+use crate::repositories::binance::params::*;
 use crate::repositories::binance::spot::indicators::*;
+use crate::repositories::binance::spot::indicators::stream::*;
+use crate::repositories::binance::spot::indicators::history::*;
 
 pub struct IndicatorsWorker {
   ctx: Ctx,
@@ -36,7 +39,7 @@ impl IndicatorsWorker {
     Ok(())
   }
 
-  pub async fn atr<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn atr<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
@@ -48,14 +51,14 @@ impl IndicatorsWorker {
       ctx.clone(),
       symbol,
       interval,
-      14,
-      100,
+      params.atr_period,
+      params.limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn zlema<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn zlema<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
@@ -67,14 +70,14 @@ impl IndicatorsWorker {
       ctx.clone(),
       symbol,
       interval,
-      14,
-      100,
+      params.zlema_period,
+      params.limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn ha_zlema<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn ha_zlema<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
@@ -86,14 +89,14 @@ impl IndicatorsWorker {
       ctx.clone(),
       symbol,
       interval,
-      14,
-      100,
+      params.zlema_period,
+      params.limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn kdj<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn kdj<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
@@ -105,15 +108,15 @@ impl IndicatorsWorker {
       ctx.clone(),
       symbol,
       interval,
-      9,
-      3,
-      100,
+      params.kdj_long_period,
+      params.kdj_short_period,
+      params.limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn bbands<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn bbands<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
@@ -125,108 +128,172 @@ impl IndicatorsWorker {
       ctx.clone(),
       symbol,
       interval,
-      14,
-      100,
+      params.bbands_period,
+      params.bbands_deviation,
+      params.limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn ichimoku_cloud<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn ichimoku_cloud<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
 
-    let tenkan_period: i32;
-    let kijun_period: i32;
-    let senkou_period: i32;
-    let limit: i64;
-    if interval == "1m" {
-      tenkan_period = 129;
-      kijun_period = 374;
-      senkou_period = 748;
-      limit = 1440;
-    } else if interval == "15m" {
-      tenkan_period = 60;
-      kijun_period = 174;
-      senkou_period = 349;
-      limit = 672;
-    } else if interval == "4h" {
-      tenkan_period = 11;
-      kijun_period = 32;
-      senkou_period = 65;
-      limit = 126;
-    } else {
-      tenkan_period = 9;
-      kijun_period = 26;
-      senkou_period = 52;
-      limit = 100;
-    }
     println!("binance spot indicators rsmq workers ichimoku cloud {symbol:} {interval:}");
     let _ = IndicatorsRepository::ichimoku_cloud(
       ctx.clone(),
       symbol,
       interval,
-      tenkan_period,
-      kijun_period,
-      senkou_period,
-      limit,
+      params.tenkan_period,
+      params.kijun_period,
+      params.senkou_period,
+      params.ichimoku_limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn volume_profile<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn volume_profile<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
 
-    let limit: i64;
-    if interval == "1m" {
-      limit = 1440
-    } else if interval == "15m" {
-      limit = 672
-    } else if interval == "4h" {
-      limit = 126
-    } else {
-      limit = 100
-    }
-
     println!("binance spot indicators rsmq workers volume profile {symbol:} {interval:}");
     let _ = IndicatorsRepository::volume_profile(
       ctx.clone(),
       symbol,
       interval,
-      limit,
+      params.volume_profile_segments,
+      params.volume_profile_limit,
     ).await;
 
     Ok(())
   }
 
-  pub async fn andean_oscillator<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  pub async fn andean_oscillator<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
   where
     T: AsRef<str>
   {
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
 
-    let limit: i64;
-    if interval == "1m" {
-      limit = 1440
-    } else if interval == "15m" {
-      limit = 672
-    } else if interval == "4h" {
-      limit = 126
-    } else {
-      limit = 100
+    println!("binance spot indicators rsmq workers andean oscillator {symbol:} {interval:}");
+    let _ = IndicatorsRepository::andean_oscillator(ctx.clone(), symbol, interval, params.andean_period, params.andean_length, params.andean_limit).await;
+
+    Ok(())
+  }
+
+  pub async fn rsi<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers rsi {symbol:} {interval:}");
+    let _ = IndicatorsRepository::rsi(
+      ctx.clone(),
+      symbol,
+      interval,
+      params.rsi_period,
+      params.limit,
+    ).await;
+
+    Ok(())
+  }
+
+  pub async fn macd<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers macd {symbol:} {interval:}");
+    let _ = IndicatorsRepository::macd(
+      ctx.clone(),
+      symbol,
+      interval,
+      params.macd_fast_period,
+      params.macd_slow_period,
+      params.macd_signal_period,
+      params.limit,
+    ).await;
+
+    Ok(())
+  }
+
+  pub async fn adx<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers adx {symbol:} {interval:}");
+    let _ = IndicatorsRepository::adx(
+      ctx.clone(),
+      symbol,
+      interval,
+      params.adx_period,
+      params.limit,
+    ).await;
+
+    Ok(())
+  }
+
+  pub async fn supertrend<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers supertrend {symbol:} {interval:}");
+    let _ = IndicatorsRepository::supertrend(
+      ctx.clone(),
+      symbol,
+      interval,
+      params.supertrend_period,
+      params.supertrend_multiplier,
+      params.limit,
+    ).await;
+
+    Ok(())
+  }
+
+  pub async fn stream<T>(ctx: Ctx, symbol: T, interval: T, params: &IndicatorParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers stream {symbol:} {interval:}");
+    if let Err(err) = StreamRepository::flush(ctx.clone(), symbol, interval, params.engine()).await {
+      println!("binance spot indicators stream {symbol:} {interval:} failed {err:?}");
     }
 
-    println!("binance spot indicators rsmq workers andean oscillator {symbol:} {interval:}");
-    let _ = IndicatorsRepository::andean_oscillator(ctx.clone(), symbol, interval, 50, 9, limit).await;
+    Ok(())
+  }
+
+  pub async fn history<T>(ctx: Ctx, symbol: T, interval: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    println!("binance spot indicators rsmq workers history {symbol:} {interval:}");
+    if let Err(err) = HistoryRepository::flush(ctx.clone(), symbol, interval).await {
+      println!("binance spot indicators history {symbol:} {interval:} failed {err:?}");
+    }
 
     Ok(())
   }
@@ -252,14 +319,24 @@ impl IndicatorsWorker {
 
     println!("binance spot indicators rsmq workers process {symbol:} {interval:}");
     Self::pivot(ctx.clone(), symbol, interval).await?;
-    Self::atr(ctx.clone(), symbol, interval).await?;
-    Self::zlema(ctx.clone(), symbol, interval).await?;
-    Self::ha_zlema(ctx.clone(), symbol, interval).await?;
-    Self::kdj(ctx.clone(), symbol, interval).await?;
-    Self::bbands(ctx.clone(), symbol, interval).await?;
-    Self::ichimoku_cloud(ctx.clone(), symbol, interval).await?;
-    Self::volume_profile(ctx.clone(), symbol, interval).await?;
-    Self::andean_oscillator(ctx.clone(), symbol, interval).await?;
+    let params = ParamsRepository::get(ctx.clone(), "spot", symbol, interval).await?;
+    if Env::int("BINANCE_SPOT_INDICATORS_STREAM") > 0 {
+      Self::stream(ctx.clone(), symbol, interval, &params).await?;
+    } else {
+      Self::atr(ctx.clone(), symbol, interval, &params).await?;
+      Self::zlema(ctx.clone(), symbol, interval, &params).await?;
+      Self::ha_zlema(ctx.clone(), symbol, interval, &params).await?;
+      Self::kdj(ctx.clone(), symbol, interval, &params).await?;
+      Self::bbands(ctx.clone(), symbol, interval, &params).await?;
+      Self::ichimoku_cloud(ctx.clone(), symbol, interval, &params).await?;
+      Self::volume_profile(ctx.clone(), symbol, interval, &params).await?;
+      Self::andean_oscillator(ctx.clone(), symbol, interval, &params).await?;
+      Self::rsi(ctx.clone(), symbol, interval, &params).await?;
+      Self::macd(ctx.clone(), symbol, interval, &params).await?;
+      Self::adx(ctx.clone(), symbol, interval, &params).await?;
+      Self::supertrend(ctx.clone(), symbol, interval, &params).await?;
+    }
+    Self::history(ctx.clone(), symbol, interval).await?;
 
     mutex.unlock().await.unwrap();
 
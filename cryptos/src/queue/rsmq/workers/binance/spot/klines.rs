@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use chrono::prelude::Utc;
 use rsmq_async::RsmqConnection;
 use tokio::task::JoinSet;
 
@@ -8,6 +9,7 @@ use crate::config::binance::spot::config as Config;
 use crate::queue::rsmq::payload::binance::spot::klines::*;
 use crate::repositories::binance::spot::scalping::*;
 use crate::repositories::binance::spot::klines::*;
+use crate::repositories::binance::spot::indicators::*;
 
 pub struct KlinesWorker {
   ctx: Ctx,
@@ -20,11 +22,40 @@ impl KlinesWorker {
     }
   }
 
+  pub async fn flush<T>(ctx: Ctx, symbol: T, interval: T, endtime: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_key = format!("{}:{}:{}", Config::LOCKS_KLINES_FLUSH, interval, symbol);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_key,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(30)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_key)));
+    }
+
+    println!("binance spot klines flush {symbol:} {interval:} {endtime:} {limit:}");
+    if let Err(err) = KlinesRepository::flush(ctx.clone(), symbol, interval, endtime, limit).await {
+      println!("binance spot klines flush {symbol:} {interval:} failed {err:?}");
+    }
+
+    mutex.unlock().await.unwrap();
+    Ok(())
+  }
+
   pub async fn sync<T>(ctx: Ctx, interval: T) -> Result<(), Box<dyn std::error::Error>> 
   where
     T: AsRef<str>
   {
     let interval = interval.as_ref();
+    let timestamp = Interval::parse(interval)?.timestamp();
   
     let rdb = ctx.rdb.lock().await.clone();
     let mutex_id = xid::new().to_string();
@@ -39,7 +70,6 @@ impl KlinesWorker {
     }
  
     let symbols = ScalpingRepository::scan(ctx.clone()).await.unwrap();
-    let timestamp = KlinesRepository::timestamp(interval);
     let _ = KlinesRepository::sync(
       ctx.clone(),
       symbols.iter().map(String::as_ref).collect(),
@@ -51,6 +81,102 @@ impl KlinesWorker {
     Ok(())
   }
 
+  pub async fn aggregate<T>(ctx: Ctx, interval: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+    let timestamp = timeframe.prev(timeframe.timestamp());
+
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_key = format!("{}:{}", Config::LOCKS_KLINES_AGGREGATE, interval);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_key,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_key)));
+    }
+
+    let symbols = ScalpingRepository::scan(ctx.clone()).await.unwrap();
+    for symbol in symbols.iter() {
+      match KlinesRepository::aggregate(ctx.clone(), symbol.as_str(), interval, timestamp).await {
+        Ok(true) => println!("binance spot klines aggregate {symbol:} {interval:} {timestamp:} success"),
+        Ok(false) => (),
+        Err(err) => println!("binance spot klines aggregate {symbol:} {interval:} failed {err:?}"),
+      }
+    }
+
+    mutex.unlock().await.unwrap();
+    Ok(())
+  }
+
+  pub async fn clean<T>(ctx: Ctx, interval: T) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+
+    let retention = match KlinesRepository::retention("BINANCE_SPOT_KLINES_RETENTION").get(&timeframe) {
+      Some(retention) => *retention,
+      None => return Ok(()),
+    };
+
+    let mut timestamp = timeframe.align(Utc::now().timestamp_millis() - retention);
+    let lookback = timeframe.timestamp() - IndicatorsRepository::lookback(timeframe) * timeframe.timestep();
+    if timestamp > lookback {
+      println!("binance spot klines clean {interval:} retention shorter than indicators look back");
+      timestamp = lookback;
+    }
+    if timeframe == Interval::Minute1 {
+      // aggregation of the previous bucket still reads its 1m bars
+      for item in Interval::from_env("BINANCE_SPOT_INTERVALS") {
+        let start = item.prev(item.timestamp());
+        if timestamp > start {
+          timestamp = start;
+        }
+      }
+    }
+
+    let rdb = ctx.rdb.lock().await.clone();
+    let mutex_id = xid::new().to_string();
+    let redis_key = format!("{}:{}", Config::LOCKS_KLINES_CLEAN, interval);
+    let mut mutex = RedisMutex::new(
+      rdb,
+      &redis_key,
+      &mutex_id,
+    );
+    if !mutex.lock(Duration::from_secs(60)).await.unwrap() {
+      return Err(Box::from(format!("mutex failed {}", redis_key)));
+    }
+
+    let mut limit = Env::int("BINANCE_SPOT_KLINES_CLEAN_LIMIT") as i64;
+    if limit < 1 {
+      limit = 5000;
+    }
+    for _ in 0..20 {
+      let effective_rows = match KlinesRepository::clean(ctx.clone(), interval, timestamp, limit).await {
+        Ok(effective_rows) => effective_rows,
+        Err(err) => {
+          println!("binance spot klines clean {interval:} failed {err:?}");
+          break
+        }
+      };
+      println!("binance spot klines clean {interval:} before {timestamp:} deleted {effective_rows:}");
+      if (effective_rows as i64) < limit {
+        break
+      }
+      tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    mutex.unlock().await.unwrap();
+    Ok(())
+  }
+
   pub async fn subscribe(&self, workers: &mut JoinSet<()>) -> Result<(), Box<dyn std::error::Error>> 
   {
     println!("binance spot klines rsmq workers subscribe");
@@ -65,12 +191,30 @@ impl KlinesWorker {
             Ok(Some(message)) => {
               let (action, content) = serde_json::from_slice::<(String, String)>(message.message.as_bytes()).unwrap();
               match action.as_str() {
+                Config::RSMQ_JOBS_KLINES_FLUSH => {
+                  let payload = serde_json::from_slice::<KlinesFlushPayload<&str>>(content.as_bytes()).unwrap();
+                  if let Err(err) = Self::flush(ctx.clone(), payload.symbol, payload.interval, payload.endtime, payload.limit).await {
+                    println!("{err:?}");
+                  }
+                }
                 Config::RSMQ_JOBS_KLINES_SYNC => {
                   let payload = serde_json::from_slice::<KlinesSyncPayload<&str>>(content.as_bytes()).unwrap();
                   if let Err(err) = Self::sync(ctx.clone(), payload.interval).await {
                     println!("{err:?}");
                   }
                 }
+                Config::RSMQ_JOBS_KLINES_AGGREGATE => {
+                  let payload = serde_json::from_slice::<KlinesAggregatePayload<&str>>(content.as_bytes()).unwrap();
+                  if let Err(err) = Self::aggregate(ctx.clone(), payload.interval).await {
+                    println!("{err:?}");
+                  }
+                }
+                Config::RSMQ_JOBS_KLINES_CLEAN => {
+                  let payload = serde_json::from_slice::<KlinesCleanPayload<&str>>(content.as_bytes()).unwrap();
+                  if let Err(err) = Self::clean(ctx.clone(), payload.interval).await {
+                    println!("{err:?}");
+                  }
+                }
                 _ => (),
               };
             }
@@ -7,6 +7,7 @@ use crate::common::*;
 use crate::config::binance::spot::config as Config;
 use crate::queue::rsmq::payload::binance::spot::plans::*;
 use crate::repositories::binance::spot::plans::*;
+use crate::repositories::binance::spot::exchange::*;
 
 pub struct PlansWorker {
   ctx: Ctx,
@@ -39,7 +40,7 @@ impl PlansWorker {
     }
 
     println!("binance spot plans rsmq workers process {symbol:} {interval:}");
-    if let Err(err) = PlansRepository::flush(ctx.clone(), symbol, interval).await {
+    if let Err(err) = PlansRepository::flush(ctx.clone(), &SpotExchange::new(ctx.clone()), symbol, interval).await {
       println!("binance spot plans {symbol:} {interval:} flush failed {err:?}")
     }
 
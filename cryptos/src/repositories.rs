@@ -1,4 +1,6 @@
 pub mod auth;
 pub mod jwe;
+pub mod exchange;
+pub mod indicators;
 pub mod binance;
 pub mod uniswap;
\ No newline at end of file
@@ -2,7 +2,11 @@ use serde::{Serialize, Deserialize};
 
 pub mod spot;
 pub mod futures;
+pub mod delivery;
 pub mod margin;
+pub mod client;
+pub mod time;
+pub mod params;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiError {
@@ -0,0 +1,300 @@
+use std::time::Duration;
+use std::collections::HashMap;
+
+use url::{Url, form_urlencoded};
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+use hmac::{Hmac, Mac};
+use chrono::prelude::Utc;
+use redis::AsyncCommands;
+use reqwest::{header, Method, StatusCode};
+use rsa::{pkcs8::DecodePrivateKey, RsaPrivateKey};
+use serde::de::DeserializeOwned;
+
+use crate::common::*;
+use crate::config::binance::futures::config as FuturesConfig;
+use crate::config::binance::spot::config as SpotConfig;
+use crate::config::binance::delivery::config as DeliveryConfig;
+use crate::repositories::binance::ApiError;
+use crate::repositories::binance::time::*;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Security {
+  Public,
+  // api key header without signature, used by listen keys
+  Stream,
+  Account,
+  Trade,
+}
+
+pub struct ApiClient {
+  ctx: Ctx,
+  market: &'static str,
+  weight_key: &'static str,
+  orders_key: &'static str,
+  banned_key: &'static str,
+}
+
+impl ApiClient {
+  pub fn new(ctx: Ctx, market: &'static str) -> Self {
+    let (weight_key, orders_key, banned_key) = match market {
+      "spot" => (SpotConfig::REDIS_KEY_API_WEIGHT, SpotConfig::REDIS_KEY_API_ORDERS, SpotConfig::REDIS_KEY_API_BANNED),
+      "delivery" => (DeliveryConfig::REDIS_KEY_API_WEIGHT, DeliveryConfig::REDIS_KEY_API_ORDERS, DeliveryConfig::REDIS_KEY_API_BANNED),
+      _ => (FuturesConfig::REDIS_KEY_API_WEIGHT, FuturesConfig::REDIS_KEY_API_ORDERS, FuturesConfig::REDIS_KEY_API_BANNED),
+    };
+    Self {
+      ctx: ctx,
+      market: market,
+      weight_key: weight_key,
+      orders_key: orders_key,
+      banned_key: banned_key,
+    }
+  }
+
+  pub async fn get<R>(
+    &self,
+    path: &str,
+    params: &HashMap<&str, &str>,
+    security: Security,
+    weight: i64,
+  ) -> Result<R, Box<dyn std::error::Error>>
+  where
+    R: DeserializeOwned
+  {
+    self.request(Method::GET, path, params, security, weight).await
+  }
+
+  pub async fn post<R>(
+    &self,
+    path: &str,
+    params: &HashMap<&str, &str>,
+    security: Security,
+    weight: i64,
+  ) -> Result<R, Box<dyn std::error::Error>>
+  where
+    R: DeserializeOwned
+  {
+    self.request(Method::POST, path, params, security, weight).await
+  }
+
+  pub async fn put<R>(
+    &self,
+    path: &str,
+    params: &HashMap<&str, &str>,
+    security: Security,
+    weight: i64,
+  ) -> Result<R, Box<dyn std::error::Error>>
+  where
+    R: DeserializeOwned
+  {
+    self.request(Method::PUT, path, params, security, weight).await
+  }
+
+  pub async fn delete<R>(
+    &self,
+    path: &str,
+    params: &HashMap<&str, &str>,
+    security: Security,
+    weight: i64,
+  ) -> Result<R, Box<dyn std::error::Error>>
+  where
+    R: DeserializeOwned
+  {
+    self.request(Method::DELETE, path, params, security, weight).await
+  }
+
+  pub async fn request<R>(
+    &self,
+    method: Method,
+    path: &str,
+    params: &HashMap<&str, &str>,
+    security: Security,
+    weight: i64,
+  ) -> Result<R, Box<dyn std::error::Error>>
+  where
+    R: DeserializeOwned
+  {
+    let is_order = security == Security::Trade && method != Method::GET;
+    self.acquire(weight, is_order).await?;
+
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in params.iter() {
+      serializer.append_pair(key, value);
+    }
+    if security == Security::Account || security == Security::Trade {
+      let recv_window = self.limit("RECV_WINDOW", 5000).to_string();
+      let timestamp = TimeRepository::timestamp(self.ctx.clone(), self.market).await?.to_string();
+      serializer.append_pair("recvWindow", &recv_window);
+      serializer.append_pair("timestamp", &timestamp);
+    }
+    let mut query = serializer.finish();
+
+    match security {
+      Security::Account => {
+        type HmacSha256 = Hmac<Sha256>;
+        let mut mac = HmacSha256::new_from_slice(self.env("ACCOUNT_API_SECRET").as_bytes())?;
+        mac.update(query.as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+        query = form_urlencoded::Serializer::new(query).append_pair("signature", &signature).finish();
+      }
+      Security::Trade => {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(&self.env("TRADE_API_SECRET"))?;
+        let signature = private_key.sign(
+          rsa::pkcs1v15::Pkcs1v15Sign::new::<rsa::sha2::Sha256>(),
+          &Sha256::digest(query.as_bytes()),
+        )?;
+        let signature = general_purpose::STANDARD.encode(signature);
+        query = form_urlencoded::Serializer::new(query).append_pair("signature", &signature).finish();
+      }
+      _ => (),
+    }
+
+    let mut url = Url::parse(format!("{}{}", self.env("API_ENDPOINT"), path).as_str())?;
+    if !query.is_empty() {
+      url.set_query(Some(&query));
+    }
+
+    let mut headers = header::HeaderMap::new();
+    match security {
+      Security::Stream | Security::Account => {
+        headers.insert("X-MBX-APIKEY", self.env("ACCOUNT_API_KEY").parse()?);
+      }
+      Security::Trade => {
+        headers.insert("X-MBX-APIKEY", self.env("TRADE_API_KEY").parse()?);
+      }
+      _ => (),
+    }
+
+    let client = reqwest::Client::new();
+    let response = client.request(method, url)
+      .headers(headers)
+      .timeout(Duration::from_secs(30))
+      .send()
+      .await?;
+
+    self.track(response.headers(), response.status()).await?;
+
+    let status_code = response.status();
+
+    if status_code.is_server_error() {
+      return Err(Box::new(ApiError{
+        code: status_code.as_u16().into(),
+        message: "".to_string(),
+      }))
+    }
+
+    if status_code.is_client_error() {
+      let content = response.text().await.unwrap_or_default();
+      match serde_json::from_str::<ApiError>(&content) {
+        Ok(err) => return Err(Box::new(err)),
+        Err(_) => return Err(Box::new(ApiError{
+          code: status_code.as_u16().into(),
+          message: content,
+        }))
+      }
+    }
+
+    if !status_code.is_success() {
+      return Err(Box::from(format!("request error: {}", status_code)))
+    }
+
+    Ok(response.json::<R>().await?)
+  }
+
+  fn env(&self, key: &str) -> String {
+    Env::var(format!("BINANCE_{}_{}", self.market.to_uppercase(), key))
+  }
+
+  fn limit(&self, key: &str, default: i64) -> i64 {
+    match self.env(key).parse::<i64>() {
+      Ok(limit) if limit > 0 => limit,
+      _ => default,
+    }
+  }
+
+  // reserve weight in the shared minute bucket, wait for the next bucket when
+  // the reservation would take the ip above 90% of its limit
+  async fn acquire(&self, weight: i64, is_order: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rdb = self.ctx.rdb.lock().await.clone();
+
+    let banned: Option<i64> = rdb.get(self.banned_key).await?;
+    if let Some(until) = banned {
+      return Err(Box::from(format!("binance {} api banned until {}", self.market, until)))
+    }
+
+    let (default_weight, default_orders) = match self.market {
+      "spot" => (6000, 100),
+      _ => (2400, 300),
+    };
+    let weight_limit = self.limit("API_WEIGHT_LIMIT", default_weight) * 9 / 10;
+    let orders_limit = self.limit("API_ORDERS_LIMIT", default_orders) * 9 / 10;
+
+    loop {
+      let timestamp = Utc::now().timestamp();
+      let redis_key = format!("{}:{}", self.weight_key, timestamp / 60);
+      let used: i64 = rdb.incr(&redis_key, weight).await?;
+      () = rdb.expire(&redis_key, 120).await?;
+      if used <= weight_limit {
+        break
+      }
+      let _: i64 = rdb.decr(&redis_key, weight).await?;
+      println!("binance {} api weight {} over {}, wait for next minute", self.market, used, weight_limit);
+      tokio::time::sleep(Duration::from_secs((60 - timestamp % 60) as u64)).await;
+    }
+
+    while is_order {
+      let timestamp = Utc::now().timestamp();
+      let redis_key = format!("{}:{}", self.orders_key, timestamp / 10);
+      let used: i64 = rdb.incr(&redis_key, 1).await?;
+      () = rdb.expire(&redis_key, 20).await?;
+      if used <= orders_limit {
+        break
+      }
+      let _: i64 = rdb.decr(&redis_key, 1).await?;
+      println!("binance {} api orders {} over {}, wait for next window", self.market, used, orders_limit);
+      tokio::time::sleep(Duration::from_secs((10 - timestamp % 10) as u64)).await;
+    }
+
+    Ok(())
+  }
+
+  // the exchange counts are authoritative, raise the shared buckets to them
+  async fn track(&self, headers: &header::HeaderMap, status_code: StatusCode) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rdb = self.ctx.rdb.lock().await.clone();
+    let timestamp = Utc::now().timestamp();
+
+    let script = redis::Script::new(r"
+      local current = tonumber(redis.call('GET', KEYS[1]) or '0')
+      if tonumber(ARGV[1]) > current then
+        redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+      end
+      return 1
+    ");
+    let header = |key: &str| headers.get(key).and_then(|value| value.to_str().ok()).and_then(|value| value.parse::<i64>().ok());
+
+    if let Some(used) = header("x-mbx-used-weight-1m") {
+      let _: i64 = script
+        .key(format!("{}:{}", self.weight_key, timestamp / 60))
+        .arg(used)
+        .arg(120)
+        .invoke_async(&mut rdb)
+        .await?;
+    }
+    if let Some(used) = header("x-mbx-order-count-10s") {
+      let _: i64 = script
+        .key(format!("{}:{}", self.orders_key, timestamp / 10))
+        .arg(used)
+        .arg(20)
+        .invoke_async(&mut rdb)
+        .await?;
+    }
+
+    if status_code == StatusCode::TOO_MANY_REQUESTS || status_code == StatusCode::IM_A_TEAPOT {
+      let retry_after = header("retry-after").unwrap_or(60);
+      println!("binance {} api rate limited {} retry after {}s", self.market, status_code, retry_after);
+      () = rdb.set_ex(self.banned_key, timestamp + retry_after, retry_after as u64).await?;
+    }
+
+    Ok(())
+  }
+}
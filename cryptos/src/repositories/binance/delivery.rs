@@ -0,0 +1,8 @@
+pub mod account;
+pub mod symbols;
+pub mod exchange;
+pub mod klines;
+pub mod tickers;
+pub mod indicators;
+pub mod orders;
+pub mod positions;
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use redis::AsyncCommands;
+use serde::{Deserialize, Deserializer};
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::repositories::binance::delivery::positions::*;
+use crate::schema::binance::delivery::positions::*;
+use crate::config::binance::delivery::config as Config;
+
+pub struct AccountRepository {}
+
+#[derive(Deserialize)]
+struct AccountInfo {
+  assets: Vec<Balance>,
+  positions: Vec<PositionInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Balance {
+  asset: String,
+  #[serde(alias = "walletBalance", deserialize_with = "to_f64")]
+  balance: f64,
+  #[serde(alias = "availableBalance", deserialize_with = "to_f64")]
+  free: f64,
+  #[serde(alias = "unrealizedProfit", deserialize_with = "to_f64")]
+  unrealized_profit: f64,
+  #[serde(alias = "marginBalance", deserialize_with = "to_f64")]
+  margin: f64,
+  #[serde(alias = "initialMargin", deserialize_with = "to_f64")]
+  initial_margin: f64,
+  #[serde(alias = "maintMargin", deserialize_with = "to_f64")]
+  maint_margin: f64,
+}
+
+// position amounts are contracts, notional value is in the margin asset
+#[derive(Debug, Deserialize)]
+struct PositionInfo {
+  symbol: String,
+  #[serde(alias = "positionSide")]
+  position_side: String,
+  isolated: bool,
+  #[serde(deserialize_with = "to_i32")]
+  leverage: i32,
+  #[serde(alias = "maxQty", deserialize_with = "to_f64")]
+  capital: f64,
+  #[serde(alias = "notionalValue", deserialize_with = "to_f64")]
+  notional: f64,
+  #[serde(alias = "entryPrice", deserialize_with = "to_f64")]
+  entry_price: f64,
+  #[serde(alias = "positionAmt", deserialize_with = "to_f64")]
+  entry_quantity: f64,
+  #[serde(alias = "updateTime")]
+  update_time: i64,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+fn to_i32<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<i32>().map_err(serde::de::Error::custom)
+}
+
+impl AccountRepository {
+  pub async fn balance<T>(ctx: Ctx, asset: T) -> Result<(f64, f64, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let asset = asset.as_ref();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_BALANCE, asset);
+    let fields = vec!["balance", "free", "unrealized_profit", "margin", "initial_margin", "maint_margin"];
+    match redis::cmd("HMGET")
+      .arg(&redis_key)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((
+        Some(balance),
+        Some(free),
+        Some(unrealized_profit),
+        Some(margin),
+        Some(initial_margin),
+        Some(maint_margin)),
+      ) => Ok((
+        balance,
+        free,
+        unrealized_profit,
+        margin,
+        initial_margin,
+        maint_margin,
+      )),
+      Ok(_) => return Err(Box::from(format!("balance of {asset:} not exists"))),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let account_info: AccountInfo = client.get("/dapi/v1/account", &HashMap::new(), Security::Account, 5).await?;
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let mut currencies: Vec<String> = Vec::new();
+    let last_currencies: Vec<String> = rdb.smembers(Config::REDIS_KEY_CURRENCIES).await.unwrap();
+
+    let mut pipe = redis::pipe();
+    account_info.assets.iter().for_each(|coin| {
+      if coin.balance <= 0.0 {
+        return;
+      }
+      pipe.hset_multiple(
+        format!("{}:{}", Config::REDIS_KEY_BALANCE, coin.asset),
+        &[
+          ("balance", coin.balance.to_string()),
+          ("free", coin.free.to_string()),
+          ("unrealized_profit", coin.unrealized_profit.to_string()),
+          ("margin", coin.margin.to_string()),
+          ("initial_margin", coin.initial_margin.to_string()),
+          ("maint_margin", coin.maint_margin.to_string()),
+        ],
+      );
+      pipe.sadd(Config::REDIS_KEY_CURRENCIES, &coin.asset);
+      currencies.push(coin.asset.clone());
+      println!("coin balance {} {} {}", coin.asset, coin.balance, coin.unrealized_profit);
+    });
+
+    last_currencies.iter().for_each(|last_asset| {
+      if currencies.iter().any(|asset| asset == last_asset) {
+        return;
+      }
+      pipe.srem(Config::REDIS_KEY_CURRENCIES, &last_asset);
+      pipe.del(format!("{}:{}", Config::REDIS_KEY_BALANCE, last_asset));
+      println!("coin balance remove {}", last_asset);
+    });
+
+    () = pipe.query_async(&mut rdb).await?;
+
+    for position in account_info.positions.iter() {
+      if position.isolated || position.update_time == 0 {
+        continue
+      }
+
+      // one way mode reports BOTH, the sign of the amount tells the side
+      let side: i32 = match position.position_side.as_str() {
+        "LONG" => 1,
+        "SHORT" => 2,
+        "BOTH" if position.entry_quantity > 0.0 => 1,
+        "BOTH" if position.entry_quantity < 0.0 => 2,
+        _ => continue,
+      };
+      let mut entry_quantity = position.entry_quantity;
+
+      if side == 1 && entry_quantity < 0.0 {
+        entry_quantity = 0.0;
+      }
+      if side == 2 && entry_quantity > 0.0 {
+        entry_quantity = 0.0;
+      }
+      if entry_quantity < 0.0 {
+        entry_quantity = -entry_quantity;
+      }
+
+      let entity = match PositionsRepository::get(ctx.clone(), position.symbol.clone(), side).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        if entry_quantity == 0.0 {
+          continue
+        }
+        let id = xid::new().to_string();
+        match PositionsRepository::create(
+          ctx.clone(),
+          id,
+          position.symbol.clone(),
+          side,
+          position.leverage,
+          position.capital,
+          position.notional.abs(),
+          position.entry_price,
+          entry_quantity,
+          Utc::now().timestamp_micros(),
+          1,
+        ).await {
+          Ok(result) => {
+            println!("binance delivery position {0:} {side:} create success {result:}", position.symbol);
+          }
+          Err(err) => {
+            println!("binance delivery position {0:} {side:} create failed {err:?}", position.symbol)
+          }
+        }
+      } else {
+        let entity = entity.unwrap();
+        if entity.entry_price == position.entry_price && entity.entry_quantity == entry_quantity {
+          continue
+        }
+        match PositionsRepository::update(
+          ctx.clone(),
+          entity.id,
+          entity.version,
+          (
+            positions::leverage.eq(position.leverage),
+            positions::capital.eq(position.capital),
+            positions::notional.eq(position.notional.abs()),
+            positions::entry_price.eq(position.entry_price),
+            positions::entry_quantity.eq(entry_quantity),
+            positions::timestamp.eq(Utc::now().timestamp_micros()),
+            positions::version.eq(positions::version + 1),
+          ),
+        ).await {
+          Ok(result) => {
+            println!("binance delivery position {0:} {side:} update success {result:}", position.symbol);
+          }
+          Err(err) => {
+            println!("binance delivery position {0:} {side:} update failed {err:?}", position.symbol)
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::common::*;
+use crate::models::binance::delivery::symbol::Depth;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::client::*;
+use crate::repositories::binance::delivery::account::*;
+use crate::repositories::binance::delivery::klines::*;
+use crate::repositories::binance::delivery::orders::*;
+use crate::repositories::binance::delivery::symbols::*;
+use crate::repositories::binance::delivery::tickers::*;
+
+// strategies size orders in the base asset, coin margined contracts are
+// a fixed usd value each, quantities are converted at the current price
+pub struct DeliveryExchange {
+  ctx: Ctx,
+}
+
+impl DeliveryExchange {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+
+  async fn to_base(&self, symbol: &str, contracts: f64, price: f64) -> Result<f64, Box<dyn std::error::Error>> {
+    let (contract_size, _) = SymbolsRepository::contract(self.ctx.clone(), symbol).await?;
+    if price <= 0.0 {
+      return Ok(0.0)
+    }
+    Ok(contracts * contract_size / price)
+  }
+}
+
+fn levels(items: &Vec<Vec<String>>) -> Vec<(f64, f64)> {
+  items.iter().filter_map(|item| {
+    match (item[0].parse::<f64>(), item[1].parse::<f64>()) {
+      (Ok(price), Ok(quantity)) => Some((price, quantity)),
+      _ => None,
+    }
+  }).collect()
+}
+
+impl Exchange for DeliveryExchange {
+  fn name(&self) -> &'static str {
+    "binance:delivery"
+  }
+
+  async fn klines(&self, symbol: &str, interval: &str, endtime: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>> {
+    KlinesRepository::flush(self.ctx.clone(), symbol, interval, endtime, limit).await
+  }
+
+  async fn price(&self, symbol: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    TickersRepository::price(self.ctx.clone(), symbol).await
+  }
+
+  async fn depth(&self, symbol: &str, limit: i64) -> Result<Book, Box<dyn std::error::Error>> {
+    let limit_val = match limit {
+      0..=5 => 5,
+      6..=10 => 10,
+      11..=20 => 20,
+      21..=50 => 50,
+      51..=100 => 100,
+      101..=500 => 500,
+      _ => 1000,
+    };
+    let weight = match limit_val {
+      5..=50 => 2,
+      100 => 5,
+      500 => 10,
+      _ => 20,
+    };
+    let limit_str = limit_val.to_string();
+
+    let mut params = HashMap::<&str, &str>::new();
+    params.insert("symbol", symbol);
+    params.insert("limit", &limit_str);
+
+    let client = ApiClient::new(self.ctx.clone(), "delivery");
+    let depth: Depth = client.get("/dapi/v1/depth", &params, Security::Public, weight).await?;
+    let mut asks = levels(&depth.asks);
+    let mut bids = levels(&depth.bids);
+    asks.truncate(limit as usize);
+    bids.truncate(limit as usize);
+    Ok(Book {
+      asks: asks,
+      bids: bids,
+    })
+  }
+
+  async fn filters(&self, symbol: &str) -> Result<SymbolFilters, Box<dyn std::error::Error>> {
+    let (tick_size, step_size, contract_size) = SymbolsRepository::filters(self.ctx.clone(), symbol).await?;
+    let price = TickersRepository::price(self.ctx.clone(), symbol).await?;
+    Ok(SymbolFilters {
+      tick_size: tick_size,
+      step_size: self.to_base(symbol, step_size, price).await?,
+      notional: contract_size,
+    })
+  }
+
+  // the margin is held in the coin rather than the usd quote, so the coin stands in as the quote asset
+  async fn pairs(&self, symbol: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+    let (base_asset, _) = SymbolsRepository::pairs(self.ctx.clone(), symbol).await?;
+    let (_, margin_asset) = SymbolsRepository::contract(self.ctx.clone(), symbol).await?;
+    Ok((base_asset, margin_asset))
+  }
+
+  // free margin of the coin valued in usd at its perpetual, the unit orders are sized in
+  async fn balance(&self, asset: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    let (_, free, _, _, _, _) = AccountRepository::balance(self.ctx.clone(), asset).await?;
+    let symbol = SymbolsRepository::perpetual(self.ctx.clone(), asset).await?;
+    let price = TickersRepository::price(self.ctx.clone(), &symbol).await?;
+    Ok(free * price)
+  }
+
+  async fn place(&self, order: &OrderRequest) -> Result<i64, Box<dyn std::error::Error>> {
+    let (position_side, side) = match (order.side, order.is_open) {
+      (1, true) => ("LONG", "BUY"),
+      (1, false) => ("LONG", "SELL"),
+      (2, true) => ("SHORT", "SELL"),
+      (2, false) => ("SHORT", "BUY"),
+      _ => return Err(Box::from(format!("order side {} not supported", order.side))),
+    };
+    if order.is_open {
+      let (_, margin_asset) = SymbolsRepository::contract(self.ctx.clone(), &order.symbol).await?;
+      let (_, free, _, _, _, _) = AccountRepository::balance(self.ctx.clone(), &margin_asset).await?;
+      if free <= 0.0 {
+        return Err(Box::from(format!("order of {} margin {} not enough", order.symbol, margin_asset)))
+      }
+    }
+    let (_, step_size, contract_size) = SymbolsRepository::filters(self.ctx.clone(), &order.symbol).await?;
+    let contracts = SymbolsRepository::contracts(order.price * order.quantity, contract_size, step_size);
+    if contracts <= 0.0 {
+      return Err(Box::from(format!("order of {} {} less than one contract of {}", order.symbol, order.quantity, contract_size)))
+    }
+    OrdersRepository::submit(
+      self.ctx.clone(),
+      &order.symbol[..],
+      position_side,
+      side,
+      order.price,
+      contracts,
+    ).await
+  }
+
+  async fn cancel(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::cancel(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: self.to_base(symbol, quantity, price).await?,
+      executed_quantity: self.to_base(symbol, executed_quantity, if avg_price > 0.0 { avg_price } else { price }).await?,
+      avg_price: avg_price,
+    })
+  }
+
+  async fn query(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::query(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: self.to_base(symbol, quantity, price).await?,
+      executed_quantity: self.to_base(symbol, executed_quantity, if avg_price > 0.0 { avg_price } else { price }).await?,
+      avg_price: avg_price,
+    })
+  }
+}
\ No newline at end of file
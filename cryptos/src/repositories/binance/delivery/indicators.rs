@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use chrono::{prelude::Utc, DateTime, Local};
+
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::repositories::indicators::maths;
+use crate::config::binance::delivery::config as Config;
+use crate::repositories::binance::delivery::klines::*;
+use crate::repositories::binance::delivery::symbols::*;
+
+#[derive(Default)]
+pub struct IndicatorsRepository {}
+
+impl IndicatorsRepository {
+  async fn save(ctx: Ctx, symbol: &str, interval: &str, values: &[(&str, String)]) -> Result<(), Box<dyn std::error::Error>> {
+    let ttl = Duration::from_secs(30+86400);
+    let day = Local::now().format("%m%d").to_string();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let is_exists: bool = rdb.exists(&redis_key).await.unwrap();
+    () = rdb.hset_multiple(&redis_key, values).await?;
+    if !is_exists {
+      () = rdb.expire(&redis_key, ttl.as_secs().try_into().unwrap()).await?;
+    }
+
+    Ok(())
+  }
+
+  pub async fn pivot<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let (tick_size, _, _) = SymbolsRepository::filters(ctx.clone(), symbol).await?;
+
+    let (_, high, low, close, timestamp) = match KlinesRepository::series(ctx.clone(), symbol, interval, 1).await?.pop() {
+      Some(result) => result,
+      None => return Err(Box::from(format!("[{symbol:}] {interval:} klines not exists"))),
+    };
+
+    if timestamp < Interval::parse(interval)?.timestamp() - 60000 {
+      return Err(Box::from(format!("[{symbol:}] waiting for {interval:} klines flush")))
+    }
+
+    let dt = DateTime::from_timestamp_millis(timestamp).unwrap();
+    if dt.format("%m%d").to_string() != Utc::now().format("%m%d").to_string() {
+      return Err(Box::from(format!("[{symbol:}] {interval:} timestamp is not today")))
+    }
+
+    let [r3, r2, r1, s1, s2, s3] = maths::pivot(close, high, low, tick_size);
+
+    Self::save(ctx.clone(), symbol, interval, &[
+      ("r3", r3.to_string()),
+      ("r2", r2.to_string()),
+      ("r1", r1.to_string()),
+      ("s1", s1.to_string()),
+      ("s2", s2.to_string()),
+      ("s3", s3.to_string()),
+    ]).await
+  }
+
+  pub async fn atr<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    period: i32,
+    limit: i64,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let items = KlinesRepository::series(ctx.clone(), symbol, interval, limit).await?;
+    if items.len() < limit as usize {
+      return Err(Box::from(format!("[{symbol:}] {interval:} klines not enough")))
+    }
+
+    let timeframe = Interval::parse(interval)?;
+    let (_, _, _, _, first_timestamp) = items[0];
+    if first_timestamp < timeframe.timestamp() - 60000 {
+      return Err(Box::from(format!("[{symbol:}] waiting for {interval:} klines flush")))
+    }
+    for window in items.windows(2) {
+      if window[0].4 != timeframe.next(window[1].4) {
+        return Err(Box::from(format!("[{symbol:}] {interval:} klines lost")))
+      }
+    }
+
+    let highs = items.iter().rev().map(|item| item.1).collect::<Vec<f64>>();
+    let lows = items.iter().rev().map(|item| item.2).collect::<Vec<f64>>();
+    let closes = items.iter().rev().map(|item| item.3).collect::<Vec<f64>>();
+
+    let result = match maths::atr(&highs, &lows, &closes, period) {
+      Ok(result) => result,
+      Err(err) => return Err(Box::from(format!("[{symbol:}] {interval:} {err:}"))),
+    };
+
+    Self::save(ctx.clone(), symbol, interval, &[("atr", result.to_string())]).await
+  }
+}
\ No newline at end of file
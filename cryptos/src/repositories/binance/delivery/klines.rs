@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use chrono::prelude::Utc;
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::models::binance::delivery::kline::*;
+use crate::schema::binance::delivery::klines::*;
+
+#[derive(Default)]
+pub struct KlinesRepository {}
+
+impl KlinesRepository
+{
+  pub async fn series<T> (
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    limit: i64,
+  ) -> Result<Vec<(f64, f64, f64, f64, i64)>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let series = klines::table
+      .select((klines::open, klines::high, klines::low, klines::close, klines::timestamp))
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq(interval))
+      .order(klines::timestamp.desc())
+      .limit(limit)
+      .load::<(f64, f64, f64, f64, i64)>(&mut conn)?;
+
+    Ok(series)
+  }
+
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    timestamp: i64,
+  ) -> Result<Option<Kline>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match klines::table
+      .select(Kline::as_select())
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq(interval))
+      .filter(klines::timestamp.eq(timestamp))
+      .first(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn upsert(
+    ctx: Ctx,
+    items: Vec<Kline>,
+  ) -> Result<usize, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let mut effective_rows = 0;
+    for chunk in items.chunks(1000) {
+      effective_rows += diesel::insert_into(klines::table)
+        .values(chunk)
+        .on_conflict((klines::symbol, klines::interval, klines::timestamp))
+        .do_update()
+        .set((
+          klines::open.eq(excluded(klines::open)),
+          klines::close.eq(excluded(klines::close)),
+          klines::high.eq(excluded(klines::high)),
+          klines::low.eq(excluded(klines::low)),
+          klines::volume.eq(excluded(klines::volume)),
+          klines::quota.eq(excluded(klines::quota)),
+          klines::updated_at.eq(excluded(klines::updated_at)),
+        ))
+        .execute(&mut conn)?;
+    }
+
+    Ok(effective_rows)
+  }
+
+  pub async fn clean<T>(
+    ctx: Ctx,
+    interval: T,
+    timestamp: i64,
+    limit: i64,
+  ) -> Result<usize, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let ids = klines::table
+      .select(klines::id)
+      .filter(klines::interval.eq(interval))
+      .filter(klines::timestamp.lt(timestamp))
+      .limit(limit);
+    match diesel::delete(klines::table.filter(klines::id.eq_any(ids))).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // volume counts contracts and quota the base asset, as the dapi reports them
+  pub async fn flush<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    endtime: i64,
+    limit: i64,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let endtime_val = endtime.to_string();
+    let weight = match limit {
+      0..=99 => 1,
+      100..=499 => 2,
+      500..=1000 => 5,
+      _ => 10,
+    };
+    let limit = limit.to_string();
+
+    let mut params = HashMap::<&str, &str>::new();
+    params.insert("symbol", symbol);
+    params.insert("interval", interval);
+    if endtime > 0 {
+      params.insert("endTime", &endtime_val);
+    }
+    params.insert("limit", &limit);
+
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let klines: Vec<(i64, String, String, String, String, String, i64, String, i64, String, String, String)> = client.get("/dapi/v1/klines", &params, Security::Public, weight).await?;
+    let now = Utc::now();
+    let items = klines.iter().map(|(timestamp, open, high, low, close, volume, _, quota, ..)| {
+      Kline::new(
+        xid::new().to_string(),
+        symbol.to_string(),
+        interval.to_string(),
+        open.parse::<f64>().unwrap(),
+        close.parse::<f64>().unwrap(),
+        high.parse::<f64>().unwrap(),
+        low.parse::<f64>().unwrap(),
+        volume.parse::<f64>().unwrap(),
+        quota.parse::<f64>().unwrap(),
+        *timestamp,
+        now,
+        now,
+      )
+    }).collect::<Vec<_>>();
+    if !items.is_empty() {
+      Self::upsert(ctx.clone(), items).await?;
+    }
+
+    Ok(())
+  }
+}
\ No newline at end of file
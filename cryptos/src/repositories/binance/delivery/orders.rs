@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use serde::{Deserialize, Deserializer};
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::schema::binance::delivery::orders::*;
+use crate::models::binance::delivery::order::*;
+
+#[derive(Deserialize)]
+struct OrderInfo {
+  symbol: String,
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(alias = "type")]
+  order_type: String,
+  #[serde(alias = "positionSide")]
+  position_side: String,
+  side: String,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "avgPrice", deserialize_with = "to_f64")]
+  avg_price: f64,
+  #[serde(alias = "stopPrice", deserialize_with = "to_f64")]
+  stop_price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "time")]
+  open_time: i64,
+  #[serde(alias = "updateTime")]
+  update_time: i64,
+  #[serde(alias = "workingType")]
+  working_type: String,
+  #[serde(alias = "priceProtect")]
+  price_protect: bool,
+  #[serde(alias = "reduceOnly")]
+  reduce_only: bool,
+  #[serde(alias = "closePosition")]
+  close_position: bool,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct TradeInfo {
+  symbol: String,
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(alias = "type")]
+  order_type: String,
+  #[serde(alias = "positionSide")]
+  position_side: String,
+  side: String,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "avgPrice", deserialize_with = "to_f64")]
+  avg_price: f64,
+  #[serde(alias = "stopPrice", deserialize_with = "to_f64")]
+  stop_price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "workingType")]
+  working_type: String,
+  #[serde(alias = "priceProtect")]
+  price_protect: bool,
+  #[serde(alias = "reduceOnly")]
+  reduce_only: bool,
+  #[serde(alias = "closePosition")]
+  close_position: bool,
+  #[serde(alias = "updateTime")]
+  update_time: i64,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct StateInfo {
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "avgPrice", deserialize_with = "to_f64")]
+  avg_price: f64,
+  status: String,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+pub struct OrdersRepository {}
+
+impl OrdersRepository {
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<Option<Order>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match orders::table
+      .select(Order::as_select())
+      .filter(orders::symbol.eq(symbol))
+      .filter(orders::order_id.eq(order_id))
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    order_id: i64,
+    order_type: String,
+    position_side: String,
+    side: String,
+    price: f64,
+    avg_price: f64,
+    stop_price: f64,
+    quantity: f64,
+    executed_quantity: f64,
+    open_time: i64,
+    update_time: i64,
+    working_type: String,
+    price_protect: bool,
+    reduce_only: bool,
+    close_position: bool,
+    status: String,
+    remark: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let order = Order::new(
+      id,
+      symbol,
+      order_id,
+      order_type,
+      position_side,
+      side,
+      price,
+      avg_price,
+      stop_price,
+      quantity,
+      executed_quantity,
+      open_time,
+      update_time,
+      working_type,
+      price_protect,
+      reduce_only,
+      close_position,
+      status,
+      remark,
+      now,
+      now,
+    );
+    match diesel::insert_into(orders::table)
+      .values(&order)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>> 
+  where
+    V: diesel::AsChangeset<Target = orders::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(orders::table.find(id)).set(values).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // quantity is a number of contracts, not an amount of the base asset
+  pub async fn submit<T>(
+    ctx: Ctx,
+    symbol: T,
+    position_side: T,
+    side: T,
+    price: f64,
+    quantity: f64,
+  ) -> Result<i64, Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    println!("orders submit");
+    let symbol = symbol.as_ref();
+    let position_side = position_side.as_ref();
+    let side = side.as_ref();
+    let price_val = price.to_string();
+    let quantity_val = quantity.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("positionSide", position_side);
+    params.insert("side", side);
+    params.insert("type", "LIMIT");
+    params.insert("price", &price_val);
+    params.insert("quantity", &quantity_val);
+    params.insert("timeInForce", "GTC");
+    params.insert("newOrderRespType", "RESULT");
+
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let trade: TradeInfo = client.post("/dapi/v1/order", &params, Security::Trade, 1).await?;
+    println!("response {:?}", trade.order_id);
+
+    let id = xid::new().to_string();
+    match Self::create(
+      ctx.clone(),
+      id,
+      trade.symbol.to_owned(),
+      trade.order_id,
+      trade.order_type.to_owned(),
+      trade.position_side.to_owned(),
+      trade.side.to_owned(),
+      trade.price,
+      trade.avg_price,
+      trade.stop_price,
+      trade.quantity,
+      trade.executed_quantity,
+      trade.update_time,
+      0,
+      trade.working_type.to_owned(),
+      trade.price_protect,
+      trade.reduce_only,
+      trade.close_position,
+      trade.status.to_owned(),
+      "".to_owned(),
+    ).await {
+      Ok(result) => {
+        println!("binance delivery order {symbol:}[{position_side:}] {0:} create success {result:}", trade.order_id);
+      }
+      Err(err) => {
+        println!("binance delivery order {symbol:}[{position_side:}] {0:} create failed {err:?}", trade.order_id)
+      }
+    }
+
+    Ok(trade.order_id)
+  }
+
+  pub async fn sync<T>(
+    ctx: Ctx,
+    symbol: T,
+    start_time: i64,
+    limit: i64,
+  ) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let start_time_val = start_time.to_string();
+    let limit = limit.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    if start_time > 0 {
+      params.insert("startTime", &start_time_val);
+    }
+    params.insert("limit", &limit);
+
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let orders: Vec<OrderInfo> = client.get("/dapi/v1/allOrders", &params, Security::Account, 20).await?;
+
+    for order in orders.iter() {
+      let entity: Option<Order> = match Self::get(ctx.clone(), order.symbol.clone(), order.order_id).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        let id = xid::new().to_string();
+        match Self::create(
+          ctx.clone(),
+          id,
+          symbol.to_owned(),
+          order.order_id,
+          order.order_type.to_owned(),
+          order.position_side.to_owned(),
+          order.side.to_owned(),
+          order.price,
+          order.avg_price,
+          order.stop_price,
+          order.quantity,
+          order.executed_quantity,
+          order.open_time,
+          order.update_time,
+          order.working_type.to_owned(),
+          order.price_protect,
+          order.reduce_only,
+          order.close_position,
+          order.status.to_owned(),
+          "".to_owned(),
+        ).await {
+          Ok(result) => {
+            println!("binance delivery order {0:}[{1:}] {2:} create success {result:}", order.symbol, order.position_side, order.order_id);
+          }
+          Err(err) => {
+            println!("binance delivery order {0:}[{1:}] {2:} create failed {err:?}", order.symbol, order.position_side, order.order_id)
+          }
+        }
+      } else {
+        let entity = entity.unwrap();
+        if entity.price == order.price
+          && entity.avg_price == order.avg_price
+          && entity.stop_price == order.stop_price
+          && entity.quantity == order.quantity
+          && entity.executed_quantity == order.executed_quantity
+          && entity.update_time == order.update_time
+          && entity.working_type == order.working_type
+          && entity.price_protect == order.price_protect
+          && entity.reduce_only == order.reduce_only
+          && entity.close_position == order.close_position
+          && entity.status == order.status {
+          continue
+        }
+        match Self::update(
+          ctx.clone(),
+          entity.id,
+          (
+            orders::price.eq(order.price),
+            orders::avg_price.eq(order.avg_price),
+            orders::stop_price.eq(order.stop_price),
+            orders::quantity.eq(order.quantity),
+            orders::executed_quantity.eq(order.executed_quantity),
+            orders::update_time.eq(order.update_time),
+            orders::working_type.eq(order.working_type.to_owned()),
+            orders::price_protect.eq(order.price_protect),
+            orders::reduce_only.eq(order.reduce_only),
+            orders::close_position.eq(order.close_position),
+            orders::status.eq(order.status.to_owned()),
+          ),
+        ).await {
+          Ok(result) => {
+            println!("binance delivery order {0:}[{1:}] {2:} update success {result:}", order.symbol, order.position_side, order.order_id);
+          }
+          Err(err) => {
+            println!("binance delivery order {0:}[{1:}] {2:} update failed {err:?}", order.symbol, order.position_side, order.order_id)
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  pub async fn cancel<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let info: StateInfo = client.delete("/dapi/v1/order", &params, Security::Trade, 1).await?;
+
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, info.avg_price))
+  }
+
+  pub async fn query<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let info: StateInfo = client.get("/dapi/v1/order", &params, Security::Account, 1).await?;
+
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, info.avg_price))
+  }
+}
\ No newline at end of file
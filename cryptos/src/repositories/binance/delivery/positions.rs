@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use chrono::prelude::Utc;
+
+use crate::common::*;
+use crate::models::binance::delivery::position::*;
+use crate::schema::binance::delivery::positions::*;
+
+#[derive(Default)]
+pub struct PositionsRepository {}
+
+impl PositionsRepository
+{
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    side: i32,
+  ) -> Result<Option<Position>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match positions::table
+      .select(Position::as_select())
+      .filter(positions::symbol.eq(symbol))
+      .filter(positions::side.eq(side))
+      .filter(positions::status.eq(1))
+      .first(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn gets(ctx: Ctx, conditions: &mut HashMap<&str, MixValue>) -> Result<Vec<(String, String, i32, i32, f64, f64, f64, f64, i64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let mut query = positions::table.into_boxed();
+    if let Some(MixValue::Int(side)) = conditions.get("side") {
+      query = query.filter(positions::side.eq(side));
+    }
+    let positions = query
+      .select((
+        positions::id,
+        positions::symbol,
+        positions::side,
+        positions::leverage,
+        positions::capital,
+        positions::notional,
+        positions::entry_price,
+        positions::entry_quantity,
+        positions::timestamp,
+      ))
+      .load::<(String, String, i32, i32, f64, f64, f64, f64, i64)>(&mut conn)?;
+    Ok(positions)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    side: i32,
+    leverage: i32,
+    capital: f64,
+    notional: f64,
+    entry_price: f64,
+    entry_quantity: f64,
+    timestamp: i64,
+    status: i32,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let position = Position::new(
+      id,
+      symbol,
+      side,
+      leverage,
+      capital,
+      notional,
+      entry_price,
+      entry_quantity,
+      timestamp,
+      status,
+      0,
+      now,
+      now,
+    );
+    match diesel::insert_into(positions::table)
+      .values(&position)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>> 
+  where
+    V: diesel::AsChangeset<Target = positions::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(positions::table.find(id))
+      .filter(positions::version.eq(version))
+      .set(values)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // positions on a delivered quarterly are settled by the exchange
+  pub async fn settle<T>(
+    ctx: Ctx,
+    symbol: T,
+  ) -> Result<usize, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(positions::table)
+      .filter(positions::symbol.eq(symbol))
+      .filter(positions::status.eq(1))
+      .set((
+        positions::status.eq(0),
+        positions::version.eq(positions::version + 1),
+        positions::updated_at.eq(Utc::now()),
+      ))
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn symbols(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let symbols = positions::table
+      .select(positions::symbol)
+      .filter(positions::status.eq(1))
+      .filter(positions::entry_quantity.gt(0.0))
+      .distinct()
+      .load::<String>(&mut conn)?;
+    Ok(symbols)
+  }
+}
\ No newline at end of file
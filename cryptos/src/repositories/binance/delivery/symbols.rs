@@ -0,0 +1,418 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use serde::Deserialize;
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::schema::binance::delivery::symbols::*;
+use crate::models::binance::delivery::symbol::*;
+
+#[derive(Default)]
+pub struct SymbolsRepository {}
+
+#[derive(Deserialize)]
+struct ExchangeInfo {
+  symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Deserialize)]
+struct SymbolInfo {
+  symbol: String,
+  pair: String,
+  #[serde(alias = "baseAsset")]
+  base_asset: String,
+  #[serde(alias = "quoteAsset")]
+  quote_asset: String,
+  #[serde(alias = "marginAsset")]
+  margin_asset: String,
+  #[serde(alias = "contractType")]
+  contract_type: String,
+  #[serde(alias = "contractSize")]
+  contract_size: f64,
+  #[serde(alias = "deliveryDate")]
+  delivery_date: i64,
+  #[serde(alias = "contractStatus")]
+  status: String,
+  filters: Vec<HashMap<String, serde_json::Value>>,
+}
+
+fn field(filter: &HashMap<String, serde_json::Value>, key: &str) -> String {
+  match filter.get(key) {
+    Some(serde_json::Value::String(value)) => value.clone(),
+    Some(value) => value.to_string(),
+    None => "".to_string(),
+  }
+}
+
+impl SymbolsRepository {
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+  ) -> Result<Option<Symbol>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match symbols::table
+      .select(Symbol::as_select())
+      .filter(symbols::symbol.eq(symbol))
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    pair: String,
+    base_asset: String,
+    quote_asset: String,
+    margin_asset: String,
+    contract_type: String,
+    contract_size: f64,
+    delivery_date: i64,
+    filters: Filters,
+    status: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Symbol::new(
+      id,
+      symbol,
+      pair,
+      base_asset,
+      quote_asset,
+      margin_asset,
+      contract_type,
+      contract_size,
+      delivery_date,
+      filters,
+      Depth {
+        asks: vec![],
+        bids: vec![],
+        last_update_id: 0,
+      },
+      status,
+      now,
+      now,
+    );
+    match diesel::insert_into(symbols::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = symbols::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(symbols::table.find(id)).set(values).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    println!("symbols flush");
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let exchange_info: ExchangeInfo = client.get("/dapi/v1/exchangeInfo", &HashMap::new(), Security::Public, 1).await?;
+
+    let mut listings: Vec<String> = Vec::new();
+    for info in exchange_info.symbols.iter() {
+      let mut filters = Filters {
+        price: "".to_string(),
+        quote: "".to_string(),
+        notional: "".to_string(),
+        market_quote: "".to_string(),
+        orders: "".to_string(),
+        percent_price: "".to_string(),
+        others: BTreeMap::new(),
+      };
+      let mut max_orders = "".to_string();
+      let mut max_algo_orders = "".to_string();
+      for filter in info.filters.iter() {
+        match field(filter, "filterType").as_str() {
+          "PRICE_FILTER" => {
+            filters.price = format!("{},{},{}", field(filter, "minPrice"), field(filter, "maxPrice"), field(filter, "tickSize"));
+          }
+          "LOT_SIZE" => {
+            filters.quote = format!("{},{},{}", field(filter, "minQty"), field(filter, "maxQty"), field(filter, "stepSize"));
+          }
+          "MARKET_LOT_SIZE" => {
+            filters.market_quote = format!("{},{},{}", field(filter, "minQty"), field(filter, "maxQty"), field(filter, "stepSize"));
+          }
+          "MAX_NUM_ORDERS" => {
+            max_orders = field(filter, "limit");
+          }
+          "MAX_NUM_ALGO_ORDERS" => {
+            max_algo_orders = field(filter, "limit");
+          }
+          "PERCENT_PRICE" => {
+            filters.percent_price = format!("{},{},{}", field(filter, "multiplierUp"), field(filter, "multiplierDown"), field(filter, "multiplierDecimal"));
+          }
+          filter_type => {
+            let filter: BTreeMap<&String, &serde_json::Value> = filter.iter().collect();
+            filters.others.insert(filter_type.to_string(), serde_json::to_value(filter)?);
+          }
+        }
+      }
+      filters.orders = format!("{},{}", max_orders, max_algo_orders);
+      // coin margined contracts have no notional floor, one contract is the minimum
+      filters.notional = info.contract_size.to_string();
+
+      listings.push(info.symbol.clone());
+
+      let entity = match Self::get(ctx.clone(), &info.symbol).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        let id = xid::new().to_string();
+        match Self::create(
+          ctx.clone(),
+          id,
+          info.symbol.clone(),
+          info.pair.clone(),
+          info.base_asset.clone(),
+          info.quote_asset.clone(),
+          info.margin_asset.clone(),
+          info.contract_type.clone(),
+          info.contract_size,
+          info.delivery_date,
+          filters,
+          info.status.clone(),
+        ).await {
+          Ok(result) => {
+            println!("binance delivery symbol {0:} create success {result:}", info.symbol);
+          }
+          Err(err) => {
+            println!("binance delivery symbol {0:} create failed {err:?}", info.symbol)
+          }
+        }
+        continue
+      }
+
+      let entity = entity.unwrap();
+      let old_filters = serde_json::to_string(&entity.filters)?;
+      let new_filters = serde_json::to_string(&filters)?;
+      if old_filters != new_filters || entity.contract_type != info.contract_type || entity.delivery_date != info.delivery_date {
+        Self::update(ctx.clone(), entity.id.clone(), (
+          symbols::filters.eq(filters),
+          symbols::contract_type.eq(&info.contract_type),
+          symbols::delivery_date.eq(info.delivery_date),
+          symbols::updated_at.eq(Utc::now()),
+        )).await?;
+        println!("binance delivery symbol {0:} contract changed", info.symbol);
+      }
+
+      if entity.status != info.status {
+        Self::update(ctx.clone(), entity.id.clone(), (
+          symbols::status.eq(&info.status),
+          symbols::updated_at.eq(Utc::now()),
+        )).await?;
+        println!("binance delivery symbol {0:} status {1:} -> {2:}", info.symbol, entity.status, info.status);
+      }
+    }
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let entities = symbols::table
+      .select((symbols::id, symbols::symbol))
+      .filter(symbols::status.ne("DELIVERED"))
+      .load::<(String, String)>(&mut conn)?;
+    drop(conn);
+    drop(pool);
+
+    // expired quarterlies drop out of exchangeInfo once settled
+    for (id, symbol) in entities.iter() {
+      if listings.contains(symbol) {
+        continue
+      }
+      Self::update(ctx.clone(), id.clone(), (
+        symbols::status.eq("DELIVERED"),
+        symbols::updated_at.eq(Utc::now()),
+      )).await?;
+      println!("binance delivery symbol {symbol:} delivered");
+    }
+
+    Ok(())
+  }
+
+  pub async fn count(ctx: Ctx) -> Result<i64, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let count = symbols::table
+      .filter(symbols::status.eq("TRADING"))
+      .count()
+      .get_result(&mut conn)?;
+    Ok(count)
+  }
+
+  pub async fn listings(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let symbols = symbols::table
+      .select(symbols::symbol)
+      .filter(symbols::status.eq("TRADING"))
+      .order(symbols::symbol.asc())
+      .load::<String>(&mut conn)?;
+    Ok(symbols)
+  }
+
+  // trading quarterlies delivering before the given timestamp
+  pub async fn expiring(ctx: Ctx, timestamp: i64) -> Result<Vec<(String, String, i64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let symbols = symbols::table
+      .select((symbols::symbol, symbols::pair, symbols::delivery_date))
+      .filter(symbols::status.eq("TRADING"))
+      .filter(symbols::contract_type.eq_any(vec!["CURRENT_QUARTER", "NEXT_QUARTER"]))
+      .filter(symbols::delivery_date.le(timestamp))
+      .order(symbols::delivery_date.asc())
+      .load::<(String, String, i64)>(&mut conn)?;
+    Ok(symbols)
+  }
+
+  // the next trading quarterly of the same pair, where an expiring position rolls to
+  pub async fn rollover<T>(ctx: Ctx, symbol: T) -> Result<Option<String>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let entity = match Self::get(ctx.clone(), symbol).await? {
+      Some(entity) => entity,
+      None => return Err(Box::from(format!("symbol {symbol:} not exists"))),
+    };
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    match symbols::table
+      .select(symbols::symbol)
+      .filter(symbols::pair.eq(&entity.pair))
+      .filter(symbols::status.eq("TRADING"))
+      .filter(symbols::contract_type.eq_any(vec!["CURRENT_QUARTER", "NEXT_QUARTER"]))
+      .filter(symbols::delivery_date.gt(entity.delivery_date))
+      .order(symbols::delivery_date.asc())
+      .first::<String>(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn pairs<T>(ctx: Ctx, symbol: T) -> Result<(String, String), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match symbols::table
+      .select((symbols::base_asset, symbols::quote_asset))
+      .filter(symbols::symbol.eq(symbol))
+      .first::<(String, String)>(&mut conn) {
+      Ok(result) => Ok(result),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  // the trading perpetual margined in the coin, its price values balances of that coin
+  pub async fn perpetual<T>(ctx: Ctx, margin_asset: T) -> Result<String, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let margin_asset = margin_asset.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match symbols::table
+      .select(symbols::symbol)
+      .filter(symbols::margin_asset.eq(margin_asset))
+      .filter(symbols::contract_type.eq("PERPETUAL"))
+      .filter(symbols::status.eq("TRADING"))
+      .order(symbols::symbol.asc())
+      .first::<String>(&mut conn) {
+      Ok(result) => Ok(result),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  pub async fn contract<T>(ctx: Ctx, symbol: T) -> Result<(f64, String), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match symbols::table
+      .select((symbols::contract_size, symbols::margin_asset))
+      .filter(symbols::symbol.eq(symbol))
+      .first::<(f64, String)>(&mut conn) {
+      Ok(result) => Ok(result),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  // quantities are counted in contracts, step size is a contract count
+  pub async fn filters<T>(ctx: Ctx, symbol: T) -> Result<(f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let (tick_size, step_size, contract_size): (f64, f64, f64);
+    match symbols::table
+      .select((symbols::filters, symbols::contract_size))
+      .filter(symbols::symbol.eq(symbol))
+      .first::<(Filters, f64)>(&mut conn) {
+      Ok((filters, size)) => {
+        let values: Vec<&str> = filters.price.split(",").collect();
+        tick_size = values[2].parse::<f64>().unwrap();
+        let values: Vec<&str> = filters.quote.split(",").collect();
+        step_size = values[2].parse::<f64>().unwrap();
+        contract_size = size;
+      }
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok((tick_size, step_size, contract_size))
+  }
+
+  // a usd notional as a whole number of contracts, rounded down
+  pub fn contracts(notional: f64, contract_size: f64, step_size: f64) -> f64 {
+    if contract_size <= 0.0 || step_size <= 0.0 {
+      return 0.0
+    }
+    ((notional / contract_size) / step_size).floor() * step_size
+  }
+}
\ No newline at end of file
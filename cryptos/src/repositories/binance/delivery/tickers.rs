@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use serde::{Deserialize, Deserializer};
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::config::binance::delivery::config as Config;
+
+#[derive(Default)]
+pub struct TickersRepository {}
+
+#[derive(Deserialize)]
+struct TickerInfo {
+  symbol: String,
+  #[serde(alias = "lastPrice", deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "openPrice", deserialize_with = "to_f64")]
+  open: f64,
+  #[serde(alias = "highPrice", deserialize_with = "to_f64")]
+  high: f64,
+  #[serde(alias = "lowPrice", deserialize_with = "to_f64")]
+  low: f64,
+  #[serde(deserialize_with = "to_f64")]
+  volume: f64,
+  #[serde(alias = "baseVolume", deserialize_with = "to_f64")]
+  quota: f64,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+impl TickersRepository {
+  pub async fn gets<T>(
+    ctx: Ctx,
+    symbols: Vec<T>,
+    fields: Vec<T>,
+  ) -> Vec<String>
+  where
+    T: AsRef<str>
+  {
+    let symbols = symbols.iter().map(|s| s.as_ref()).collect::<Vec<&str>>();
+    let fields = fields.iter().map(|s| s.as_ref()).collect::<Vec<&str>>();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let mut pipe = redis::pipe();
+    for symbol in symbols.iter() {
+      pipe.cmd("HMGET").arg(format!("{}:{}", Config::REDIS_KEY_TICKERS, symbol)).arg(&fields);
+    }
+    let values: Vec<Vec<Option<String>>> = match pipe.query_async(&mut rdb).await {
+      Ok(values) => values,
+      Err(_) => return vec![],
+    };
+    values.into_iter().map(|items| {
+      items.into_iter().flatten().collect::<Vec<_>>().join(",")
+    }).collect()
+  }
+
+  pub async fn price<T>(
+    ctx: Ctx,
+    symbol: T,
+  ) -> Result<f64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let timestamp = Utc::now().timestamp_millis();
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_TICKERS, symbol);
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let fields = vec!["price", "timestamp"];
+
+    let (price, lasttime): (f64, i64) = match redis::cmd("HMGET")
+      .arg(&redis_key)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((Some(price), Some(lasttime))) => (price, lasttime),
+      Ok(_) => return Err(Box::from(format!("ticker of {symbol:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+
+    // tickers are polled every minute, there is no realtime stream behind them
+    if timestamp-lasttime > 120000 {
+      return Err(Box::from(format!("ticker of {symbol:} has been expired")))
+    }
+
+    Ok(price)
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<usize, Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "delivery");
+    let tickers: Vec<TickerInfo> = client.get("/dapi/v1/ticker/24hr", &HashMap::new(), Security::Public, 40).await?;
+
+    let timestamp = Utc::now().timestamp_millis();
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let mut pipe = redis::pipe();
+    for ticker in tickers.iter() {
+      pipe.hset_multiple(
+        format!("{}:{}", Config::REDIS_KEY_TICKERS, ticker.symbol),
+        &[
+          ("price", ticker.price.to_string()),
+          ("open", ticker.open.to_string()),
+          ("high", ticker.high.to_string()),
+          ("low", ticker.low.to_string()),
+          ("volume", ticker.volume.to_string()),
+          ("quota", ticker.quota.to_string()),
+          ("timestamp", timestamp.to_string()),
+        ],
+      ).ignore();
+    }
+    () = pipe.query_async(&mut rdb).await?;
+
+    Ok(tickers.len())
+  }
+}
\ No newline at end of file
@@ -1,6 +1,8 @@
 pub mod account;
 pub mod analysis;
 pub mod symbols;
+pub mod depth;
+pub mod exchange;
 pub mod klines;
 pub mod tickers;
 pub mod indicators;
@@ -10,4 +12,5 @@ pub mod orders;
 pub mod positions;
 pub mod gambling;
 pub mod scalping;
+pub mod triggers;
 pub mod tradings;
\ No newline at end of file
@@ -1,17 +1,12 @@
-use std::time::Duration;
 use std::collections::HashMap;
 
-use url::Url;
-use sha2::Sha256;
-use hmac::{Hmac, Mac};
-use reqwest::header;
-
 use chrono::prelude::Utc;
 use diesel::prelude::*;
 use redis::AsyncCommands;
 use serde::{Deserialize, Deserializer};
 
 use crate::common::*;
+use crate::repositories::binance::client::*;
 use crate::repositories::binance::futures::positions::*;
 use crate::schema::binance::futures::positions::*;
 use crate::config::binance::futures::config as Config;
@@ -41,6 +36,12 @@ struct Balance {
   maint_margin: f64,
 }
 
+#[derive(Deserialize)]
+struct ListenKeyInfo {
+  #[serde(alias = "listenKey")]
+  listen_key: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct PositionInfo {
   symbol: String,
@@ -114,49 +115,31 @@ impl AccountRepository {
     }
   }
 
-  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    let timestamp = Utc::now().timestamp_millis().to_string();
-
-    let mut params = HashMap::<&str, &str>::new();
-    params.insert("timeInForce", "GTC");
-    params.insert("recvWindow", "60000");
-    params.insert("timestamp", &timestamp);
-
-    let mut url = Url::parse_with_params(format!("{}/fapi/v2/account", Env::var("BINANCE_FUTURES_API_ENDPOINT")).as_str(), &params)?;
-    let query: &str = match url.query() {
-      Some(query) => query,
-      None => "",
-    };
-
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(Env::var("BINANCE_FUTURES_ACCOUNT_API_SECRET").as_bytes()).unwrap();
-    mac.update(query.as_bytes());
-    let signature = hex::encode(&mac.finalize().into_bytes().to_vec());
-
-    url.query_pairs_mut().append_pair("signature", signature.as_str());
+  pub async fn listen_key(ctx: Ctx) -> Result<String, Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let info: ListenKeyInfo = client.post("/fapi/v1/listenKey", &HashMap::new(), Security::Stream, 1).await?;
 
-    let mut headers = header::HeaderMap::new();
-    headers.insert("X-MBX-APIKEY", Env::var("BINANCE_FUTURES_ACCOUNT_API_KEY").parse().unwrap());
+    Ok(info.listen_key)
+  }
 
-    let client = reqwest::Client::new();
-    let response = client.get(url)
-      .headers(headers)
-      .timeout(Duration::from_secs(30))
-      .send()
-      .await?;
+  pub async fn keepalive<T>(ctx: Ctx, listen_key: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let listen_key = listen_key.as_ref();
 
-    let status_code = response.status();
+    let mut params = HashMap::<&str, &str>::new();
+    params.insert("listenKey", listen_key);
 
-    if status_code.is_client_error() {
-      println!("response {}", response.text().await.unwrap());
-      return Err(Box::from(format!("bad request: {}", status_code)))
-    }
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let _: serde_json::Value = client.put("/fapi/v1/listenKey", &params, Security::Stream, 1).await?;
 
-    if !status_code.is_success() {
-      return Err(Box::from(format!("request error: {}", status_code)))
-    }
+    Ok(())
+  }
 
-    let account_info = response.json::<AccountInfo>().await.unwrap();
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let account_info: AccountInfo = client.get("/fapi/v2/account", &HashMap::new(), Security::Account, 5).await?;
 
     let mut rdb = ctx.rdb.lock().await.clone();
 
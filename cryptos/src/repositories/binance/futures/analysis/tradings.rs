@@ -1 +1,3 @@
-pub mod scalping;
\ No newline at end of file
+pub mod launchpad;
+pub mod scalping;
+pub mod triggers;
\ No newline at end of file
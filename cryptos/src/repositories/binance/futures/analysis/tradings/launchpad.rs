@@ -0,0 +1,119 @@
+use chrono::{prelude::Utc, NaiveDate, TimeZone};
+use diesel::prelude::*;
+
+use crate::common::*;
+use crate::models::binance::futures::analysis::tradings::launchpad::*;
+use crate::schema::binance::futures::analysis::tradings::launchpad::*;
+use crate::schema::binance::futures::tradings::launchpad::dsl as tradings;
+
+#[derive(Default)]
+pub struct LaunchpadRepository {}
+
+impl LaunchpadRepository {
+  pub async fn count(ctx: Ctx) -> Result<i64, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let result = launchpad::table
+      .count()
+      .get_result(&mut conn)?;
+    Ok(result)
+  }
+
+  pub async fn listings(ctx: Ctx, current: i64, page_size: i64) -> Result<Vec<(String, NaiveDate, i32, i32, f64, f64, f64, f64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let result = launchpad::table
+      .select((
+        launchpad::id,
+        launchpad::day,
+        launchpad::buys_count,
+        launchpad::sells_count,
+        launchpad::buys_amount,
+        launchpad::sells_amount,
+        launchpad::profit,
+        launchpad::additive_profit,
+      ))
+      .order(launchpad::day.desc())
+      .offset((current - 1) * page_size)
+      .limit(page_size)
+      .load::<(String, NaiveDate, i32, i32, f64, f64, f64, f64)>(&mut conn)?;
+    Ok(result)
+  }
+
+  // tradings closed during the day are summed, the additive profit carries the previous days
+  pub async fn flush(ctx: Ctx, day: NaiveDate) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap());
+    let end_time = start_time + chrono::Duration::days(1);
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let items = tradings::launchpad
+      .select((tradings::buy_price, tradings::sell_price, tradings::buy_quantity, tradings::sell_quantity))
+      .filter(tradings::status.eq(3))
+      .filter(tradings::updated_at.ge(start_time))
+      .filter(tradings::updated_at.lt(end_time))
+      .load::<(f64, f64, f64, f64)>(&mut conn)?;
+
+    let buys_count = items.len() as i32;
+    let sells_count = items.len() as i32;
+    let buys_amount = items.iter().map(|item| item.0 * item.2).sum::<f64>();
+    let sells_amount = items.iter().map(|item| item.1 * item.3).sum::<f64>();
+    let profit = sells_amount - buys_amount;
+
+    let last_profit = match launchpad::table
+      .select(launchpad::additive_profit)
+      .filter(launchpad::day.lt(day))
+      .order(launchpad::day.desc())
+      .first::<f64>(&mut conn) {
+      Ok(result) => result,
+      Err(diesel::result::Error::NotFound) => 0.0,
+      Err(err) => return Err(err.into()),
+    };
+
+    let now = Utc::now();
+    let id = match launchpad::table
+      .select(launchpad::id)
+      .filter(launchpad::day.eq(day))
+      .first::<String>(&mut conn) {
+      Ok(result) => Some(result),
+      Err(diesel::result::Error::NotFound) => None,
+      Err(err) => return Err(err.into()),
+    };
+    match id {
+      Some(id) => {
+        diesel::update(launchpad::table.find(id))
+          .set((
+            launchpad::buys_count.eq(buys_count),
+            launchpad::sells_count.eq(sells_count),
+            launchpad::buys_amount.eq(buys_amount),
+            launchpad::sells_amount.eq(sells_amount),
+            launchpad::profit.eq(profit),
+            launchpad::additive_profit.eq(last_profit + profit),
+            launchpad::updated_at.eq(now),
+          ))
+          .execute(&mut conn)?;
+      }
+      None => {
+        let entity = Launchpad::new(
+          xid::new().to_string(),
+          day,
+          buys_count,
+          sells_count,
+          buys_amount,
+          sells_amount,
+          profit,
+          last_profit + profit,
+          now,
+          now,
+        );
+        diesel::insert_into(launchpad::table)
+          .values(&entity)
+          .execute(&mut conn)?;
+      }
+    }
+    println!("binance futures analysis launchpad {day:} profit {profit:}");
+
+    Ok(())
+  }
+}
\ No newline at end of file
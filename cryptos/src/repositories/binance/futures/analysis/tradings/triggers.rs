@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use chrono::{prelude::Utc, NaiveDate, TimeZone};
+use diesel::prelude::*;
+
+use crate::common::*;
+use crate::models::binance::futures::analysis::tradings::trigger::*;
+use crate::schema::binance::futures::analysis::tradings::triggers::*;
+use crate::schema::binance::futures::tradings::triggers::dsl as tradings;
+
+#[derive(Default)]
+pub struct TriggersRepository {}
+
+impl TriggersRepository {
+  pub async fn count(ctx: Ctx, conditions: &mut HashMap<&str, MixValue>) -> Result<i64, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let mut query = triggers::table.into_boxed();
+    if let Some(MixValue::Int(side)) = conditions.get("side") {
+      query = query.filter(triggers::side.eq(side));
+    }
+    let count = query
+      .count()
+      .get_result(&mut conn)?;
+    Ok(count)
+  }
+
+  pub async fn listings(ctx: Ctx, conditions: &mut HashMap<&str, MixValue>, current: i64, page_size: i64) -> Result<Vec<(String, i32, NaiveDate, i32, i32, f64, f64, f64, f64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let mut query = triggers::table.into_boxed();
+    if let Some(MixValue::Int(side)) = conditions.get("side") {
+      query = query.filter(triggers::side.eq(side));
+    }
+    let analysis = query
+      .select((
+        triggers::id,
+        triggers::side,
+        triggers::day,
+        triggers::buys_count,
+        triggers::sells_count,
+        triggers::buys_amount,
+        triggers::sells_amount,
+        triggers::profit,
+        triggers::additive_profit,
+      ))
+      .order(triggers::day.desc())
+      .offset((current - 1) * page_size)
+      .limit(page_size)
+      .load::<(String, i32, NaiveDate, i32, i32, f64, f64, f64, f64)>(&mut conn)?;
+    Ok(analysis)
+  }
+
+  // tradings closed during the day are summed per side, the additive profit carries the previous days
+  pub async fn flush(ctx: Ctx, day: NaiveDate) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap());
+    let end_time = start_time + chrono::Duration::days(1);
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    for side in [1, 2] {
+      let items = tradings::triggers
+        .select((tradings::buy_price, tradings::sell_price, tradings::buy_quantity, tradings::sell_quantity))
+        .filter(tradings::side.eq(side))
+        .filter(tradings::status.eq(3))
+        .filter(tradings::updated_at.ge(start_time))
+        .filter(tradings::updated_at.lt(end_time))
+        .load::<(f64, f64, f64, f64)>(&mut conn)?;
+
+      let buys_count = items.len() as i32;
+      let sells_count = items.len() as i32;
+      let buys_amount = items.iter().map(|item| item.0 * item.2).sum::<f64>();
+      let sells_amount = items.iter().map(|item| item.1 * item.3).sum::<f64>();
+      let profit = sells_amount - buys_amount;
+
+      let last_profit = match triggers::table
+        .select(triggers::additive_profit)
+        .filter(triggers::side.eq(side))
+        .filter(triggers::day.lt(day))
+        .order(triggers::day.desc())
+        .first::<f64>(&mut conn) {
+        Ok(result) => result,
+        Err(diesel::result::Error::NotFound) => 0.0,
+        Err(err) => return Err(err.into()),
+      };
+
+      let now = Utc::now();
+      let id = match triggers::table
+        .select(triggers::id)
+        .filter(triggers::side.eq(side))
+        .filter(triggers::day.eq(day))
+        .first::<String>(&mut conn) {
+        Ok(result) => Some(result),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(err) => return Err(err.into()),
+      };
+      match id {
+        Some(id) => {
+          diesel::update(triggers::table.find(id))
+            .set((
+              triggers::buys_count.eq(buys_count),
+              triggers::sells_count.eq(sells_count),
+              triggers::buys_amount.eq(buys_amount),
+              triggers::sells_amount.eq(sells_amount),
+              triggers::profit.eq(profit),
+              triggers::additive_profit.eq(last_profit + profit),
+              triggers::updated_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+        }
+        None => {
+          let entity = Trigger::new(
+            xid::new().to_string(),
+            side,
+            day,
+            buys_count,
+            sells_count,
+            buys_amount,
+            sells_amount,
+            profit,
+            last_profit + profit,
+            now,
+            now,
+          );
+          diesel::insert_into(triggers::table)
+            .values(&entity)
+            .execute(&mut conn)?;
+        }
+      }
+      println!("binance futures analysis triggers {day:} side {side:} profit {profit:}");
+    }
+
+    Ok(())
+  }
+}
\ No newline at end of file
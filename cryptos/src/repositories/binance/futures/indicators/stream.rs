@@ -0,0 +1,233 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::collections::HashMap;
+
+use tokio::sync::Mutex;
+use chrono::{prelude::Utc, DateTime, Local};
+use diesel::prelude::*;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::repositories::indicators::*;
+use crate::repositories::binance::futures::indicators::*;
+use crate::repositories::binance::futures::indicators::history::HistoryRepository;
+use crate::schema::binance::futures::klines::*;
+
+static ENGINES: OnceLock<Mutex<HashMap<String, IndicatorsEngine>>> = OnceLock::new();
+
+#[derive(Default)]
+pub struct StreamRepository {}
+
+impl StreamRepository {
+  fn candles(conn: &mut PgConnection, symbol: &str, interval: &str, limit: i64) -> Result<Vec<Candle>, Box<dyn std::error::Error>> {
+    let items = klines::table
+      .select((klines::open, klines::close, klines::high, klines::low, klines::volume, klines::timestamp))
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq(interval))
+      .order(klines::timestamp.desc())
+      .limit(limit)
+      .load::<(f64, f64, f64, f64, f64, i64)>(conn)?;
+    Ok(items.iter().map(|&(open, close, high, low, volume, timestamp)| {
+      Candle::new(timestamp, open, high, low, close, volume)
+    }).collect())
+  }
+
+  async fn warmup(ctx: Ctx, symbol: &str, interval: &str, params: EngineParams, live: &Candle) -> Result<IndicatorsEngine, Box<dyn std::error::Error>> {
+    let timeframe = Interval::parse(interval)?;
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let mut items = Self::candles(&mut conn, symbol, interval, params.warmup_limit() as i64)?;
+    if items.len() < params.warmup_limit() {
+      return Err(Box::from(format!("[{symbol:}] {interval:} klines not enough")))
+    }
+    if items[0].timestamp != live.timestamp {
+      return Err(Box::from(format!("[{symbol:}] {interval:} klines changed during warm up")))
+    }
+    for i in 1..items.len() {
+      if items[i-1].timestamp != timeframe.next(items[i].timestamp) {
+        return Err(Box::from(format!("[{symbol:}] {interval:} klines lost")))
+      }
+    }
+    items.reverse();
+
+    let engine = IndicatorsEngine::warmup(params, &items[..items.len()-1]);
+    println!("binance futures indicators stream {symbol:} {interval:} warm up {}", items.len());
+
+    Ok(engine)
+  }
+
+  pub async fn flush<T>(ctx: Ctx, symbol: T, interval: T, params: EngineParams) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let timeframe = Interval::parse(interval)?;
+    let current_timestamp = timeframe.timestamp();
+
+    let items = {
+      let pool = ctx.pool.read().await;
+      let mut conn = pool.get().unwrap();
+      Self::candles(&mut conn, symbol, interval, 2)?
+    };
+    if items.len() < 2 {
+      return Err(Box::from(format!("[{symbol:}] {interval:} klines not enough")))
+    }
+    let (live, last) = (items[0], items[1]);
+    if live.timestamp < current_timestamp - 60000 {
+      return Err(Box::from(format!("[{symbol:}] waiting for {interval:} klines flush")))
+    }
+    if last.timestamp != timeframe.prev(live.timestamp) {
+      return Err(Box::from(format!("[{symbol:}] {interval:} klines lost")))
+    }
+
+    let dt = DateTime::from_timestamp_millis(live.timestamp).unwrap();
+    if dt.format("%m%d").to_string() != Utc::now().format("%m%d").to_string() {
+      return Err(Box::from(format!("[{symbol:}] {interval:} timestamp is not today")))
+    }
+    let day = Local::now().format("%m%d").to_string();
+
+    let (tick_size, _) = IndicatorsRepository::filters(ctx.clone(), symbol).await?;
+    let tick_size = Decimal::from_f64(tick_size).unwrap();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let last_cloud: Option<String> = rdb.hget(&redis_key, "ichimoku_cloud").await?;
+
+    // the engine is taken out of the map while in use so other symbols are not blocked
+    let engines = ENGINES.get_or_init(|| Mutex::new(HashMap::new()));
+    let engine_key = format!("{}:{}", interval, symbol);
+    let engine = engines.lock().await.remove(&engine_key);
+    let engine = match engine {
+      Some(engine) if engine.params != params => None,
+      Some(engine) if engine.last() == Some(&last) => Some(engine),
+      Some(mut engine) if engine.timestamp() == timeframe.prev(last.timestamp) => {
+        engine.commit(&last);
+        Some(engine)
+      }
+      _ => None,
+    };
+    let engine = match engine {
+      Some(engine) => engine,
+      None => Self::warmup(ctx.clone(), symbol, interval, params, &live).await?,
+    };
+
+    let mut values: Vec<(&str, String)> = Vec::new();
+
+    if let Some(result) = engine.atr(&live) {
+      values.push(("atr", result.to_string()));
+    }
+
+    if let Some((last_value, value)) = engine.zlema(&live) {
+      values.push(("zlema", format!("{},{},{},{}", last_value, value, live.close, current_timestamp)));
+    }
+
+    if let Some((last_value, value)) = engine.ha_zlema(&live) {
+      values.push(("ha_zlema", format!("{},{},{},{}", last_value, value, live.average_price(), current_timestamp)));
+    }
+
+    if let Some((slowk, slowd)) = engine.kdj(&live) {
+      let slowk = Decimal::from_f64(slowk).unwrap();
+      let slowd = Decimal::from_f64(slowd).unwrap();
+      let slowj = slowk * dec!(3) - slowd * dec!(2);
+      values.push(("kdj", format!("{},{},{},{},{}", slowk, slowd, slowj, live.typical_price(), current_timestamp)));
+    }
+
+    match engine.bbands(&live) {
+      Ok(Some([b1, b2, b3, w1, w2, w3])) => {
+        values.push(("bbands", format!("{},{},{},{},{},{},{},{}", b1, b2, b3, w1, w2, w3, live.typical_price(), current_timestamp)));
+      }
+      Ok(None) => (),
+      Err(err) => println!("[{symbol:}] {interval:} bbands {err:}"),
+    }
+
+    if let Some(lines) = engine.ichimoku_cloud(&live) {
+      let last_lines = last_cloud.as_ref().map(|value| {
+        let data: Vec<&str> = value.split(",").collect();
+        (data[1].parse::<f64>().unwrap(), data[2].parse::<f64>().unwrap())
+      });
+      let signal = signals::ichimoku_cloud(&lines, last_lines);
+      let first_avg_price = Decimal::from_f64(live.typical_price()).unwrap();
+      values.push(("ichimoku_cloud", format!(
+        "{},{},{},{},{},{},{},{}",
+        signal,
+        (lines.conversion_line / tick_size).floor() * tick_size,
+        (lines.base_line / tick_size).floor() * tick_size,
+        (lines.senkou_span_a / tick_size).floor() * tick_size,
+        (lines.senkou_span_b / tick_size).ceil() * tick_size,
+        (lines.chikou_span / tick_size).floor() * tick_size,
+        (first_avg_price / tick_size).floor() * tick_size,
+        live.timestamp,
+      )));
+    }
+
+    match engine.volume_profile(&live) {
+      Ok(Some((poc, vah, val, poc_ratio))) => {
+        values.push(("vah", ((vah / tick_size).ceil() * tick_size).to_string()));
+        values.push(("val", ((val / tick_size).floor() * tick_size).to_string()));
+        values.push(("poc", ((poc / tick_size).floor() * tick_size).to_string()));
+        values.push(("poc_ratio", poc_ratio.to_string()));
+      }
+      Ok(None) => (),
+      Err(err) => println!("[{symbol:}] {interval:} volume profile {err:}"),
+    }
+
+    match engine.andean_oscillator(&live) {
+      Ok(Some((bull, bear, signal))) => {
+        values.push(("ao_bull", bull.to_string()));
+        values.push(("ao_bear", bear.to_string()));
+        values.push(("ao_signal", signal.to_string()));
+      }
+      Ok(None) => (),
+      Err(err) => println!("[{symbol:}] {interval:} andean oscillator {err:}"),
+    }
+
+    if let Some((last_value, value)) = engine.rsi(&live) {
+      values.push(("rsi", value.to_string()));
+      values.push(("rsi_state", format!("{},{},{},{}", last_value, value, live.close, current_timestamp)));
+    }
+
+    if let Some([value, signal, hist, last_hist]) = engine.macd(&live) {
+      values.push(("macd", value.to_string()));
+      values.push(("macd_signal", signal.to_string()));
+      values.push(("macd_hist", hist.to_string()));
+      values.push(("macd_state", format!("{},{},{},{}", last_hist, hist, live.close, current_timestamp)));
+    }
+
+    if let Some([value, plus_di, minus_di]) = engine.adx(&live) {
+      values.push(("adx", value.to_string()));
+      values.push(("plus_di", plus_di.to_string()));
+      values.push(("minus_di", minus_di.to_string()));
+      values.push(("adx_state", format!("{},{},{},{},{}", value, plus_di, minus_di, live.close, current_timestamp)));
+    }
+
+    if let Some((line, trend, last_trend)) = engine.supertrend(&live) {
+      values.push(("supertrend", line.to_string()));
+      values.push(("supertrend_trend", trend.to_string()));
+      values.push(("supertrend_state", format!("{},{},{},{}", last_trend, trend, live.close, current_timestamp)));
+    }
+
+    engines.lock().await.insert(engine_key, engine);
+
+    if values.is_empty() {
+      return Ok(())
+    }
+
+    let ttl = Duration::from_secs(30+86400);
+    let is_exists: bool = rdb.exists(&redis_key).await.unwrap();
+    () = rdb.hset_multiple(&redis_key, values.as_slice()).await?;
+    if !is_exists {
+      () = rdb.expire(&redis_key, ttl.as_secs().try_into().unwrap()).await?;
+    }
+    HistoryRepository::stage(ctx.clone(), symbol, interval, live.timestamp, values.as_slice()).await?;
+    println!("binance futures indicators stream {symbol:} {interval:} {} fields", values.len());
+
+    Ok(())
+  }
+}
\ No newline at end of file
@@ -1,19 +1,18 @@
-use url::Url;
 use std::collections::HashMap;
-use std::ops::Sub;
-use std::time::Duration;
 
 use diesel::prelude::*;
 use diesel::query_builder::QueryFragment;
-use rust_decimal::prelude::*;
-use rust_decimal_macros::dec;
-use chrono::{prelude::Utc, Timelike};
+use diesel::upsert::excluded;
+use chrono::{prelude::Utc, DateTime};
 
 use crate::common::*;
+use crate::repositories::binance::client::*;
 use crate::models::binance::futures::kline::*;
 use crate::schema::binance::futures::klines::*;
 use crate::queue::nats::jobs::binance::futures::klines::*;
 
+pub mod audits;
+
 #[derive(Default)]
 pub struct KlinesRepository {}
 
@@ -215,6 +214,128 @@ impl KlinesRepository
     }
   }
 
+  pub async fn upsert(
+    ctx: Ctx,
+    items: Vec<Kline>,
+  ) -> Result<usize, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let mut effective_rows = 0;
+    for chunk in items.chunks(1000) {
+      effective_rows += diesel::insert_into(klines::table)
+        .values(chunk)
+        .on_conflict((klines::symbol, klines::interval, klines::timestamp))
+        .do_update()
+        .set((
+          klines::open.eq(excluded(klines::open)),
+          klines::close.eq(excluded(klines::close)),
+          klines::high.eq(excluded(klines::high)),
+          klines::low.eq(excluded(klines::low)),
+          klines::volume.eq(excluded(klines::volume)),
+          klines::quota.eq(excluded(klines::quota)),
+          klines::updated_at.eq(excluded(klines::updated_at)),
+        ))
+        .execute(&mut conn)?;
+    }
+
+    Ok(effective_rows)
+  }
+
+  pub async fn gaps<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    start: i64,
+    end: i64,
+  ) -> Result<Vec<(i64, i64)>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let timelines = klines::table
+      .select(klines::timestamp)
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq(interval))
+      .filter(klines::timestamp.ge(start))
+      .filter(klines::timestamp.le(end))
+      .order(klines::timestamp.asc())
+      .load::<i64>(&mut conn)?;
+
+    let mut gaps = Vec::new();
+    let mut expected = timeframe.align(start);
+    for timestamp in timelines.iter() {
+      if *timestamp > expected {
+        gaps.push((expected, timeframe.prev(*timestamp)));
+      }
+      expected = timeframe.next(*timestamp);
+    }
+    if expected <= end {
+      gaps.push((expected, timeframe.align(end)));
+    }
+
+    Ok(gaps)
+  }
+
+  pub fn retention<T>(key: T) -> HashMap<Interval, i64>
+  where
+    T: AsRef<str>
+  {
+    let mut value = Env::var(key);
+    if value.is_empty() {
+      value = "1m:30,15m:365".to_string();
+    }
+    let mut retention = HashMap::new();
+    for item in value.split(",") {
+      let (interval, days) = match item.trim().split_once(":") {
+        Some(result) => result,
+        None => continue,
+      };
+      let interval = match Interval::parse(interval) {
+        Ok(interval) => interval,
+        Err(_) => continue,
+      };
+      match days.parse::<i64>() {
+        Ok(days) if days > 0 => {
+          retention.insert(interval, days * 86400000);
+        }
+        _ => (),
+      }
+    }
+    retention
+  }
+
+  pub async fn clean<T>(
+    ctx: Ctx,
+    interval: T,
+    timestamp: i64,
+    limit: i64,
+  ) -> Result<usize, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let ids = klines::table
+      .select(klines::id)
+      .filter(klines::interval.eq(interval))
+      .filter(klines::timestamp.lt(timestamp))
+      .limit(limit);
+    match diesel::delete(klines::table.filter(klines::id.eq_any(ids))).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows),
+      Err(err) => Err(err.into()),
+    }
+  }
+
   pub async fn flush<T>(
     ctx: Ctx,
     symbol: T,
@@ -228,6 +349,12 @@ impl KlinesRepository
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
     let endtime_val = endtime.to_string();
+    let weight = match limit {
+      0..=99 => 1,
+      100..=499 => 2,
+      500..=1000 => 5,
+      _ => 10,
+    };
     let limit = limit.to_string();
 
     let mut params = HashMap::<&str, &str>::new();
@@ -238,73 +365,27 @@ impl KlinesRepository
     }
     params.insert("limit", &limit);
 
-    let url = Url::parse_with_params(format!("{}/fapi/v1/klines", Env::var("BINANCE_FUTURES_API_ENDPOINT")).as_str(), &params)?;
-
-    let client = reqwest::Client::new();
-    let response = client.get(url)
-      .timeout(Duration::from_secs(5))
-      .send()
-      .await?;
-
-    let status_code = response.status();
-
-    if status_code.is_client_error() {
-      println!("response {}", response.text().await.unwrap());
-      return Err(Box::from(format!("bad request: {}", status_code)))
-    }
-
-    if !status_code.is_success() {
-      return Err(Box::from(format!("request error: {}", status_code)))
-    }
-
-    let klines = response.json::<Vec<(i64, String, String, String, String, String, i64, String, i64, String, String, String)>>().await.unwrap();
-    for (timestamp, open, high, low, close, volume, _, quota, ..) in klines.iter() {
-      let open = open.parse::<f64>().unwrap();
-      let close = close.parse::<f64>().unwrap();
-      let high = high.parse::<f64>().unwrap();
-      let low = low.parse::<f64>().unwrap();
-      let volume = volume.parse::<f64>().unwrap();
-      let quota = quota.parse::<f64>().unwrap();
-      let timestamp = *timestamp;
-
-      let kline: Option<Kline> = match Self::get(ctx.clone(), symbol, interval, timestamp).await {
-        Ok(Some(result)) => Some(result),
-        Ok(None) => None,
-        Err(err) => {
-          println!("error {:?}", err);
-          continue
-        },
-      };
-
-      if kline.is_none() {
-        let id = xid::new().to_string();
-        Self::create(
-          ctx.clone(),
-          id,
-          symbol.to_string(),
-          interval.to_string(),
-          open,
-          close,
-          high,
-          low,
-          volume,
-          quota,
-          timestamp,
-        ).await?;
-      } else {
-        Self::update(
-          ctx.clone(),
-          kline.unwrap().id,
-          (
-            klines::open.eq(open),
-            klines::close.eq(close),
-            klines::high.eq(high),
-            klines::low.eq(low),
-            klines::volume.eq(volume),
-            klines::quota.eq(quota),
-          ),
-        ).await?;
-      }
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let klines: Vec<(i64, String, String, String, String, String, i64, String, i64, String, String, String)> = client.get("/fapi/v1/klines", &params, Security::Public, weight).await?;
+    let now = Utc::now();
+    let items = klines.iter().map(|(timestamp, open, high, low, close, volume, _, quota, ..)| {
+      Kline::new(
+        xid::new().to_string(),
+        symbol.to_string(),
+        interval.to_string(),
+        open.parse::<f64>().unwrap(),
+        close.parse::<f64>().unwrap(),
+        high.parse::<f64>().unwrap(),
+        low.parse::<f64>().unwrap(),
+        volume.parse::<f64>().unwrap(),
+        quota.parse::<f64>().unwrap(),
+        *timestamp,
+        now,
+        now,
+      )
+    }).collect::<Vec<_>>();
+    if !items.is_empty() {
+      Self::upsert(ctx.clone(), items).await?;
     }
 
     Ok(())
@@ -322,8 +403,9 @@ impl KlinesRepository
     let symbol = symbol.as_ref();
     let interval = interval.as_ref();
 
-    let timestamp = Self::timestamp(interval);
-    let timestep = Self::timestep(interval);
+    let timeframe = Interval::parse(interval)?;
+    let timestamp = timeframe.timestamp();
+    let timestep = timeframe.timestep();
 
     let values = Self::timelines(ctx.clone(), symbol, interval, timestamp - offset * timestep).await?;
 
@@ -383,29 +465,115 @@ impl KlinesRepository
     let fields = ["open", "close", "high", "low", "volume", "quota"].to_vec();
     let interval = interval.as_ref();
     let values = Self::gets(ctx.clone(), symbols.clone(), fields, interval, timestamp).await;
+
+    let now = Utc::now();
+    let mut items = Vec::new();
     for (i, data) in values.iter().enumerate() {
-      if data.len() == 0 {
+      if data.len() < 6 {
         continue
       }
 
-      let symbol = symbols.clone()[i];
-      let open = data[0].parse::<f64>().unwrap();
-      let close = data[1].parse::<f64>().unwrap();
-      let high = data[2].parse::<f64>().unwrap();
-      let low = data[3].parse::<f64>().unwrap();
-      let volume = data[4].parse::<f64>().unwrap();
-      let quota = data[4].parse::<f64>().unwrap();
-
-      let kline: Option<Kline> = match Self::get(ctx.clone(), symbol, interval, timestamp).await {
-        Ok(Some(result)) => Some(result),
-        Ok(None) => None,
-        Err(err) => return Err(err.into()),
-      };
+      items.push(Kline::new(
+        xid::new().to_string(),
+        symbols[i].to_string(),
+        interval.to_string(),
+        data[0].parse::<f64>().unwrap(),
+        data[1].parse::<f64>().unwrap(),
+        data[2].parse::<f64>().unwrap(),
+        data[3].parse::<f64>().unwrap(),
+        data[4].parse::<f64>().unwrap(),
+        data[5].parse::<f64>().unwrap(),
+        timestamp,
+        now,
+        now,
+      ));
+    }
+
+    if items.is_empty() {
+      return Ok(())
+    }
+
+    let changed = items.iter().map(|item| item.symbol.clone()).collect::<Vec<_>>();
+    Self::upsert(ctx.clone(), items).await?;
+
+    let job = KlinesJob::new(ctx.clone());
+    for symbol in changed.iter() {
+      let _ = job.update(symbol.as_str(), interval).await;
+    }
+    Ok(())
+  }
+
+  pub async fn aggregate<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    timestamp: i64,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let timeframe = Interval::parse(interval)?;
+    if timeframe == Interval::Minute1 {
+      return Err(Box::from("klines aggregate source interval can not be aggregated"))
+    }
+    let start = timeframe.align(timestamp);
+    let end = timeframe.next(start);
+    if end > Utc::now().timestamp_millis() {
+      return Err(Box::from(format!("klines {symbol:} {interval:} {start:} not closed")))
+    }
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let items = klines::table
+      .select((klines::open, klines::close, klines::high, klines::low, klines::volume, klines::quota, klines::updated_at))
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq("1m"))
+      .filter(klines::timestamp.ge(start))
+      .filter(klines::timestamp.lt(end))
+      .order(klines::timestamp.asc())
+      .load::<(f64, f64, f64, f64, f64, f64, DateTime<Utc>)>(&mut conn)?;
+    drop(conn);
+    drop(pool);
+
+    // rebuilt only when one of its 1m bars was written after the last aggregate
+    let kline: Option<Kline> = Self::get(ctx.clone(), symbol, interval, start).await?;
+    if let Some(kline) = &kline {
+      let updated_at = items.iter().map(|item| item.6).max();
+      if kline.updated_at.timestamp_millis() >= end && updated_at.map_or(true, |updated_at| kline.updated_at >= updated_at) {
+        return Ok(false)
+      }
+    }
+
+    if items.len() as i64 != (end - start) / Interval::Minute1.timestep() {
+      println!("klines aggregate {symbol:} {interval:} {start:} lost {} of 1m, flush from api", (end - start) / Interval::Minute1.timestep() - items.len() as i64);
+      Self::flush(ctx.clone(), symbol, interval, end - 1, 1).await?;
+      if let Some(kline) = Self::get(ctx.clone(), symbol, interval, start).await? {
+        Self::update(ctx.clone(), kline.id, klines::updated_at.eq(Utc::now())).await?;
+      }
+    } else {
+      let open = items[0].0;
+      let close = items[items.len() - 1].1;
+      let mut high = items[0].2;
+      let mut low = items[0].3;
+      let mut volume = 0.0;
+      let mut quota = 0.0;
+      for (_, _, h, l, v, q, _) in items.iter() {
+        if *h > high {
+          high = *h;
+        }
+        if *l < low {
+          low = *l;
+        }
+        volume += v;
+        quota += q;
+      }
 
-      let success;
       if kline.is_none() {
         let id = xid::new().to_string();
-        success = match Self::create(
+        Self::create(
           ctx.clone(),
           id,
           symbol.to_string(),
@@ -416,13 +584,10 @@ impl KlinesRepository
           low,
           volume,
           quota,
-          timestamp,
-        ).await {
-          Ok(result) => result,
-          Err(err) => return Err(err.into()),
-        }
+          start,
+        ).await?;
       } else {
-        success = match Self::update(
+        Self::update(
           ctx.clone(),
           kline.unwrap().id,
           (
@@ -432,56 +597,15 @@ impl KlinesRepository
             klines::low.eq(low),
             klines::volume.eq(volume),
             klines::quota.eq(quota),
+            klines::updated_at.eq(Utc::now()),
           ),
-        ).await {
-          Ok(result) => result,
-          Err(err) => return Err(err.into()),
-        }
-      }
-
-      if success {
-        let job = KlinesJob::new(ctx.clone());
-        let _ = job.update(symbol, interval).await;
+        ).await?;
       }
     }
-    Ok(())
-  }
 
-  pub fn timestep<T>(interval: T) -> i64
-  where
-    T: AsRef<str>
-  {
-    let interval = interval.as_ref();
-    if interval == "1m" {
-      return 60000
-    } else if interval == "15m" {
-      return 900000
-    } else if interval == "4h" {
-      return 14400000
-    }
-    return 86400000
-  }
+    let job = KlinesJob::new(ctx.clone());
+    job.update(symbol, interval).await?;
 
-  pub fn timestamp<T>(interval: T) -> i64 
-  where
-    T: AsRef<str>
-  {
-    let interval = interval.as_ref();
-    let mut datetime = Utc::now();
-    datetime = datetime.sub(Duration::from_secs(datetime.second() as u64));
-    datetime = datetime.sub(Duration::from_nanos(datetime.nanosecond() as u64));
-    if interval == "15m" {
-      let minutes = datetime.minute() as u64 - ((Decimal::from_u64(datetime.minute() as u64).unwrap() / dec!(15)).floor() * dec!(15)).to_u64().unwrap();
-      datetime = datetime.sub(Duration::from_secs(minutes * 60));
-    } else if interval == "4h" {
-      let hours = datetime.hour() as u64 - ((Decimal::from_u64(datetime.hour() as u64).unwrap() / dec!(4)).floor() * dec!(4)).to_u64().unwrap();
-      let minutes = datetime.minute() as u64;
-      datetime = datetime.sub(Duration::from_secs(hours * 3600 + minutes * 60));
-    } else if interval == "1d" {
-      let hours = datetime.hour() as u64;
-      let minutes = datetime.minute() as u64;
-      datetime = datetime.sub(Duration::from_secs(hours * 3600 + minutes * 60));
-    }
-    datetime.timestamp_millis()
+    Ok(true)
   }
 }
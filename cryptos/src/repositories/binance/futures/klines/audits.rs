@@ -0,0 +1,159 @@
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+
+use crate::common::*;
+use crate::models::binance::futures::kline::audit::*;
+use crate::schema::binance::futures::klines::klines;
+use crate::schema::binance::futures::klines::audits::audits;
+
+#[derive(Default)]
+pub struct AuditsRepository {}
+
+impl AuditsRepository {
+  pub async fn symbols<T>(ctx: Ctx, interval: T) -> Result<Vec<String>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let symbols = klines::table
+      .select(klines::symbol)
+      .filter(klines::interval.eq(interval))
+      .distinct()
+      .order(klines::symbol.asc())
+      .load::<String>(&mut conn)?;
+
+    Ok(symbols)
+  }
+
+  pub async fn scan<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    start: i64,
+    end: i64,
+  ) -> Result<Vec<Audit>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let items = klines::table
+      .select((klines::timestamp, klines::open, klines::close, klines::high, klines::low, klines::volume))
+      .filter(klines::symbol.eq(symbol))
+      .filter(klines::interval.eq(interval))
+      .filter(klines::timestamp.ge(start))
+      .filter(klines::timestamp.le(end))
+      .order(klines::timestamp.asc())
+      .load::<(i64, f64, f64, f64, f64, f64)>(&mut conn)?;
+    drop(conn);
+    drop(pool);
+
+    let now = Utc::now();
+    let mut audits = Vec::new();
+    let mut push = |kind: &str, timestamp: i64, detail: String| {
+      audits.push(Audit::new(
+        xid::new().to_string(),
+        symbol.to_string(),
+        interval.to_string(),
+        kind.to_string(),
+        timestamp,
+        detail,
+        now,
+      ));
+    };
+
+    let mut expected = timeframe.align(start);
+    let mut zero_start = 0;
+    let mut zero_count = 0;
+    let mut returns = Vec::new();
+    for (i, (timestamp, open, close, high, low, volume)) in items.iter().enumerate() {
+      if i > 0 && items[i - 1].0 == *timestamp {
+        push("duplicate", *timestamp, "timestamp stored more than once".to_string());
+        continue
+      }
+      if *timestamp > expected {
+        push("missing", expected, format!("from {} to {}", expected, timeframe.prev(*timestamp)));
+      }
+      expected = timeframe.next(*timestamp);
+
+      if *high < open.max(*close) || *low > open.min(*close) || *high < *low {
+        push("ohlc", *timestamp, format!("open {open:} close {close:} high {high:} low {low:}"));
+      }
+
+      if *volume == 0.0 {
+        if zero_count == 0 {
+          zero_start = *timestamp;
+        }
+        zero_count += 1;
+      } else {
+        if zero_count >= 5 {
+          push("zero_volume", zero_start, format!("{zero_count:} bars without volume"));
+        }
+        zero_count = 0;
+      }
+
+      if i > 0 && items[i - 1].2 > 0.0 && *close > 0.0 {
+        returns.push((*timestamp, (close / items[i - 1].2).ln()));
+      }
+    }
+    if expected <= end {
+      push("missing", expected, format!("from {} to {}", expected, timeframe.align(end)));
+    }
+    if zero_count >= 5 {
+      push("zero_volume", zero_start, format!("{zero_count:} bars without volume"));
+    }
+
+    if returns.len() > 1 {
+      let mean = returns.iter().map(|(_, r)| r).sum::<f64>() / returns.len() as f64;
+      let variance = returns.iter().map(|(_, r)| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+      let limit = (10.0 * variance.sqrt()).max(0.1);
+      for (timestamp, r) in returns.iter() {
+        if (r - mean).abs() > limit {
+          push("outlier", *timestamp, format!("close moved {:.2}%", (r.exp() - 1.0) * 100.0));
+        }
+      }
+    }
+
+    Ok(audits)
+  }
+
+  pub async fn replace<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    items: Vec<Audit>,
+  ) -> Result<usize, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    conn.transaction::<usize, diesel::result::Error, _>(|conn| {
+      diesel::delete(
+        audits::table
+          .filter(audits::symbol.eq(symbol))
+          .filter(audits::interval.eq(interval))
+      ).execute(conn)?;
+      let mut effective_rows = 0;
+      for chunk in items.chunks(1000) {
+        effective_rows += diesel::insert_into(audits::table)
+          .values(chunk)
+          .execute(conn)?;
+      }
+      Ok(effective_rows)
+    }).map_err(|err| err.into())
+  }
+}
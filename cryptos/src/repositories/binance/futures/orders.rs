@@ -1,19 +1,12 @@
-use std::time::Duration;
 use std::collections::HashMap;
 
-use url::Url;
-use base64::{engine::general_purpose, Engine as _};
-use sha2::{Digest, Sha256};
-use hmac::{Hmac, Mac};
 use chrono::prelude::Utc;
 use diesel::prelude::*;
 use diesel::query_builder::QueryFragment;
-use reqwest::header;
-use rsa::{pkcs8::DecodePrivateKey, RsaPrivateKey};
 use serde::{Deserialize, Deserializer};
 
 use crate::common::*;
-use crate::repositories::binance::ApiError;
+use crate::repositories::binance::client::*;
 use crate::schema::binance::futures::orders::*;
 use crate::models::binance::futures::order::*;
 
@@ -85,6 +78,21 @@ struct TradeInfo {
   status: String,
 }
 
+#[derive(Deserialize)]
+struct StateInfo {
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "avgPrice", deserialize_with = "to_f64")]
+  avg_price: f64,
+  status: String,
+}
+
 fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
 where
   D: Deserializer<'de>,
@@ -211,7 +219,6 @@ impl OrdersRepository {
     let side = side.as_ref();
     let price_val = price.to_string();
     let quantity_val = quantity.to_string();
-    let timestamp = Utc::now().timestamp_millis().to_string();
 
     let mut params = HashMap::new();
     params.insert("symbol", symbol);
@@ -222,59 +229,9 @@ impl OrdersRepository {
     params.insert("quantity", &quantity_val);
     params.insert("timeInForce", "GTC");
     params.insert("newOrderRespType", "RESULT");
-    params.insert("recvWindow", "60000");
-    params.insert("timestamp", &timestamp);
-
-    let payload = params.iter().map(|(k,v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
- 
-    let private_key = RsaPrivateKey::from_pkcs8_pem(&Env::var("BINANCE_FUTURES_TRADE_API_SECRET"))?;
-    let signature = private_key.sign(
-      rsa::pkcs1v15::Pkcs1v15Sign::new::<rsa::sha2::Sha256>(),
-      &Sha256::digest(payload.as_bytes()),
-    )?;
-    let signature = general_purpose::STANDARD.encode(signature);
-
-    params.insert("signature", &signature);
-
-    let url = Url::parse(format!("{}/fapi/v1/order", Env::var("BINANCE_FUTURES_API_ENDPOINT")).as_str())?;
 
-    let mut headers = header::HeaderMap::new();
-    headers.insert("X-MBX-APIKEY", Env::var("BINANCE_FUTURES_TRADE_API_KEY").parse().unwrap());
-
-    let client = reqwest::Client::new();
-    let response = client.post(url)
-      .headers(headers)
-      .form(&params)
-      .timeout(Duration::from_secs(5))
-      .send()
-      .await?;
-
-    let status_code = response.status();
-
-    if status_code.is_server_error() {
-      return Err(Box::new(ApiError{
-        code: status_code.as_u16().into(),
-        message: "".to_string(),
-      }))
-    }
-
-    if status_code.is_client_error() {
-      match response.json::<ApiError>().await {
-        Ok(err) => {
-          return Err(Box::new(err))
-        }
-        Err(_) => return Err(Box::new(ApiError{
-          code: status_code.as_u16().into(),
-          message: "".to_string(),
-        }))
-      }
-    }
-
-    if !status_code.is_success() {
-      return Err(Box::from(format!("request error: {}", status_code)))
-    }
-
-    let trade = response.json::<TradeInfo>().await.unwrap();
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let trade: TradeInfo = client.post("/fapi/v1/order", &params, Security::Trade, 1).await?;
     println!("response {:?}", trade.order_id);
 
     let id = xid::new().to_string();
@@ -323,7 +280,6 @@ impl OrdersRepository {
     let symbol = symbol.as_ref();
     let start_time_val = start_time.to_string();
     let limit = limit.to_string();
-    let timestamp = Utc::now().timestamp_millis().to_string();
 
     let mut params = HashMap::new();
     params.insert("symbol", symbol);
@@ -331,44 +287,9 @@ impl OrdersRepository {
       params.insert("startTime", &start_time_val);
     }
     params.insert("limit", &limit);
-    params.insert("recvWindow", "60000");
-    params.insert("timestamp", &timestamp);
-
-    let mut url = Url::parse_with_params(format!("{}/fapi/v1/allOrders", Env::var("BINANCE_FUTURES_API_ENDPOINT")).as_str(), &params)?;
-    let query: &str = match url.query() {
-      Some(query) => query,
-      None => "",
-    };
-
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(Env::var("BINANCE_FUTURES_ACCOUNT_API_SECRET").as_bytes()).unwrap();
-    mac.update(query.as_bytes());
-    let signature = hex::encode(&mac.finalize().into_bytes().to_vec());
-
-    url.query_pairs_mut().append_pair("signature", signature.as_str());
-
-    let mut headers = header::HeaderMap::new();
-    headers.insert("X-MBX-APIKEY", Env::var("BINANCE_FUTURES_ACCOUNT_API_KEY").parse().unwrap());
-
-    let client = reqwest::Client::new();
-    let response = client.get(url)
-      .headers(headers)
-      .timeout(Duration::from_secs(30))
-      .send()
-      .await?;
 
-    let status_code = response.status();
-
-    if status_code.is_client_error() {
-      println!("response {}", response.text().await.unwrap());
-      return Err(Box::from(format!("bad request: {}", status_code)))
-    }
-
-    if !status_code.is_success() {
-      return Err(Box::from(format!("request error: {}", status_code)))
-    }
-
-    let orders = response.json::<Vec<OrderInfo>>().await.unwrap();
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let orders: Vec<OrderInfo> = client.get("/fapi/v1/allOrders", &params, Security::Account, 5).await?;
 
     for order in orders.iter() {
       let entity: Option<Order> = match Self::get(ctx.clone(), order.symbol.clone(), order.order_id).await {
@@ -451,4 +372,46 @@ impl OrdersRepository {
 
     Ok(())
   }
+
+  pub async fn cancel<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let info: StateInfo = client.delete("/fapi/v1/order", &params, Security::Trade, 1).await?;
+
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, info.avg_price))
+  }
+
+  pub async fn query<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+
+    let client = ApiClient::new(ctx.clone(), "futures");
+    let info: StateInfo = client.get("/fapi/v1/order", &params, Security::Account, 1).await?;
+
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, info.avg_price))
+  }
 }
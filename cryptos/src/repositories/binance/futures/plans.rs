@@ -1,16 +1,13 @@
 use std::collections::HashMap;
 
-use std::ops::Sub;
-use std::time::Duration;
 
-use chrono::{prelude::Utc, Timelike};
+use chrono::prelude::Utc;
 use diesel::prelude::*;
 use diesel::query_builder::QueryFragment;
 use rust_decimal::prelude::*;
-use rust_decimal_macros::dec;
 
 use crate::common::*;
-use crate::repositories::binance::futures::symbols::*;
+use crate::repositories::exchange::*;
 use crate::repositories::binance::futures::strategies::*;
 use crate::models::binance::futures::plan::*;
 use crate::schema::binance::futures::plans::*;
@@ -170,12 +167,14 @@ impl PlansRepository {
     }
   }
 
-  pub async fn flush<T>(
+  pub async fn flush<E, T>(
     ctx: Ctx,
+    exchange: &E,
     symbol: T,
     interval: T,
   ) -> Result<(), Box<dyn std::error::Error>> 
   where
+    E: Exchange,
     T: AsRef<str>
   {
     let symbol = symbol.as_ref();
@@ -186,7 +185,7 @@ impl PlansRepository {
       symbol,
       vec!["kdj", "ichimoku_cloud"],
       interval,
-      Self::timestamp(interval) - 60000,
+      Interval::parse(interval)?.timestamp() - 60000,
     ).await {
       Ok(Some(result)) => result,
       Ok(None) => return Err(Box::from(format!("strategy of {symbol:} {interval:} not exists"))),
@@ -198,13 +197,15 @@ impl PlansRepository {
     for indicators in vec![
       vec!["bbands"],
       vec!["zlema", "ha_zlema"],
+      vec!["adx", "supertrend"],
+      vec!["rsi", "macd"],
     ] {
       let entity = match StrategiesRepository::last(
         ctx.clone(),
         symbol,
         indicators,
         interval,
-        strategy.timestamp - 14 * Self::timestep(interval),
+        strategy.timestamp - 14 * Interval::parse(interval)?.timestep(),
       ).await {
         Ok(Some(result)) => result,
         Ok(None) => continue,
@@ -219,14 +220,20 @@ impl PlansRepository {
       if entity.indicator == "zlema" || entity.indicator == "ha_zlema" {
         amount += 5.0;
       }
+      if entity.indicator == "adx" || entity.indicator == "supertrend" {
+        amount += 10.0;
+      }
+      if entity.indicator == "rsi" || entity.indicator == "macd" {
+        amount += 5.0;
+      }
     }
 
-    let (tick_size, step_size, _) = match SymbolsRepository::filters(ctx.clone(), symbol).await {
+    let filters = match exchange.filters(symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
-    let tick_size = Decimal::from_f64(tick_size).unwrap();
-    let step_size = Decimal::from_f64(step_size).unwrap();
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
 
     let amount = Decimal::from_f64(amount).unwrap();
     let mut price = Decimal::from_f64(strategy.price).unwrap();
@@ -277,41 +284,5 @@ impl PlansRepository {
     Ok(())
   }
 
-  pub fn timestamp<T>(interval: T) -> i64 
-  where
-    T: AsRef<str>
-  {
-    let interval = interval.as_ref();
-    let mut datetime = Utc::now();
-    datetime = datetime.sub(Duration::from_secs(datetime.second() as u64));
-    datetime = datetime.sub(Duration::from_nanos(datetime.nanosecond() as u64));
-    if interval == "15m" {
-      let minutes = datetime.minute() as u64 - ((Decimal::from_u64(datetime.minute() as u64).unwrap() / dec!(15)).floor() * dec!(15)).to_u64().unwrap();
-      datetime = datetime.sub(Duration::from_secs(minutes * 60));
-    } else if interval == "4h" {
-      let hours = datetime.hour() as u64 - ((Decimal::from_u64(datetime.hour() as u64).unwrap() / dec!(4)).floor() * dec!(4)).to_u64().unwrap();
-      let minutes = datetime.minute() as u64;
-      datetime = datetime.sub(Duration::from_secs(hours * 3600 + minutes * 60));
-    } else if interval == "1d" {
-      let hours = datetime.hour() as u64;
-      let minutes = datetime.minute() as u64;
-      datetime = datetime.sub(Duration::from_secs(hours * 3600 + minutes * 60));
-    }
-    datetime.timestamp_millis()
-  }
 
-  pub fn timestep<T>(interval: T) -> i64
-  where
-    T: AsRef<str>
-  {
-    let interval = interval.as_ref();
-    if interval == "1m" {
-      return 60000
-    } else if interval == "15m" {
-      return 900000
-    } else if interval == "4h" {
-      return 14400000
-    }
-    return 86400000
-  }
 }
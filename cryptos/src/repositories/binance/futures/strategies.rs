@@ -4,13 +4,12 @@ use chrono::{prelude::Utc, Local};
 use diesel::prelude::*;
 use diesel::query_builder::QueryFragment;
 use redis::AsyncCommands;
-use rust_decimal::prelude::*;
-use rust_decimal_macros::dec;
 
 use crate::common::*;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::params::*;
+use crate::repositories::indicators::signals;
 use crate::config::binance::futures::config as Config;
-use crate::repositories::binance::futures::tickers::*;
-use crate::repositories::binance::futures::symbols::*;
 use crate::models::binance::futures::symbol::Filters;
 use crate::schema::binance::futures::symbols::*;
 use crate::models::binance::futures::strategy::*;
@@ -174,12 +173,14 @@ impl StrategiesRepository {
     }
   }
 
-  pub async fn atr<T>(
+  pub async fn atr<E, T>(
     ctx: Ctx,
+    exchange: &E,
     symbol: T,
     interval: T,
   ) -> Result<(), Box<dyn std::error::Error>>
   where
+    E: Exchange,
     T: AsRef<str>
   {
     let mut rdb = ctx.rdb.lock().await.clone();
@@ -187,49 +188,37 @@ impl StrategiesRepository {
     let indicator = "atr";
     let interval = interval.as_ref();
 
+    // indicators live under the market of the exchange, so any market shares this strategy
     let day = Local::now().format("%m%d").to_string();
-    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let redis_key = format!("{}:indicators:{}:{}:{}", exchange.name(), interval, symbol, day);
     let atr: Option<f64> = match rdb.hget(&redis_key, indicator).await {
       Ok(Some(result)) => result,
       Ok(None) => return Err(Box::from(format!("atr of {symbol:} {interval:} not exists"))),
       Err(err) => return Err(err.into()),
     };
-    let atr = Decimal::from_f64(atr.unwrap()).unwrap();
+    let atr = atr.unwrap();
 
-    let price = match TickersRepository::price(
-      ctx.clone(),
-      &symbol,
-    ).await {
+    let price = match exchange.price(symbol).await {
       Ok(price) => price,
       Err(err) => return Err(err.into()),
     };
-    let price = Decimal::from_f64(price).unwrap();
 
-    let (tick_size, _, _) = match SymbolsRepository::filters(ctx.clone(), symbol).await {
+    let filters = match exchange.filters(symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
-    let tick_size = Decimal::from_f64(tick_size).unwrap();
-
-    let profit_target = price * dec!(2) - atr * dec!(1.5);
-    let stop_loss_point = price - atr;
-    let take_profit_price = stop_loss_point + (profit_target - stop_loss_point) / dec!(2);
-
-    let profit_target = (profit_target / tick_size).ceil() * tick_size;
-    let stop_loss_point = (stop_loss_point / tick_size).floor() * tick_size;
-    let take_profit_price = (take_profit_price / tick_size).ceil() * tick_size;
 
-    let risk_reward_ratio = ((price - stop_loss_point) / (profit_target - price)).round_dp(4).to_f32().unwrap();
-    let take_profit_ratio = (price / take_profit_price).round_dp(4).to_f32().unwrap();
+    let params = ParamsRepository::get(ctx.clone(), exchange.name().trim_start_matches("binance:"), symbol, interval).await?;
+    let levels = signals::atr(price, atr, filters.tick_size, params.atr_profit_ratio, params.atr_stop_ratio);
 
     () = rdb.hset_multiple(
       &redis_key,
       &[
-        ("profit_target", profit_target.to_string()),
-        ("stop_loss_point", stop_loss_point.to_string()),
-        ("take_profit_price", take_profit_price.to_string()),
-        ("risk_reward_ratio", risk_reward_ratio.to_string()),
-        ("take_profit_ratio", take_profit_ratio.to_string()),
+        ("profit_target", levels.profit_target.to_string()),
+        ("stop_loss_point", levels.stop_loss_point.to_string()),
+        ("take_profit_price", levels.take_profit_price.to_string()),
+        ("risk_reward_ratio", levels.risk_reward_ratio.to_string()),
+        ("take_profit_ratio", levels.take_profit_ratio.to_string()),
       ],
     ).await?;
 
@@ -264,16 +253,10 @@ impl StrategiesRepository {
     let price = values[2].parse::<f64>().unwrap();
     let timestamp = values[3].parse::<i64>().unwrap();
 
-    if zlema1 * zlema2 >= 0.0 {
-      return Ok(())
-    }
-
-    let signal: i32;
-    if zlema2 > 0.0 {
-      signal = 1;
-    } else {
-      signal = 2;
-    }
+    let signal = match signals::zlema(zlema1, zlema2) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
 
     let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
       Ok(Some(result)) => Some(result),
@@ -338,16 +321,10 @@ impl StrategiesRepository {
     let price = values[2].parse::<f64>().unwrap();
     let timestamp = values[3].parse::<i64>().unwrap();
 
-    if zlema1 * zlema2 >= 0.0 {
-      return Ok(())
-    }
-
-    let signal: i32;
-    if zlema2 > 0.0 {
-      signal = 1;
-    } else {
-      signal = 2;
-    }
+    let signal = match signals::zlema(zlema1, zlema2) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
 
     let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
       Ok(Some(result)) => Some(result),
@@ -413,14 +390,12 @@ impl StrategiesRepository {
     let price = values[3].parse::<f64>().unwrap();
     let timestamp = values[4].parse::<i64>().unwrap();
 
-    let signal: i32;
-    if k < 20.0 && d > 60.0 && j < 60.0 {
-      signal = 1;
-    } else if k > 80.0 && d > 70.0 && j > 90.0 {
-      signal = 2;
-    } else {
-      return Ok(())
-    }
+    let params = ParamsRepository::get(ctx.clone(), "futures", symbol, interval).await?;
+
+    let signal = match signals::kdj(k, d, j, params.kdj_oversold, params.kdj_overbought) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
 
     let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
       Ok(Some(result)) => Some(result),
@@ -489,24 +464,18 @@ impl StrategiesRepository {
     let price = values[6].parse::<f64>().unwrap();
     let timestamp = values[7].parse::<i64>().unwrap();
 
-    let signal: i32;
-    if b1 < 0.5 && b2 < 0.5 && b3 > 0.5 {
-      signal = 1;
-    } else if b1 > 0.5 && b2 < 0.5 && b3 < 0.5 {
-      signal = 2;
-    } else if b1 > 0.8 && b2 > 0.8 && b3 > 0.8 {
-      signal = 1;
-    } else if b1 > 0.8 && b2 > 0.8 && b3 < 0.8 {
-      signal = 2;
-    } else {
-      return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} invalid")))
-    }
-
-    if w1 < 0.1 && w2 < 0.1 && w3 < 0.1 {
-      if w1 < 0.03 || w2 < 0.03 || w3 > 0.03 {
-        return Ok(())
-      } 
-    }
+    let params = ParamsRepository::get(ctx.clone(), "futures", symbol, interval).await?;
+    let signal = match signals::bbands(
+      [b1, b2, b3, w1, w2, w3],
+      params.bbands_middle,
+      params.bbands_upper,
+      params.bbands_squeeze,
+      params.bbands_narrow,
+    ) {
+      Ok(Some(result)) => result,
+      Ok(None) => return Ok(()),
+      Err(_) => return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} invalid"))),
+    };
 
     let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
       Ok(Some(result)) => Some(result),
@@ -609,6 +578,283 @@ impl StrategiesRepository {
     Ok(())
   }
 
+  pub async fn rsi<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let symbol = symbol.as_ref();
+    let indicator = "rsi";
+    let interval = interval.as_ref();
+
+    let day = Local::now().format("%m%d").to_string();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let data: Option<String> = match rdb.hget(&redis_key, "rsi_state").await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+    let data = data.unwrap();
+    let values: Vec<&str> = data.split(",").collect();
+
+    let last_value = values[0].parse::<f64>().unwrap();
+    let value = values[1].parse::<f64>().unwrap();
+    let price = values[2].parse::<f64>().unwrap();
+    let timestamp = values[3].parse::<i64>().unwrap();
+
+    let params = ParamsRepository::get(ctx.clone(), "futures", symbol, interval).await?;
+
+    let signal = match signals::rsi(last_value, value, params.rsi_oversold, params.rsi_overbought) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
+
+    let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
+      Ok(Some(result)) => Some(result),
+      Ok(None) => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    if !strategy.is_none() {
+      let strategy = strategy.unwrap();
+      if strategy.signal == signal {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} waiting for change")))
+      }
+      if strategy.timestamp >= timestamp {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} exists")))
+      }
+    }
+
+    let id = xid::new().to_string();
+    let _ = match Self::create(
+      ctx.clone(),
+      id,
+      symbol.to_string(),
+      indicator.to_string(),
+      interval.to_string(),
+      price,
+      signal,
+      timestamp,
+      "".to_string(),
+    ).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(())
+  }
+
+  pub async fn macd<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let symbol = symbol.as_ref();
+    let indicator = "macd";
+    let interval = interval.as_ref();
+
+    let day = Local::now().format("%m%d").to_string();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let data: Option<String> = match rdb.hget(&redis_key, "macd_state").await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+    let data = data.unwrap();
+    let values: Vec<&str> = data.split(",").collect();
+
+    let last_hist = values[0].parse::<f64>().unwrap();
+    let hist = values[1].parse::<f64>().unwrap();
+    let price = values[2].parse::<f64>().unwrap();
+    let timestamp = values[3].parse::<i64>().unwrap();
+
+    let signal = match signals::macd(last_hist, hist) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
+
+    let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
+      Ok(Some(result)) => Some(result),
+      Ok(None) => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    if !strategy.is_none() {
+      let strategy = strategy.unwrap();
+      if strategy.signal == signal {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} waiting for change")))
+      }
+      if strategy.timestamp >= timestamp {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} exists")))
+      }
+    }
+
+    let id = xid::new().to_string();
+    let _ = match Self::create(
+      ctx.clone(),
+      id,
+      symbol.to_string(),
+      indicator.to_string(),
+      interval.to_string(),
+      price,
+      signal,
+      timestamp,
+      "".to_string(),
+    ).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(())
+  }
+
+  pub async fn adx<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let symbol = symbol.as_ref();
+    let indicator = "adx";
+    let interval = interval.as_ref();
+
+    let day = Local::now().format("%m%d").to_string();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let data: Option<String> = match rdb.hget(&redis_key, "adx_state").await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+    let data = data.unwrap();
+    let values: Vec<&str> = data.split(",").collect();
+
+    let adx = values[0].parse::<f64>().unwrap();
+    let plus_di = values[1].parse::<f64>().unwrap();
+    let minus_di = values[2].parse::<f64>().unwrap();
+    let price = values[3].parse::<f64>().unwrap();
+    let timestamp = values[4].parse::<i64>().unwrap();
+
+    let params = ParamsRepository::get(ctx.clone(), "futures", symbol, interval).await?;
+
+    let signal = match signals::adx(adx, plus_di, minus_di, params.adx_threshold) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
+
+    let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
+      Ok(Some(result)) => Some(result),
+      Ok(None) => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    if !strategy.is_none() {
+      let strategy = strategy.unwrap();
+      if strategy.signal == signal {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} waiting for change")))
+      }
+      if strategy.timestamp >= timestamp {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} exists")))
+      }
+    }
+
+    let id = xid::new().to_string();
+    let _ = match Self::create(
+      ctx.clone(),
+      id,
+      symbol.to_string(),
+      indicator.to_string(),
+      interval.to_string(),
+      price,
+      signal,
+      timestamp,
+      "".to_string(),
+    ).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(())
+  }
+
+  pub async fn supertrend<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let symbol = symbol.as_ref();
+    let indicator = "supertrend";
+    let interval = interval.as_ref();
+
+    let day = Local::now().format("%m%d").to_string();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS, interval, symbol, day);
+    let data: Option<String> = match rdb.hget(&redis_key, "supertrend_state").await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+    let data = data.unwrap();
+    let values: Vec<&str> = data.split(",").collect();
+
+    let last_trend = values[0].parse::<i32>().unwrap();
+    let trend = values[1].parse::<i32>().unwrap();
+    let price = values[2].parse::<f64>().unwrap();
+    let timestamp = values[3].parse::<i64>().unwrap();
+
+    let signal = match signals::supertrend(last_trend, trend) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
+
+    let strategy: Option<Strategy> = match Self::get(ctx.clone(), symbol, indicator, interval).await {
+      Ok(Some(result)) => Some(result),
+      Ok(None) => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    if !strategy.is_none() {
+      let strategy = strategy.unwrap();
+      if strategy.signal == signal {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} waiting for change")))
+      }
+      if strategy.timestamp >= timestamp {
+        return Err(Box::from(format!("{indicator:} of {symbol:} {interval:} exists")))
+      }
+    }
+
+    let id = xid::new().to_string();
+    let _ = match Self::create(
+      ctx.clone(),
+      id,
+      symbol.to_string(),
+      indicator.to_string(),
+      interval.to_string(),
+      price,
+      signal,
+      timestamp,
+      "".to_string(),
+    ).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    Ok(())
+  }
+
   pub async fn filters<T>(ctx: Ctx, symbol: T) -> Result<(f64, f64), Box<dyn std::error::Error>> 
   where
     T: AsRef<str>
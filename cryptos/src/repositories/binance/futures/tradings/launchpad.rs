@@ -0,0 +1,483 @@
+use chrono::{prelude::Utc, DateTime, Duration};
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::models::binance::futures::tradings::launchpad::*;
+use crate::schema::binance::futures::tradings::launchpad::*;
+use crate::repositories::binance::ApiError;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::futures::symbols::*;
+
+#[derive(Default)]
+pub struct LaunchpadRepository {}
+
+// status 0 entry placed, 1 take profit placed, 2 stop placed, 3 closed, 4 canceled
+impl LaunchpadRepository {
+  pub async fn find<T>(
+    ctx:Ctx,
+    id: T,
+  ) -> Result<Option<Launchpad>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match launchpad::table
+      .find(id)
+      .select(Launchpad::as_select())
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn scan(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let ids = launchpad::table
+      .select(launchpad::id)
+      .filter(launchpad::status.eq_any([0, 1, 2]))
+      .load::<String>(&mut conn)?;
+    Ok(ids)
+  }
+
+  pub async fn count<T>(ctx: Ctx, symbol: T) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let count = launchpad::table
+      .filter(launchpad::symbol.eq(symbol))
+      .count()
+      .get_result(&mut conn)?;
+    Ok(count)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    level: i32,
+    buy_price: f64,
+    sell_price: f64,
+    stop_price: f64,
+    buy_quantity: f64,
+    buy_order_id: i64,
+    expired_at: DateTime<Utc>,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Launchpad::new(
+      id,
+      symbol,
+      level,
+      buy_price,
+      sell_price,
+      stop_price,
+      buy_quantity,
+      buy_quantity,
+      buy_order_id,
+      0,
+      0,
+      0,
+      "".to_owned(),
+      expired_at,
+      now,
+      now,
+    );
+    match diesel::insert_into(launchpad::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    value: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = launchpad::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(launchpad::table.find(id).filter(launchpad::version.eq(version)))
+      .set(value)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // entry levels as price ratio to the listing price and amount in quote, e.g. 0.99:30,0.97:30
+  pub fn ladder() -> Vec<(f64, f64)> {
+    let mut value = Env::var("BINANCE_FUTURES_LAUNCHPAD_LADDER");
+    if value.is_empty() {
+      value = "0.99:30,0.97:30,0.94:40,0.9:50".to_string();
+    }
+    let mut ladder = Vec::new();
+    for item in value.split(",") {
+      let (ratio, amount) = match item.trim().split_once(":") {
+        Some(result) => result,
+        None => continue,
+      };
+      match (ratio.parse::<f64>(), amount.parse::<f64>()) {
+        (Ok(ratio), Ok(amount)) if ratio > 0.0 && ratio <= 1.0 && amount > 0.0 => {
+          ladder.push((ratio, amount));
+        }
+        _ => (),
+      }
+    }
+    ladder.truncate(Config::LAUNCHPAD_MAX_LEVELS);
+    ladder
+  }
+
+  pub async fn place<E, T>(ctx: Ctx, exchange: &E, symbol: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let listed_at = match SymbolsRepository::listed_at(ctx.clone(), symbol).await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("launchpad of {symbol:} is not a new listing"))),
+      Err(err) => return Err(err.into()),
+    };
+
+    if listed_at.timestamp() < Utc::now().timestamp() - Config::LAUNCHPAD_WINDOW {
+      return Err(Box::from(format!("launchpad of {symbol:} window has been closed")))
+    }
+
+    if Self::count(ctx.clone(), symbol).await? > 0 {
+      return Err(Box::from(format!("launchpad of {symbol:} has been placed")))
+    }
+
+    let price = match Self::price(exchange, symbol).await {
+      Ok(price) => Decimal::from_f64(price).unwrap(),
+      Err(err) => return Err(err.into()),
+    };
+
+    let filters = match exchange.filters(symbol).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
+    let notional = Decimal::from_f64(filters.notional).unwrap();
+
+    let (_, quote_asset) = match exchange.pairs(symbol).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    let free = match exchange.balance(&quote_asset).await {
+      Ok(result) => Decimal::from_f64(result).unwrap(),
+      Err(err) => return Err(err.into()),
+    };
+
+    let max_amount = Decimal::from_f64(Config::LAUNCHPAD_MAX_AMOUNT).unwrap();
+    let max_level_amount = Decimal::from_f64(Config::LAUNCHPAD_MAX_LEVEL_AMOUNT).unwrap();
+    let take_profit = Decimal::from_f64(Config::LAUNCHPAD_TAKE_PROFIT).unwrap();
+    let stop_loss = Decimal::from_f64(Config::LAUNCHPAD_STOP_LOSS).unwrap();
+    let expired_at = listed_at + Duration::seconds(Config::LAUNCHPAD_TIME_STOP);
+
+    let mut total = dec!(0.0);
+    for (level, (ratio, amount)) in Self::ladder().into_iter().enumerate() {
+      let mut amount = Decimal::from_f64(amount).unwrap().min(max_level_amount);
+      if amount < notional {
+        amount = notional;
+      }
+
+      let buy_price = price * Decimal::from_f64(ratio).unwrap();
+      let buy_price = (buy_price / tick_size).floor() * tick_size;
+      if buy_price <= dec!(0.0) {
+        break
+      }
+      let buy_quantity = amount / buy_price;
+      let mut buy_quantity = (buy_quantity / step_size).floor() * step_size;
+      if buy_price * buy_quantity < notional {
+        buy_quantity += step_size;
+      }
+
+      let amount = buy_price * buy_quantity;
+      if amount > max_level_amount {
+        println!("launchpad of {symbol:} level {level:} minimum notional over the level cap, skipped");
+        continue
+      }
+      if total + amount > max_amount || total + amount > free {
+        println!("launchpad of {symbol:} level {level:} over the cap, ladder stopped");
+        break
+      }
+
+      let sell_price = buy_price * take_profit;
+      let sell_price = (sell_price / tick_size).ceil() * tick_size;
+      let stop_price = buy_price * stop_loss;
+      let stop_price = (stop_price / tick_size).floor() * tick_size;
+
+      let order_id = match exchange.place(&OrderRequest {
+        symbol: symbol.to_owned(),
+        side: 1,
+        is_open: true,
+        price: buy_price.to_f64().unwrap(),
+        quantity: buy_quantity.to_f64().unwrap(),
+      }).await {
+        Ok(result) => result,
+        Err(err) => {
+          if err.is::<ApiError>() {
+            return Err(err.into())
+          } else {
+            println!("error {:?}", err);
+            0
+          }
+        }
+      };
+
+      Self::create(
+        ctx.clone(),
+        xid::new().to_string(),
+        symbol.to_owned(),
+        level as i32,
+        buy_price.to_f64().unwrap(),
+        sell_price.to_f64().unwrap(),
+        stop_price.to_f64().unwrap(),
+        buy_quantity.to_f64().unwrap(),
+        order_id,
+        expired_at,
+      ).await?;
+
+      total += amount;
+      println!("launchpad of {symbol:} level {level:} place {buy_price:} {buy_quantity:} success");
+    }
+
+    Ok(())
+  }
+
+  pub async fn flush<E, T>(ctx: Ctx, exchange: &E, id: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let entity = match Self::find(ctx.clone(), id).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("launchpad trading of {id:} not exists"))),
+    };
+
+    let timestamp = Utc::now().timestamp();
+
+    match entity.status {
+      0 => {
+        let mut state = if entity.buy_order_id > 0 {
+          exchange.query(&entity.symbol, entity.buy_order_id).await?
+        } else {
+          OrderState {
+            order_id: 0,
+            status: "REJECTED".to_owned(),
+            price: entity.buy_price,
+            quantity: 0.0,
+            executed_quantity: 0.0,
+            avg_price: 0.0,
+          }
+        };
+        if state.status == "NEW" || state.status == "PARTIALLY_FILLED" {
+          if entity.created_at.timestamp() + Config::LAUNCHPAD_ENTRY_TIMEOUT > timestamp {
+            return Ok(())
+          }
+          state = exchange.cancel(&entity.symbol, entity.buy_order_id).await?;
+        }
+        if state.executed_quantity <= 0.0 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              launchpad::status.eq(4),
+              launchpad::remark.eq(state.status),
+              launchpad::version.eq(entity.version + 1),
+              launchpad::updated_at.eq(Utc::now()),
+            ),
+          ).await?;
+          return Ok(())
+        }
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: 1,
+          is_open: false,
+          price: entity.sell_price,
+          quantity: state.executed_quantity,
+        }).await?;
+
+        Self::update(
+          ctx.clone(),
+          entity.id,
+          entity.version,
+          (
+            launchpad::buy_quantity.eq(state.executed_quantity),
+            launchpad::sell_quantity.eq(state.executed_quantity),
+            launchpad::sell_order_id.eq(order_id),
+            launchpad::status.eq(1),
+            launchpad::version.eq(entity.version + 1),
+            launchpad::updated_at.eq(Utc::now()),
+          ),
+        ).await?;
+      }
+      1 | 2 => {
+        let state = exchange.query(&entity.symbol, entity.sell_order_id).await?;
+        if state.status == "FILLED" {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              launchpad::sell_price.eq(state.price),
+              launchpad::status.eq(3),
+              launchpad::version.eq(entity.version + 1),
+              launchpad::updated_at.eq(Utc::now()),
+            ),
+          ).await?;
+          return Ok(())
+        }
+
+        // the exchange dropped the sell order, what is left of it is placed again
+        let is_dropped = ["CANCELED", "EXPIRED", "REJECTED"].contains(&state.status.as_str());
+        if is_dropped && entity.status == 1 {
+          let remaining = state.quantity - state.executed_quantity;
+          if remaining <= 0.0 {
+            Self::update(
+              ctx.clone(),
+              entity.id,
+              entity.version,
+              (
+                launchpad::sell_price.eq(state.price),
+                launchpad::status.eq(3),
+                launchpad::version.eq(entity.version + 1),
+                launchpad::updated_at.eq(Utc::now()),
+              ),
+            ).await?;
+            return Ok(())
+          }
+
+          let order_id = exchange.place(&OrderRequest {
+            symbol: entity.symbol.clone(),
+            side: 1,
+            is_open: false,
+            price: entity.sell_price,
+            quantity: remaining,
+          }).await?;
+
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              launchpad::sell_order_id.eq(order_id),
+              launchpad::version.eq(entity.version + 1),
+              launchpad::updated_at.eq(Utc::now()),
+            ),
+          ).await?;
+          println!("launchpad of {0:} level {1:} sell order {2:} replaced", entity.symbol, entity.level, state.status);
+          return Ok(())
+        }
+
+        let price = Self::price(exchange, &entity.symbol).await?;
+        let remark = if entity.status == 2 {
+          // the stop order chases the market until it fills
+          if !is_dropped && entity.updated_at.timestamp() + 60 > timestamp {
+            return Ok(())
+          }
+          entity.remark.clone()
+        } else if price <= entity.stop_price {
+          "stop".to_owned()
+        } else if entity.expired_at.timestamp() <= timestamp {
+          "timeout".to_owned()
+        } else {
+          return Ok(())
+        };
+
+        let state = if is_dropped {
+          state
+        } else {
+          exchange.cancel(&entity.symbol, entity.sell_order_id).await?
+        };
+        let remaining = state.quantity - state.executed_quantity;
+        if state.status == "FILLED" || remaining <= 0.0 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              launchpad::sell_price.eq(state.price),
+              launchpad::status.eq(3),
+              launchpad::version.eq(entity.version + 1),
+              launchpad::updated_at.eq(Utc::now()),
+            ),
+          ).await?;
+          return Ok(())
+        }
+
+        let filters = exchange.filters(&entity.symbol).await?;
+        let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+        let sell_price = Decimal::from_f64(price).unwrap() * dec!(0.995);
+        let sell_price = (sell_price / tick_size).floor() * tick_size;
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: 1,
+          is_open: false,
+          price: sell_price.to_f64().unwrap(),
+          quantity: remaining,
+        }).await?;
+
+        Self::update(
+          ctx.clone(),
+          entity.id,
+          entity.version,
+          (
+            launchpad::sell_price.eq(sell_price.to_f64().unwrap()),
+            launchpad::sell_order_id.eq(order_id),
+            launchpad::status.eq(2),
+            launchpad::remark.eq(remark),
+            launchpad::version.eq(entity.version + 1),
+            launchpad::updated_at.eq(Utc::now()),
+          ),
+        ).await?;
+        println!("launchpad of {0:} level {1:} stop at {sell_price:}", entity.symbol, entity.level);
+      }
+      _ => (),
+    }
+
+    Ok(())
+  }
+
+  // listings are not streamed into the tickers, so the best bid is read from the rest book
+  async fn price<E>(exchange: &E, symbol: &str) -> Result<f64, Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+  {
+    let book = exchange.depth(symbol, 5).await?;
+    match book.bids.first() {
+      Some((price, _)) => Ok(*price),
+      None => Err(Box::from(format!("depth of {symbol:} not exists"))),
+    }
+  }
+}
\ No newline at end of file
@@ -10,12 +10,9 @@ use crate::config::binance::futures::config as Config;
 use crate::models::binance::futures::tradings::scalping::*;
 use crate::schema::binance::futures::tradings::scalping::*;
 use crate::repositories::binance::ApiError;
-use crate::repositories::binance::futures::tickers::*;
-use crate::repositories::binance::futures::symbols::*;
-use crate::repositories::binance::futures::account::*;
+use crate::repositories::exchange::*;
 use crate::repositories::binance::futures::positions::*;
 use crate::repositories::binance::futures::plans::*;
-use crate::repositories::binance::futures::orders::*;
 use crate::repositories::binance::futures::scalping::ScalpingRepository as ParentRepositoy;
 use crate::repositories::binance::futures::scalping::plans::PlansRepository as ScalpingPlansRepository;
 
@@ -125,8 +122,9 @@ impl ScalpingRepository {
     }
   }
 
-  pub async fn place<T>(ctx: Ctx, plan_id: T) -> Result<(), Box<dyn std::error::Error>> 
+  pub async fn place<E, T>(ctx: Ctx, exchange: &E, plan_id: T) -> Result<(), Box<dyn std::error::Error>> 
   where
+    E: Exchange,
     T: AsRef<str>
   {
     let plan_id = plan_id.as_ref();
@@ -139,22 +137,24 @@ impl ScalpingRepository {
 
     let timestamp = Utc::now().timestamp();
 
-    if plan.interval == "1m" && plan.created_at.timestamp() < timestamp - 900
-      || plan.interval == "15m" && plan.created_at.timestamp() < timestamp - 2700
-      || plan.interval == "4h" && plan.created_at.timestamp() < timestamp - 5400
-      || plan.interval == "1d" && plan.created_at.timestamp() < timestamp - 21600 {
+    let timeframe = Interval::parse(&plan.interval)?;
+    if plan.created_at.timestamp() < timestamp - timeframe.expiry() {
       let _ = ScalpingPlansRepository::delete(ctx.clone(), plan_id).await;
       return Err(Box::from(format!("plan has been expired")))
     }
 
+    let tier = match timeframe {
+      Interval::Minute1 | Interval::Minute3 | Interval::Minute5 => "1m",
+      Interval::Minute15 | Interval::Minute30 | Interval::Hour1 | Interval::Hour2 => "15m",
+      Interval::Hour4 | Interval::Hour6 | Interval::Hour8 | Interval::Hour12 => "4h",
+      _ => "1d",
+    };
+
     let position_side: &str;
-    let side: &str;
     if plan.side == 1 {
       position_side = "LONG";
-      side = "BUY";
     } else {
       position_side = "SHORT";
-      side = "SELL";
     }
 
     let scalping = match ParentRepositoy::get(ctx.clone(), plan.symbol.clone(), plan.side).await {
@@ -173,22 +173,19 @@ impl ScalpingRepository {
       return Err(Box::from(format!("plan of {0:}[{position_side:}] lower than scalping price {1:}", plan.symbol, scalping.price)))
     }
 
-    let price = match TickersRepository::price(
-      ctx.clone(),
-      plan.symbol.clone(),
-    ).await {
+    let price = match exchange.price(&plan.symbol).await {
       Ok(price) => price,
       Err(err) => return Err(err.into()),
     };
     let price = Decimal::from_f64(price).unwrap();
 
-    let (tick_size, step_size, notional) = match SymbolsRepository::filters(ctx.clone(), scalping.symbol.clone()).await {
+    let filters = match exchange.filters(&scalping.symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
-    let tick_size = Decimal::from_f64(tick_size).unwrap();
-    let step_size = Decimal::from_f64(step_size).unwrap();
-    let notional = Decimal::from_f64(notional).unwrap();
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
+    let notional = Decimal::from_f64(filters.notional).unwrap();
 
     let mut buy_price = Decimal::from_f64(plan.price).unwrap();
     if plan.side == 1 {
@@ -231,46 +228,46 @@ impl ScalpingRepository {
     let mut sell_price = dec!(0.0);
     if plan.side == 1 {
       if plan.amount > 15.0 {
-        if plan.interval == "1m" {
+        if tier == "1m" {
           sell_price = buy_price * dec!(1.0105);
-        } else if plan.interval == "15m" {
+        } else if tier == "15m" {
           sell_price = buy_price * dec!(1.0125);
-        } else if plan.interval == "4h" {
+        } else if tier == "4h" {
           sell_price = buy_price * dec!(1.0185);
-        } else if plan.interval == "1d" {
+        } else if tier == "1d" {
           sell_price = buy_price * dec!(1.0385);
         }
       } else {
-        if plan.interval == "1m" {
+        if tier == "1m" {
           sell_price = buy_price * dec!(1.0085);
-        } else if plan.interval == "15m" {
+        } else if tier == "15m" {
           sell_price = buy_price * dec!(1.0105);
-        } else if plan.interval == "4h" {
+        } else if tier == "4h" {
           sell_price = buy_price * dec!(1.012);
-        } else if plan.interval == "1d" {
+        } else if tier == "1d" {
           sell_price = buy_price * dec!(1.0135);
         }
       }
       sell_price = (sell_price / tick_size).ceil() * tick_size;
     } else {
       if plan.amount > 15.0 {
-        if plan.interval == "1m" {
+        if tier == "1m" {
           sell_price = buy_price * dec!(0.9895);
-        } else if plan.interval == "15m" {
+        } else if tier == "15m" {
           sell_price = buy_price * dec!(0.9875);
-        } else if plan.interval == "4h" {
+        } else if tier == "4h" {
           sell_price = buy_price * dec!(0.9815);
-        } else if plan.interval == "1d" {
+        } else if tier == "1d" {
           sell_price = buy_price * dec!(0.9615);
         }
       } else {
-        if plan.interval == "1m" {
+        if tier == "1m" {
           sell_price = buy_price * dec!(0.9915);
-        } else if plan.interval == "15m" {
+        } else if tier == "15m" {
           sell_price = buy_price * dec!(0.9895);
-        } else if plan.interval == "4h" {
+        } else if tier == "4h" {
           sell_price = buy_price * dec!(0.988);
-        } else if plan.interval == "1d" {
+        } else if tier == "1d" {
           sell_price = buy_price * dec!(0.9865);
         }
       }
@@ -290,18 +287,12 @@ impl ScalpingRepository {
       return Err(Box::from(format!("scalping of {0:}[{position_side:}] can not buy now", plan.symbol)))
     }
 
-    let (_, quote_asset) = match SymbolsRepository::pairs(
-      ctx.clone(),
-      plan.symbol.clone(),
-    ).await {
+    let (_, quote_asset) = match exchange.pairs(&plan.symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
 
-    let (_, free, _, _, _, _) = match AccountRepository::balance(
-      ctx.clone(),
-      &quote_asset,
-    ).await {
+    let free = match exchange.balance(&quote_asset).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
@@ -310,14 +301,13 @@ impl ScalpingRepository {
       return Err(Box::from(format!("scalping of {0:}[{position_side:}] free not enough", plan.symbol)))
     }
 
-    let order_id = match OrdersRepository::submit(
-      ctx.clone(),
-      &plan.symbol[..],
-      position_side,
-      side,
-      buy_price.to_f64().unwrap(),
-      buy_quantity.to_f64().unwrap(),
-    ).await {
+    let order_id = match exchange.place(&OrderRequest {
+      symbol: plan.symbol.clone(),
+      side: plan.side,
+      is_open: true,
+      price: buy_price.to_f64().unwrap(),
+      quantity: buy_quantity.to_f64().unwrap(),
+    }).await {
       Ok(result) => result,
       Err(err) => {
         if err.is::<ApiError>() {
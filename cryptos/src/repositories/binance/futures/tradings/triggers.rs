@@ -0,0 +1,514 @@
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::common::*;
+use crate::config::binance::futures::config as Config;
+use crate::models::binance::futures::tradings::trigger::*;
+use crate::schema::binance::futures::tradings::triggers::*;
+use crate::schema::binance::futures::triggers::dsl as parent;
+use crate::repositories::binance::ApiError;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::futures::triggers::TriggersRepository as ParentRepository;
+
+// a trigger enters once the price reaches it, then rests a take order and
+// watches the stop price. status 0 waits for the entry, 1 holds the take order,
+// 2 holds the stop order, 3 is closed and 4 canceled
+#[derive(Default)]
+pub struct TriggersRepository {}
+
+impl TriggersRepository {
+  pub async fn find<T>(
+    ctx:Ctx,
+    id: T,
+  ) -> Result<Option<Trigger>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match triggers::table
+      .find(id)
+      .select(Trigger::as_select())
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn scan(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let ids = triggers::table
+      .select(triggers::id)
+      .filter(triggers::status.eq_any(vec![0, 1, 2]))
+      .load::<String>(&mut conn)?;
+    Ok(ids)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    trigger_id: String,
+    side: i32,
+    buy_price: f64,
+    sell_price: f64,
+    buy_quantity: f64,
+    sell_quantity: f64,
+    buy_order_id: i64,
+    sell_order_id: i64,
+    status: i32,
+    remark: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Trigger::new(
+      id,
+      symbol,
+      trigger_id,
+      side,
+      buy_price,
+      sell_price,
+      buy_quantity,
+      sell_quantity,
+      buy_order_id,
+      sell_order_id,
+      status,
+      0,
+      remark,
+      now,
+      now,
+    );
+    match diesel::insert_into(triggers::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    value: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = triggers::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(triggers::table.find(id))
+      .filter(triggers::version.eq(version))
+      .set(value)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn place<E, T>(ctx: Ctx, exchange: &E, trigger_id: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let trigger_id = trigger_id.as_ref();
+
+    let trigger = match ParentRepository::find(ctx.clone(), trigger_id).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("trigger of {trigger_id:} not exists"))),
+    };
+
+    if trigger.status != 1 {
+      return Ok(())
+    }
+    if trigger.expired_at < Utc::now() {
+      return Err(Box::from(format!("trigger of {trigger_id:} has been expired")))
+    }
+
+    let price = exchange.price(&trigger.symbol).await?;
+    let is_reached = match trigger.side {
+      1 => price <= trigger.price,
+      2 => price >= trigger.price,
+      _ => return Err(Box::from(format!("trigger side {} not supported", trigger.side))),
+    };
+    if !is_reached {
+      return Ok(())
+    }
+
+    let (_, quote_asset) = exchange.pairs(&trigger.symbol).await?;
+    let free = exchange.balance(&quote_asset).await?;
+    if free < Config::TRIGGERS_MIN_BINANCE {
+      return Err(Box::from(format!("trigger of {0:} free not enough", trigger.symbol)))
+    }
+
+    let filters = exchange.filters(&trigger.symbol).await?;
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
+    let entry_price = Decimal::from_f64(trigger.price).unwrap();
+    let entry_price = (entry_price / tick_size).round() * tick_size;
+    let quantity = Decimal::from_f64(trigger.capital).unwrap() / entry_price;
+    let quantity = (quantity / step_size).floor() * step_size;
+    if (quantity * entry_price).to_f64().unwrap() < filters.notional {
+      return Err(Box::from(format!("trigger of {0:} capital less than notional {1:}", trigger.symbol, filters.notional)))
+    }
+
+    // claim the trigger first so a concurrent place can not enter twice
+    if !ParentRepository::update(
+      ctx.clone(),
+      trigger.id.clone(),
+      trigger.version,
+      (
+        parent::status.eq(2),
+        parent::version.eq(trigger.version + 1),
+      ),
+    ).await? {
+      return Err(Box::from(format!("trigger of {trigger_id:} changed")))
+    }
+
+    let order_id = match exchange.place(&OrderRequest {
+      symbol: trigger.symbol.clone(),
+      side: trigger.side,
+      is_open: true,
+      price: entry_price.to_f64().unwrap(),
+      quantity: quantity.to_f64().unwrap(),
+    }).await {
+      Ok(result) => result,
+      Err(err) => {
+        if err.is::<ApiError>() {
+          let _ = ParentRepository::update(
+            ctx.clone(),
+            trigger.id.clone(),
+            trigger.version + 1,
+            (
+              parent::status.eq(1),
+              parent::remark.eq(err.to_string()),
+              parent::version.eq(trigger.version + 2),
+            ),
+          ).await;
+          return Err(err.into())
+        } else {
+          println!("error {:?}", err);
+          0
+        }
+      }
+    };
+
+    let (buy_price, sell_price, buy_order_id, sell_order_id) = if trigger.side == 1 {
+      (entry_price.to_f64().unwrap(), trigger.take_price, order_id, 0)
+    } else {
+      (trigger.take_price, entry_price.to_f64().unwrap(), 0, order_id)
+    };
+
+    Self::create(
+      ctx.clone(),
+      xid::new().to_string(),
+      trigger.symbol.clone(),
+      trigger.id.clone(),
+      trigger.side,
+      buy_price,
+      sell_price,
+      quantity.to_f64().unwrap(),
+      quantity.to_f64().unwrap(),
+      buy_order_id,
+      sell_order_id,
+      0,
+      "".to_owned(),
+    ).await?;
+
+    println!("trigger of {0:} side {1:} entry {entry_price:} {quantity:} placed", trigger.symbol, trigger.side);
+
+    Ok(())
+  }
+
+  pub async fn flush<E, T>(ctx: Ctx, exchange: &E, id: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let entity = match Self::find(ctx.clone(), id).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("trigger trading of {id:} not exists"))),
+    };
+    let trigger = match ParentRepository::find(ctx.clone(), &entity.trigger_id).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("trigger of {0:} not exists", entity.trigger_id))),
+    };
+
+    let (entry_price, entry_order_id, exit_order_id) = if entity.side == 1 {
+      (entity.buy_price, entity.buy_order_id, entity.sell_order_id)
+    } else {
+      (entity.sell_price, entity.sell_order_id, entity.buy_order_id)
+    };
+
+    match entity.status {
+      0 => {
+        let mut state = if entry_order_id > 0 {
+          exchange.query(&entity.symbol, entry_order_id).await?
+        } else {
+          OrderState {
+            order_id: 0,
+            status: "REJECTED".to_owned(),
+            price: entry_price,
+            quantity: 0.0,
+            executed_quantity: 0.0,
+            avg_price: 0.0,
+          }
+        };
+        let is_expired = trigger.expired_at <= Utc::now();
+        if state.status == "NEW" || state.status == "PARTIALLY_FILLED" {
+          if !is_expired {
+            return Ok(())
+          }
+          // the entry stops resting once the trigger expires, a partial fill still gets its exit
+          state = exchange.cancel(&entity.symbol, entry_order_id).await?;
+        }
+        if state.executed_quantity <= 0.0 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              triggers::status.eq(4),
+              triggers::remark.eq(if is_expired { "expired".to_owned() } else { state.status }),
+              triggers::version.eq(entity.version + 1),
+              triggers::updated_at.eq(Utc::now()),
+            ),
+          ).await?;
+          if !ParentRepository::update(
+            ctx.clone(),
+            trigger.id.clone(),
+            trigger.version,
+            (
+              parent::status.eq(if is_expired { 4 } else { 1 }),
+              parent::version.eq(trigger.version + 1),
+            ),
+          ).await? {
+            return Err(Box::from(format!("trigger of {0:} changed", trigger.id)))
+          }
+          return Ok(())
+        }
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: entity.side,
+          is_open: false,
+          price: trigger.take_price,
+          quantity: state.executed_quantity,
+        }).await?;
+
+        Self::close_order(ctx.clone(), &entity, order_id, state.executed_quantity, 1).await?;
+        if !ParentRepository::update(
+          ctx.clone(),
+          trigger.id.clone(),
+          trigger.version,
+          (
+            parent::take_order_id.eq(order_id),
+            parent::version.eq(trigger.version + 1),
+          ),
+        ).await? {
+          return Err(Box::from(format!("trigger of {0:} changed", trigger.id)))
+        }
+      }
+      1 => {
+        let state = exchange.query(&entity.symbol, exit_order_id).await?;
+        if state.status == "FILLED" {
+          return Self::finish(ctx.clone(), &entity, &trigger, state.avg_price, state.executed_quantity).await
+        }
+
+        let price = exchange.price(&entity.symbol).await?;
+        let is_stopped = match entity.side {
+          1 => price <= trigger.stop_price,
+          _ => price >= trigger.stop_price,
+        };
+        if !is_stopped {
+          return Ok(())
+        }
+
+        let state = exchange.cancel(&entity.symbol, exit_order_id).await?;
+        let remaining = state.quantity - state.executed_quantity;
+        if remaining <= 0.0 {
+          return Self::finish(ctx.clone(), &entity, &trigger, state.avg_price, state.executed_quantity).await
+        }
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: entity.side,
+          is_open: false,
+          price: price,
+          quantity: remaining,
+        }).await?;
+
+        Self::close_order(ctx.clone(), &entity, order_id, remaining, 2).await?;
+        // the fill of the replaced exit order is booked now, finish only sees the last one
+        if !ParentRepository::update(
+          ctx.clone(),
+          trigger.id.clone(),
+          trigger.version,
+          (
+            parent::profit.eq(trigger.profit + Self::profit(&entity, state.avg_price, state.executed_quantity)),
+            parent::stop_order_id.eq(order_id),
+            parent::version.eq(trigger.version + 1),
+          ),
+        ).await? {
+          return Err(Box::from(format!("trigger of {0:} changed", trigger.id)))
+        }
+        println!("trigger of {0:} side {1:} stopped at {price:}", entity.symbol, entity.side);
+      }
+      2 => {
+        let state = exchange.query(&entity.symbol, exit_order_id).await?;
+        if state.status == "FILLED" {
+          return Self::finish(ctx.clone(), &entity, &trigger, state.avg_price, state.executed_quantity).await
+        }
+
+        // the stop order chases the market until it fills, one the exchange dropped is placed again
+        let is_dropped = ["CANCELED", "EXPIRED", "REJECTED"].contains(&state.status.as_str());
+        if !is_dropped && entity.updated_at.timestamp() + 60 > Utc::now().timestamp() {
+          return Ok(())
+        }
+
+        let state = if is_dropped {
+          state
+        } else {
+          exchange.cancel(&entity.symbol, exit_order_id).await?
+        };
+        let remaining = state.quantity - state.executed_quantity;
+        if state.status == "FILLED" || remaining <= 0.0 {
+          return Self::finish(ctx.clone(), &entity, &trigger, state.avg_price, state.executed_quantity).await
+        }
+
+        let filters = exchange.filters(&entity.symbol).await?;
+        let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+        let price = Decimal::from_f64(exchange.price(&entity.symbol).await?).unwrap();
+        let price = match entity.side {
+          1 => (price * dec!(0.995) / tick_size).floor() * tick_size,
+          _ => (price * dec!(1.005) / tick_size).ceil() * tick_size,
+        };
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: entity.side,
+          is_open: false,
+          price: price.to_f64().unwrap(),
+          quantity: remaining,
+        }).await?;
+
+        Self::close_order(ctx.clone(), &entity, order_id, remaining, 2).await?;
+        if !ParentRepository::update(
+          ctx.clone(),
+          trigger.id.clone(),
+          trigger.version,
+          (
+            parent::profit.eq(trigger.profit + Self::profit(&entity, state.avg_price, state.executed_quantity)),
+            parent::stop_order_id.eq(order_id),
+            parent::version.eq(trigger.version + 1),
+          ),
+        ).await? {
+          return Err(Box::from(format!("trigger of {0:} changed", trigger.id)))
+        }
+        println!("trigger of {0:} side {1:} stop moved to {price:}", entity.symbol, entity.side);
+      }
+      _ => (),
+    }
+
+    Ok(())
+  }
+
+  async fn close_order(
+    ctx: Ctx,
+    entity: &Trigger,
+    order_id: i64,
+    quantity: f64,
+    status: i32,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    if entity.side == 1 {
+      Self::update(
+        ctx.clone(),
+        entity.id.clone(),
+        entity.version,
+        (
+          triggers::sell_order_id.eq(order_id),
+          triggers::sell_quantity.eq(quantity),
+          triggers::status.eq(status),
+          triggers::version.eq(entity.version + 1),
+          triggers::updated_at.eq(Utc::now()),
+        ),
+      ).await
+    } else {
+      Self::update(
+        ctx.clone(),
+        entity.id.clone(),
+        entity.version,
+        (
+          triggers::buy_order_id.eq(order_id),
+          triggers::buy_quantity.eq(quantity),
+          triggers::status.eq(status),
+          triggers::version.eq(entity.version + 1),
+          triggers::updated_at.eq(Utc::now()),
+        ),
+      ).await
+    }
+  }
+
+  async fn finish(
+    ctx: Ctx,
+    entity: &Trigger,
+    trigger: &crate::models::binance::futures::trigger::Trigger,
+    price: f64,
+    quantity: f64,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    let profit = Self::profit(entity, price, quantity);
+
+    let (buy_price, sell_price) = if entity.side == 1 { (entity.buy_price, price) } else { (price, entity.sell_price) };
+    Self::update(
+      ctx.clone(),
+      entity.id.clone(),
+      entity.version,
+      (
+        triggers::buy_price.eq(buy_price),
+        triggers::sell_price.eq(sell_price),
+        triggers::status.eq(3),
+        triggers::version.eq(entity.version + 1),
+        triggers::updated_at.eq(Utc::now()),
+      ),
+    ).await?;
+    if !ParentRepository::update(
+      ctx.clone(),
+      trigger.id.clone(),
+      trigger.version,
+      (
+        parent::profit.eq(trigger.profit + profit),
+        parent::status.eq(3),
+        parent::version.eq(trigger.version + 1),
+      ),
+    ).await? {
+      return Err(Box::from(format!("trigger of {0:} changed", trigger.id)))
+    }
+
+    println!("trigger of {0:} side {1:} closed at {price:} profit {profit:}", entity.symbol, entity.side);
+    Ok(())
+  }
+
+  fn profit(entity: &Trigger, price: f64, quantity: f64) -> f64 {
+    if quantity <= 0.0 {
+      return 0.0
+    }
+    let entry_price = if entity.side == 1 { entity.buy_price } else { entity.sell_price };
+    match entity.side {
+      1 => (price - entry_price) * quantity,
+      _ => (entry_price - price) * quantity,
+    }
+  }
+}
\ No newline at end of file
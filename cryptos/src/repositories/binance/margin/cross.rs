@@ -1,5 +1,7 @@
 pub mod analysis;
 pub mod account;
+pub mod exchange;
+pub mod interests;
 pub mod orders;
 pub mod positions;
 pub mod scalping;
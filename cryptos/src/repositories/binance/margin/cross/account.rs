@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::config::binance::margin::cross::config as Config;
+
+pub struct AccountRepository {}
+
+#[derive(Deserialize)]
+struct AccountInfo {
+  #[serde(alias = "marginLevel", deserialize_with = "to_f64")]
+  margin_level: f64,
+  #[serde(alias = "totalAssetOfBtc", deserialize_with = "to_f64")]
+  total_asset: f64,
+  #[serde(alias = "totalLiabilityOfBtc", deserialize_with = "to_f64")]
+  total_liability: f64,
+  #[serde(alias = "totalNetAssetOfBtc", deserialize_with = "to_f64")]
+  net_asset: f64,
+  #[serde(alias = "userAssets")]
+  user_assets: Vec<Balance>,
+}
+
+#[derive(Deserialize)]
+struct Balance {
+  asset: String,
+  #[serde(deserialize_with = "to_f64")]
+  free: f64,
+  #[serde(deserialize_with = "to_f64")]
+  locked: f64,
+  #[serde(deserialize_with = "to_f64")]
+  borrowed: f64,
+  #[serde(deserialize_with = "to_f64")]
+  interest: f64,
+  #[serde(alias = "netAsset", deserialize_with = "to_f64")]
+  net_asset: f64,
+}
+
+#[derive(Deserialize)]
+struct MaxBorrowable {
+  #[serde(deserialize_with = "to_f64")]
+  amount: f64,
+  #[serde(alias = "borrowLimit", deserialize_with = "to_f64")]
+  borrow_limit: f64,
+}
+
+#[derive(Deserialize)]
+struct Transaction {
+  #[serde(alias = "tranId")]
+  tran_id: i64,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+impl AccountRepository {
+  pub async fn balance<T>(ctx: Ctx, asset: T) -> Result<(f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let asset = asset.as_ref();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_BALANCE, asset);
+    let fields = vec!["free", "locked", "borrowed", "interest"];
+    match redis::cmd("HMGET")
+      .arg(&redis_key)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((Some(free), Some(locked), Some(borrowed), Some(interest))) => Ok((free, locked, borrowed, interest)),
+      Ok(_) => return Err(Box::from(format!("balance of {asset:} not exists"))),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  // margin level is total asset over total liability, it reads 999 without any loan
+  pub async fn level(ctx: Ctx) -> Result<(f64, f64, f64), Box<dyn std::error::Error>> {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let fields = vec!["margin_level", "total_asset", "total_liability"];
+    match redis::cmd("HMGET")
+      .arg(Config::REDIS_KEY_ACCOUNT)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((Some(margin_level), Some(total_asset), Some(total_liability))) => Ok((margin_level, total_asset, total_liability)),
+      Ok(_) => return Err(Box::from("margin level not exists")),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  pub async fn is_safe(ctx: Ctx) -> Result<bool, Box<dyn std::error::Error>> {
+    let (margin_level, _, total_liability) = Self::level(ctx.clone()).await?;
+    Ok(total_liability == 0.0 || margin_level >= Config::MARGIN_LEVEL_WARNING)
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let account_info: AccountInfo = client.get("/sapi/v1/margin/account", &HashMap::new(), Security::Account, 10).await?;
+    println!("account_info {}", account_info.user_assets.len());
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let mut currencies: Vec<String> = Vec::new();
+    let last_currencies: Vec<String> = rdb.smembers(Config::REDIS_KEY_CURRENCIES).await.unwrap();
+
+    let mut pipe = redis::pipe();
+    pipe.hset_multiple(
+      Config::REDIS_KEY_ACCOUNT,
+      &[
+        ("margin_level", account_info.margin_level.to_string()),
+        ("total_asset", account_info.total_asset.to_string()),
+        ("total_liability", account_info.total_liability.to_string()),
+        ("net_asset", account_info.net_asset.to_string()),
+      ],
+    );
+    account_info.user_assets.iter().for_each(|coin| {
+      if coin.free <= 0.0 && coin.locked <= 0.0 && coin.borrowed <= 0.0 {
+        return;
+      }
+      pipe.hset_multiple(
+        format!("{}:{}", Config::REDIS_KEY_BALANCE, coin.asset),
+        &[
+          ("free", coin.free.to_string()),
+          ("locked", coin.locked.to_string()),
+          ("borrowed", coin.borrowed.to_string()),
+          ("interest", coin.interest.to_string()),
+          ("net_asset", coin.net_asset.to_string()),
+        ],
+      );
+      pipe.sadd(Config::REDIS_KEY_CURRENCIES, &coin.asset);
+      currencies.push(coin.asset.clone());
+      println!("coin balance {} {} {} {} {}", coin.asset, coin.free, coin.locked, coin.borrowed, coin.interest);
+    });
+
+    last_currencies.iter().for_each(|last_asset| {
+      if currencies.iter().any(|asset| asset == last_asset) {
+        return;
+      }
+      pipe.srem(Config::REDIS_KEY_CURRENCIES, &last_asset);
+      pipe.del(format!("{}:{}", Config::REDIS_KEY_BALANCE, last_asset));
+      println!("coin balance remove {}", last_asset);
+    });
+
+    () = pipe.query_async(&mut rdb).await?;
+
+    println!("account flush margin level {}", account_info.margin_level);
+    Ok(())
+  }
+
+  pub async fn max_borrowable<T>(ctx: Ctx, asset: T) -> Result<(f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let asset = asset.as_ref();
+
+    let mut params = HashMap::new();
+    params.insert("asset", asset);
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let result: MaxBorrowable = client.get("/sapi/v1/margin/maxBorrowable", &params, Security::Account, 50).await?;
+
+    Ok((result.amount, result.borrow_limit))
+  }
+
+  pub async fn borrow<T>(ctx: Ctx, asset: T, amount: f64) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    Self::transfer(ctx, asset.as_ref(), amount, "BORROW").await
+  }
+
+  pub async fn repay<T>(ctx: Ctx, asset: T, amount: f64) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    Self::transfer(ctx, asset.as_ref(), amount, "REPAY").await
+  }
+
+  async fn transfer(ctx: Ctx, asset: &str, amount: f64, kind: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let amount = amount.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("asset", asset);
+    params.insert("isIsolated", "FALSE");
+    params.insert("amount", &amount);
+    params.insert("type", kind);
+
+    // borrow-repay is weighted on the uid, it barely touches the ip weight
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let result: Transaction = client.post("/sapi/v1/margin/borrow-repay", &params, Security::Trade, 1).await?;
+    println!("binance margin cross {kind:} {asset:} {amount:} success {}", result.tran_id);
+
+    Ok(result.tran_id)
+  }
+}
\ No newline at end of file
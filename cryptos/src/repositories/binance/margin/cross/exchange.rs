@@ -0,0 +1,100 @@
+use crate::common::*;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::spot::exchange::*;
+use crate::repositories::binance::margin::cross::account::*;
+use crate::repositories::binance::margin::cross::orders::*;
+
+// margin trades the spot books, market data comes from spot and only the
+// account and orders go through the margin endpoints
+pub struct CrossExchange {
+  ctx: Ctx,
+  spot: SpotExchange,
+}
+
+impl CrossExchange {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx.clone(),
+      spot: SpotExchange::new(ctx),
+    }
+  }
+}
+
+impl Exchange for CrossExchange {
+  // indicators are keyed by name, margin reads the spot ones
+  fn name(&self) -> &'static str {
+    "binance:spot"
+  }
+
+  async fn klines(&self, symbol: &str, interval: &str, endtime: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>> {
+    self.spot.klines(symbol, interval, endtime, limit).await
+  }
+
+  async fn price(&self, symbol: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    self.spot.price(symbol).await
+  }
+
+  async fn depth(&self, symbol: &str, limit: i64) -> Result<Book, Box<dyn std::error::Error>> {
+    self.spot.depth(symbol, limit).await
+  }
+
+  async fn filters(&self, symbol: &str) -> Result<SymbolFilters, Box<dyn std::error::Error>> {
+    self.spot.filters(symbol).await
+  }
+
+  async fn pairs(&self, symbol: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+    self.spot.pairs(symbol).await
+  }
+
+  async fn balance(&self, asset: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    let (free, _, _, _) = AccountRepository::balance(self.ctx.clone(), asset).await?;
+    Ok(free)
+  }
+
+  // longs borrow the quote to buy, shorts borrow the base to sell,
+  // closing orders repay whichever loan the fill covers
+  async fn place(&self, order: &OrderRequest) -> Result<i64, Box<dyn std::error::Error>> {
+    let (side, side_effect_type) = match (order.side, order.is_open) {
+      (1, true) => ("BUY", "MARGIN_BUY"),
+      (1, false) => ("SELL", "AUTO_REPAY"),
+      (2, true) => ("SELL", "MARGIN_BUY"),
+      (2, false) => ("BUY", "AUTO_REPAY"),
+      _ => return Err(Box::from(format!("order side {} not supported", order.side))),
+    };
+    if order.is_open && !AccountRepository::is_safe(self.ctx.clone()).await? {
+      return Err(Box::from("margin level too low to open"))
+    }
+    OrdersRepository::submit(
+      self.ctx.clone(),
+      &order.symbol[..],
+      side,
+      side_effect_type,
+      order.price,
+      order.quantity,
+    ).await
+  }
+
+  async fn cancel(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::cancel(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: quantity,
+      executed_quantity: executed_quantity,
+      avg_price: avg_price,
+    })
+  }
+
+  async fn query(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::query(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: quantity,
+      executed_quantity: executed_quantity,
+      avg_price: avg_price,
+    })
+  }
+}
\ No newline at end of file
@@ -0,0 +1,519 @@
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use redis::AsyncCommands;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::common::*;
+use crate::config::binance::margin::cross::config as Config;
+use crate::models::binance::margin::cross::tradings::scalping::*;
+use crate::schema::binance::margin::cross::tradings::scalping::*;
+use crate::repositories::binance::ApiError;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::spot::plans::*;
+use crate::repositories::binance::margin::cross::positions::*;
+use crate::repositories::binance::margin::cross::scalping::ScalpingRepository as ParentRepositoy;
+
+// plans come from the spot strategies, margin adds the short side. buy and sell
+// columns follow the order side, a long enters with the buy and a short with the sell.
+// status 0 waits for the entry, 1 waits for the exit, 2 is closed and 3 canceled
+#[derive(Default)]
+pub struct ScalpingRepository {}
+
+impl ScalpingRepository {
+  pub async fn find<T>(
+    ctx:Ctx,
+    id: T,
+  ) -> Result<Option<Scalping>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match scalping::table
+      .find(id)
+      .select(Scalping::as_select())
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn scan(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let ids = scalping::table
+      .select(scalping::id)
+      .filter(scalping::status.eq_any(vec![0, 1]))
+      .load::<String>(&mut conn)?;
+    Ok(ids)
+  }
+
+  pub async fn is_placed<T>(ctx: Ctx, plan_id: T) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let plan_id = plan_id.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let count: i64 = scalping::table
+      .filter(scalping::plan_id.eq(plan_id))
+      .count()
+      .get_result(&mut conn)?;
+    Ok(count > 0)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    scalping_id: String,
+    plan_id: String,
+    side: i32,
+    buy_price: f64,
+    sell_price: f64,
+    buy_quantity: f64,
+    sell_quantity: f64,
+    buy_order_id: i64,
+    sell_order_id: i64,
+    status: i32,
+    remark: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Scalping::new(
+      id,
+      symbol,
+      scalping_id,
+      plan_id,
+      side,
+      buy_price,
+      sell_price,
+      buy_quantity,
+      sell_quantity,
+      buy_order_id,
+      sell_order_id,
+      status,
+      0,
+      remark,
+      now,
+      now,
+    );
+    match diesel::insert_into(scalping::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    value: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = scalping::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(scalping::table.find(id))
+      .filter(scalping::version.eq(version))
+      .set(value)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn place<E, T>(ctx: Ctx, exchange: &E, plan_id: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let plan_id = plan_id.as_ref();
+
+    if Self::is_placed(ctx.clone(), plan_id).await? {
+      return Ok(())
+    }
+
+    let plan = match PlansRepository::find(ctx.clone(), plan_id).await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("plan of {plan_id:} not exists"))),
+      Err(err) => return Err(err.into()),
+    };
+
+    if plan.side != 1 && plan.side != 2 {
+      return Err(Box::from(format!("plan side {} not supported", plan.side)))
+    }
+
+    let timestamp = Utc::now().timestamp();
+
+    let timeframe = Interval::parse(&plan.interval)?;
+    if plan.created_at.timestamp() < timestamp - timeframe.expiry() {
+      return Err(Box::from(format!("plan has been expired")))
+    }
+
+    let tier = match timeframe {
+      Interval::Minute1 | Interval::Minute3 | Interval::Minute5 => "1m",
+      Interval::Minute15 | Interval::Minute30 | Interval::Hour1 | Interval::Hour2 => "15m",
+      Interval::Hour4 | Interval::Hour6 | Interval::Hour8 | Interval::Hour12 => "4h",
+      _ => "1d",
+    };
+
+    let scalping = match ParentRepositoy::get(ctx.clone(), plan.symbol.clone()).await {
+      Ok(Some(result)) => result,
+      Ok(None) => return Err(Box::from(format!("scalping of {0:} not exists", plan.symbol))),
+      Err(err) => return Err(err.into()),
+    };
+
+    if plan.side == 1 && plan.price > scalping.price {
+      return Err(Box::from(format!("plan of {0:} higher than scalping price {1:}", plan.symbol, scalping.price)))
+    }
+    if plan.side == 2 && plan.price < scalping.price {
+      return Err(Box::from(format!("plan of {0:} lower than scalping price {1:}", plan.symbol, scalping.price)))
+    }
+
+    let price = match exchange.price(&plan.symbol).await {
+      Ok(price) => price,
+      Err(err) => return Err(err.into()),
+    };
+    let price = Decimal::from_f64(price).unwrap();
+
+    let filters = match exchange.filters(&scalping.symbol).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
+    let notional = dec!(10.0);
+
+    let mut entry_price = Decimal::from_f64(plan.price).unwrap();
+    if plan.side == 1 {
+      if entry_price > price {
+        entry_price = price;
+      }
+      entry_price = (entry_price / tick_size).floor() * tick_size;
+      if price > entry_price {
+        return Err(Box::from(format!("plan of {0:} higher than buy price {entry_price:}", plan.symbol)))
+      }
+    } else {
+      if entry_price < price {
+        entry_price = price;
+      }
+      entry_price = (entry_price / tick_size).ceil() * tick_size;
+      if price < entry_price {
+        return Err(Box::from(format!("plan of {0:} lower than sell price {entry_price:}", plan.symbol)))
+      }
+    }
+
+    let position_price = match PositionsRepository::get(ctx.clone(), scalping.symbol.clone(), plan.side).await {
+      Ok(Some(position)) => Decimal::from_f64(position.entry_price).unwrap(),
+      Ok(None) => dec!(0.0),
+      Err(err) => return Err(err.into()),
+    };
+
+    if position_price > dec!(0.0) {
+      if plan.side == 1 && price > position_price {
+        return Err(Box::from(format!("plan of {0:} higher than entry price {position_price:}", scalping.symbol)))
+      }
+      if plan.side == 2 && price < position_price {
+        return Err(Box::from(format!("plan of {0:} lower than entry price {position_price:}", scalping.symbol)))
+      }
+    }
+
+    let ratio = if plan.amount > 15.0 {
+      match tier {
+        "1m" => dec!(1.0105),
+        "15m" => dec!(1.0125),
+        "4h" => dec!(1.0185),
+        _ => dec!(1.0385),
+      }
+    } else {
+      match tier {
+        "1m" => dec!(1.0085),
+        "15m" => dec!(1.0105),
+        "4h" => dec!(1.012),
+        _ => dec!(1.0135),
+      }
+    };
+    let exit_price = if plan.side == 1 {
+      (entry_price * ratio / tick_size).ceil() * tick_size
+    } else {
+      (entry_price / ratio / tick_size).floor() * tick_size
+    };
+
+    let quantity = notional / entry_price;
+    let quantity = (quantity / step_size).ceil() * step_size;
+
+    if !Self::can_open(
+      ctx.clone(),
+      scalping.id.clone(),
+      scalping.symbol.clone(),
+      plan.side,
+      entry_price.to_f64().unwrap(),
+    ).await {
+      return Err(Box::from(format!("scalping of {0:} can not open now", plan.symbol)))
+    }
+
+    let (_, quote_asset) = match exchange.pairs(&plan.symbol).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    let free = match exchange.balance(&quote_asset).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    if free < Config::SCALPING_MIN_BINANCE {
+      return Err(Box::from(format!("scalping of {0:} free not enough", plan.symbol)))
+    }
+
+    let order_id = match exchange.place(&OrderRequest {
+      symbol: plan.symbol.clone(),
+      side: plan.side,
+      is_open: true,
+      price: entry_price.to_f64().unwrap(),
+      quantity: quantity.to_f64().unwrap(),
+    }).await {
+      Ok(result) => result,
+      Err(err) => {
+        if err.is::<ApiError>() {
+          return Err(err.into())
+        } else {
+          println!("error {:?}", err);
+          0
+        }
+      }
+    };
+
+    let (buy_price, sell_price, buy_order_id, sell_order_id) = if plan.side == 1 {
+      (entry_price, exit_price, order_id, 0)
+    } else {
+      (exit_price, entry_price, 0, order_id)
+    };
+
+    let id = xid::new().to_string();
+    let success = match Self::create(
+      ctx.clone(),
+      id,
+      plan.symbol.clone(),
+      scalping.id,
+      plan_id.to_owned(),
+      plan.side,
+      buy_price.to_f64().unwrap(),
+      sell_price.to_f64().unwrap(),
+      quantity.to_f64().unwrap(),
+      quantity.to_f64().unwrap(),
+      buy_order_id,
+      sell_order_id,
+      0,
+      "".to_owned(),
+    ).await {
+      Ok(result) => result,
+      Err(err) => return Err(err.into()),
+    };
+
+    if success {
+      println!("scalping of {0:} side {1:} place {entry_price:} {quantity:} success", plan.symbol, plan.side);
+    }
+
+    Ok(())
+  }
+
+  pub async fn flush<E, T>(ctx: Ctx, exchange: &E, id: T) -> Result<(), Box<dyn std::error::Error>>
+  where
+    E: Exchange,
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let entity = match Self::find(ctx.clone(), id).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("scalping trading of {id:} not exists"))),
+    };
+
+    let (entry_order_id, exit_order_id, exit_price) = if entity.side == 1 {
+      (entity.buy_order_id, entity.sell_order_id, entity.sell_price)
+    } else {
+      (entity.sell_order_id, entity.buy_order_id, entity.buy_price)
+    };
+
+    match entity.status {
+      0 => {
+        if entry_order_id == 0 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              scalping::status.eq(3),
+              scalping::remark.eq("entry not placed"),
+              scalping::version.eq(entity.version + 1),
+            ),
+          ).await?;
+          return Ok(())
+        }
+        let state = exchange.query(&entity.symbol, entry_order_id).await?;
+        if state.status == "NEW" || state.status == "PARTIALLY_FILLED" {
+          return Ok(())
+        }
+        if state.executed_quantity <= 0.0 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              scalping::status.eq(3),
+              scalping::remark.eq(state.status),
+              scalping::version.eq(entity.version + 1),
+            ),
+          ).await?;
+          return Ok(())
+        }
+
+        PositionsRepository::fill(ctx.clone(), &entity.symbol, entity.side, state.price, state.executed_quantity).await?;
+
+        let order_id = exchange.place(&OrderRequest {
+          symbol: entity.symbol.clone(),
+          side: entity.side,
+          is_open: false,
+          price: exit_price,
+          quantity: state.executed_quantity,
+        }).await?;
+
+        let success = if entity.side == 1 {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              scalping::buy_quantity.eq(state.executed_quantity),
+              scalping::sell_quantity.eq(state.executed_quantity),
+              scalping::sell_order_id.eq(order_id),
+              scalping::status.eq(1),
+              scalping::version.eq(entity.version + 1),
+            ),
+          ).await?
+        } else {
+          Self::update(
+            ctx.clone(),
+            entity.id,
+            entity.version,
+            (
+              scalping::buy_quantity.eq(state.executed_quantity),
+              scalping::sell_quantity.eq(state.executed_quantity),
+              scalping::buy_order_id.eq(order_id),
+              scalping::status.eq(1),
+              scalping::version.eq(entity.version + 1),
+            ),
+          ).await?
+        };
+        if success {
+          println!("scalping of {0:} exit {exit_price:} placed {order_id:}", entity.symbol);
+        }
+      }
+      1 => {
+        let state = exchange.query(&entity.symbol, exit_order_id).await?;
+        if state.status != "FILLED" {
+          return Ok(())
+        }
+        PositionsRepository::reduce(ctx.clone(), &entity.symbol, entity.side, state.executed_quantity).await?;
+        Self::update(
+          ctx.clone(),
+          entity.id,
+          entity.version,
+          (
+            scalping::status.eq(2),
+            scalping::version.eq(entity.version + 1),
+            scalping::updated_at.eq(Utc::now()),
+          ),
+        ).await?;
+        println!("scalping of {0:} side {1:} closed at {2:}", entity.symbol, entity.side, state.price);
+      }
+      _ => (),
+    }
+
+    Ok(())
+  }
+
+  pub async fn can_open<T>(
+    ctx: Ctx,
+    scalping_id: T,
+    symbol: T,
+    side: i32,
+    price: f64,
+  ) -> bool
+  where
+    T: AsRef<str>
+  {
+    let scalping_id = scalping_id.as_ref();
+    let symbol = symbol.as_ref();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}:{}", Config::REDIS_KEY_TRADINGS_LAST_PRICE, symbol, side);
+    let mut cached_price: f64 = match rdb.get(&redis_key).await {
+      Ok(Some(result)) => result,
+      _ => 0.0,
+    };
+
+    // every new entry has to improve on the last one by about four percent
+    let is_near = |last: f64| {
+      if side == 1 {
+        price >= last * 0.9615
+      } else {
+        price <= last * 1.04
+      }
+    };
+
+    if cached_price > 0.0 && is_near(cached_price) {
+      return false
+    }
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let query = scalping::table
+      .select((scalping::buy_price, scalping::sell_price, scalping::status))
+      .filter(scalping::scalping_id.eq(scalping_id))
+      .filter(scalping::side.eq(side))
+      .filter(scalping::status.eq_any(vec![0, 1]));
+    let result = if side == 1 {
+      query.order(scalping::buy_price.asc()).first::<(f64, f64, i32)>(&mut conn)
+    } else {
+      query.order(scalping::sell_price.desc()).first::<(f64, f64, i32)>(&mut conn)
+    };
+    let (buy_price, sell_price, status) = match result {
+      Ok(result) => result,
+      Err(_) => return true,
+    };
+
+    if status == 0 {
+      return false
+    }
+
+    let entry_price = if side == 1 { buy_price } else { sell_price };
+    if is_near(entry_price) {
+      return false
+    }
+
+    let is_better = if side == 1 { cached_price > entry_price } else { cached_price < entry_price };
+    if cached_price == 0.0 || is_better {
+      cached_price = entry_price;
+      let _: () = rdb.set(&redis_key, cached_price).await.unwrap();
+    }
+
+    true
+  }
+}
\ No newline at end of file
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::config::binance::margin::isolated::config as Config;
+
+pub struct AccountRepository {}
+
+#[derive(Deserialize)]
+struct AccountInfo {
+  assets: Vec<PairInfo>,
+}
+
+#[derive(Deserialize)]
+struct PairInfo {
+  symbol: String,
+  #[serde(alias = "baseAsset")]
+  base_asset: Balance,
+  #[serde(alias = "quoteAsset")]
+  quote_asset: Balance,
+  #[serde(alias = "marginLevel", deserialize_with = "to_f64")]
+  margin_level: f64,
+  #[serde(alias = "indexPrice", deserialize_with = "to_f64")]
+  index_price: f64,
+  #[serde(alias = "liquidatePrice", deserialize_with = "to_f64")]
+  liquidate_price: f64,
+}
+
+#[derive(Deserialize)]
+struct Balance {
+  asset: String,
+  #[serde(deserialize_with = "to_f64")]
+  free: f64,
+  #[serde(deserialize_with = "to_f64")]
+  locked: f64,
+  #[serde(deserialize_with = "to_f64")]
+  borrowed: f64,
+  #[serde(deserialize_with = "to_f64")]
+  interest: f64,
+  #[serde(alias = "netAsset", deserialize_with = "to_f64")]
+  net_asset: f64,
+}
+
+#[derive(Deserialize)]
+struct MaxBorrowable {
+  #[serde(deserialize_with = "to_f64")]
+  amount: f64,
+  #[serde(alias = "borrowLimit", deserialize_with = "to_f64")]
+  borrow_limit: f64,
+}
+
+#[derive(Deserialize)]
+struct Transaction {
+  #[serde(alias = "tranId")]
+  tran_id: i64,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+// every isolated pair is its own account, balances are kept per symbol and asset
+impl AccountRepository {
+  pub async fn balance<T>(ctx: Ctx, symbol: T, asset: T) -> Result<(f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let asset = asset.as_ref();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let redis_key = format!("{}:{}:{}", Config::REDIS_KEY_BALANCE, symbol, asset);
+    let fields = vec!["free", "locked", "borrowed", "interest"];
+    match redis::cmd("HMGET")
+      .arg(&redis_key)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((Some(free), Some(locked), Some(borrowed), Some(interest))) => Ok((free, locked, borrowed, interest)),
+      Ok(_) => return Err(Box::from(format!("balance of {symbol:} {asset:} not exists"))),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  pub async fn symbols(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let symbols: Vec<String> = rdb.smembers(Config::REDIS_KEY_SYMBOLS).await?;
+    Ok(symbols)
+  }
+
+  // liability is the loan of both assets valued in the quote asset
+  pub async fn level<T>(ctx: Ctx, symbol: T) -> Result<(f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_ACCOUNT, symbol);
+    let fields = vec!["margin_level", "liquidate_price", "liability"];
+    match redis::cmd("HMGET")
+      .arg(&redis_key)
+      .arg(&fields)
+      .query_async(&mut rdb)
+      .await
+    {
+      Ok((Some(margin_level), Some(liquidate_price), Some(liability))) => Ok((margin_level, liquidate_price, liability)),
+      Ok(_) => return Err(Box::from(format!("margin level of {symbol:} not exists"))),
+      Err(err) => return Err(err.into()),
+    }
+  }
+
+  pub async fn is_safe<T>(ctx: Ctx, symbol: T) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let (margin_level, _, liability) = Self::level(ctx.clone(), symbol).await?;
+    Ok(liability == 0.0 || margin_level >= Config::MARGIN_LEVEL_WARNING)
+  }
+
+  pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let account_info: AccountInfo = client.get("/sapi/v1/margin/isolated/account", &HashMap::new(), Security::Account, 10).await?;
+    println!("account_info {}", account_info.assets.len());
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+
+    let mut symbols: Vec<String> = Vec::new();
+    let last_symbols: Vec<String> = rdb.smembers(Config::REDIS_KEY_SYMBOLS).await.unwrap();
+
+    let mut pipe = redis::pipe();
+    account_info.assets.iter().for_each(|pair| {
+      let liability = (pair.base_asset.borrowed + pair.base_asset.interest) * pair.index_price
+        + pair.quote_asset.borrowed + pair.quote_asset.interest;
+      pipe.hset_multiple(
+        format!("{}:{}", Config::REDIS_KEY_ACCOUNT, pair.symbol),
+        &[
+          ("margin_level", pair.margin_level.to_string()),
+          ("liquidate_price", pair.liquidate_price.to_string()),
+          ("liability", liability.to_string()),
+        ],
+      );
+      for coin in [&pair.base_asset, &pair.quote_asset] {
+        pipe.hset_multiple(
+          format!("{}:{}:{}", Config::REDIS_KEY_BALANCE, pair.symbol, coin.asset),
+          &[
+            ("free", coin.free.to_string()),
+            ("locked", coin.locked.to_string()),
+            ("borrowed", coin.borrowed.to_string()),
+            ("interest", coin.interest.to_string()),
+            ("net_asset", coin.net_asset.to_string()),
+          ],
+        );
+        println!("coin balance {} {} {} {} {} {}", pair.symbol, coin.asset, coin.free, coin.locked, coin.borrowed, coin.interest);
+      }
+      pipe.sadd(Config::REDIS_KEY_SYMBOLS, &pair.symbol);
+      symbols.push(pair.symbol.clone());
+    });
+
+    last_symbols.iter().for_each(|last_symbol| {
+      if symbols.iter().any(|symbol| symbol == last_symbol) {
+        return;
+      }
+      pipe.srem(Config::REDIS_KEY_SYMBOLS, &last_symbol);
+      pipe.del(format!("{}:{}", Config::REDIS_KEY_ACCOUNT, last_symbol));
+      println!("isolated pair remove {}", last_symbol);
+    });
+
+    () = pipe.query_async(&mut rdb).await?;
+
+    println!("account flush");
+    Ok(())
+  }
+
+  pub async fn max_borrowable<T>(ctx: Ctx, symbol: T, asset: T) -> Result<(f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let asset = asset.as_ref();
+
+    let mut params = HashMap::new();
+    params.insert("asset", asset);
+    params.insert("isolatedSymbol", symbol);
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let result: MaxBorrowable = client.get("/sapi/v1/margin/maxBorrowable", &params, Security::Account, 50).await?;
+
+    Ok((result.amount, result.borrow_limit))
+  }
+
+  pub async fn borrow<T>(ctx: Ctx, symbol: T, asset: T, amount: f64) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    Self::transfer(ctx, symbol.as_ref(), asset.as_ref(), amount, "BORROW").await
+  }
+
+  pub async fn repay<T>(ctx: Ctx, symbol: T, asset: T, amount: f64) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    Self::transfer(ctx, symbol.as_ref(), asset.as_ref(), amount, "REPAY").await
+  }
+
+  async fn transfer(ctx: Ctx, symbol: &str, asset: &str, amount: f64, kind: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let amount = amount.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("asset", asset);
+    params.insert("isIsolated", "TRUE");
+    params.insert("symbol", symbol);
+    params.insert("amount", &amount);
+    params.insert("type", kind);
+
+    // borrow-repay is weighted on the uid, it barely touches the ip weight
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let result: Transaction = client.post("/sapi/v1/margin/borrow-repay", &params, Security::Trade, 1).await?;
+    println!("binance margin isolated {symbol:} {kind:} {asset:} {amount:} success {}", result.tran_id);
+
+    Ok(result.tran_id)
+  }
+}
\ No newline at end of file
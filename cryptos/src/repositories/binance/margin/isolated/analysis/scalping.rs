@@ -0,0 +1,126 @@
+use chrono::{prelude::Utc, NaiveDate, TimeZone};
+use diesel::prelude::*;
+
+use crate::common::*;
+use crate::models::binance::margin::isolated::analysis::tradings::scalping::*;
+use crate::schema::binance::margin::isolated::analysis::tradings::scalping::*;
+use crate::schema::binance::margin::isolated::tradings::scalping::dsl as tradings;
+
+#[derive(Default)]
+pub struct ScalpingRepository {}
+
+impl ScalpingRepository {
+  pub async fn count(ctx: Ctx) -> Result<i64, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let count = scalping::table
+      .count()
+      .get_result(&mut conn)?;
+    Ok(count)
+  }
+
+  pub async fn listings(ctx: Ctx, current: i64, page_size: i64) -> Result<Vec<(String, i32, NaiveDate, f64, f64, f64, f64, f64, f64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let analysis = scalping::table
+      .select((
+        scalping::id,
+        scalping::side,
+        scalping::day,
+        scalping::buys_count,
+        scalping::sells_count,
+        scalping::buys_amount,
+        scalping::sells_amount,
+        scalping::profit,
+        scalping::additive_profit,
+      ))
+      .order(scalping::day.desc())
+      .offset((current - 1) * page_size)
+      .limit(page_size)
+      .load::<(String, i32, NaiveDate, f64, f64, f64, f64, f64, f64)>(&mut conn)?;
+    Ok(analysis)
+  }
+
+  // tradings closed during the day are summed per side, the additive profit carries the previous days
+  pub async fn flush(ctx: Ctx, day: NaiveDate) -> Result<(), Box<dyn std::error::Error>> {
+    let start_time = Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap());
+    let end_time = start_time + chrono::Duration::days(1);
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    for side in [1, 2] {
+      let items = tradings::scalping
+        .select((tradings::buy_price, tradings::sell_price, tradings::buy_quantity, tradings::sell_quantity))
+        .filter(tradings::side.eq(side))
+        .filter(tradings::status.eq(2))
+        .filter(tradings::updated_at.ge(start_time))
+        .filter(tradings::updated_at.lt(end_time))
+        .load::<(f64, f64, f64, f64)>(&mut conn)?;
+
+      let buys_count = items.len() as f64;
+      let sells_count = items.len() as f64;
+      let buys_amount = items.iter().map(|item| item.0 * item.2).sum::<f64>();
+      let sells_amount = items.iter().map(|item| item.1 * item.3).sum::<f64>();
+      let profit = sells_amount - buys_amount;
+
+      let last_profit = match scalping::table
+        .select(scalping::additive_profit)
+        .filter(scalping::side.eq(side))
+        .filter(scalping::day.lt(day))
+        .order(scalping::day.desc())
+        .first::<f64>(&mut conn) {
+        Ok(result) => result,
+        Err(diesel::result::Error::NotFound) => 0.0,
+        Err(err) => return Err(err.into()),
+      };
+
+      let now = Utc::now();
+      let id = match scalping::table
+        .select(scalping::id)
+        .filter(scalping::side.eq(side))
+        .filter(scalping::day.eq(day))
+        .first::<String>(&mut conn) {
+        Ok(result) => Some(result),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(err) => return Err(err.into()),
+      };
+      match id {
+        Some(id) => {
+          diesel::update(scalping::table.find(id))
+            .set((
+              scalping::buys_count.eq(buys_count),
+              scalping::sells_count.eq(sells_count),
+              scalping::buys_amount.eq(buys_amount),
+              scalping::sells_amount.eq(sells_amount),
+              scalping::profit.eq(profit),
+              scalping::additive_profit.eq(last_profit + profit),
+              scalping::updated_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+        }
+        None => {
+          let entity = Scalping::new(
+            xid::new().to_string(),
+            side,
+            day,
+            buys_count,
+            sells_count,
+            buys_amount,
+            sells_amount,
+            profit,
+            last_profit + profit,
+            now,
+            now,
+          );
+          diesel::insert_into(scalping::table)
+            .values(&entity)
+            .execute(&mut conn)?;
+        }
+      }
+      println!("binance margin isolated analysis scalping {day:} side {side:} profit {profit:}");
+    }
+
+    Ok(())
+  }
+}
\ No newline at end of file
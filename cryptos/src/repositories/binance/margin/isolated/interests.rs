@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Deserializer};
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::models::binance::margin::isolated::interest::*;
+use crate::schema::binance::margin::isolated::interests::*;
+
+#[derive(Deserialize)]
+struct InterestHistory {
+  rows: Vec<InterestInfo>,
+}
+
+#[derive(Deserialize)]
+struct InterestInfo {
+  #[serde(alias = "txId")]
+  tx_id: i64,
+  #[serde(alias = "interestAccuredTime")]
+  timestamp: i64,
+  asset: String,
+  #[serde(deserialize_with = "to_f64")]
+  principal: f64,
+  #[serde(deserialize_with = "to_f64")]
+  interest: f64,
+  #[serde(alias = "interestRate", deserialize_with = "to_f64")]
+  interest_rate: f64,
+  #[serde(alias = "type")]
+  interest_type: String,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+#[derive(Default)]
+pub struct InterestsRepository {}
+
+impl InterestsRepository {
+  pub async fn total<T>(
+    ctx: Ctx,
+    symbol: T,
+    asset: T,
+    start_time: i64,
+  ) -> Result<f64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let asset = asset.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let items = interests::table
+      .select(interests::interest)
+      .filter(interests::symbol.eq(symbol))
+      .filter(interests::asset.eq(asset))
+      .filter(interests::timestamp.ge(start_time))
+      .load::<f64>(&mut conn)?;
+
+    Ok(items.iter().sum())
+  }
+
+  pub async fn last_time<T>(ctx: Ctx, symbol: T, asset: T) -> Result<i64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let asset = asset.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match interests::table
+      .select(interests::timestamp)
+      .filter(interests::symbol.eq(symbol))
+      .filter(interests::asset.eq(asset))
+      .order(interests::timestamp.desc())
+      .first::<i64>(&mut conn) {
+        Ok(result) => Ok(result),
+        Err(diesel::result::Error::NotFound) => Ok(0),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  // interest is accrued hourly on every borrowed asset, rows already saved are skipped by tx id
+  pub async fn flush<T>(
+    ctx: Ctx,
+    symbol: T,
+    asset: T,
+    start_time: i64,
+  ) -> Result<usize, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let asset = asset.as_ref();
+    let start_time_val = start_time.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("asset", asset);
+    params.insert("isolatedSymbol", symbol);
+    if start_time > 0 {
+      params.insert("startTime", &start_time_val);
+    }
+    params.insert("size", "100");
+
+    // one page holds at most 100 rows, hourly accruals over a month span several pages
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let now = Utc::now();
+    let mut items = Vec::new();
+    let mut current = 1;
+    loop {
+      let current_val = current.to_string();
+      let mut params = params.clone();
+      params.insert("current", &current_val);
+      let history: InterestHistory = client.get("/sapi/v1/margin/interestHistory", &params, Security::Account, 1).await?;
+      let size = history.rows.len();
+      items.extend(history.rows.iter().map(|row| {
+        Interest::new(
+          xid::new().to_string(),
+          symbol.to_owned(),
+          row.asset.clone(),
+          row.tx_id,
+          row.principal,
+          row.interest,
+          row.interest_rate,
+          row.interest_type.clone(),
+          row.timestamp,
+          now,
+          now,
+        )
+      }));
+      if size < 100 {
+        break
+      }
+      current += 1;
+    }
+    if items.is_empty() {
+      return Ok(0)
+    }
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::insert_into(interests::table)
+      .values(&items)
+      .on_conflict(interests::tx_id)
+      .do_nothing()
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use serde::{Deserialize, Deserializer};
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::schema::binance::margin::isolated::orders::*;
+use crate::models::binance::margin::isolated::order::*;
+
+#[derive(Deserialize)]
+struct OrderInfo {
+  symbol: String,
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(alias = "type")]
+  order_type: String,
+  side: String,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "stopPrice", deserialize_with = "to_f64")]
+  stop_price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "time")]
+  open_time: i64,
+  #[serde(alias = "updateTime")]
+  update_time: i64,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct TradeInfo {
+  symbol: String,
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(alias = "type")]
+  order_type: String,
+  side: String,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "transactTime")]
+  transact_time: i64,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct StateInfo {
+  #[serde(alias = "orderId")]
+  order_id: i64,
+  #[serde(deserialize_with = "to_f64")]
+  price: f64,
+  #[serde(alias = "origQty", deserialize_with = "to_f64")]
+  quantity: f64,
+  #[serde(alias = "executedQty", deserialize_with = "to_f64")]
+  executed_quantity: f64,
+  #[serde(alias = "cummulativeQuoteQty", deserialize_with = "to_f64")]
+  quote_quantity: f64,
+  status: String,
+}
+
+fn to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let s: &str = Deserialize::deserialize(deserializer)?;
+  s.parse::<f64>().map_err(serde::de::Error::custom)
+}
+
+pub struct OrdersRepository {}
+
+impl OrdersRepository {
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<Option<Order>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match orders::table
+      .select(Order::as_select())
+      .filter(orders::symbol.eq(symbol))
+      .filter(orders::order_id.eq(order_id))
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    order_id: i64,
+    order_type: String,
+    side: String,
+    price: f64,
+    stop_price: f64,
+    quantity: f64,
+    executed_quantity: f64,
+    side_effect_type: String,
+    open_time: i64,
+    update_time: i64,
+    status: String,
+    remark: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let order = Order::new(
+      id,
+      symbol,
+      order_id,
+      order_type,
+      side,
+      price,
+      stop_price,
+      quantity,
+      executed_quantity,
+      side_effect_type,
+      open_time,
+      update_time,
+      status,
+      remark,
+      now,
+      now,
+    );
+    match diesel::insert_into(orders::table)
+      .values(&order)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    value: V,
+  ) -> Result<bool, Box<dyn std::error::Error>> 
+  where
+    V: diesel::AsChangeset<Target = orders::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(orders::table.find(id)).set(value).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // MARGIN_BUY borrows what the order lacks, AUTO_REPAY pays the loan back from the fill
+  pub async fn submit<T>(
+    ctx: Ctx,
+    symbol: T,
+    side: T,
+    side_effect_type: T,
+    price: f64,
+    quantity: f64,
+  ) -> Result<i64, Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    println!("orders submit");
+    let symbol = symbol.as_ref();
+    let side = side.as_ref();
+    let side_effect_type = side_effect_type.as_ref();
+    let price_val = price.to_string();
+    let quantity_val = quantity.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("side", side);
+    params.insert("type", "LIMIT");
+    params.insert("price", &price_val);
+    params.insert("quantity", &quantity_val);
+    params.insert("timeInForce", "GTC");
+    params.insert("isIsolated", "TRUE");
+    params.insert("sideEffectType", side_effect_type);
+    params.insert("newOrderRespType", "RESULT");
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let trade: TradeInfo = client.post("/sapi/v1/margin/order", &params, Security::Trade, 6).await?;
+    println!("response {:?}", trade.order_id);
+
+    let id = xid::new().to_string();
+    match Self::create(
+      ctx.clone(),
+      id,
+      trade.symbol.to_owned(),
+      trade.order_id,
+      trade.order_type.to_owned(),
+      trade.side.to_owned(),
+      trade.price,
+      0.0,
+      trade.quantity,
+      trade.executed_quantity,
+      side_effect_type.to_owned(),
+      trade.transact_time,
+      0,
+      trade.status.clone(),
+      "".to_owned(),
+    ).await {
+      Ok(result) => {
+        println!("binance margin isolated order {0:} {1:} create success {result:}", symbol, trade.order_id);
+      }
+      Err(err) => {
+        println!("binance margin isolated order {0:} {1:} create failed {err:?}", symbol, trade.order_id)
+      }
+    }
+
+    Ok(trade.order_id)
+  }
+
+  pub async fn sync<T>(
+    ctx: Ctx,
+    symbol: T,
+    start_time: i64,
+    limit: i64,
+  ) -> Result<(), Box<dyn std::error::Error>> 
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let start_time_val = start_time.to_string();
+    let limit = limit.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    if start_time > 0 {
+      params.insert("startTime", &start_time_val);
+    }
+    params.insert("isIsolated", "TRUE");
+    params.insert("limit", &limit);
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let orders: Vec<OrderInfo> = client.get("/sapi/v1/margin/allOrders", &params, Security::Account, 200).await?;
+
+    for order in orders.iter() {
+      let entity: Option<Order> = match Self::get(ctx.clone(), order.symbol.clone(), order.order_id).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        let id = xid::new().to_string();
+        match Self::create(
+          ctx.clone(),
+          id,
+          symbol.to_owned(),
+          order.order_id,
+          order.order_type.to_owned(),
+          order.side.to_owned(),
+          order.price,
+          order.stop_price,
+          order.quantity,
+          order.executed_quantity,
+          "".to_string(),
+          order.open_time,
+          order.update_time,
+          order.status.to_owned(),
+          "".to_string(),
+        ).await {
+          Ok(result) => {
+            println!("binance margin isolated order {0:} {1:} create success {result:}", order.symbol, order.order_id);
+          }
+          Err(err) => {
+            println!("binance margin isolated order {0:} {1:} create failed {err:?}", order.symbol, order.order_id)
+          }
+        }
+      } else {
+        let entity = entity.unwrap();
+        if entity.price == order.price
+          && entity.stop_price == order.stop_price
+          && entity.quantity == order.quantity
+          && entity.executed_quantity == order.executed_quantity
+          && entity.update_time == order.update_time
+          && entity.status == order.status {
+          continue
+        }
+        match Self::update(
+          ctx.clone(),
+          entity.id,
+          (
+            orders::price.eq(order.price),
+            orders::stop_price.eq(order.stop_price),
+            orders::quantity.eq(order.quantity),
+            orders::executed_quantity.eq(order.executed_quantity),
+            orders::update_time.eq(order.update_time),
+            orders::status.eq(order.status.to_owned()),
+          ),
+        ).await {
+          Ok(result) => {
+            println!("binance margin isolated order {0:} {1:} update success {result:}", order.symbol, order.order_id);
+          }
+          Err(err) => {
+            println!("binance margin isolated order {0:} {1:} update failed {err:?}", order.symbol, order.order_id)
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  pub async fn cancel<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+    params.insert("isIsolated", "TRUE");
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let info: StateInfo = client.delete("/sapi/v1/margin/order", &params, Security::Trade, 10).await?;
+
+    let avg_price = if info.executed_quantity > 0.0 { info.quote_quantity / info.executed_quantity } else { 0.0 };
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, avg_price))
+  }
+
+  pub async fn query<T>(
+    ctx: Ctx,
+    symbol: T,
+    order_id: i64,
+  ) -> Result<(i64, String, f64, f64, f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let order_id = order_id.to_string();
+
+    let mut params = HashMap::new();
+    params.insert("symbol", symbol);
+    params.insert("orderId", &order_id);
+    params.insert("isIsolated", "TRUE");
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let info: StateInfo = client.get("/sapi/v1/margin/order", &params, Security::Account, 10).await?;
+
+    let avg_price = if info.executed_quantity > 0.0 { info.quote_quantity / info.executed_quantity } else { 0.0 };
+    Ok((info.order_id, info.status, info.price, info.quantity, info.executed_quantity, avg_price))
+  }
+}
\ No newline at end of file
@@ -1,4 +1,218 @@
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use chrono::prelude::Utc;
+
+use crate::common::*;
+use crate::models::binance::margin::isolated::position::*;
+use crate::schema::binance::margin::isolated::positions::*;
+
 #[derive(Default)]
 pub struct PositionsRepository {}
 
-impl PositionsRepository {}
+impl PositionsRepository {
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    side: i32,
+  ) -> Result<Option<Position>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match positions::table
+      .select(Position::as_select())
+      .filter(positions::symbol.eq(symbol))
+      .filter(positions::side.eq(side))
+      .filter(positions::status.eq(1))
+      .first(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn gets(ctx: Ctx) -> Result<Vec<(String, String, i32, f64, f64, f64, f64, i64)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let positions = positions::table
+      .select((
+        positions::id,
+        positions::symbol,
+        positions::side,
+        positions::notional,
+        positions::entry_price,
+        positions::entry_quantity,
+        positions::entry_amount,
+        positions::timestamp,
+      ))
+      .filter(positions::status.eq(1))
+      .load::<(String, String, i32, f64, f64, f64, f64, i64)>(&mut conn)?;
+    Ok(positions)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    side: i32,
+    leverage: i32,
+    capital: f64,
+    notional: f64,
+    entry_price: f64,
+    entry_quantity: f64,
+    entry_amount: f64,
+    timestamp: i64,
+    status: i32,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let position = Position::new(
+      id,
+      symbol,
+      side,
+      leverage,
+      capital,
+      notional,
+      entry_price,
+      entry_quantity,
+      entry_amount,
+      timestamp,
+      status,
+      0,
+      now,
+      now,
+    );
+    match diesel::insert_into(positions::table)
+      .values(&position)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = positions::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(positions::table.find(id))
+      .filter(positions::version.eq(version))
+      .set(values)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  // filled entries are averaged into the open position of the side
+  pub async fn fill<T>(
+    ctx: Ctx,
+    symbol: T,
+    side: i32,
+    price: f64,
+    quantity: f64,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let timestamp = Utc::now().timestamp_millis();
+
+    let position = match Self::get(ctx.clone(), symbol, side).await? {
+      Some(result) => result,
+      None => {
+        return Self::create(
+          ctx.clone(),
+          xid::new().to_string(),
+          symbol.to_owned(),
+          side,
+          1,
+          price * quantity,
+          price * quantity,
+          price,
+          quantity,
+          price * quantity,
+          timestamp,
+          1,
+        ).await
+      }
+    };
+
+    let entry_quantity = position.entry_quantity + quantity;
+    let entry_amount = position.entry_amount + price * quantity;
+    Self::update(
+      ctx.clone(),
+      position.id,
+      position.version,
+      (
+        positions::capital.eq(position.capital + price * quantity),
+        positions::notional.eq(entry_amount),
+        positions::entry_price.eq(entry_amount / entry_quantity),
+        positions::entry_quantity.eq(entry_quantity),
+        positions::entry_amount.eq(entry_amount),
+        positions::timestamp.eq(timestamp),
+        positions::version.eq(position.version + 1),
+      ),
+    ).await
+  }
+
+  // exits keep the entry price, the position closes once nothing is left
+  pub async fn reduce<T>(
+    ctx: Ctx,
+    symbol: T,
+    side: i32,
+    quantity: f64,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+
+    let position = match Self::get(ctx.clone(), symbol, side).await? {
+      Some(result) => result,
+      None => return Ok(false),
+    };
+
+    let entry_quantity = position.entry_quantity - quantity;
+    if entry_quantity <= 0.0 {
+      return Self::update(
+        ctx.clone(),
+        position.id,
+        position.version,
+        (
+          positions::entry_quantity.eq(0.0),
+          positions::entry_amount.eq(0.0),
+          positions::notional.eq(0.0),
+          positions::status.eq(2),
+          positions::version.eq(position.version + 1),
+        ),
+      ).await
+    }
+
+    let entry_amount = position.entry_price * entry_quantity;
+    Self::update(
+      ctx.clone(),
+      position.id,
+      position.version,
+      (
+        positions::notional.eq(entry_amount),
+        positions::entry_quantity.eq(entry_quantity),
+        positions::entry_amount.eq(entry_amount),
+        positions::version.eq(position.version + 1),
+      ),
+    ).await
+  }
+}
\ No newline at end of file
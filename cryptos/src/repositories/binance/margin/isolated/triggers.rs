@@ -0,0 +1,149 @@
+use chrono::{prelude::Utc, DateTime};
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+
+use crate::common::*;
+use crate::models::binance::margin::isolated::trigger::*;
+use crate::schema::binance::margin::isolated::triggers::*;
+
+// status 1 waits for the price, 2 has an open trading, 3 is closed and 4 expired
+#[derive(Default)]
+pub struct TriggersRepository {}
+
+impl TriggersRepository {
+  pub async fn find<T>(
+    ctx: Ctx,
+    id: T,
+  ) -> Result<Option<Trigger>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let id = id.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match triggers::table
+      .find(id)
+      .select(Trigger::as_select())
+      .first(&mut conn) {
+        Ok(result) => Ok(Some(result)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+      }
+  }
+
+  pub async fn scan(ctx: Ctx) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let ids = triggers::table
+      .select(triggers::id)
+      .filter(triggers::status.eq(1))
+      .filter(triggers::expired_at.gt(Utc::now()))
+      .load::<String>(&mut conn)?;
+    Ok(ids)
+  }
+
+  pub async fn listings(ctx: Ctx, current: i64, page_size: i64) -> Result<Vec<(String, String, i32, f64, f64, f64, f64, f64, i32)>, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let items = triggers::table
+      .select((
+        triggers::id,
+        triggers::symbol,
+        triggers::side,
+        triggers::capital,
+        triggers::price,
+        triggers::take_price,
+        triggers::stop_price,
+        triggers::profit,
+        triggers::status,
+      ))
+      .order(triggers::timestamp.desc())
+      .offset((current - 1) * page_size)
+      .limit(page_size)
+      .load::<(String, String, i32, f64, f64, f64, f64, f64, i32)>(&mut conn)?;
+    Ok(items)
+  }
+
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    side: i32,
+    capital: f64,
+    price: f64,
+    take_price: f64,
+    stop_price: f64,
+    expired_at: DateTime<Utc>,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Trigger::new(
+      id,
+      symbol,
+      side,
+      capital,
+      price,
+      take_price,
+      stop_price,
+      0,
+      0,
+      0.0,
+      now.timestamp_millis(),
+      1,
+      0,
+      "".to_owned(),
+      expired_at,
+      now,
+      now,
+    );
+    match diesel::insert_into(triggers::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    version: i64,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = triggers::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(triggers::table.find(id))
+      .filter(triggers::version.eq(version))
+      .set(values)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn expire(ctx: Ctx) -> Result<usize, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    let now = Utc::now();
+    match diesel::update(triggers::table)
+      .filter(triggers::status.eq(1))
+      .filter(triggers::expired_at.le(now))
+      .set((
+        triggers::status.eq(4),
+        triggers::version.eq(triggers::version + 1),
+        triggers::updated_at.eq(now),
+      ))
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
\ No newline at end of file
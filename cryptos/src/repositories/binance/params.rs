@@ -0,0 +1,523 @@
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use serde::{Deserialize, Serialize};
+
+use crate::common::*;
+use crate::repositories::indicators::*;
+use crate::models::binance::param::*;
+use crate::schema::binance::params::*;
+
+#[derive(Default)]
+pub struct ParamsRepository {}
+
+// the parameter set the indicators and strategies run with after the overrides are applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorParams {
+  pub atr_period: i32,
+  pub zlema_period: i32,
+  pub kdj_long_period: i32,
+  pub kdj_short_period: i32,
+  pub bbands_period: i32,
+  pub bbands_deviation: f64,
+  pub tenkan_period: i32,
+  pub kijun_period: i32,
+  pub senkou_period: i32,
+  pub ichimoku_limit: i64,
+  pub volume_profile_segments: i32,
+  pub volume_profile_limit: i64,
+  pub andean_period: i32,
+  pub andean_length: i32,
+  pub andean_limit: i64,
+  pub rsi_period: i32,
+  pub macd_fast_period: i32,
+  pub macd_slow_period: i32,
+  pub macd_signal_period: i32,
+  pub adx_period: i32,
+  pub supertrend_period: i32,
+  pub supertrend_multiplier: f64,
+  pub limit: i64,
+  pub atr_profit_ratio: f64,
+  pub atr_stop_ratio: f64,
+  pub kdj_oversold: f64,
+  pub kdj_overbought: f64,
+  pub bbands_middle: f64,
+  pub bbands_upper: f64,
+  pub bbands_squeeze: f64,
+  pub bbands_narrow: f64,
+  pub rsi_oversold: f64,
+  pub rsi_overbought: f64,
+  pub adx_threshold: f64,
+}
+
+impl IndicatorParams {
+  pub fn defaults(interval: Interval) -> Self {
+    let (tenkan_period, kijun_period, senkou_period, ichimoku_limit) = match interval {
+      Interval::Minute1 => (129, 374, 748, 1440),
+      Interval::Minute15 => (60, 174, 349, 672),
+      Interval::Hour4 => (11, 32, 65, 126),
+      _ => (9, 26, 52, 100),
+    };
+    let lookback = match interval {
+      Interval::Minute1 => 1440,
+      Interval::Minute15 => 672,
+      Interval::Hour4 => 126,
+      _ => 100,
+    };
+    Self {
+      atr_period: 14,
+      zlema_period: 14,
+      kdj_long_period: 9,
+      kdj_short_period: 3,
+      bbands_period: 14,
+      bbands_deviation: 2.0,
+      tenkan_period: tenkan_period,
+      kijun_period: kijun_period,
+      senkou_period: senkou_period,
+      ichimoku_limit: ichimoku_limit,
+      volume_profile_segments: 100,
+      volume_profile_limit: lookback,
+      andean_period: 50,
+      andean_length: 9,
+      andean_limit: lookback,
+      rsi_period: 14,
+      macd_fast_period: 12,
+      macd_slow_period: 26,
+      macd_signal_period: 9,
+      adx_period: 14,
+      supertrend_period: 10,
+      supertrend_multiplier: 3.0,
+      limit: 100,
+      atr_profit_ratio: 1.5,
+      atr_stop_ratio: 1.0,
+      kdj_oversold: 20.0,
+      kdj_overbought: 80.0,
+      bbands_middle: 0.5,
+      bbands_upper: 0.8,
+      bbands_squeeze: 0.1,
+      bbands_narrow: 0.03,
+      rsi_oversold: 30.0,
+      rsi_overbought: 70.0,
+      adx_threshold: 25.0,
+    }
+  }
+
+  pub fn merge(self, values: &Values) -> Result<Self, Box<dyn std::error::Error>> {
+    let mut data = serde_json::to_value(&self)?;
+    if let (Some(data), serde_json::Value::Object(values)) = (data.as_object_mut(), serde_json::to_value(values)?) {
+      data.extend(values);
+    }
+    Ok(serde_json::from_value(data)?)
+  }
+
+  pub fn check(&self) -> Result<(), Box<dyn std::error::Error>> {
+    let periods = [
+      ("atr_period", self.atr_period),
+      ("zlema_period", self.zlema_period),
+      ("kdj_long_period", self.kdj_long_period),
+      ("kdj_short_period", self.kdj_short_period),
+      ("bbands_period", self.bbands_period),
+      ("tenkan_period", self.tenkan_period),
+      ("kijun_period", self.kijun_period),
+      ("senkou_period", self.senkou_period),
+      ("volume_profile_segments", self.volume_profile_segments),
+      ("andean_period", self.andean_period),
+      ("andean_length", self.andean_length),
+      ("rsi_period", self.rsi_period),
+      ("macd_fast_period", self.macd_fast_period),
+      ("macd_slow_period", self.macd_slow_period),
+      ("macd_signal_period", self.macd_signal_period),
+      ("adx_period", self.adx_period),
+      ("supertrend_period", self.supertrend_period),
+    ];
+    for (key, value) in periods {
+      if value < 1 {
+        return Err(Box::from(format!("{key:} must be greater than 0")))
+      }
+    }
+    let windows = [
+      ("bbands_period", self.bbands_period),
+      ("tenkan_period", self.tenkan_period),
+      ("kijun_period", self.kijun_period),
+      ("senkou_period", self.senkou_period),
+    ];
+    for (key, value) in windows {
+      if value < 2 {
+        return Err(Box::from(format!("{key:} must be greater than 1")))
+      }
+    }
+    if self.bbands_deviation <= 0.0 {
+      return Err(Box::from("bbands_deviation must be greater than 0"))
+    }
+    if self.macd_fast_period >= self.macd_slow_period {
+      return Err(Box::from("macd_fast_period must be less than macd_slow_period"))
+    }
+    if self.supertrend_multiplier <= 0.0 {
+      return Err(Box::from("supertrend_multiplier must be greater than 0"))
+    }
+    if self.rsi_oversold >= self.rsi_overbought {
+      return Err(Box::from("rsi_oversold must be less than rsi_overbought"))
+    }
+
+    let limits = [
+      ("limit", self.limit, [
+        self.atr_period,
+        self.zlema_period,
+        self.bbands_period,
+        self.kdj_long_period + self.kdj_short_period * 2,
+        self.rsi_period + 1,
+        self.macd_slow_period + self.macd_signal_period,
+        self.adx_period * 2,
+        self.supertrend_period + 1,
+      ].into_iter().max().unwrap()),
+      ("ichimoku_limit", self.ichimoku_limit, self.senkou_period.max(self.kijun_period * 2)),
+      ("volume_profile_limit", self.volume_profile_limit, 1),
+      ("andean_limit", self.andean_limit, self.andean_period.max(self.andean_length)),
+    ];
+    for (key, value, period) in limits {
+      if value <= period as i64 {
+        return Err(Box::from(format!("{key:} must be greater than {period:}")))
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn engine(&self) -> EngineParams {
+    EngineParams {
+      atr_period: self.atr_period as usize,
+      zlema_period: self.zlema_period as usize,
+      kdj_long_period: self.kdj_long_period as usize,
+      kdj_short_period: self.kdj_short_period as usize,
+      bbands_period: self.bbands_period as usize,
+      bbands_deviation: self.bbands_deviation,
+      tenkan_period: self.tenkan_period as usize,
+      kijun_period: self.kijun_period as usize,
+      senkou_period: self.senkou_period as usize,
+      volume_profile_segments: self.volume_profile_segments as usize,
+      volume_profile_limit: self.volume_profile_limit as usize,
+      andean_period: self.andean_period as usize,
+      andean_length: self.andean_length as usize,
+      andean_limit: self.andean_limit as usize,
+      rsi_period: self.rsi_period as usize,
+      macd_fast_period: self.macd_fast_period as usize,
+      macd_slow_period: self.macd_slow_period as usize,
+      macd_signal_period: self.macd_signal_period as usize,
+      adx_period: self.adx_period as usize,
+      supertrend_period: self.supertrend_period as usize,
+      supertrend_multiplier: self.supertrend_multiplier,
+      ichimoku_limit: self.ichimoku_limit as usize,
+      limit: self.limit as usize,
+    }
+  }
+}
+
+impl Values {
+  pub fn merge(self, values: &Values) -> Result<Self, Box<dyn std::error::Error>> {
+    let mut data = serde_json::to_value(&self)?;
+    if let (Some(data), serde_json::Value::Object(values)) = (data.as_object_mut(), serde_json::to_value(values)?) {
+      data.extend(values);
+    }
+    Ok(serde_json::from_value(data)?)
+  }
+}
+
+impl ParamsRepository {
+  // an empty symbol holds the default set of the market
+  pub async fn find<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+    interval: T,
+  ) -> Result<Option<Param>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match params::table
+      .select(Param::as_select())
+      .filter(params::market.eq(market))
+      .filter(params::symbol.eq(symbol))
+      .filter(params::interval.eq(interval))
+      .first(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn listings<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+  ) -> Result<Vec<Param>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let mut query = params::table
+      .select(Param::as_select())
+      .filter(params::market.eq(market))
+      .into_boxed();
+    if symbol != "" {
+      query = query.filter(params::symbol.eq(symbol));
+    }
+    let items = query
+      .order((params::symbol.asc(), params::interval.asc()))
+      .load::<Param>(&mut conn)?;
+
+    Ok(items)
+  }
+
+  // code defaults, then the market default of the interval, then the symbol of the interval
+  pub async fn get<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+    interval: T,
+  ) -> Result<IndicatorParams, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let items = {
+      let pool = ctx.pool.read().await;
+      let mut conn = pool.get().unwrap();
+      params::table
+        .select(params::values)
+        .filter(params::market.eq(market))
+        .filter(params::symbol.eq_any(vec!["", symbol]))
+        .filter(params::interval.eq(interval))
+        .order(params::symbol.asc())
+        .load::<Values>(&mut conn)?
+    };
+
+    let mut result = IndicatorParams::defaults(Interval::parse(interval)?);
+    for values in items.iter() {
+      result = result.merge(values)?;
+    }
+    result.check()?;
+
+    Ok(result)
+  }
+
+  pub async fn set<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+    interval: T,
+    values: Values,
+  ) -> Result<Values, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let values = match Self::find(ctx.clone(), market, symbol, interval).await? {
+      Some(entity) => entity.values.merge(&values)?,
+      None => values,
+    };
+    Self::check(ctx.clone(), market, symbol, interval, &values).await?;
+
+    Self::save(ctx.clone(), market, symbol, interval, values).await
+  }
+
+  pub async fn unset<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+    interval: T,
+    keys: Vec<T>,
+  ) -> Result<Values, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let entity = match Self::find(ctx.clone(), market, symbol, interval).await? {
+      Some(result) => result,
+      None => return Err(Box::from(format!("params of {market:} {symbol:} {interval:} not exists"))),
+    };
+
+    let mut data = serde_json::to_value(&entity.values)?;
+    if let Some(data) = data.as_object_mut() {
+      for key in keys.iter() {
+        data.remove(key.as_ref());
+      }
+    }
+    let values: Values = serde_json::from_value(data)?;
+    Self::check(ctx.clone(), market, symbol, interval, &values).await?;
+
+    Self::save(ctx.clone(), market, symbol, interval, values).await
+  }
+
+  // the same layers get merges with values in place of the stored row, a market default
+  // has to stay valid under every symbol of the interval overriding it
+  async fn check(
+    ctx: Ctx,
+    market: &str,
+    symbol: &str,
+    interval: &str,
+    values: &Values,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    let items = {
+      let pool = ctx.pool.read().await;
+      let mut conn = pool.get().unwrap();
+      params::table
+        .select((params::symbol, params::values))
+        .filter(params::market.eq(market))
+        .filter(params::interval.eq(interval))
+        .load::<(String, Values)>(&mut conn)?
+    };
+
+    let defaults = IndicatorParams::defaults(Interval::parse(interval)?);
+    if symbol.is_empty() {
+      let result = defaults.merge(values)?;
+      result.check()?;
+      for (symbol, values) in items.iter().filter(|(symbol, _)| !symbol.is_empty()) {
+        if let Err(err) = result.clone().merge(values)?.check() {
+          return Err(Box::from(format!("params of {market:} {symbol:} {interval:} {err:}")))
+        }
+      }
+    } else {
+      let mut result = defaults;
+      for (_, values) in items.iter().filter(|(symbol, _)| symbol.is_empty()) {
+        result = result.merge(values)?;
+      }
+      result.merge(values)?.check()?;
+    }
+
+    Ok(())
+  }
+
+  async fn save(
+    ctx: Ctx,
+    market: &str,
+    symbol: &str,
+    interval: &str,
+    values: Values,
+  ) -> Result<Values, Box<dyn std::error::Error>> {
+    let now = Utc::now();
+    let entity = Param::new(
+      xid::new().to_string(),
+      market.to_string(),
+      symbol.to_string(),
+      interval.to_string(),
+      values.clone(),
+      now,
+      now,
+    );
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    diesel::insert_into(params::table)
+      .values(&entity)
+      .on_conflict((params::market, params::symbol, params::interval))
+      .do_update()
+      .set((
+        params::values.eq(excluded(params::values)),
+        params::updated_at.eq(excluded(params::updated_at)),
+      ))
+      .execute(&mut conn)?;
+
+    Ok(values)
+  }
+
+  pub async fn delete<T>(
+    ctx: Ctx,
+    market: T,
+    symbol: T,
+    interval: T,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let market = market.as_ref();
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    // the symbols of the interval fall back to the code defaults once the market default is gone
+    if symbol.is_empty() {
+      Self::check(ctx.clone(), market, symbol, interval, &Values::default()).await?;
+    }
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let effective_rows = diesel::delete(params::table)
+      .filter(params::market.eq(market))
+      .filter(params::symbol.eq(symbol))
+      .filter(params::interval.eq(interval))
+      .execute(&mut conn)?;
+
+    Ok(effective_rows > 0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_check_defaults() {
+    for interval in [Interval::Minute1, Interval::Minute15, Interval::Hour4, Interval::Day1] {
+      assert!(IndicatorParams::defaults(interval).check().is_ok());
+    }
+  }
+
+  #[test]
+  fn test_check_periods() {
+    let values = Values {
+      atr_period: Some(0),
+      ..Default::default()
+    };
+    let result = IndicatorParams::defaults(Interval::Day1).merge(&values).unwrap().check();
+    assert_eq!(result.unwrap_err().to_string(), "atr_period must be greater than 0");
+
+    for (values, key) in [
+      (Values { bbands_period: Some(1), ..Default::default() }, "bbands_period"),
+      (Values { tenkan_period: Some(1), ..Default::default() }, "tenkan_period"),
+      (Values { kijun_period: Some(1), ..Default::default() }, "kijun_period"),
+      (Values { senkou_period: Some(1), ..Default::default() }, "senkou_period"),
+    ] {
+      let result = IndicatorParams::defaults(Interval::Day1).merge(&values).unwrap().check();
+      assert_eq!(result.unwrap_err().to_string(), format!("{key:} must be greater than 1"));
+    }
+  }
+
+  #[test]
+  fn test_check_limits() {
+    let values = Values {
+      macd_fast_period: Some(26),
+      ..Default::default()
+    };
+    let result = IndicatorParams::defaults(Interval::Day1).merge(&values).unwrap().check();
+    assert_eq!(result.unwrap_err().to_string(), "macd_fast_period must be less than macd_slow_period");
+
+    let values = Values {
+      ichimoku_limit: Some(52),
+      ..Default::default()
+    };
+    let result = IndicatorParams::defaults(Interval::Day1).merge(&values).unwrap().check();
+    assert_eq!(result.unwrap_err().to_string(), "ichimoku_limit must be greater than 52");
+  }
+}
\ No newline at end of file
@@ -1,6 +1,8 @@
 pub mod account;
 pub mod analysis;
 pub mod symbols;
+pub mod depth;
+pub mod exchange;
 pub mod tickers;
 pub mod klines;
 pub mod indicators;
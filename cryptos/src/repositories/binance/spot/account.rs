@@ -1,15 +1,10 @@
-use std::time::Duration;
 use std::collections::HashMap;
 
-use url::Url;
-use sha2::Sha256;
-use hmac::{Hmac, Mac};
 use serde::{Deserialize, Deserializer};
-use chrono::prelude::Utc;
-use reqwest::header;
 use redis::AsyncCommands;
 
 use crate::common::*;
+use crate::repositories::binance::client::*;
 use crate::config::binance::spot::config as Config;
 
 pub struct AccountRepository {}
@@ -60,47 +55,8 @@ impl AccountRepository {
   }
 
   pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
-    let timestamp = Utc::now().timestamp_millis().to_string();
-
-    let mut params = HashMap::<&str, &str>::new();
-    params.insert("recvWindow", "60000");
-    params.insert("timestamp", &timestamp);
-
-    let mut url = Url::parse_with_params(format!("{}/api/v3/account", Env::var("BINANCE_SPOT_API_ENDPOINT")).as_str(), &params)?;
-    let query: &str = match url.query() {
-      Some(query) => query,
-      None => "",
-    };
-
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(Env::var("BINANCE_SPOT_ACCOUNT_API_SECRET").as_bytes()).unwrap();
-    mac.update(query.as_bytes());
-    let signature = hex::encode(&mac.finalize().into_bytes().to_vec());
-
-    url.query_pairs_mut().append_pair("signature", signature.as_str());
-
-    let mut headers = header::HeaderMap::new();
-    headers.insert("X-MBX-APIKEY", Env::var("BINANCE_SPOT_ACCOUNT_API_KEY").parse().unwrap());
-
-    let client = reqwest::Client::new();
-    let response = client.get(url)
-      .headers(headers)
-      .timeout(Duration::from_secs(3))
-      .send()
-      .await?;
-
-    let status_code = response.status();
-
-    if status_code.is_client_error() {
-      println!("response {}", response.text().await.unwrap());
-      return Err(Box::from(format!("bad request: {}", status_code)))
-    }
-
-    if !status_code.is_success() {
-      return Err(Box::from(format!("request error: {}", status_code)))
-    }
-
-    let account_info = response.json::<AccountInfo>().await.unwrap();
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let account_info: AccountInfo = client.get("/api/v3/account", &HashMap::new(), Security::Account, 20).await?;
     println!("account_info {}", account_info.balances.len());
 
     let mut rdb = ctx.rdb.lock().await.clone();
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::repositories::binance::client::*;
+use crate::config::binance::spot::config as Config;
+use crate::schema::binance::spot::symbols::*;
+use crate::models::binance::spot::symbol::*;
+
+#[derive(Default)]
+pub struct DepthRepository {}
+
+impl DepthRepository {
+  pub async fn snapshot<T>(ctx: Ctx, symbol: T, limit: i64) -> Result<Depth, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let weight = match limit {
+      0..=100 => 5,
+      101..=500 => 25,
+      501..=1000 => 50,
+      _ => 250,
+    };
+    let limit = limit.to_string();
+
+    let mut params = HashMap::<&str, &str>::new();
+    params.insert("symbol", symbol);
+    params.insert("limit", &limit);
+
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let depth: Depth = client.get("/api/v3/depth", &params, Security::Public, weight).await?;
+
+    Ok(depth)
+  }
+
+  pub async fn get<T>(ctx: Ctx, symbol: T) -> Result<Option<Depth>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_DEPTH, symbol);
+    let content: Option<String> = rdb.get(&redis_key).await?;
+    match content {
+      Some(content) => Ok(Some(serde_json::from_str::<Depth>(&content)?)),
+      None => Ok(None),
+    }
+  }
+
+  pub async fn cache<T>(ctx: Ctx, symbol: T, depth: &Depth) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}", Config::REDIS_KEY_DEPTH, symbol);
+    let content = serde_json::to_string(depth)?;
+    () = rdb.set_ex(&redis_key, content, 30).await?;
+    Ok(())
+  }
+
+  pub async fn price<T>(ctx: Ctx, symbol: T) -> Result<(f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let depth = match Self::get(ctx.clone(), symbol).await? {
+      Some(depth) => depth,
+      None => return Err(Box::from(format!("depth of {symbol:} not exists"))),
+    };
+    if depth.bids.is_empty() || depth.asks.is_empty() {
+      return Err(Box::from(format!("depth of {symbol:} is empty")))
+    }
+    let bid = depth.bids[0][0].parse::<f64>()?;
+    let ask = depth.asks[0][0].parse::<f64>()?;
+    Ok((bid, ask))
+  }
+
+  pub async fn spread<T>(ctx: Ctx, symbol: T) -> Result<f64, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let (bid, ask) = Self::price(ctx.clone(), symbol).await?;
+    let mid = (bid + ask) / 2.0;
+    Ok((ask - bid) / mid * 10000.0)
+  }
+
+  pub async fn volume<T>(ctx: Ctx, symbol: T, bps: f64) -> Result<(f64, f64), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let depth = match Self::get(ctx.clone(), symbol).await? {
+      Some(depth) => depth,
+      None => return Err(Box::from(format!("depth of {symbol:} not exists"))),
+    };
+    if depth.bids.is_empty() || depth.asks.is_empty() {
+      return Err(Box::from(format!("depth of {symbol:} is empty")))
+    }
+
+    let bid = depth.bids[0][0].parse::<f64>()?;
+    let ask = depth.asks[0][0].parse::<f64>()?;
+    let mid = (bid + ask) / 2.0;
+    let lower = mid * (1.0 - bps / 10000.0);
+    let upper = mid * (1.0 + bps / 10000.0);
+
+    let mut bids_volume = 0.0;
+    for item in depth.bids.iter() {
+      let price = item[0].parse::<f64>()?;
+      if price < lower {
+        break
+      }
+      bids_volume += price * item[1].parse::<f64>()?;
+    }
+
+    let mut asks_volume = 0.0;
+    for item in depth.asks.iter() {
+      let price = item[0].parse::<f64>()?;
+      if price > upper {
+        break
+      }
+      asks_volume += price * item[1].parse::<f64>()?;
+    }
+
+    Ok((bids_volume, asks_volume))
+  }
+
+  pub async fn flush<T>(ctx: Ctx, symbol: T, limit: usize) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let depth = match Self::get(ctx.clone(), symbol).await? {
+      Some(depth) => depth,
+      None => return Ok(false),
+    };
+
+    let depth = Depth {
+      asks: depth.asks.into_iter().take(limit).collect(),
+      bids: depth.bids.into_iter().take(limit).collect(),
+      last_update_id: depth.last_update_id,
+    };
+
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(symbols::table.filter(symbols::symbol.eq(symbol)))
+      .set(symbols::depth.eq(depth))
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
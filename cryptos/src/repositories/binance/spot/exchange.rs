@@ -0,0 +1,119 @@
+use crate::common::*;
+use crate::repositories::exchange::*;
+use crate::repositories::binance::spot::account::*;
+use crate::repositories::binance::spot::depth::*;
+use crate::repositories::binance::spot::klines::*;
+use crate::repositories::binance::spot::orders::*;
+use crate::repositories::binance::spot::symbols::*;
+use crate::repositories::binance::spot::tickers::*;
+
+pub struct SpotExchange {
+  ctx: Ctx,
+}
+
+impl SpotExchange {
+  pub fn new(ctx: Ctx) -> Self {
+    Self {
+      ctx: ctx,
+    }
+  }
+}
+
+fn levels(items: &Vec<Vec<String>>) -> Vec<(f64, f64)> {
+  items.iter().filter_map(|item| {
+    match (item[0].parse::<f64>(), item[1].parse::<f64>()) {
+      (Ok(price), Ok(quantity)) => Some((price, quantity)),
+      _ => None,
+    }
+  }).collect()
+}
+
+impl Exchange for SpotExchange {
+  fn name(&self) -> &'static str {
+    "binance:spot"
+  }
+
+  async fn klines(&self, symbol: &str, interval: &str, endtime: i64, limit: i64) -> Result<(), Box<dyn std::error::Error>> {
+    KlinesRepository::flush(self.ctx.clone(), symbol, interval, endtime, limit).await
+  }
+
+  async fn price(&self, symbol: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    TickersRepository::price(self.ctx.clone(), symbol).await
+  }
+
+  async fn depth(&self, symbol: &str, limit: i64) -> Result<Book, Box<dyn std::error::Error>> {
+    let depth = match DepthRepository::get(self.ctx.clone(), symbol).await? {
+      Some(depth) => depth,
+      None => DepthRepository::snapshot(self.ctx.clone(), symbol, limit).await?,
+    };
+    let mut asks = levels(&depth.asks);
+    let mut bids = levels(&depth.bids);
+    asks.truncate(limit as usize);
+    bids.truncate(limit as usize);
+    Ok(Book {
+      asks: asks,
+      bids: bids,
+    })
+  }
+
+  async fn filters(&self, symbol: &str) -> Result<SymbolFilters, Box<dyn std::error::Error>> {
+    let (tick_size, step_size) = SymbolsRepository::filters(self.ctx.clone(), symbol).await?;
+    let notional = match SymbolsRepository::get(self.ctx.clone(), symbol).await? {
+      Some(entity) => entity.filters.notional.split(",").next().unwrap_or("").parse::<f64>().unwrap_or(0.0),
+      None => return Err(Box::from(format!("symbol of {symbol:} not exists"))),
+    };
+    Ok(SymbolFilters {
+      tick_size: tick_size,
+      step_size: step_size,
+      notional: notional,
+    })
+  }
+
+  async fn pairs(&self, symbol: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+    SymbolsRepository::pairs(self.ctx.clone(), symbol).await
+  }
+
+  async fn balance(&self, asset: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    let (free, _) = AccountRepository::balance(self.ctx.clone(), asset).await?;
+    Ok(free)
+  }
+
+  async fn place(&self, order: &OrderRequest) -> Result<i64, Box<dyn std::error::Error>> {
+    let side = match (order.side, order.is_open) {
+      (1, true) => "BUY",
+      (1, false) => "SELL",
+      _ => return Err(Box::from(format!("order side {} not supported", order.side))),
+    };
+    OrdersRepository::submit(
+      self.ctx.clone(),
+      &order.symbol[..],
+      side,
+      order.price,
+      order.quantity,
+    ).await
+  }
+
+  async fn cancel(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::cancel(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: quantity,
+      executed_quantity: executed_quantity,
+      avg_price: avg_price,
+    })
+  }
+
+  async fn query(&self, symbol: &str, order_id: i64) -> Result<OrderState, Box<dyn std::error::Error>> {
+    let (order_id, status, price, quantity, executed_quantity, avg_price) = OrdersRepository::query(self.ctx.clone(), symbol, order_id).await?;
+    Ok(OrderState {
+      order_id: order_id,
+      status: status,
+      price: price,
+      quantity: quantity,
+      executed_quantity: executed_quantity,
+      avg_price: avg_price,
+    })
+  }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::prelude::Utc;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+
+use redis::AsyncCommands;
+
+use crate::common::*;
+use crate::config::binance::spot::config as Config;
+use crate::models::binance::spot::indicator::*;
+use crate::schema::binance::spot::indicators::*;
+
+#[derive(Default)]
+pub struct HistoryRepository {}
+
+impl HistoryRepository {
+  pub async fn get<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    timestamp: i64,
+  ) -> Result<Option<Indicator>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    match indicators::table
+      .select(Indicator::as_select())
+      .filter(indicators::symbol.eq(symbol))
+      .filter(indicators::interval.eq(interval))
+      .filter(indicators::timestamp.eq(timestamp))
+      .first(&mut conn) {
+      Ok(result) => Ok(Some(result)),
+      Err(diesel::result::Error::NotFound) => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn range<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    start_time: i64,
+    end_time: i64,
+    limit: i64,
+  ) -> Result<Vec<(i64, Fields)>, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+
+    let items = indicators::table
+      .select((indicators::timestamp, indicators::fields))
+      .filter(indicators::symbol.eq(symbol))
+      .filter(indicators::interval.eq(interval))
+      .filter(indicators::timestamp.ge(start_time))
+      .filter(indicators::timestamp.le(end_time))
+      .order(indicators::timestamp.asc())
+      .limit(limit)
+      .load::<(i64, Fields)>(&mut conn)?;
+
+    Ok(items)
+  }
+
+  pub async fn upsert(
+    ctx: Ctx,
+    entity: Indicator,
+  ) -> Result<usize, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let effective_rows = diesel::insert_into(indicators::table)
+      .values(&entity)
+      .on_conflict((indicators::symbol, indicators::interval, indicators::timestamp))
+      .do_update()
+      .set((
+        indicators::fields.eq(excluded(indicators::fields)),
+        indicators::updated_at.eq(excluded(indicators::updated_at)),
+      ))
+      .execute(&mut conn)?;
+
+    Ok(effective_rows)
+  }
+
+  // values are staged under the candle they were computed for, indicators that failed
+  // on a candle leave nothing behind for it
+  pub async fn stage<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+    timestamp: i64,
+    values: &[(&str, String)],
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+
+    let ttl = Duration::from_millis(timeframe.timestep() as u64 * 2) + Duration::from_secs(30);
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS_HISTORY, interval, symbol, timestamp);
+    () = rdb.hset_multiple(&redis_key, values).await?;
+    () = rdb.expire(&redis_key, ttl.as_secs().try_into().unwrap()).await?;
+
+    Ok(())
+  }
+
+  // the candle before the open one has closed, its staged values are the last ones computed for it,
+  // runs landing on it just after the close are picked up by the next flush
+  pub async fn flush<T>(
+    ctx: Ctx,
+    symbol: T,
+    interval: T,
+  ) -> Result<(), Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let symbol = symbol.as_ref();
+    let interval = interval.as_ref();
+    let timeframe = Interval::parse(interval)?;
+    let timestamp = timeframe.prev(timeframe.timestamp());
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = format!("{}:{}:{}:{}", Config::REDIS_KEY_INDICATORS_HISTORY, interval, symbol, timestamp);
+    let values: HashMap<String, String> = rdb.hgetall(&redis_key).await?;
+    if values.is_empty() {
+      return Ok(())
+    }
+
+    let now = Utc::now();
+    let entity = Indicator::new(
+      xid::new().to_string(),
+      symbol.to_string(),
+      interval.to_string(),
+      Fields { values: values },
+      timestamp,
+      now,
+      now,
+    );
+    Self::upsert(ctx.clone(), entity).await?;
+
+    Ok(())
+  }
+}
\ No newline at end of file
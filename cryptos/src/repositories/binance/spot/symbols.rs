@@ -1,11 +1,50 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::prelude::Utc;
 use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use serde::Deserialize;
 
 use crate::common::*;
+use crate::repositories::binance::client::*;
 use crate::schema::binance::spot::symbols::*;
+use crate::schema::binance::spot::symbols::changes::changes;
 use crate::models::binance::spot::symbol::*;
+use crate::models::binance::spot::symbol::change::*;
+use crate::repositories::binance::spot::scalping::*;
+use crate::queue::nats::jobs::binance::spot::symbols::*;
 
+#[derive(Default)]
 pub struct SymbolsRepository {}
 
+#[derive(Deserialize)]
+struct ExchangeInfo {
+  symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Deserialize)]
+struct SymbolInfo {
+  symbol: String,
+  #[serde(alias = "baseAsset")]
+  base_asset: String,
+  #[serde(alias = "quoteAsset")]
+  quote_asset: String,
+  #[serde(alias = "isSpotTradingAllowed")]
+  is_spot: bool,
+  #[serde(alias = "isMarginTradingAllowed")]
+  is_margin: bool,
+  status: String,
+  filters: Vec<HashMap<String, serde_json::Value>>,
+}
+
+fn field(filter: &HashMap<String, serde_json::Value>, key: &str) -> String {
+  match filter.get(key) {
+    Some(serde_json::Value::String(value)) => value.clone(),
+    Some(value) => value.to_string(),
+    None => "".to_string(),
+  }
+}
+
 impl SymbolsRepository {
   pub async fn get<T>(
     ctx: Ctx,
@@ -29,12 +68,269 @@ impl SymbolsRepository {
       }
   }
 
+  pub async fn create(
+    ctx: Ctx,
+    id: String,
+    symbol: String,
+    base_asset: String,
+    quote_asset: String,
+    filters: Filters,
+    is_spot: bool,
+    is_margin: bool,
+    status: String,
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let now = Utc::now();
+    let entity = Symbol::new(
+      id,
+      symbol,
+      base_asset,
+      quote_asset,
+      filters,
+      Depth {
+        asks: vec![],
+        bids: vec![],
+        last_update_id: 0,
+      },
+      is_spot,
+      is_margin,
+      status,
+      now,
+      now,
+    );
+    match diesel::insert_into(symbols::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn update<V>(
+    ctx: Ctx,
+    id: String,
+    values: V,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    V: diesel::AsChangeset<Target = symbols::table>,
+    <V as diesel::AsChangeset>::Changeset: QueryFragment<diesel::pg::Pg>,
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+    match diesel::update(symbols::table.find(id)).set(values).execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  pub async fn change<T>(
+    ctx: Ctx,
+    symbol: T,
+    field: T,
+    old_value: T,
+    new_value: T,
+  ) -> Result<bool, Box<dyn std::error::Error>>
+  where
+    T: AsRef<str>
+  {
+    let pool = ctx.pool.write().await;
+    let mut conn = pool.get().unwrap();
+
+    let entity = Change::new(
+      xid::new().to_string(),
+      symbol.as_ref().to_owned(),
+      field.as_ref().to_owned(),
+      old_value.as_ref().to_owned(),
+      new_value.as_ref().to_owned(),
+      Utc::now(),
+    );
+    match diesel::insert_into(changes::table)
+      .values(&entity)
+      .execute(&mut conn) {
+      Ok(effective_rows) => Ok(effective_rows > 0),
+      Err(err) => Err(err.into()),
+    }
+  }
+
   pub async fn flush(ctx: Ctx) -> Result<(), Box<dyn std::error::Error>> {
     println!("symbols flush");
-    let _ = ctx.clone();
-    if 1 > 0 {
-      return Err(Box::from("symbols repository flush failed"))
+    let client = ApiClient::new(ctx.clone(), "spot");
+    let exchange_info: ExchangeInfo = client.get("/api/v3/exchangeInfo", &HashMap::new(), Security::Public, 20).await?;
+
+    let scalping = ScalpingRepository::scan(ctx.clone()).await?;
+
+    let mut listings: Vec<String> = Vec::new();
+    for info in exchange_info.symbols.iter() {
+      let mut filters = Filters {
+        price: "".to_string(),
+        quote: "".to_string(),
+        notional: "".to_string(),
+        market_quote: "".to_string(),
+        orders: "".to_string(),
+        percent_price: "".to_string(),
+        iceberg_parts: "".to_string(),
+        trailing_delta: "".to_string(),
+        others: BTreeMap::new(),
+      };
+      let mut max_orders = "".to_string();
+      let mut max_algo_orders = "".to_string();
+      for filter in info.filters.iter() {
+        match field(filter, "filterType").as_str() {
+          "PRICE_FILTER" => {
+            filters.price = format!("{},{},{}", field(filter, "minPrice"), field(filter, "maxPrice"), field(filter, "tickSize"));
+          }
+          "LOT_SIZE" => {
+            filters.quote = format!("{},{},{}", field(filter, "minQty"), field(filter, "maxQty"), field(filter, "stepSize"));
+          }
+          "MARKET_LOT_SIZE" => {
+            filters.market_quote = format!("{},{},{}", field(filter, "minQty"), field(filter, "maxQty"), field(filter, "stepSize"));
+          }
+          "NOTIONAL" => {
+            filters.notional = format!("{},{}", field(filter, "minNotional"), field(filter, "maxNotional"));
+          }
+          "MIN_NOTIONAL" => {
+            filters.notional = format!("{},", field(filter, "minNotional"));
+          }
+          "MAX_NUM_ORDERS" => {
+            max_orders = field(filter, "maxNumOrders");
+          }
+          "MAX_NUM_ALGO_ORDERS" => {
+            max_algo_orders = field(filter, "maxNumAlgoOrders");
+          }
+          "PERCENT_PRICE_BY_SIDE" => {
+            filters.percent_price = format!(
+              "{},{},{},{}",
+              field(filter, "bidMultiplierUp"),
+              field(filter, "bidMultiplierDown"),
+              field(filter, "askMultiplierUp"),
+              field(filter, "askMultiplierDown"),
+            );
+          }
+          "ICEBERG_PARTS" => {
+            filters.iceberg_parts = field(filter, "limit");
+          }
+          "TRAILING_DELTA" => {
+            filters.trailing_delta = format!(
+              "{},{},{},{}",
+              field(filter, "minTrailingAboveDelta"),
+              field(filter, "maxTrailingAboveDelta"),
+              field(filter, "minTrailingBelowDelta"),
+              field(filter, "maxTrailingBelowDelta"),
+            );
+          }
+          filter_type => {
+            let filter: BTreeMap<&String, &serde_json::Value> = filter.iter().collect();
+            filters.others.insert(filter_type.to_string(), serde_json::to_value(filter)?);
+          }
+        }
+      }
+      filters.orders = format!("{},{}", max_orders, max_algo_orders);
+
+      listings.push(info.symbol.clone());
+
+      let entity = match Self::get(ctx.clone(), &info.symbol).await {
+        Ok(Some(result)) => Some(result),
+        Ok(None) => None,
+        Err(err) => return Err(err.into()),
+      };
+      if entity.is_none() {
+        let id = xid::new().to_string();
+        match Self::create(
+          ctx.clone(),
+          id,
+          info.symbol.clone(),
+          info.base_asset.clone(),
+          info.quote_asset.clone(),
+          filters,
+          info.is_spot,
+          info.is_margin,
+          info.status.clone(),
+        ).await {
+          Ok(result) => {
+            println!("binance spot symbol {0:} create success {result:}", info.symbol);
+            Self::change(ctx.clone(), info.symbol.as_str(), "status", "", info.status.as_str()).await?;
+          }
+          Err(err) => {
+            println!("binance spot symbol {0:} create failed {err:?}", info.symbol)
+          }
+        }
+        continue
+      }
+
+      let entity = entity.unwrap();
+      let old_filters = serde_json::to_string(&entity.filters)?;
+      let new_filters = serde_json::to_string(&filters)?;
+      if old_filters != new_filters {
+        Self::update(ctx.clone(), entity.id.clone(), (
+          symbols::filters.eq(filters),
+          symbols::updated_at.eq(Utc::now()),
+        )).await?;
+        Self::change(ctx.clone(), info.symbol.as_str(), "filters", old_filters.as_str(), new_filters.as_str()).await?;
+        println!("binance spot symbol {0:} filters changed", info.symbol);
+      }
+
+      if entity.is_spot != info.is_spot || entity.is_margin != info.is_margin {
+        Self::update(ctx.clone(), entity.id.clone(), (
+          symbols::is_spot.eq(info.is_spot),
+          symbols::is_margin.eq(info.is_margin),
+          symbols::updated_at.eq(Utc::now()),
+        )).await?;
+        Self::change(
+          ctx.clone(),
+          info.symbol.as_str(),
+          "permissions",
+          format!("{},{}", entity.is_spot, entity.is_margin).as_str(),
+          format!("{},{}", info.is_spot, info.is_margin).as_str(),
+        ).await?;
+        println!("binance spot symbol {0:} permissions changed", info.symbol);
+      }
+
+      if entity.status != info.status {
+        Self::transit(ctx.clone(), entity.id.clone(), &info.symbol, &entity.status, &info.status, &scalping).await?;
+      }
     }
+
+    let pool = ctx.pool.read().await;
+    let mut conn = pool.get().unwrap();
+    let entities = symbols::table
+      .select((symbols::id, symbols::symbol, symbols::status))
+      .filter(symbols::status.ne("DELISTED"))
+      .load::<(String, String, String)>(&mut conn)?;
+    drop(conn);
+    drop(pool);
+
+    for (id, symbol, status) in entities.iter() {
+      if listings.contains(symbol) {
+        continue
+      }
+      Self::transit(ctx.clone(), id.clone(), symbol, status, &"DELISTED".to_string(), &scalping).await?;
+    }
+
+    Ok(())
+  }
+
+  async fn transit(
+    ctx: Ctx,
+    id: String,
+    symbol: &String,
+    old_status: &String,
+    new_status: &String,
+    scalping: &Vec<String>,
+  ) -> Result<(), Box<dyn std::error::Error>> {
+    Self::update(ctx.clone(), id, (
+      symbols::status.eq(new_status),
+      symbols::updated_at.eq(Utc::now()),
+    )).await?;
+    Self::change(ctx.clone(), symbol.as_str(), "status", old_status.as_str(), new_status.as_str()).await?;
+    println!("binance spot symbol {symbol:} status {old_status:} -> {new_status:}");
+
+    if old_status == "TRADING" && scalping.contains(symbol) {
+      let job = SymbolsJob::new(ctx.clone());
+      job.halt(symbol.as_str(), new_status.as_str()).await?;
+    }
+
     Ok(())
   }
 
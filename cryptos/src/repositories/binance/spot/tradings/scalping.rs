@@ -10,12 +10,9 @@ use crate::config::binance::spot::config as Config;
 use crate::models::binance::spot::tradings::scalping::*;
 use crate::schema::binance::spot::tradings::scalping::*;
 use crate::repositories::binance::ApiError;
-use crate::repositories::binance::spot::tickers::*;
-use crate::repositories::binance::spot::symbols::*;
-use crate::repositories::binance::spot::account::*;
+use crate::repositories::exchange::*;
 use crate::repositories::binance::spot::positions::*;
 use crate::repositories::binance::spot::plans::*;
-use crate::repositories::binance::spot::orders::*;
 use crate::repositories::binance::spot::scalping::ScalpingRepository as ParentRepositoy;
 use crate::repositories::binance::spot::scalping::plans::PlansRepository as ScalpingPlansRepository;
 
@@ -125,8 +122,9 @@ impl ScalpingRepository {
     }
   }
 
-  pub async fn place<T>(ctx: Ctx, plan_id: T) -> Result<(), Box<dyn std::error::Error>> 
+  pub async fn place<E, T>(ctx: Ctx, exchange: &E, plan_id: T) -> Result<(), Box<dyn std::error::Error>> 
   where
+    E: Exchange,
     T: AsRef<str>
   {
     let plan_id = plan_id.as_ref();
@@ -144,14 +142,19 @@ impl ScalpingRepository {
 
     let timestamp = Utc::now().timestamp();
 
-    if plan.interval == "1m" && plan.created_at.timestamp() < timestamp - 900
-      || plan.interval == "15m" && plan.created_at.timestamp() < timestamp - 2700
-      || plan.interval == "4h" && plan.created_at.timestamp() < timestamp - 5400
-      || plan.interval == "1d" && plan.created_at.timestamp() < timestamp - 21600 {
+    let timeframe = Interval::parse(&plan.interval)?;
+    if plan.created_at.timestamp() < timestamp - timeframe.expiry() {
       let _ = ScalpingPlansRepository::delete(ctx.clone(), plan_id).await;
       return Err(Box::from(format!("plan has been expired")))
     }
 
+    let tier = match timeframe {
+      Interval::Minute1 | Interval::Minute3 | Interval::Minute5 => "1m",
+      Interval::Minute15 | Interval::Minute30 | Interval::Hour1 | Interval::Hour2 => "15m",
+      Interval::Hour4 | Interval::Hour6 | Interval::Hour8 | Interval::Hour12 => "4h",
+      _ => "1d",
+    };
+
     let scalping = match ParentRepositoy::get(ctx.clone(), plan.symbol.clone()).await {
       Ok(Some(result)) => result,
       Ok(None) => return Err(Box::from(format!("scalping of {0:} not exists", plan.symbol))),
@@ -163,21 +166,18 @@ impl ScalpingRepository {
       return Err(Box::from(format!("plan of {0:} higher than scalping price {1:}", plan.symbol, scalping.price)))
     }
 
-    let price = match TickersRepository::price(
-      ctx.clone(),
-      plan.symbol.clone(),
-    ).await {
+    let price = match exchange.price(&plan.symbol).await {
       Ok(price) => price,
       Err(err) => return Err(err.into()),
     };
     let price = Decimal::from_f64(price).unwrap();
 
-    let (tick_size, step_size) = match SymbolsRepository::filters(ctx.clone(), scalping.symbol.clone()).await {
+    let filters = match exchange.filters(&scalping.symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
-    let tick_size = Decimal::from_f64(tick_size).unwrap();
-    let step_size = Decimal::from_f64(step_size).unwrap();
+    let tick_size = Decimal::from_f64(filters.tick_size).unwrap();
+    let step_size = Decimal::from_f64(filters.step_size).unwrap();
     let notional = dec!(10.0);
 
     let mut buy_price = Decimal::from_f64(plan.price).unwrap();
@@ -203,23 +203,23 @@ impl ScalpingRepository {
 
     let mut sell_price = dec!(0.0);
     if plan.amount > 15.0 {
-      if plan.interval == "1m" {
+      if tier == "1m" {
         sell_price = buy_price * dec!(1.0105);
-      } else if plan.interval == "15m" {
+      } else if tier == "15m" {
         sell_price = buy_price * dec!(1.0125);
-      } else if plan.interval == "4h" {
+      } else if tier == "4h" {
         sell_price = buy_price * dec!(1.0185);
-      } else if plan.interval == "1d" {
+      } else if tier == "1d" {
         sell_price = buy_price * dec!(1.0385);
       }
     } else {
-      if plan.interval == "1m" {
+      if tier == "1m" {
         sell_price = buy_price * dec!(1.0085);
-      } else if plan.interval == "15m" {
+      } else if tier == "15m" {
         sell_price = buy_price * dec!(1.0105);
-      } else if plan.interval == "4h" {
+      } else if tier == "4h" {
         sell_price = buy_price * dec!(1.012);
-      } else if plan.interval == "1d" {
+      } else if tier == "1d" {
         sell_price = buy_price * dec!(1.0135);
       }
     }
@@ -237,18 +237,12 @@ impl ScalpingRepository {
       return Err(Box::from(format!("scalping of {0:} can not buy now", plan.symbol)))
     }
 
-    let (_, quote_asset) = match SymbolsRepository::pairs(
-      ctx.clone(),
-      plan.symbol.clone(),
-    ).await {
+    let (_, quote_asset) = match exchange.pairs(&plan.symbol).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
 
-    let (free, _) = match AccountRepository::balance(
-      ctx.clone(),
-      &quote_asset,
-    ).await {
+    let free = match exchange.balance(&quote_asset).await {
       Ok(result) => result,
       Err(err) => return Err(err.into()),
     };
@@ -257,13 +251,13 @@ impl ScalpingRepository {
       return Err(Box::from(format!("scalping of {0:} free not enough", plan.symbol)))
     }
 
-    let order_id = match OrdersRepository::submit(
-      ctx.clone(),
-      &plan.symbol[..],
-      "BUY",
-      buy_price.to_f64().unwrap(),
-      buy_quantity.to_f64().unwrap(),
-    ).await {
+    let order_id = match exchange.place(&OrderRequest {
+      symbol: plan.symbol.clone(),
+      side: plan.side,
+      is_open: true,
+      price: buy_price.to_f64().unwrap(),
+      quantity: buy_quantity.to_f64().unwrap(),
+    }).await {
       Ok(result) => result,
       Err(err) => {
         if err.is::<ApiError>() {
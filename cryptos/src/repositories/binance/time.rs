@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use chrono::prelude::Utc;
+use redis::AsyncCommands;
+use serde::Deserialize;
+
+use crate::common::*;
+use crate::config::binance::futures::config as FuturesConfig;
+use crate::config::binance::spot::config as SpotConfig;
+use crate::config::binance::delivery::config as DeliveryConfig;
+use crate::repositories::binance::client::*;
+use crate::queue::nats::jobs::binance::time::*;
+
+#[derive(Deserialize)]
+struct ServerTime {
+  #[serde(alias = "serverTime")]
+  server_time: i64,
+}
+
+#[derive(Default)]
+pub struct TimeRepository {}
+
+impl TimeRepository {
+  fn redis_key(market: &str) -> &'static str {
+    match market {
+      "spot" => SpotConfig::REDIS_KEY_TIME_OFFSET,
+      "delivery" => DeliveryConfig::REDIS_KEY_TIME_OFFSET,
+      _ => FuturesConfig::REDIS_KEY_TIME_OFFSET,
+    }
+  }
+
+  pub async fn offset(ctx: Ctx, market: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let offset: Option<i64> = rdb.hget(Self::redis_key(market), "offset").await?;
+    Ok(offset.unwrap_or(0))
+  }
+
+  pub async fn timestamp(ctx: Ctx, market: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    Ok(Utc::now().timestamp_millis() + Self::offset(ctx, market).await?)
+  }
+
+  pub async fn flush(ctx: Ctx, market: &'static str) -> Result<i64, Box<dyn std::error::Error>> {
+    let path = match market {
+      "spot" => "/api/v3/time",
+      "delivery" => "/dapi/v1/time",
+      _ => "/fapi/v1/time",
+    };
+
+    let client = ApiClient::new(ctx.clone(), market);
+    let started_at = Utc::now().timestamp_millis();
+    let info: ServerTime = client.get(path, &HashMap::new(), Security::Public, 1).await?;
+    let finished_at = Utc::now().timestamp_millis();
+
+    // slow round trips say little about the clock
+    let latency = finished_at - started_at;
+    if latency > 1000 {
+      return Err(Box::from(format!("binance {} time latency {}ms too high", market, latency)))
+    }
+    let sample = info.server_time - (started_at + finished_at) / 2;
+
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let redis_key = Self::redis_key(market);
+    let last: Option<i64> = rdb.hget(redis_key, "offset").await?;
+    let offset = match last {
+      // a clock step is taken as is, small jitter is smoothed out
+      Some(last) if (sample - last).abs() < 1000 => last + (sample - last) / 5,
+      _ => sample,
+    };
+    () = rdb.hset_multiple(
+      redis_key,
+      &[
+        ("offset", offset),
+        ("sample", sample),
+        ("latency", latency),
+        ("timestamp", finished_at),
+      ],
+    ).await?;
+
+    let drift_limit = match Env::var(format!("BINANCE_{}_TIME_DRIFT_LIMIT", market.to_uppercase())).parse::<i64>() {
+      Ok(limit) if limit > 0 => limit,
+      _ => 1000,
+    };
+    if offset.abs() > drift_limit {
+      println!("alarm: binance {} time drift {}ms over {}ms", market, offset, drift_limit);
+      let job = TimeJob::new(ctx.clone());
+      job.drift(market, offset, drift_limit).await?;
+    }
+
+    Ok(offset)
+  }
+}
@@ -0,0 +1,55 @@
+use std::future::Future;
+
+// venue neutral view of a market, strategies and tradings only talk to this
+pub trait Exchange {
+  fn name(&self) -> &'static str;
+
+  fn klines(&self, symbol: &str, interval: &str, endtime: i64, limit: i64) -> impl Future<Output = Result<(), Box<dyn std::error::Error>>>;
+
+  fn price(&self, symbol: &str) -> impl Future<Output = Result<f64, Box<dyn std::error::Error>>>;
+
+  fn depth(&self, symbol: &str, limit: i64) -> impl Future<Output = Result<Book, Box<dyn std::error::Error>>>;
+
+  fn filters(&self, symbol: &str) -> impl Future<Output = Result<SymbolFilters, Box<dyn std::error::Error>>>;
+
+  fn pairs(&self, symbol: &str) -> impl Future<Output = Result<(String, String), Box<dyn std::error::Error>>>;
+
+  fn balance(&self, asset: &str) -> impl Future<Output = Result<f64, Box<dyn std::error::Error>>>;
+
+  fn place(&self, order: &OrderRequest) -> impl Future<Output = Result<i64, Box<dyn std::error::Error>>>;
+
+  fn cancel(&self, symbol: &str, order_id: i64) -> impl Future<Output = Result<OrderState, Box<dyn std::error::Error>>>;
+
+  fn query(&self, symbol: &str, order_id: i64) -> impl Future<Output = Result<OrderState, Box<dyn std::error::Error>>>;
+}
+
+pub struct Book {
+  pub asks: Vec<(f64, f64)>,
+  pub bids: Vec<(f64, f64)>,
+}
+
+pub struct SymbolFilters {
+  pub tick_size: f64,
+  pub step_size: f64,
+  pub notional: f64,
+}
+
+pub struct OrderRequest {
+  pub symbol: String,
+  // 1 long, 2 short, spot only knows long
+  pub side: i32,
+  // true opens or adds to the position, false reduces it
+  pub is_open: bool,
+  pub price: f64,
+  pub quantity: f64,
+}
+
+#[derive(Debug)]
+pub struct OrderState {
+  pub order_id: i64,
+  pub status: String,
+  pub price: f64,
+  pub quantity: f64,
+  pub executed_quantity: f64,
+  pub avg_price: f64,
+}
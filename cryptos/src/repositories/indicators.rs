@@ -0,0 +1,5 @@
+pub mod engine;
+pub mod maths;
+pub mod signals;
+
+pub use engine::*;
\ No newline at end of file
@@ -0,0 +1,1246 @@
+use std::collections::VecDeque;
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::repositories::indicators::maths;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+  pub timestamp: i64,
+  pub open: f64,
+  pub high: f64,
+  pub low: f64,
+  pub close: f64,
+  pub volume: f64,
+}
+
+impl Candle {
+  pub fn new(timestamp: i64, open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+    Self {
+      timestamp: timestamp,
+      open: open,
+      high: high,
+      low: low,
+      close: close,
+      volume: volume,
+    }
+  }
+
+  // the batch path goes through decimals for these prices, doing the same keeps both outputs equal
+  pub fn typical_price(&self) -> f64 {
+    maths::typical_price(self.close, self.high, self.low)
+  }
+
+  pub fn average_price(&self) -> f64 {
+    maths::average_price(self.open, self.close, self.high, self.low)
+  }
+
+  fn decimal_high(&self) -> f64 {
+    Decimal::from_f64(self.high).unwrap().to_f64().unwrap()
+  }
+
+  fn decimal_low(&self) -> f64 {
+    Decimal::from_f64(self.low).unwrap().to_f64().unwrap()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineParams {
+  pub atr_period: usize,
+  pub zlema_period: usize,
+  pub kdj_long_period: usize,
+  pub kdj_short_period: usize,
+  pub bbands_period: usize,
+  pub bbands_deviation: f64,
+  pub tenkan_period: usize,
+  pub kijun_period: usize,
+  pub senkou_period: usize,
+  pub volume_profile_segments: usize,
+  pub volume_profile_limit: usize,
+  pub andean_period: usize,
+  pub andean_length: usize,
+  pub andean_limit: usize,
+  pub rsi_period: usize,
+  pub macd_fast_period: usize,
+  pub macd_slow_period: usize,
+  pub macd_signal_period: usize,
+  pub adx_period: usize,
+  pub supertrend_period: usize,
+  pub supertrend_multiplier: f64,
+  pub ichimoku_limit: usize,
+  pub limit: usize,
+}
+
+impl EngineParams {
+  // candles loaded on warm up, enough for the longest batch window
+  pub fn warmup_limit(&self) -> usize {
+    self.limit.max(self.ichimoku_limit).max(self.volume_profile_limit).max(self.andean_limit)
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CloudLines {
+  pub conversion_line: Decimal,
+  pub base_line: Decimal,
+  pub last_conversion_line: Decimal,
+  pub last_base_line: Decimal,
+  pub senkou_span_a: Decimal,
+  pub senkou_span_b: Decimal,
+  pub chikou_span: Decimal,
+}
+
+// window sum kept up to date on every push
+#[derive(Debug, Clone)]
+struct Rolling {
+  size: usize,
+  values: VecDeque<f64>,
+  sum: f64,
+  pushes: usize,
+}
+
+impl Rolling {
+  fn new(size: usize) -> Self {
+    Self {
+      size: size,
+      values: VecDeque::with_capacity(size + 1),
+      sum: 0.0,
+      pushes: 0,
+    }
+  }
+
+  fn push(&mut self, value: f64) {
+    self.values.push_back(value);
+    self.sum += value;
+    if self.values.len() > self.size {
+      self.sum -= self.values.pop_front().unwrap();
+    }
+    self.pushes += 1;
+    // the running sum drifts, it is rebuilt once per full turn of the window
+    if self.pushes % self.size.max(1) == 0 {
+      self.sum = self.values.iter().sum::<f64>();
+    }
+  }
+
+  fn is_full(&self) -> bool {
+    self.values.len() == self.size
+  }
+}
+
+// monotonic queues, the front always holds the extreme of the window
+#[derive(Debug, Clone)]
+struct Extremes {
+  size: usize,
+  index: usize,
+  highs: VecDeque<(usize, f64)>,
+  lows: VecDeque<(usize, f64)>,
+}
+
+impl Extremes {
+  fn new(size: usize) -> Self {
+    Self {
+      size: size,
+      index: 0,
+      highs: VecDeque::new(),
+      lows: VecDeque::new(),
+    }
+  }
+
+  fn push(&mut self, value: f64) {
+    self.index += 1;
+    while matches!(self.highs.back(), Some(&(_, v)) if v <= value) {
+      self.highs.pop_back();
+    }
+    self.highs.push_back((self.index, value));
+    while matches!(self.lows.back(), Some(&(_, v)) if v >= value) {
+      self.lows.pop_back();
+    }
+    self.lows.push_back((self.index, value));
+    while matches!(self.highs.front(), Some(&(i, _)) if i + self.size <= self.index) {
+      self.highs.pop_front();
+    }
+    while matches!(self.lows.front(), Some(&(i, _)) if i + self.size <= self.index) {
+      self.lows.pop_front();
+    }
+  }
+
+  fn max(&self) -> Option<f64> {
+    self.highs.front().map(|&(_, v)| v)
+  }
+
+  fn min(&self) -> Option<f64> {
+    self.lows.front().map(|&(_, v)| v)
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Kdj {
+  long_period: usize,
+  short_period: usize,
+  highs: VecDeque<f64>,
+  lows: VecDeque<f64>,
+  fastk: VecDeque<f64>,
+  slowk: VecDeque<f64>,
+}
+
+impl Kdj {
+  fn new(long_period: usize, short_period: usize) -> Self {
+    Self {
+      long_period: long_period,
+      short_period: short_period,
+      highs: VecDeque::new(),
+      lows: VecDeque::new(),
+      fastk: VecDeque::new(),
+      slowk: VecDeque::new(),
+    }
+  }
+
+  fn fastk(&self, candle: &Candle) -> Option<f64> {
+    if self.highs.len() < self.long_period - 1 {
+      return None
+    }
+    let highest = self.highs.iter().fold(candle.decimal_high(), |a, &b| a.max(b));
+    let lowest = self.lows.iter().fold(candle.decimal_low(), |a, &b| a.min(b));
+    let diff = (highest - lowest) / 100.0;
+    if diff == 0.0 {
+      return Some(0.0)
+    }
+    Some((candle.typical_price() - lowest) / diff)
+  }
+
+  fn average(values: &VecDeque<f64>, value: f64, period: usize) -> Option<f64> {
+    if values.len() < period - 1 {
+      return None
+    }
+    Some((values.iter().sum::<f64>() + value) / period as f64)
+  }
+
+  fn commit(&mut self, candle: &Candle) {
+    if let Some(fastk) = self.fastk(candle) {
+      if let Some(slowk) = Self::average(&self.fastk, fastk, self.short_period) {
+        self.slowk.push_back(slowk);
+        if self.slowk.len() > self.short_period - 1 {
+          self.slowk.pop_front();
+        }
+      }
+      self.fastk.push_back(fastk);
+      if self.fastk.len() > self.short_period - 1 {
+        self.fastk.pop_front();
+      }
+    }
+    self.highs.push_back(candle.decimal_high());
+    self.lows.push_back(candle.decimal_low());
+    if self.highs.len() > self.long_period - 1 {
+      self.highs.pop_front();
+      self.lows.pop_front();
+    }
+  }
+
+  fn peek(&self, candle: &Candle) -> Option<(f64, f64)> {
+    let fastk = self.fastk(candle)?;
+    let slowk = Self::average(&self.fastk, fastk, self.short_period)?;
+    let slowd = Self::average(&self.slowk, slowk, self.short_period)?;
+    Some((slowk, slowd))
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Bbands {
+  period: usize,
+  deviation: f64,
+  prices: VecDeque<f64>,
+  bands: VecDeque<(f64, f64, f64)>,
+  highs: VecDeque<f64>,
+  lows: VecDeque<f64>,
+}
+
+impl Bbands {
+  fn new(period: usize, deviation: f64) -> Self {
+    Self {
+      period: period,
+      deviation: deviation,
+      prices: VecDeque::new(),
+      bands: VecDeque::new(),
+      highs: VecDeque::new(),
+      lows: VecDeque::new(),
+    }
+  }
+
+  // sma bands, TA-Lib flattens a variance under 1e-8 to zero
+  fn band(&self, price: f64) -> Option<(f64, f64, f64)> {
+    if self.prices.len() < self.period - 1 {
+      return None
+    }
+    let period = self.period as f64;
+    let sum = self.prices.iter().sum::<f64>() + price;
+    let squares = self.prices.iter().map(|v| v * v).sum::<f64>() + price * price;
+    let middle = sum / period;
+    let variance = squares / period - middle * middle;
+    let deviation = if variance < 0.00000001 { 0.0 } else { variance.sqrt() };
+    Some((middle + deviation * self.deviation, middle, middle - deviation * self.deviation))
+  }
+
+  fn commit(&mut self, candle: &Candle) {
+    let price = candle.typical_price();
+    if let Some(band) = self.band(price) {
+      self.bands.push_back(band);
+      if self.bands.len() > 2 {
+        self.bands.pop_front();
+      }
+    }
+    self.prices.push_back(price);
+    if self.prices.len() > self.period - 1 {
+      self.prices.pop_front();
+    }
+    self.highs.push_back(candle.decimal_high());
+    self.lows.push_back(candle.decimal_low());
+    if self.highs.len() > self.period * 2 {
+      self.highs.pop_front();
+      self.lows.pop_front();
+    }
+  }
+
+  // same offsets as the batch path, %b looks back 2 * period - 2 candles on the high weighted price
+  fn peek(&self, candle: &Candle) -> Result<Option<[Decimal; 6]>, Box<dyn std::error::Error>> {
+    if self.bands.len() < 2 || self.highs.len() < self.period * 2 {
+      return Ok(None)
+    }
+    let (u3, _, l3) = match self.band(candle.typical_price()) {
+      Some(band) => band,
+      None => return Ok(None),
+    };
+    let (u1, m1, l1) = self.bands[0];
+    let (u2, _, l2) = self.bands[1];
+    if u1 == l1 || u2 == l2 || u3 == l3 {
+      return Err(Box::from("klined invalid"))
+    }
+
+    let price = |i: usize| Decimal::from_f64(self.highs[i] + self.highs[i] + self.lows[i]).unwrap() / dec!(3);
+    let (p1, p2, p3) = (price(0), price(1), price(2));
+    let b1 = (p1 - Decimal::from_f64(l1).unwrap()) / Decimal::from_f64(u1 - l1).unwrap();
+    let b2 = (p2 - Decimal::from_f64(l2).unwrap()) / Decimal::from_f64(u2 - l2).unwrap();
+    let b3 = (p3 - Decimal::from_f64(l3).unwrap()) / Decimal::from_f64(u3 - l3).unwrap();
+    let w1 = Decimal::from_f64(u1 - l1).unwrap() / Decimal::from_f64(m1).unwrap();
+    let w2 = Decimal::from_f64(u2 - l2).unwrap() / Decimal::from_f64(m1).unwrap();
+    let w3 = Decimal::from_f64(u3 - l3).unwrap() / Decimal::from_f64(m1).unwrap();
+
+    Ok(Some([b1, b2, b3, w1, w2, w3]))
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Ichimoku {
+  tenkan_period: usize,
+  kijun_period: usize,
+  senkou_period: usize,
+  conversion: Rolling,
+  base: Rolling,
+  senkou: Rolling,
+  chikou: Extremes,
+}
+
+impl Ichimoku {
+  fn new(tenkan_period: usize, kijun_period: usize, senkou_period: usize) -> Self {
+    Self {
+      tenkan_period: tenkan_period,
+      kijun_period: kijun_period,
+      senkou_period: senkou_period,
+      conversion: Rolling::new(tenkan_period - 1),
+      base: Rolling::new(kijun_period - 1),
+      senkou: Rolling::new(senkou_period - 1),
+      chikou: Extremes::new(kijun_period - 1),
+    }
+  }
+
+  fn commit(&mut self, price: f64) {
+    self.conversion.push(price);
+    self.base.push(price);
+    self.senkou.push(price);
+    self.chikou.push(price);
+  }
+
+  fn peek(&self, price: f64) -> Option<CloudLines> {
+    if !self.conversion.is_full() || !self.base.is_full() || !self.senkou.is_full() {
+      return None
+    }
+    let average = |sum: f64, size: usize| Decimal::from_f64(sum).unwrap() / Decimal::from_usize(size).unwrap();
+
+    let last_conversion_line = average(self.conversion.sum, self.tenkan_period - 1);
+    let last_base_line = average(self.base.sum, self.kijun_period - 1);
+    let conversion_line = average(self.conversion.sum + price, self.tenkan_period);
+    let base_line = average(self.base.sum + price, self.kijun_period);
+    let senkou_span_a = (conversion_line + base_line) / dec!(2);
+    let senkou_span_b = average(self.senkou.sum + price, self.senkou_period);
+
+    let chikou_span_min = self.chikou.min()?.min(price);
+    let chikou_span_max = self.chikou.max()?.max(price);
+    let chikou_span = Decimal::from_f64(chikou_span_min + chikou_span_max).unwrap() / dec!(2);
+
+    Some(CloudLines {
+      conversion_line: conversion_line,
+      base_line: base_line,
+      last_conversion_line: last_conversion_line,
+      last_base_line: last_base_line,
+      senkou_span_a: senkou_span_a,
+      senkou_span_b: senkou_span_b,
+      chikou_span: chikou_span,
+    })
+  }
+}
+
+#[derive(Debug, Clone)]
+struct VolumeProfile {
+  limit: usize,
+  segments: usize,
+  items: VecDeque<(f64, f64)>,
+}
+
+impl VolumeProfile {
+  fn new(limit: usize, segments: usize) -> Self {
+    Self {
+      limit: limit,
+      segments: segments,
+      items: VecDeque::new(),
+    }
+  }
+
+  fn commit(&mut self, candle: &Candle) {
+    self.items.push_back((candle.typical_price(), candle.volume));
+    if self.items.len() > self.limit - 1 {
+      self.items.pop_front();
+    }
+  }
+
+  // the segments are scaled to the window range, so they are rebuilt from the window kept in memory
+  fn peek(&self, candle: &Candle) -> Result<Option<(Decimal, Decimal, Decimal, Decimal)>, Box<dyn std::error::Error>> {
+    if self.items.len() < self.limit - 1 {
+      return Ok(None)
+    }
+    let mut prices: Vec<f64> = self.items.iter().map(|v| v.0).collect();
+    let mut volumes: Vec<f64> = self.items.iter().map(|v| v.1).collect();
+    prices.push(candle.typical_price());
+    volumes.push(candle.volume);
+    Ok(Some(maths::volume_profile(&prices, &volumes, self.segments)?))
+  }
+}
+
+fn is_zero(value: f64) -> bool {
+  -0.00000001 < value && value < 0.00000001
+}
+
+// ema seeded with the sma of the first period values, as TA-Lib does
+#[derive(Debug, Clone, Copy)]
+struct Ema {
+  period: usize,
+  k: f64,
+  count: usize,
+  value: f64,
+}
+
+impl Ema {
+  fn new(period: usize) -> Self {
+    Self {
+      period: period,
+      k: 2.0 / (period as f64 + 1.0),
+      count: 0,
+      value: 0.0,
+    }
+  }
+
+  fn push(&mut self, value: f64) {
+    self.count += 1;
+    if self.count < self.period {
+      self.value += value;
+    } else if self.count == self.period {
+      self.value = (self.value + value) / self.period as f64;
+    } else {
+      self.value = (value - self.value) * self.k + self.value;
+    }
+  }
+
+  fn value(&self) -> Option<f64> {
+    if self.count < self.period {
+      return None
+    }
+    Some(self.value)
+  }
+}
+
+// wilder smoothing of the true range, seeded with the sma of the first period ranges
+#[derive(Debug, Clone, Copy)]
+struct Atr {
+  period: usize,
+  close: Option<f64>,
+  count: usize,
+  value: f64,
+}
+
+impl Atr {
+  fn new(period: usize) -> Self {
+    Self {
+      period: period,
+      close: None,
+      count: 0,
+      value: 0.0,
+    }
+  }
+
+  fn push(&mut self, candle: &Candle) {
+    if let Some(close) = self.close {
+      let range = (candle.high - candle.low).max((close - candle.high).abs()).max((close - candle.low).abs());
+      let period = self.period as f64;
+      self.count += 1;
+      if self.count < self.period {
+        self.value += range;
+      } else if self.count == self.period {
+        self.value = (self.value + range) / period;
+      } else {
+        self.value = (self.value * (period - 1.0) + range) / period;
+      }
+    }
+    self.close = Some(candle.close);
+  }
+
+  fn value(&self) -> Option<f64> {
+    if self.count < self.period {
+      return None
+    }
+    Some(self.value)
+  }
+}
+
+// ema of the price against the price lag candles later, the lagged prices are kept
+#[derive(Debug, Clone)]
+struct Zlema {
+  lag: usize,
+  prices: VecDeque<f64>,
+  ema: Ema,
+}
+
+impl Zlema {
+  fn new(period: usize) -> Self {
+    Self {
+      lag: (period - 1) / 2,
+      prices: VecDeque::new(),
+      ema: Ema::new(period),
+    }
+  }
+
+  fn lagged(&self, price: f64) -> Option<f64> {
+    if self.lag == 0 {
+      return Some(price)
+    }
+    if self.prices.len() < self.lag {
+      return None
+    }
+    Some(self.prices[0])
+  }
+
+  fn commit(&mut self, price: f64) {
+    if let Some(lagged) = self.lagged(price) {
+      self.ema.push(lagged - price);
+    }
+    if self.lag > 0 {
+      self.prices.push_back(price);
+      if self.prices.len() > self.lag {
+        self.prices.pop_front();
+      }
+    }
+  }
+
+  fn peek(&self, price: f64) -> Option<(f64, f64)> {
+    let last_value = self.ema.value()?;
+    let mut ema = self.ema;
+    ema.push(self.lagged(price)? - price);
+    Some((last_value, ema.value()?))
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rsi {
+  period: usize,
+  price: Option<f64>,
+  count: usize,
+  gain: f64,
+  loss: f64,
+}
+
+impl Rsi {
+  fn new(period: usize) -> Self {
+    Self {
+      period: period,
+      price: None,
+      count: 0,
+      gain: 0.0,
+      loss: 0.0,
+    }
+  }
+
+  fn push(&mut self, price: f64) {
+    if let Some(last_price) = self.price {
+      let period = self.period as f64;
+      let diff = price - last_price;
+      self.count += 1;
+      if self.count > self.period {
+        self.gain *= period - 1.0;
+        self.loss *= period - 1.0;
+      }
+      if diff < 0.0 {
+        self.loss -= diff;
+      } else {
+        self.gain += diff;
+      }
+      if self.count >= self.period {
+        self.gain /= period;
+        self.loss /= period;
+      }
+    }
+    self.price = Some(price);
+  }
+
+  fn value(&self) -> Option<f64> {
+    if self.count < self.period {
+      return None
+    }
+    let total = self.gain + self.loss;
+    if is_zero(total) {
+      return Some(0.0)
+    }
+    Some(100.0 * (self.gain / total))
+  }
+
+  fn peek(&self, price: f64) -> Option<(f64, f64)> {
+    let last_value = self.value()?;
+    let mut rsi = *self;
+    rsi.push(price);
+    Some((last_value, rsi.value()?))
+  }
+}
+
+// the fast ema starts slow - fast prices later, so both are seeded on the same price
+#[derive(Debug, Clone, Copy)]
+struct Macd {
+  skip: usize,
+  count: usize,
+  fast: Ema,
+  slow: Ema,
+  signal: Ema,
+  values: Option<[f64; 3]>,
+}
+
+impl Macd {
+  fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+    Self {
+      skip: slow_period - fast_period,
+      count: 0,
+      fast: Ema::new(fast_period),
+      slow: Ema::new(slow_period),
+      signal: Ema::new(signal_period),
+      values: None,
+    }
+  }
+
+  fn push(&mut self, price: f64) {
+    self.count += 1;
+    self.slow.push(price);
+    if self.count > self.skip {
+      self.fast.push(price);
+    }
+    if let (Some(fast), Some(slow)) = (self.fast.value(), self.slow.value()) {
+      let macd = fast - slow;
+      self.signal.push(macd);
+      if let Some(signal) = self.signal.value() {
+        self.values = Some([macd, signal, macd - signal]);
+      }
+    }
+  }
+
+  fn peek(&self, price: f64) -> Option<[f64; 4]> {
+    let [_, _, last_hist] = self.values?;
+    let mut macd = *self;
+    macd.push(price);
+    let [value, signal, hist] = macd.values?;
+    Some([value, signal, hist, last_hist])
+  }
+}
+
+// the directional movements and the true range are summed over period - 1 candles,
+// then wilder smoothed, the adx is seeded with the average of the first period dx
+#[derive(Debug, Clone, Copy)]
+struct Adx {
+  period: usize,
+  candle: Option<Candle>,
+  count: usize,
+  plus_dm: f64,
+  minus_dm: f64,
+  range: f64,
+  value: f64,
+}
+
+impl Adx {
+  fn new(period: usize) -> Self {
+    Self {
+      period: period,
+      candle: None,
+      count: 0,
+      plus_dm: 0.0,
+      minus_dm: 0.0,
+      range: 0.0,
+      value: 0.0,
+    }
+  }
+
+  fn di(&self) -> (f64, f64) {
+    if is_zero(self.range) {
+      return (0.0, 0.0)
+    }
+    (100.0 * (self.plus_dm / self.range), 100.0 * (self.minus_dm / self.range))
+  }
+
+  fn dx(&self) -> Option<f64> {
+    if is_zero(self.range) {
+      return None
+    }
+    let (plus_di, minus_di) = self.di();
+    let total = minus_di + plus_di;
+    if is_zero(total) {
+      return None
+    }
+    Some(100.0 * ((minus_di - plus_di).abs() / total))
+  }
+
+  fn push(&mut self, candle: &Candle) {
+    if let Some(last) = self.candle {
+      let diff_plus = candle.high - last.high;
+      let diff_minus = last.low - candle.low;
+      let (plus_dm, minus_dm) = if diff_minus > 0.0 && diff_plus < diff_minus {
+        (0.0, diff_minus)
+      } else if diff_plus > 0.0 && diff_plus > diff_minus {
+        (diff_plus, 0.0)
+      } else {
+        (0.0, 0.0)
+      };
+      let range = (candle.high - candle.low).max((candle.high - last.close).abs()).max((candle.low - last.close).abs());
+
+      let period = self.period as f64;
+      self.count += 1;
+      if self.count < self.period {
+        self.plus_dm += plus_dm;
+        self.minus_dm += minus_dm;
+        self.range += range;
+      } else {
+        self.plus_dm = self.plus_dm - self.plus_dm / period + plus_dm;
+        self.minus_dm = self.minus_dm - self.minus_dm / period + minus_dm;
+        self.range = self.range - self.range / period + range;
+        if self.count < self.period * 2 {
+          if let Some(dx) = self.dx() {
+            self.value += dx;
+          }
+          if self.count == self.period * 2 - 1 {
+            self.value /= period;
+          }
+        } else if let Some(dx) = self.dx() {
+          self.value = (self.value * (period - 1.0) + dx) / period;
+        }
+      }
+    }
+    self.candle = Some(*candle);
+  }
+
+  fn peek(&self, candle: &Candle) -> Option<[f64; 3]> {
+    let mut adx = *self;
+    adx.push(candle);
+    if adx.count < adx.period * 2 - 1 {
+      return None
+    }
+    let (plus_di, minus_di) = adx.di();
+    Some([adx.value, plus_di, minus_di])
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SuperTrend {
+  multiplier: f64,
+  atr: Atr,
+  count: usize,
+  upper: f64,
+  lower: f64,
+  line: f64,
+  trend: i32,
+  last_trend: i32,
+}
+
+impl SuperTrend {
+  fn new(period: usize, multiplier: f64) -> Self {
+    Self {
+      multiplier: multiplier,
+      atr: Atr::new(period),
+      count: 0,
+      upper: f64::MAX,
+      lower: f64::MIN,
+      line: 0.0,
+      trend: 1,
+      last_trend: 1,
+    }
+  }
+
+  fn push(&mut self, candle: &Candle) {
+    let last_close = self.atr.close.unwrap_or(candle.close);
+    self.atr.push(candle);
+    let atr = match self.atr.value() {
+      Some(atr) => atr,
+      None => return,
+    };
+
+    let middle = (candle.high + candle.low) / 2.0;
+    let basic_upper = middle + self.multiplier * atr;
+    let basic_lower = middle - self.multiplier * atr;
+    if self.count == 0 || basic_upper < self.upper || last_close > self.upper {
+      self.upper = basic_upper;
+    }
+    if self.count == 0 || basic_lower > self.lower || last_close < self.lower {
+      self.lower = basic_lower;
+    }
+
+    self.last_trend = self.trend;
+    if self.trend == 1 && candle.close < self.lower {
+      self.trend = -1;
+    } else if self.trend == -1 && candle.close > self.upper {
+      self.trend = 1;
+    }
+    self.line = if self.trend == 1 { self.lower } else { self.upper };
+    self.count += 1;
+  }
+
+  fn peek(&self, candle: &Candle) -> Option<(f64, i32, i32)> {
+    let mut supertrend = *self;
+    supertrend.push(candle);
+    if supertrend.count < 2 {
+      return None
+    }
+    Some((supertrend.line, supertrend.trend, supertrend.last_trend))
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AndeanLines {
+  up1: f64,
+  up2: f64,
+  dn1: f64,
+  dn2: f64,
+  bull: f64,
+  bear: f64,
+  signal: f64,
+}
+
+// same decimal steps as the batch path, seeded on the close of the first candle
+#[derive(Debug, Clone, Copy)]
+struct Andean {
+  alpha: Decimal,
+  alpha_signal: Decimal,
+  lines: Option<AndeanLines>,
+}
+
+impl Andean {
+  fn new(period: usize, length: usize) -> Self {
+    Self {
+      alpha: dec!(2) / Decimal::from_usize(period + 1).unwrap(),
+      alpha_signal: dec!(2) / Decimal::from_usize(length + 1).unwrap(),
+      lines: None,
+    }
+  }
+
+  fn seed(candle: &Candle) -> AndeanLines {
+    let close = Decimal::from_f64(candle.close).unwrap();
+    let pow_close = close.powd(dec!(2)).to_f64().unwrap();
+    let close = close.to_f64().unwrap();
+    AndeanLines {
+      up1: close,
+      up2: pow_close,
+      dn1: close,
+      dn2: pow_close,
+      bull: 0.0,
+      bear: 0.0,
+      signal: close,
+    }
+  }
+
+  fn step(&self, lines: &AndeanLines, candle: &Candle) -> Result<AndeanLines, Box<dyn std::error::Error>> {
+    let close = Decimal::from_f64(candle.close).unwrap();
+    let pow_close = close.powd(dec!(2));
+    let open = Decimal::from_f64(candle.open).unwrap();
+    let pow_open = open.powd(dec!(2));
+    let last_up1 = Decimal::from_f64(lines.up1).unwrap();
+    let last_up2 = Decimal::from_f64(lines.up2).unwrap();
+    let last_dn1 = Decimal::from_f64(lines.dn1).unwrap();
+    let last_dn2 = Decimal::from_f64(lines.dn2).unwrap();
+    let last_signal = Decimal::from_f64(lines.signal).unwrap();
+
+    let up1 = close.max(open).max(last_up1 - self.alpha * (last_up1 - close)).to_f64().unwrap();
+    let up2 = pow_close.max(pow_open).max(last_up2 - self.alpha * (last_up2 - pow_close)).to_f64().unwrap();
+    let dn1 = close.min(open).min(last_dn1 + self.alpha * (close - last_dn1)).to_f64().unwrap();
+    let dn2 = pow_close.min(pow_open).min(last_dn2 + self.alpha * (pow_close - last_dn2)).to_f64().unwrap();
+
+    let value = Decimal::from_f64(dn2).unwrap() - Decimal::from_f64(dn1).unwrap().powd(dec!(2));
+    if value < dec!(0) {
+      return Err(Box::from(format!("bears not valid {value:}")))
+    }
+    let bull = value.sqrt().unwrap().to_f64().unwrap();
+
+    let value = Decimal::from_f64(up2).unwrap() - Decimal::from_f64(up1).unwrap().powd(dec!(2));
+    if value < dec!(0) {
+      return Err(Box::from(format!("bears not valid {value:}")))
+    }
+    let bear = value.sqrt().unwrap().to_f64().unwrap();
+
+    let signal = last_signal + self.alpha_signal * (Decimal::from_f64(bull.max(bear)).unwrap() - last_signal);
+
+    Ok(AndeanLines {
+      up1: up1,
+      up2: up2,
+      dn1: dn1,
+      dn2: dn2,
+      bull: bull,
+      bear: bear,
+      signal: signal.to_f64().unwrap(),
+    })
+  }
+
+  // a failed step seeds the oscillator again on that candle, as a batch window starting there would
+  fn commit(&mut self, candle: &Candle) {
+    self.lines = match self.lines {
+      Some(lines) => Some(self.step(&lines, candle).unwrap_or_else(|_| Self::seed(candle))),
+      None => Some(Self::seed(candle)),
+    };
+  }
+
+  fn peek(&self, candle: &Candle) -> Result<Option<(f64, f64, f64)>, Box<dyn std::error::Error>> {
+    let lines = match self.lines {
+      Some(lines) => self.step(&lines, candle)?,
+      None => return Ok(None),
+    };
+    Ok(Some((lines.bull, lines.bear, lines.signal)))
+  }
+}
+
+// per symbol and interval state of the indicators, closed candles are committed once
+// and the open candle is only peeked, so every update costs the same whatever the history
+#[derive(Debug, Clone)]
+pub struct IndicatorsEngine {
+  pub params: EngineParams,
+  last: Option<Candle>,
+  kdj: Kdj,
+  bbands: Bbands,
+  ichimoku: Ichimoku,
+  volume_profile: VolumeProfile,
+  atr: Atr,
+  zlema: Zlema,
+  ha_zlema: Zlema,
+  rsi: Rsi,
+  macd: Macd,
+  adx: Adx,
+  supertrend: SuperTrend,
+  andean: Andean,
+}
+
+impl IndicatorsEngine {
+  pub fn new(params: EngineParams) -> Self {
+    Self {
+      params: params,
+      last: None,
+      kdj: Kdj::new(params.kdj_long_period, params.kdj_short_period),
+      bbands: Bbands::new(params.bbands_period, params.bbands_deviation),
+      ichimoku: Ichimoku::new(params.tenkan_period, params.kijun_period, params.senkou_period),
+      volume_profile: VolumeProfile::new(params.volume_profile_limit, params.volume_profile_segments),
+      atr: Atr::new(params.atr_period),
+      zlema: Zlema::new(params.zlema_period),
+      ha_zlema: Zlema::new(params.zlema_period),
+      rsi: Rsi::new(params.rsi_period),
+      macd: Macd::new(params.macd_fast_period, params.macd_slow_period, params.macd_signal_period),
+      adx: Adx::new(params.adx_period),
+      supertrend: SuperTrend::new(params.supertrend_period, params.supertrend_multiplier),
+      andean: Andean::new(params.andean_period, params.andean_length),
+    }
+  }
+
+  // the ema and wilder smoothings are seeded once, on the candle their batch window starts at,
+  // so the first live candle after the warm up gets the same values as the batch path
+  pub fn warmup(params: EngineParams, candles: &[Candle]) -> Self {
+    let mut engine = Self::new(params);
+    for (i, candle) in candles.iter().enumerate() {
+      engine.push(candle, candles.len() - i + 1);
+    }
+    engine
+  }
+
+  // timestamp of the last committed candle
+  pub fn timestamp(&self) -> i64 {
+    self.last.map_or(0, |candle| candle.timestamp)
+  }
+
+  // the last committed candle, a revised close no longer matches it
+  pub fn last(&self) -> Option<&Candle> {
+    self.last.as_ref()
+  }
+
+  pub fn commit(&mut self, candle: &Candle) {
+    self.push(candle, 1);
+  }
+
+  // size counts the candles from this one to the live candle
+  fn push(&mut self, candle: &Candle, size: usize) {
+    self.last = Some(*candle);
+    self.kdj.commit(candle);
+    self.bbands.commit(candle);
+    self.ichimoku.commit(candle.typical_price());
+    self.volume_profile.commit(candle);
+    if size <= self.params.limit {
+      self.atr.push(candle);
+      self.zlema.commit(candle.close);
+      self.ha_zlema.commit(candle.average_price());
+      self.rsi.push(candle.close);
+      self.macd.push(candle.close);
+      self.adx.push(candle);
+      self.supertrend.push(candle);
+    }
+    if size <= self.params.andean_limit {
+      self.andean.commit(candle);
+    }
+  }
+
+  pub fn atr(&self, candle: &Candle) -> Option<f64> {
+    let mut atr = self.atr;
+    atr.push(candle);
+    atr.value()
+  }
+
+  pub fn zlema(&self, candle: &Candle) -> Option<(f64, f64)> {
+    self.zlema.peek(candle.close)
+  }
+
+  pub fn ha_zlema(&self, candle: &Candle) -> Option<(f64, f64)> {
+    self.ha_zlema.peek(candle.average_price())
+  }
+
+  pub fn kdj(&self, candle: &Candle) -> Option<(f64, f64)> {
+    self.kdj.peek(candle)
+  }
+
+  pub fn bbands(&self, candle: &Candle) -> Result<Option<[Decimal; 6]>, Box<dyn std::error::Error>> {
+    self.bbands.peek(candle)
+  }
+
+  pub fn ichimoku_cloud(&self, candle: &Candle) -> Option<CloudLines> {
+    self.ichimoku.peek(candle.typical_price())
+  }
+
+  pub fn volume_profile(&self, candle: &Candle) -> Result<Option<(Decimal, Decimal, Decimal, Decimal)>, Box<dyn std::error::Error>> {
+    self.volume_profile.peek(candle)
+  }
+
+  pub fn andean_oscillator(&self, candle: &Candle) -> Result<Option<(f64, f64, f64)>, Box<dyn std::error::Error>> {
+    self.andean.peek(candle)
+  }
+
+  pub fn rsi(&self, candle: &Candle) -> Option<(f64, f64)> {
+    self.rsi.peek(candle.close)
+  }
+
+  pub fn macd(&self, candle: &Candle) -> Option<[f64; 4]> {
+    self.macd.peek(candle.close)
+  }
+
+  pub fn adx(&self, candle: &Candle) -> Option<[f64; 3]> {
+    self.adx.peek(candle)
+  }
+
+  pub fn supertrend(&self, candle: &Candle) -> Option<(f64, i32, i32)> {
+    self.supertrend.peek(candle)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn candles() -> Vec<Candle> {
+    let data = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/indicators/klines.csv")).unwrap();
+    data.lines().skip(1).map(|line| {
+      let values: Vec<f64> = line.split(",").map(|value| value.parse::<f64>().unwrap()).collect();
+      Candle::new(values[0] as i64, values[1], values[2], values[3], values[4], values[5])
+    }).collect()
+  }
+
+  fn params() -> EngineParams {
+    EngineParams {
+      atr_period: 14,
+      zlema_period: 14,
+      kdj_long_period: 9,
+      kdj_short_period: 3,
+      bbands_period: 14,
+      bbands_deviation: 2.0,
+      tenkan_period: 9,
+      kijun_period: 26,
+      senkou_period: 52,
+      volume_profile_segments: 100,
+      volume_profile_limit: 120,
+      andean_period: 50,
+      andean_length: 9,
+      andean_limit: 150,
+      rsi_period: 14,
+      macd_fast_period: 12,
+      macd_slow_period: 26,
+      macd_signal_period: 9,
+      adx_period: 14,
+      supertrend_period: 10,
+      supertrend_multiplier: 3.0,
+      ichimoku_limit: 100,
+      limit: 100,
+    }
+  }
+
+  fn assert_close<V: ToPrimitive>(name: &str, value: V, target: V) {
+    let (value, target) = (value.to_f64().unwrap(), target.to_f64().unwrap());
+    assert!(
+      (value - target).abs() <= 1e-8 * target.abs().max(1.0),
+      "{name:} got {value:} expected {target:}",
+    );
+  }
+
+  // the windowed indicators match the batch windows, the recursive ones match the batch
+  // over every candle since the window they were seeded on at warm up
+  #[test]
+  fn engine_matches_batch_windows() {
+    let items = candles();
+    let params = params();
+    let start = params.warmup_limit() - 1;
+    let mut engine = IndicatorsEngine::warmup(params, &items[..start]);
+    for i in start..items.len() {
+      let live = items[i];
+      let window = |limit: usize| &items[i+1-limit..=i];
+      let history = |limit: usize| &items[start+1-limit..=i];
+      let column = |items: &[Candle], f: fn(&Candle) -> f64| items.iter().map(f).collect::<Vec<f64>>();
+
+      let highs = column(window(params.limit), |v| v.high);
+      let lows = column(window(params.limit), |v| v.low);
+      let prices = column(window(params.limit), |v| v.typical_price());
+
+      let (slowk, slowd) = engine.kdj(&live).unwrap();
+      let [k, d, _] = maths::kdj(&highs, &lows, &prices, params.kdj_long_period as i32, params.kdj_short_period as i32).unwrap();
+      assert_close("kdj k", Decimal::from_f64(slowk).unwrap(), k);
+      assert_close("kdj d", Decimal::from_f64(slowd).unwrap(), d);
+
+      let values = engine.bbands(&live).unwrap().unwrap();
+      let targets = maths::bbands(&prices, &highs, &lows, params.bbands_period as i32, params.bbands_deviation).unwrap();
+      for (value, target) in values.into_iter().zip(targets) {
+        assert_close("bbands", value, target);
+      }
+
+      let lines = engine.ichimoku_cloud(&live).unwrap();
+      let prices = column(window(params.ichimoku_limit), |v| v.typical_price());
+      let target = maths::ichimoku_cloud(&prices, params.tenkan_period, params.kijun_period, params.senkou_period).unwrap();
+      assert_close("conversion line", lines.conversion_line, target.conversion_line);
+      assert_close("base line", lines.base_line, target.base_line);
+      assert_close("last conversion line", lines.last_conversion_line, target.last_conversion_line);
+      assert_close("last base line", lines.last_base_line, target.last_base_line);
+      assert_close("senkou span a", lines.senkou_span_a, target.senkou_span_a);
+      assert_close("senkou span b", lines.senkou_span_b, target.senkou_span_b);
+      assert_close("chikou span", lines.chikou_span, target.chikou_span);
+
+      let (poc, vah, val, poc_ratio) = engine.volume_profile(&live).unwrap().unwrap();
+      let prices = column(window(params.volume_profile_limit), |v| v.typical_price());
+      let volumes = column(window(params.volume_profile_limit), |v| v.volume);
+      let target = maths::volume_profile(&prices, &volumes, params.volume_profile_segments).unwrap();
+      assert_close("poc", poc, target.0);
+      assert_close("vah", vah, target.1);
+      assert_close("val", val, target.2);
+      assert_close("poc ratio", poc_ratio, target.3);
+
+      let highs = column(history(params.limit), |v| v.high);
+      let lows = column(history(params.limit), |v| v.low);
+      let closes = column(history(params.limit), |v| v.close);
+
+      let target = maths::atr(&highs, &lows, &closes, params.atr_period as i32).unwrap();
+      assert_close("atr", engine.atr(&live).unwrap(), target);
+
+      let (last_value, value) = engine.zlema(&live).unwrap();
+      let target = maths::zlema(&closes, params.zlema_period as i32).unwrap();
+      assert_close("zlema last", last_value, target.0);
+      assert_close("zlema", value, target.1);
+
+      let (last_value, value) = engine.ha_zlema(&live).unwrap();
+      let target = maths::zlema(&column(history(params.limit), |v| v.average_price()), params.zlema_period as i32).unwrap();
+      assert_close("ha_zlema last", last_value, target.0);
+      assert_close("ha_zlema", value, target.1);
+
+      let (bull, bear, signal) = engine.andean_oscillator(&live).unwrap().unwrap();
+      let target = maths::andean_oscillator(
+        &column(history(params.andean_limit), |v| v.open),
+        &column(history(params.andean_limit), |v| v.close),
+        params.andean_period as i32,
+        params.andean_length as i32,
+      ).unwrap();
+      assert_close("ao bull", bull, target.0);
+      assert_close("ao bear", bear, target.1);
+      assert_close("ao signal", signal, target.2);
+
+      let (last_value, value) = engine.rsi(&live).unwrap();
+      let target = maths::rsi(&closes, params.rsi_period as i32).unwrap();
+      assert_close("rsi last", last_value, target.0);
+      assert_close("rsi", value, target.1);
+
+      let values = engine.macd(&live).unwrap();
+      let targets = maths::macd(&closes, params.macd_fast_period as i32, params.macd_slow_period as i32, params.macd_signal_period as i32).unwrap();
+      for (value, target) in values.into_iter().zip(targets) {
+        assert_close("macd", value, target);
+      }
+
+      let values = engine.adx(&live).unwrap();
+      let targets = maths::adx(&highs, &lows, &closes, params.adx_period as i32).unwrap();
+      for (value, target) in values.into_iter().zip(targets) {
+        assert_close("adx", value, target);
+      }
+
+      let (line, trend, last_trend) = engine.supertrend(&live).unwrap();
+      let target = maths::supertrend(&highs, &lows, &closes, params.supertrend_period as i32, params.supertrend_multiplier).unwrap();
+      assert_close("supertrend", line, target.0);
+      assert_eq!((trend, last_trend), (target.1, target.2));
+
+      engine.commit(&live);
+    }
+  }
+
+  #[test]
+  fn warmup_seeds_on_the_batch_windows() {
+    let items = candles();
+    let params = params();
+    let size = params.warmup_limit();
+    let live = items[size-1];
+    let engine = IndicatorsEngine::warmup(params, &items[..size-1]);
+    let mut history = IndicatorsEngine::new(params);
+    for candle in items[..size-1].iter() {
+      history.commit(candle);
+    }
+
+    let closes: Vec<f64> = items[size-params.limit..size].iter().map(|v| v.close).collect();
+    let target = maths::rsi(&closes, params.rsi_period as i32).unwrap();
+    let (last_value, value) = engine.rsi(&live).unwrap();
+    assert_close("rsi last", last_value, target.0);
+    assert_close("rsi", value, target.1);
+    let (_, value) = history.rsi(&live).unwrap();
+    assert!((value - target.1).abs() > 1e-8);
+  }
+
+  #[test]
+  fn revised_candle_is_not_reused() {
+    let items = candles();
+    let mut engine = IndicatorsEngine::new(params());
+    for candle in items[..10].iter() {
+      engine.commit(candle);
+    }
+    let mut revised = items[9];
+    revised.close += 1.0;
+    assert_eq!(engine.timestamp(), revised.timestamp);
+    assert_ne!(engine.last(), Some(&revised));
+  }
+}
\ No newline at end of file
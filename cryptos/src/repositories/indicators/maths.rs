@@ -0,0 +1,718 @@
+use std::collections::HashMap;
+
+use talib_sys::{TA_Integer, TA_Real, TA_ATR, TA_MA, TA_MAType_TA_MAType_EMA, TA_STOCH, TA_BBANDS, TA_RSI, TA_MACD, TA_ADX, TA_PLUS_DI, TA_MINUS_DI, TA_RetCode};
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use rust_decimal::MathematicalOps;
+
+use crate::repositories::indicators::engine::CloudLines;
+
+// every slice runs from the oldest kline to the newest one, the newest is the open kline
+
+pub fn typical_price(close: f64, high: f64, low: f64) -> f64 {
+  let close = Decimal::from_f64(close).unwrap();
+  let high = Decimal::from_f64(high).unwrap();
+  let low = Decimal::from_f64(low).unwrap();
+  ((close + high + low) / dec!(3)).to_f64().unwrap()
+}
+
+pub fn average_price(open: f64, close: f64, high: f64, low: f64) -> f64 {
+  let open = Decimal::from_f64(open).unwrap();
+  let close = Decimal::from_f64(close).unwrap();
+  let high = Decimal::from_f64(high).unwrap();
+  let low = Decimal::from_f64(low).unwrap();
+  ((open + close + high + low) / dec!(4)).to_f64().unwrap()
+}
+
+// r3, r2, r1, s1, s2, s3 rounded outwards to the tick size
+pub fn pivot(close: f64, high: f64, low: f64, tick_size: f64) -> [Decimal; 6] {
+  let close = Decimal::from_f64(close).unwrap();
+  let high = Decimal::from_f64(high).unwrap();
+  let low = Decimal::from_f64(low).unwrap();
+
+  let p = (close + high + low) / dec!(3);
+
+  let s1 = p * dec!(2) - high;
+  let r1 = p * dec!(2) - low;
+  let s2 = p - (r1 - s1);
+  let r2 = p + (r1 - s1);
+  let s3 = low - (high-p) * dec!(2);
+  let r3 = high + (p-low) * dec!(2);
+
+  let tick_size = Decimal::from_f64(tick_size).unwrap();
+
+  [
+    (r3 / tick_size).ceil() * tick_size,
+    (r2 / tick_size).ceil() * tick_size,
+    (r1 / tick_size).ceil() * tick_size,
+    (s1 / tick_size).floor() * tick_size,
+    (s2 / tick_size).floor() * tick_size,
+    (s3 / tick_size).floor() * tick_size,
+  ]
+}
+
+pub fn atr(highs: &[f64], lows: &[f64], closes: &[f64], period: i32) -> Result<f64, Box<dyn std::error::Error>> {
+  unsafe {
+    let size = closes.len();
+    let mut out: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_ATR(
+      0,
+      size as i32 - 1,
+      highs.as_ptr(),
+      lows.as_ptr(),
+      closes.as_ptr(),
+      period,
+      &mut out_begin,
+      &mut out_size,
+      out.as_mut_ptr()
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 0 => {
+        out.set_len(out_size);
+        Ok(out[out_size-1])
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// ema of the price against the price lag klines later, the last two values
+pub fn zlema(prices: &[f64], period: i32) -> Result<(f64, f64), Box<dyn std::error::Error>> {
+  let lag = ((period - 1) / 2) as usize;
+  if prices.len() <= lag {
+    return Err(Box::from("klines not enough"))
+  }
+  let data: Vec<TA_Real> = (0..prices.len()-lag).map(|i| prices[i] - prices[i+lag]).collect();
+
+  unsafe {
+    let size = data.len();
+    let mut out: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_MA(
+      0,
+      size as i32 - 1,
+      data.as_ptr(),
+      period,
+      TA_MAType_TA_MAType_EMA,
+      &mut out_begin,
+      &mut out_size,
+      out.as_mut_ptr()
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 1 => {
+        out.set_len(out_size);
+        Ok((out[out_size-2], out[out_size-1]))
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// slow k, slow d and j of the stochastic over the typical prices
+pub fn kdj(highs: &[f64], lows: &[f64], prices: &[f64], long_period: i32, short_period: i32) -> Result<[Decimal; 3], Box<dyn std::error::Error>> {
+  unsafe {
+    let size = prices.len();
+    let mut out_slowk: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_slowd: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_STOCH(
+      0,
+      size as i32 - 1,
+      highs.as_ptr(),
+      lows.as_ptr(),
+      prices.as_ptr(),
+      long_period,
+      short_period,
+      0,
+      short_period,
+      0,
+      &mut out_begin,
+      &mut out_size,
+      out_slowk.as_mut_ptr(),
+      out_slowd.as_mut_ptr(),
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 0 => {
+        out_slowk.set_len(out_size);
+        out_slowd.set_len(out_size);
+        let slowk = Decimal::from_f64(out_slowk[out_size-1]).unwrap();
+        let slowd = Decimal::from_f64(out_slowd[out_size-1]).unwrap();
+        let slowj = slowk * dec!(3) - slowd * dec!(2);
+        Ok([slowk, slowd, slowj])
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// %b and width of the last three bands, the %b prices are taken from the highs and lows
+// of period klines earlier and the widths are all divided by the oldest middle band
+pub fn bbands(prices: &[f64], highs: &[f64], lows: &[f64], period: i32, deviation: f64) -> Result<[Decimal; 6], Box<dyn std::error::Error>> {
+  if prices.len() < period as usize * 2 + 1 {
+    return Err(Box::from("klines not enough"))
+  }
+
+  unsafe {
+    let size = prices.len();
+    let mut out_ubands: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_mbands: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_lbands: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_BBANDS(
+      0,
+      size as i32 - 1,
+      prices.as_ptr(),
+      period,
+      deviation,
+      deviation,
+      0,
+      &mut out_begin,
+      &mut out_size,
+      out_ubands.as_mut_ptr(),
+      out_mbands.as_mut_ptr(),
+      out_lbands.as_mut_ptr(),
+    );
+    let period = period as usize;
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 2 => {
+        out_ubands.set_len(out_size);
+        out_mbands.set_len(out_size);
+        out_lbands.set_len(out_size);
+
+        if out_ubands[out_size-3] == out_lbands[out_size-3]
+          || out_ubands[out_size-2] == out_lbands[out_size-2]
+          || out_ubands[out_size-1] == out_lbands[out_size-1] {
+          return Err(Box::from("klined invalid"))
+        }
+
+        let price = |i: usize| Decimal::from_f64(highs[i] + highs[i] + lows[i]).unwrap() / dec!(3);
+        let p1 = price(out_size-period-2);
+        let p2 = price(out_size-period-1);
+        let p3 = price(out_size-period);
+        let b1 = (p1 - Decimal::from_f64(out_lbands[out_size-3]).unwrap()) / Decimal::from_f64(out_ubands[out_size-3] - out_lbands[out_size-3]).unwrap();
+        let b2 = (p2 - Decimal::from_f64(out_lbands[out_size-2]).unwrap()) / Decimal::from_f64(out_ubands[out_size-2] - out_lbands[out_size-2]).unwrap();
+        let b3 = (p3 - Decimal::from_f64(out_lbands[out_size-1]).unwrap()) / Decimal::from_f64(out_ubands[out_size-1] - out_lbands[out_size-1]).unwrap();
+        let w1 = Decimal::from_f64(out_ubands[out_size-3] - out_lbands[out_size-3]).unwrap() / Decimal::from_f64(out_mbands[out_size-3]).unwrap();
+        let w2 = Decimal::from_f64(out_ubands[out_size-2] - out_lbands[out_size-2]).unwrap() / Decimal::from_f64(out_mbands[out_size-3]).unwrap();
+        let w3 = Decimal::from_f64(out_ubands[out_size-1] - out_lbands[out_size-1]).unwrap() / Decimal::from_f64(out_mbands[out_size-3]).unwrap();
+
+        Ok([b1, b2, b3, w1, w2, w3])
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// the lines are averages of the typical prices rather than the midpoints of the ranges
+pub fn ichimoku_cloud(prices: &[f64], tenkan_period: usize, kijun_period: usize, senkou_period: usize) -> Result<CloudLines, Box<dyn std::error::Error>> {
+  if tenkan_period < 2 || kijun_period < 2 || senkou_period < 1 {
+    return Err(Box::from("periods not valid"))
+  }
+  if prices.len() < tenkan_period.max(kijun_period).max(senkou_period) {
+    return Err(Box::from("klines not enough"))
+  }
+  let avg_prices: Vec<f64> = prices.iter().rev().copied().collect();
+
+  let average = |items: &[f64]| Decimal::from_f64(items.iter().sum::<f64>()).unwrap() / Decimal::from_usize(items.len()).unwrap();
+
+  let last_conversion_line = average(&avg_prices[1..tenkan_period]);
+  let last_base_line = average(&avg_prices[1..kijun_period]);
+  let conversion_line = average(&avg_prices[..tenkan_period]);
+  let base_line = average(&avg_prices[..kijun_period]);
+
+  let chikou_prices = &avg_prices[..kijun_period];
+  let chikou_span_min = chikou_prices.iter().copied().fold(f64::MAX, f64::min);
+  let chikou_span_max = chikou_prices.iter().copied().fold(f64::MIN, f64::max);
+
+  let senkou_span_a = (conversion_line + base_line) / dec!(2);
+  let senkou_span_b = average(&avg_prices[..senkou_period]);
+  let chikou_span = Decimal::from_f64(chikou_span_min + chikou_span_max).unwrap() / dec!(2);
+
+  Ok(CloudLines {
+    conversion_line: conversion_line,
+    base_line: base_line,
+    last_conversion_line: last_conversion_line,
+    last_base_line: last_base_line,
+    senkou_span_a: senkou_span_a,
+    senkou_span_b: senkou_span_b,
+    chikou_span: chikou_span,
+  })
+}
+
+// poc, vah, val and poc ratio, the value area is the narrowest run of segments holding 70% of the volume
+pub fn volume_profile(prices: &[f64], volumes: &[f64], segments: usize) -> Result<(Decimal, Decimal, Decimal, Decimal), Box<dyn std::error::Error>> {
+  if prices.is_empty() || segments < 1 {
+    return Err(Box::from("klines not enough"))
+  }
+
+  let avg_prices_min = prices.iter().copied().fold(f64::MAX, f64::min);
+  let avg_prices_max = prices.iter().copied().fold(f64::MIN, f64::max);
+  if avg_prices_min == avg_prices_max {
+    return Err(Box::from("klines avg prices invalid"))
+  }
+
+  let total_volumes = Decimal::from_f64(volumes.iter().sum::<f64>()).unwrap();
+  let base_volume = (total_volumes * dec!(0.7)).to_f64().unwrap();
+  let segment_price = Decimal::from_f64(avg_prices_max - avg_prices_min).unwrap() / Decimal::from_usize(segments).unwrap();
+
+  let mut poc_index: usize = 0;
+  let mut poc_volume: f64 = 0.0;
+
+  // min price, max price and volume of every segment
+  let mut items = HashMap::<usize, (f64, f64, f64)>::new();
+  for (i, &avg_price) in prices.iter().enumerate() {
+    let index = (Decimal::from_f64(avg_prices_max - avg_price).unwrap() / segment_price).floor();
+    let index = index.to_usize().unwrap().min(segments - 1);
+    match items.get_mut(&index) {
+      Some(item) => {
+        item.0 = item.0.min(avg_price);
+        item.1 = item.1.max(avg_price);
+        item.2 += volumes[i];
+        if poc_volume < item.2 {
+          poc_index = index;
+          poc_volume = item.2;
+        }
+      }
+      None => {
+        items.insert(index, (avg_price, avg_price, volumes[i]));
+        poc_index = index;
+        poc_volume = volumes[i];
+      }
+    }
+  }
+
+  let mut start_index: usize = 0;
+  let mut end_index: usize = 0;
+  let mut best_volume: f64 = 0.0;
+  for i in 0..segments {
+    if items.get(&i).is_none() {
+      continue
+    }
+    let mut area_volume: f64 = 0.0;
+    for j in i..segments {
+      if let Some(item) = items.get(&j) {
+        area_volume += item.2;
+        if area_volume > base_volume {
+          if best_volume < area_volume {
+            start_index = i;
+            end_index = j;
+            best_volume = area_volume;
+          }
+          break
+        }
+      }
+    }
+  }
+  if best_volume == 0.0 {
+    return Err(Box::from("best volume not exists"))
+  }
+
+  let middle = |index: usize| {
+    let item = items.get(&index).unwrap();
+    Decimal::from_f64(item.0 + item.1).unwrap() / dec!(2)
+  };
+  let poc = middle(poc_index);
+  let start_avg_price = middle(start_index);
+  let end_avg_price = middle(end_index);
+  let vah = start_avg_price.max(end_avg_price);
+  let val = start_avg_price.min(end_avg_price);
+  let poc_ratio = ((vah - val) / poc).round_dp(4);
+
+  Ok((poc, vah, val, poc_ratio))
+}
+
+// bull, bear and signal of the last kline
+pub fn andean_oscillator(opens: &[f64], closes: &[f64], period: i32, length: i32) -> Result<(f64, f64, f64), Box<dyn std::error::Error>> {
+  if closes.is_empty() {
+    return Err(Box::from("klines not enough"))
+  }
+
+  let alpha = dec!(2) / Decimal::from_i32(period + 1).unwrap();
+  let alpha_signal = dec!(2) / Decimal::from_i32(length + 1).unwrap();
+
+  let close = Decimal::from_f64(closes[0]).unwrap();
+  let pow_close = close.powd(dec!(2));
+  let close = close.to_f64().unwrap();
+  let pow_close = pow_close.to_f64().unwrap();
+
+  let (mut up1, mut up2, mut dn1, mut dn2) = (close, pow_close, close, pow_close);
+  let (mut bull, mut bear, mut signal) = (0.0, 0.0, close);
+
+  for i in 1..closes.len() {
+    let close = Decimal::from_f64(closes[i]).unwrap();
+    let pow_close = close.powd(dec!(2));
+    let open = Decimal::from_f64(opens[i]).unwrap();
+    let pow_open = open.powd(dec!(2));
+    let last_up1 = Decimal::from_f64(up1).unwrap();
+    let last_up2 = Decimal::from_f64(up2).unwrap();
+    let last_dn1 = Decimal::from_f64(dn1).unwrap();
+    let last_dn2 = Decimal::from_f64(dn2).unwrap();
+    let last_signal = Decimal::from_f64(signal).unwrap();
+
+    up1 = close.max(open).max(last_up1 - alpha * (last_up1 - close)).to_f64().unwrap();
+    up2 = pow_close.max(pow_open).max(last_up2 - alpha * (last_up2 - pow_close)).to_f64().unwrap();
+    dn1 = close.min(open).min(last_dn1 + alpha * (close - last_dn1)).to_f64().unwrap();
+    dn2 = pow_close.min(pow_open).min(last_dn2 + alpha * (pow_close - last_dn2)).to_f64().unwrap();
+
+    let value = Decimal::from_f64(dn2).unwrap() - Decimal::from_f64(dn1).unwrap().powd(dec!(2));
+    if value < dec!(0) {
+      return Err(Box::from(format!("bears not valid {value:}")))
+    }
+    bull = value.sqrt().unwrap().to_f64().unwrap();
+
+    let value = Decimal::from_f64(up2).unwrap() - Decimal::from_f64(up1).unwrap().powd(dec!(2));
+    if value < dec!(0) {
+      return Err(Box::from(format!("bears not valid {value:}")))
+    }
+    bear = value.sqrt().unwrap().to_f64().unwrap();
+
+    let value = last_signal + alpha_signal * (Decimal::from_f64(bull.max(bear)).unwrap() - last_signal);
+    signal = value.to_f64().unwrap();
+  }
+
+  Ok((bull, bear, signal))
+}
+
+// the last two values of the relative strength index
+pub fn rsi(prices: &[f64], period: i32) -> Result<(f64, f64), Box<dyn std::error::Error>> {
+  unsafe {
+    let size = prices.len();
+    let mut out: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_RSI(
+      0,
+      size as i32 - 1,
+      prices.as_ptr(),
+      period,
+      &mut out_begin,
+      &mut out_size,
+      out.as_mut_ptr()
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 1 => {
+        out.set_len(out_size);
+        Ok((out[out_size-2], out[out_size-1]))
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// macd, signal and histogram of the last kline with the histogram of the kline before
+pub fn macd(prices: &[f64], fast_period: i32, slow_period: i32, signal_period: i32) -> Result<[f64; 4], Box<dyn std::error::Error>> {
+  unsafe {
+    let size = prices.len();
+    let mut out_macd: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_signal: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_hist: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_MACD(
+      0,
+      size as i32 - 1,
+      prices.as_ptr(),
+      fast_period,
+      slow_period,
+      signal_period,
+      &mut out_begin,
+      &mut out_size,
+      out_macd.as_mut_ptr(),
+      out_signal.as_mut_ptr(),
+      out_hist.as_mut_ptr(),
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 1 => {
+        out_macd.set_len(out_size);
+        out_signal.set_len(out_size);
+        out_hist.set_len(out_size);
+        Ok([out_macd[out_size-1], out_signal[out_size-1], out_hist[out_size-1], out_hist[out_size-2]])
+      }
+      _ => Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+}
+
+// adx, +di and -di of the last kline
+pub fn adx(highs: &[f64], lows: &[f64], closes: &[f64], period: i32) -> Result<[f64; 3], Box<dyn std::error::Error>> {
+  let mut values = [0.0; 3];
+  for (i, func) in [TA_ADX, TA_PLUS_DI, TA_MINUS_DI].iter().enumerate() {
+    unsafe {
+      let size = closes.len();
+      let mut out: Vec<TA_Real> = Vec::with_capacity(size);
+      let mut out_begin: TA_Integer = 0;
+      let mut out_size: TA_Integer = 0;
+
+      let ret_code = func(
+        0,
+        size as i32 - 1,
+        highs.as_ptr(),
+        lows.as_ptr(),
+        closes.as_ptr(),
+        period,
+        &mut out_begin,
+        &mut out_size,
+        out.as_mut_ptr()
+      );
+      let out_size = out_size as usize;
+      match ret_code {
+        TA_RetCode::TA_SUCCESS if out_size > 0 => {
+          out.set_len(out_size);
+          values[i] = out[out_size-1];
+        }
+        _ => return Err(Box::from(format!("calc failed {ret_code:?}")))
+      }
+    }
+  }
+  Ok(values)
+}
+
+// line and trend of the last kline with the trend of the kline before, 1 up and -1 down
+pub fn supertrend(highs: &[f64], lows: &[f64], closes: &[f64], period: i32, multiplier: f64) -> Result<(f64, i32, i32), Box<dyn std::error::Error>> {
+  let atrs: Vec<TA_Real>;
+  let begin: usize;
+  unsafe {
+    let size = closes.len();
+    let mut out: Vec<TA_Real> = Vec::with_capacity(size);
+    let mut out_begin: TA_Integer = 0;
+    let mut out_size: TA_Integer = 0;
+
+    let ret_code = TA_ATR(
+      0,
+      size as i32 - 1,
+      highs.as_ptr(),
+      lows.as_ptr(),
+      closes.as_ptr(),
+      period,
+      &mut out_begin,
+      &mut out_size,
+      out.as_mut_ptr()
+    );
+    let out_size = out_size as usize;
+    match ret_code {
+      TA_RetCode::TA_SUCCESS if out_size > 1 => {
+        out.set_len(out_size);
+        atrs = out;
+        begin = out_begin as usize;
+      }
+      _ => return Err(Box::from(format!("calc failed {ret_code:?}")))
+    }
+  }
+
+  let (mut upper, mut lower) = (f64::MAX, f64::MIN);
+  let (mut line, mut trend, mut last_trend) = (0.0, 1, 1);
+  for (j, atr) in atrs.iter().enumerate() {
+    let i = begin + j;
+    let middle = (highs[i] + lows[i]) / 2.0;
+    let basic_upper = middle + multiplier * atr;
+    let basic_lower = middle - multiplier * atr;
+
+    if j == 0 || basic_upper < upper || closes[i-1] > upper {
+      upper = basic_upper;
+    }
+    if j == 0 || basic_lower > lower || closes[i-1] < lower {
+      lower = basic_lower;
+    }
+
+    last_trend = trend;
+    if trend == 1 && closes[i] < lower {
+      trend = -1;
+    } else if trend == -1 && closes[i] > upper {
+      trend = 1;
+    }
+    line = if trend == 1 { lower } else { upper };
+  }
+
+  Ok((line, trend, last_trend))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // timestamp, open, high, low, close and volume of the klines the expected values were taken from
+  fn klines(limit: usize) -> Vec<[f64; 6]> {
+    let data = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/indicators/klines.csv")).unwrap();
+    let items = data.lines().skip(1).map(|line| {
+      let values: Vec<f64> = line.split(",").map(|value| value.parse::<f64>().unwrap()).collect();
+      [values[0], values[1], values[2], values[3], values[4], values[5]]
+    }).collect::<Vec<[f64; 6]>>();
+    items[items.len()-limit..].to_vec()
+  }
+
+  fn expected(indicator: &str, field: &str) -> f64 {
+    let data = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/indicators/expected.csv")).unwrap();
+    for line in data.lines().skip(1) {
+      let values: Vec<&str> = line.split(",").collect();
+      if values[0] == indicator && values[1] == field {
+        return values[2].parse::<f64>().unwrap()
+      }
+    }
+    panic!("{indicator:} {field:} not exists")
+  }
+
+  fn assert_expected<V: ToPrimitive>(indicator: &str, field: &str, value: V) {
+    let value = value.to_f64().unwrap();
+    let target = expected(indicator, field);
+    assert!(
+      (value - target).abs() <= 1e-8 * target.abs().max(1.0),
+      "{indicator:} {field:} got {value:} expected {target:}",
+    );
+  }
+
+  fn column(items: &[[f64; 6]], index: usize) -> Vec<f64> {
+    items.iter().map(|item| item[index]).collect()
+  }
+
+  fn typical_prices(items: &[[f64; 6]]) -> Vec<f64> {
+    items.iter().map(|item| typical_price(item[4], item[2], item[3])).collect()
+  }
+
+  #[test]
+  fn pivot_matches_fixture() {
+    let items = klines(1);
+    let [r3, r2, r1, s1, s2, s3] = pivot(items[0][4], items[0][2], items[0][3], 0.01);
+    assert_expected("pivot", "r3", r3);
+    assert_expected("pivot", "r2", r2);
+    assert_expected("pivot", "r1", r1);
+    assert_expected("pivot", "s1", s1);
+    assert_expected("pivot", "s2", s2);
+    assert_expected("pivot", "s3", s3);
+  }
+
+  #[test]
+  fn atr_matches_fixture() {
+    let items = klines(100);
+    let value = atr(&column(&items, 2), &column(&items, 3), &column(&items, 4), 14).unwrap();
+    assert_expected("atr", "atr", value);
+  }
+
+  #[test]
+  fn zlema_matches_fixture() {
+    let items = klines(100);
+    let (last_value, value) = zlema(&column(&items, 4), 14).unwrap();
+    assert_expected("zlema", "last", last_value);
+    assert_expected("zlema", "value", value);
+
+    let prices: Vec<f64> = items.iter().map(|item| average_price(item[1], item[4], item[2], item[3])).collect();
+    let (last_value, value) = zlema(&prices, 14).unwrap();
+    assert_expected("ha_zlema", "last", last_value);
+    assert_expected("ha_zlema", "value", value);
+  }
+
+  #[test]
+  fn kdj_matches_fixture() {
+    let items = klines(100);
+    let [k, d, j] = kdj(&column(&items, 2), &column(&items, 3), &typical_prices(&items), 9, 3).unwrap();
+    assert_expected("kdj", "k", k);
+    assert_expected("kdj", "d", d);
+    assert_expected("kdj", "j", j);
+  }
+
+  #[test]
+  fn bbands_matches_fixture() {
+    let items = klines(100);
+    let values = bbands(&typical_prices(&items), &column(&items, 2), &column(&items, 3), 14, 2.0).unwrap();
+    for (field, value) in ["b1", "b2", "b3", "w1", "w2", "w3"].iter().zip(values) {
+      assert_expected("bbands", field, value);
+    }
+  }
+
+  #[test]
+  fn bbands_needs_two_periods() {
+    let items = klines(28);
+    assert!(bbands(&typical_prices(&items), &column(&items, 2), &column(&items, 3), 14, 2.0).is_err());
+  }
+
+  #[test]
+  fn ichimoku_cloud_matches_fixture() {
+    let items = klines(100);
+    let lines = ichimoku_cloud(&typical_prices(&items), 9, 26, 52).unwrap();
+    assert_expected("ichimoku_cloud", "conversion_line", lines.conversion_line);
+    assert_expected("ichimoku_cloud", "base_line", lines.base_line);
+    assert_expected("ichimoku_cloud", "last_conversion_line", lines.last_conversion_line);
+    assert_expected("ichimoku_cloud", "last_base_line", lines.last_base_line);
+    assert_expected("ichimoku_cloud", "senkou_span_a", lines.senkou_span_a);
+    assert_expected("ichimoku_cloud", "senkou_span_b", lines.senkou_span_b);
+    assert_expected("ichimoku_cloud", "chikou_span", lines.chikou_span);
+  }
+
+  #[test]
+  fn volume_profile_matches_fixture() {
+    let items = klines(100);
+    let (poc, vah, val, poc_ratio) = volume_profile(&typical_prices(&items), &column(&items, 5), 100).unwrap();
+    assert_expected("volume_profile", "poc", poc);
+    assert_expected("volume_profile", "vah", vah);
+    assert_expected("volume_profile", "val", val);
+    assert_expected("volume_profile", "poc_ratio", poc_ratio);
+  }
+
+  #[test]
+  fn volume_profile_rejects_flat_prices() {
+    assert!(volume_profile(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0], 10).is_err());
+  }
+
+  #[test]
+  fn andean_oscillator_matches_fixture() {
+    // the newest kline sets a new low and zeroes the bull, the window ends two klines before it
+    let items = klines(102);
+    let items = &items[..100];
+    let (bull, bear, signal) = andean_oscillator(&column(items, 1), &column(items, 4), 50, 9).unwrap();
+    assert_expected("andean_oscillator", "bull", bull);
+    assert_expected("andean_oscillator", "bear", bear);
+    assert_expected("andean_oscillator", "signal", signal);
+  }
+
+  #[test]
+  fn rsi_matches_fixture() {
+    let items = klines(100);
+    let (last_value, value) = rsi(&column(&items, 4), 14).unwrap();
+    assert_expected("rsi", "last", last_value);
+    assert_expected("rsi", "value", value);
+  }
+
+  #[test]
+  fn macd_matches_fixture() {
+    let items = klines(100);
+    let values = macd(&column(&items, 4), 12, 26, 9).unwrap();
+    for (field, value) in ["macd", "signal", "hist", "last_hist"].iter().zip(values) {
+      assert_expected("macd", field, value);
+    }
+  }
+
+  #[test]
+  fn adx_matches_fixture() {
+    let items = klines(100);
+    let values = adx(&column(&items, 2), &column(&items, 3), &column(&items, 4), 14).unwrap();
+    for (field, value) in ["adx", "plus_di", "minus_di"].iter().zip(values) {
+      assert_expected("adx", field, value);
+    }
+  }
+
+  #[test]
+  fn supertrend_matches_fixture() {
+    let items = klines(100);
+    let (line, trend, last_trend) = supertrend(&column(&items, 2), &column(&items, 3), &column(&items, 4), 10, 3.0).unwrap();
+    assert_expected("supertrend", "line", line);
+    assert_expected("supertrend", "trend", trend);
+    assert_expected("supertrend", "last_trend", last_trend);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,285 @@
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::repositories::indicators::engine::CloudLines;
+
+// 1 buy and 2 sell, the same values the strategies store
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtrLevels {
+  pub profit_target: Decimal,
+  pub stop_loss_point: Decimal,
+  pub take_profit_price: Decimal,
+  pub risk_reward_ratio: f32,
+  pub take_profit_ratio: f32,
+}
+
+pub fn atr(price: f64, atr: f64, tick_size: f64, profit_ratio: f64, stop_ratio: f64) -> AtrLevels {
+  let price = Decimal::from_f64(price).unwrap();
+  let atr = Decimal::from_f64(atr).unwrap();
+  let tick_size = Decimal::from_f64(tick_size).unwrap();
+  let profit_ratio = Decimal::from_f64(profit_ratio).unwrap();
+  let stop_ratio = Decimal::from_f64(stop_ratio).unwrap();
+
+  let profit_target = price * dec!(2) - atr * profit_ratio;
+  let stop_loss_point = price - atr * stop_ratio;
+  let take_profit_price = stop_loss_point + (profit_target - stop_loss_point) / dec!(2);
+
+  let profit_target = (profit_target / tick_size).ceil() * tick_size;
+  let stop_loss_point = (stop_loss_point / tick_size).floor() * tick_size;
+  let take_profit_price = (take_profit_price / tick_size).ceil() * tick_size;
+
+  AtrLevels {
+    profit_target: profit_target,
+    stop_loss_point: stop_loss_point,
+    take_profit_price: take_profit_price,
+    risk_reward_ratio: ((price - stop_loss_point) / (profit_target - price)).round_dp(4).to_f32().unwrap(),
+    take_profit_ratio: (price / take_profit_price).round_dp(4).to_f32().unwrap(),
+  }
+}
+
+// the zero line crossed between the last two values
+pub fn zlema(last_value: f64, value: f64) -> Option<i32> {
+  if last_value * value >= 0.0 {
+    return None
+  }
+  if value > 0.0 {
+    Some(1)
+  } else {
+    Some(2)
+  }
+}
+
+pub fn kdj(k: f64, d: f64, j: f64, oversold: f64, overbought: f64) -> Option<i32> {
+  if k < oversold && d > 60.0 && j < 60.0 {
+    Some(1)
+  } else if k > overbought && d > 70.0 && j > 90.0 {
+    Some(2)
+  } else {
+    None
+  }
+}
+
+// an error when no band rule matches, none while the bands squeeze
+pub fn bbands(values: [f64; 6], middle: f64, upper: f64, squeeze: f64, narrow: f64) -> Result<Option<i32>, Box<dyn std::error::Error>> {
+  let [b1, b2, b3, w1, w2, w3] = values;
+
+  let signal: i32;
+  if b1 < middle && b2 < middle && b3 > middle {
+    signal = 1;
+  } else if b1 > middle && b2 < middle && b3 < middle {
+    signal = 2;
+  } else if b1 > upper && b2 > upper && b3 > upper {
+    signal = 1;
+  } else if b1 > upper && b2 > upper && b3 < upper {
+    signal = 2;
+  } else {
+    return Err(Box::from("bands not valid"))
+  }
+
+  if w1 < squeeze && w2 < squeeze && w3 < squeeze {
+    if w1 < narrow || w2 < narrow || w3 > narrow {
+      return Ok(None)
+    }
+  }
+
+  Ok(Some(signal))
+}
+
+// the conversion line crossing the base line, against the lines last stored when the klines did not cross
+pub fn ichimoku_cloud(lines: &CloudLines, last_lines: Option<(f64, f64)>) -> i32 {
+  let mut signal: i32 = 0;
+  if lines.conversion_line > lines.base_line && lines.last_conversion_line < lines.last_base_line {
+    signal = 1
+  }
+  if lines.conversion_line < lines.base_line && lines.last_conversion_line > lines.last_base_line {
+    signal = 2
+  }
+  if signal == 0 {
+    if let Some((last_conversion_line, last_base_line)) = last_lines {
+      let last_conversion_line = Decimal::from_f64(last_conversion_line).unwrap();
+      let last_base_line = Decimal::from_f64(last_base_line).unwrap();
+      if lines.conversion_line > lines.base_line && last_conversion_line < last_base_line {
+        signal = 1
+      }
+      if lines.conversion_line < lines.base_line && last_conversion_line > last_base_line {
+        signal = 2
+      }
+    }
+  }
+  signal
+}
+
+// the index turning back out of the oversold or overbought zone
+pub fn rsi(last_value: f64, value: f64, oversold: f64, overbought: f64) -> Option<i32> {
+  if last_value < oversold && value >= oversold {
+    Some(1)
+  } else if last_value > overbought && value <= overbought {
+    Some(2)
+  } else {
+    None
+  }
+}
+
+// the histogram crossed the zero line between the last two klines
+pub fn macd(last_hist: f64, hist: f64) -> Option<i32> {
+  zlema(last_hist, hist)
+}
+
+// the stronger directional index while the trend is strong enough
+pub fn adx(adx: f64, plus_di: f64, minus_di: f64, threshold: f64) -> Option<i32> {
+  if adx < threshold || plus_di == minus_di {
+    return None
+  }
+  if plus_di > minus_di {
+    Some(1)
+  } else {
+    Some(2)
+  }
+}
+
+// the trend flipped between the last two klines
+pub fn supertrend(last_trend: i32, trend: i32) -> Option<i32> {
+  if last_trend == trend {
+    return None
+  }
+  if trend > 0 {
+    Some(1)
+  } else {
+    Some(2)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // rule, inputs split by semicolons and the expected signal, empty for none
+  fn cases(rule: &str) -> Vec<(Vec<String>, String)> {
+    let data = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/strategies/signals.csv")).unwrap();
+    let items = data.lines().skip(1).filter_map(|line| {
+      let values: Vec<&str> = line.split(",").collect();
+      if values[0] != rule {
+        return None
+      }
+      Some((values[1].split(";").map(|value| value.to_string()).collect(), values[2].to_string()))
+    }).collect::<Vec<(Vec<String>, String)>>();
+    assert!(!items.is_empty(), "{rule:} cases not exists");
+    items
+  }
+
+  fn floats(values: &[String]) -> Vec<f64> {
+    values.iter().map(|value| value.parse::<f64>().unwrap()).collect()
+  }
+
+  fn signal(expected: &str) -> Option<i32> {
+    if expected == "" {
+      None
+    } else {
+      Some(expected.parse::<i32>().unwrap())
+    }
+  }
+
+  #[test]
+  fn atr_matches_fixture() {
+    for (inputs, expected) in cases("atr") {
+      let values = floats(&inputs);
+      let levels = atr(values[0], values[1], values[2], values[3], values[4]);
+      let expected: Vec<&str> = expected.split(";").collect();
+      assert_eq!(levels.profit_target, Decimal::from_str(expected[0]).unwrap(), "{inputs:?}");
+      assert_eq!(levels.stop_loss_point, Decimal::from_str(expected[1]).unwrap(), "{inputs:?}");
+      assert_eq!(levels.take_profit_price, Decimal::from_str(expected[2]).unwrap(), "{inputs:?}");
+      assert_eq!(levels.risk_reward_ratio, expected[3].parse::<f32>().unwrap(), "{inputs:?}");
+      assert_eq!(levels.take_profit_ratio, expected[4].parse::<f32>().unwrap(), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn zlema_matches_fixture() {
+    for (inputs, expected) in cases("zlema") {
+      let values = floats(&inputs);
+      assert_eq!(zlema(values[0], values[1]), signal(&expected), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn kdj_matches_fixture() {
+    for (inputs, expected) in cases("kdj") {
+      let values = floats(&inputs);
+      assert_eq!(kdj(values[0], values[1], values[2], values[3], values[4]), signal(&expected), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn bbands_matches_fixture() {
+    for (inputs, expected) in cases("bbands") {
+      let values = floats(&inputs);
+      let result = bbands(
+        [values[0], values[1], values[2], values[3], values[4], values[5]],
+        values[6],
+        values[7],
+        values[8],
+        values[9],
+      );
+      if expected == "error" {
+        assert!(result.is_err(), "{inputs:?}");
+      } else {
+        assert_eq!(result.unwrap(), signal(&expected), "{inputs:?}");
+      }
+    }
+  }
+
+  #[test]
+  fn ichimoku_cloud_matches_fixture() {
+    for (inputs, expected) in cases("ichimoku_cloud") {
+      let value = |i: usize| Decimal::from_str(&inputs[i]).unwrap();
+      let lines = CloudLines {
+        conversion_line: value(0),
+        base_line: value(1),
+        last_conversion_line: value(2),
+        last_base_line: value(3),
+        senkou_span_a: dec!(0),
+        senkou_span_b: dec!(0),
+        chikou_span: dec!(0),
+      };
+      let last_lines = if inputs[4] == "" {
+        None
+      } else {
+        Some((inputs[4].parse::<f64>().unwrap(), inputs[5].parse::<f64>().unwrap()))
+      };
+      assert_eq!(ichimoku_cloud(&lines, last_lines), expected.parse::<i32>().unwrap(), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn rsi_matches_fixture() {
+    for (inputs, expected) in cases("rsi") {
+      let values = floats(&inputs);
+      assert_eq!(rsi(values[0], values[1], values[2], values[3]), signal(&expected), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn macd_matches_fixture() {
+    for (inputs, expected) in cases("macd") {
+      let values = floats(&inputs);
+      assert_eq!(macd(values[0], values[1]), signal(&expected), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn adx_matches_fixture() {
+    for (inputs, expected) in cases("adx") {
+      let values = floats(&inputs);
+      assert_eq!(adx(values[0], values[1], values[2], values[3]), signal(&expected), "{inputs:?}");
+    }
+  }
+
+  #[test]
+  fn supertrend_matches_fixture() {
+    for (inputs, expected) in cases("supertrend") {
+      let values: Vec<i32> = inputs.iter().map(|value| value.parse::<i32>().unwrap()).collect();
+      assert_eq!(supertrend(values[0], values[1]), signal(&expected), "{inputs:?}");
+    }
+  }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
 pub mod spot;
 pub mod futures;
-pub mod margin;
\ No newline at end of file
+pub mod delivery;
+pub mod margin;
+pub mod params;
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub mod symbols;
+pub mod klines;
+pub mod orders;
+pub mod positions;
\ No newline at end of file
@@ -0,0 +1,19 @@
+diesel::table! {
+  #[sql_name = "binance_delivery_symbols"]
+  symbols (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    pair -> Varchar,
+    base_asset -> Varchar,
+    quote_asset -> Varchar,
+    margin_asset -> Varchar,
+    contract_type -> Varchar,
+    contract_size -> Double,
+    delivery_date -> BigInt,
+    filters -> Jsonb,
+    depth -> Jsonb,
+    status -> Varchar,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
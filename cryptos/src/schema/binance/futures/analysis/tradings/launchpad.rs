@@ -0,0 +1,15 @@
+diesel::table! {
+  #[sql_name = "binance_futures_analysis_tradings_launchpad"]
+  launchpad (id) {
+    id -> Varchar,
+    day -> Date,
+    buys_count -> Integer,
+    sells_count -> Integer,
+    buys_amount -> Double,
+    sells_amount -> Double,
+    profit -> Double,
+    additive_profit -> Double,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
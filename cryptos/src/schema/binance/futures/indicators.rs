@@ -0,0 +1,12 @@
+diesel::table! {
+  #[sql_name = "binance_futures_indicators"]
+  indicators (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    interval -> Varchar,
+    fields -> Jsonb,
+    timestamp -> BigInt,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
+pub mod audits;
+
 diesel::table! {
   #[sql_name = "binance_futures_klines"]
   klines (id) {
@@ -1,3 +1,5 @@
+pub mod changes;
+
 diesel::table! {
   #[sql_name = "binance_futures_symbols"]
   symbols (id) {
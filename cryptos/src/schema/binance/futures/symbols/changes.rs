@@ -0,0 +1,11 @@
+diesel::table! {
+  #[sql_name = "binance_futures_symbols_changes"]
+  changes (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    field -> Varchar,
+    old_value -> Text,
+    new_value -> Text,
+    created_at -> Timestamptz,
+  }
+}
@@ -0,0 +1,21 @@
+diesel::table! {
+  #[sql_name = "binance_futures_tradings_launchpad"]
+  launchpad (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    level -> Integer,
+    buy_price -> Double,
+    sell_price -> Double,
+    stop_price -> Double,
+    buy_quantity -> Double,
+    sell_quantity -> Double,
+    buy_order_id -> BigInt,
+    sell_order_id -> BigInt,
+    status -> Integer,
+    version -> BigInt,
+    remark -> Varchar,
+    expired_at -> Timestamptz,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
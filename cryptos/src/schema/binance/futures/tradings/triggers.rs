@@ -4,6 +4,7 @@ diesel::table! {
     id -> Varchar,
     symbol -> Varchar,
     trigger_id -> Varchar,
+    side -> Integer,
     buy_price -> Double,
     sell_price -> Double,
     buy_quantity -> Double,
@@ -1,6 +1,6 @@
 diesel::table! {
-  #[sql_name = "binance_margin_cross_analysis_tradings_triggers"]
-  triggers (id) {
+  #[sql_name = "binance_margin_cross_analysis_tradings_scalping"]
+  scalping (id) {
     id -> Varchar,
     side -> Integer,
     day -> Date,
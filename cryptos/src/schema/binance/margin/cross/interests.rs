@@ -0,0 +1,15 @@
+diesel::table! {
+  #[sql_name = "binance_margin_cross_interests"]
+  interests (id) {
+    id -> Varchar,
+    asset -> Varchar,
+    tx_id -> BigInt,
+    principal -> Double,
+    interest -> Double,
+    interest_rate -> Double,
+    interest_type -> Varchar,
+    timestamp -> BigInt,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
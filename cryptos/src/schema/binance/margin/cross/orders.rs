@@ -11,6 +11,7 @@ diesel::table! {
     stop_price -> Double,
     quantity -> Double,
     executed_quantity -> Double,
+    side_effect_type -> Varchar,
     open_time -> BigInt,
     update_time -> BigInt,
     status -> Varchar,
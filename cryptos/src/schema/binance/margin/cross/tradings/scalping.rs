@@ -5,6 +5,7 @@ diesel::table! {
     symbol -> Varchar,
     scalping_id -> Varchar,
     plan_id -> Varchar,
+    side -> Integer,
     buy_price -> Double,
     sell_price -> Double,
     buy_quantity -> Double,
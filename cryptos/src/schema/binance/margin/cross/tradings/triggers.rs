@@ -1,9 +1,10 @@
 diesel::table! {
   #[sql_name = "binance_margin_cross_tradings_triggers"]
-  scalping (id) {
+  triggers (id) {
     id -> Varchar,
     symbol -> Varchar,
     trigger_id -> Varchar,
+    side -> Integer,
     buy_price -> Double,
     sell_price -> Double,
     buy_quantity -> Double,
@@ -1,4 +1,5 @@
 pub mod analysis;
+pub mod interests;
 pub mod orders;
 pub mod positions;
 pub mod scalping;
@@ -0,0 +1,22 @@
+diesel::table! {
+  #[sql_name = "binance_margin_isolated_orders"]
+  orders (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    order_id -> BigInt,
+    #[sql_name="type"]
+    order_type -> Varchar,
+    side -> Varchar,
+    price -> Double,
+    stop_price -> Double,
+    quantity -> Double,
+    executed_quantity -> Double,
+    side_effect_type -> Varchar,
+    open_time -> BigInt,
+    update_time -> BigInt,
+    status -> Varchar,
+    remark -> Varchar,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
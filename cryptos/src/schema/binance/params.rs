@@ -0,0 +1,12 @@
+diesel::table! {
+  #[sql_name = "binance_indicators_params"]
+  params (id) {
+    id -> Varchar,
+    market -> Varchar,
+    symbol -> Varchar,
+    interval -> Varchar,
+    values -> Jsonb,
+    created_at -> Timestamptz,
+    updated_at -> Timestamptz,
+  }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 pub mod analysis;
 pub mod symbols;
 pub mod klines;
+pub mod indicators;
 pub mod strategies;
 pub mod plans;
 pub mod orders;
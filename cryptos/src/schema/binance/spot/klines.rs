@@ -1,3 +1,5 @@
+pub mod audits;
+
 diesel::table! {
   #[sql_name = "binance_spot_klines"]
   klines (id) {
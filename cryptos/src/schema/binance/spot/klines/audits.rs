@@ -0,0 +1,12 @@
+diesel::table! {
+  #[sql_name = "binance_spot_klines_audits"]
+  audits (id) {
+    id -> Varchar,
+    symbol -> Varchar,
+    interval -> Varchar,
+    kind -> Varchar,
+    timestamp -> BigInt,
+    detail -> Text,
+    created_at -> Timestamptz,
+  }
+}
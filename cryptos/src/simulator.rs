@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::prelude::Utc;
+use rsa::{pkcs8::DecodePrivateKey, RsaPrivateKey, RsaPublicKey};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::common::*;
+
+pub mod engine;
+pub mod path;
+pub mod rest;
+pub mod stream;
+
+pub use engine::*;
+pub use path::*;
+
+// an in-process stand-in for the binance rest and websocket endpoints, point
+// BINANCE_{MARKET}_API_ENDPOINT and BINANCE_{MARKET}_STREAMS_ENDPOINT at it
+#[derive(Clone)]
+pub struct Simulator {
+  pub market: &'static str,
+  pub engine: Arc<Mutex<Engine>>,
+  pub account_key: String,
+  pub account_secret: String,
+  pub trade_key: String,
+  pub trade_public_key: Option<RsaPublicKey>,
+  pub weight_limit: i64,
+  pub weight: Arc<Mutex<(i64, i64)>>,
+  pub listen_keys: Arc<Mutex<HashSet<String>>>,
+  pub ticks: broadcast::Sender<(String, serde_json::Value)>,
+  pub events: broadcast::Sender<String>,
+}
+
+impl Simulator {
+  pub fn new(market: &'static str, engine: Engine) -> Self {
+    let env = |key: &str| Env::var(format!("BINANCE_{}_{}", market.to_uppercase(), key));
+    let trade_public_key = match RsaPrivateKey::from_pkcs8_pem(&env("TRADE_API_SECRET")) {
+      Ok(private_key) => Some(private_key.to_public_key()),
+      Err(_) => None,
+    };
+    let weight_limit = match env("API_WEIGHT_LIMIT").parse::<i64>() {
+      Ok(limit) if limit > 0 => limit,
+      _ if market == "spot" => 6000,
+      _ => 2400,
+    };
+    let (ticks, _) = broadcast::channel(1024);
+    let (events, _) = broadcast::channel(1024);
+    Self {
+      market: market,
+      engine: Arc::new(Mutex::new(engine)),
+      account_key: env("ACCOUNT_API_KEY"),
+      account_secret: env("ACCOUNT_API_SECRET"),
+      trade_key: env("TRADE_API_KEY"),
+      trade_public_key: trade_public_key,
+      weight_limit: weight_limit,
+      weight: Arc::new(Mutex::new((0, 0))),
+      listen_keys: Arc::new(Mutex::new(HashSet::new())),
+      ticks: ticks,
+      events: events,
+    }
+  }
+
+  pub async fn tick(&self, symbol: &str, price: f64, volume: f64) {
+    let mut engine = self.engine.lock().await;
+    let diff = engine.tick(symbol, price, volume, Utc::now().timestamp_millis());
+    let events = engine.drain();
+    drop(engine);
+
+    if let Some((first_update_id, last_update_id, prev_update_id, asks, bids)) = diff {
+      let _ = self.ticks.send((symbol.to_string(), serde_json::json!({
+        "U": first_update_id,
+        "u": last_update_id,
+        "pu": prev_update_id,
+        "a": asks,
+        "b": bids,
+      })));
+    }
+    self.publish(events);
+  }
+
+  pub fn publish(&self, events: Vec<serde_json::Value>) {
+    for event in events {
+      let _ = self.events.send(event.to_string());
+    }
+  }
+
+  pub async fn feed(&self, mut path: PricePath, tick_ms: u64) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_millis(tick_ms));
+    loop {
+      interval.tick().await;
+      let symbols = self.engine.lock().await.listings().iter().map(|(symbol, _)| symbol.to_string()).collect::<Vec<_>>();
+      let prices = {
+        let engine = self.engine.lock().await;
+        symbols.iter().map(|symbol| (symbol.clone(), engine.price(symbol).unwrap_or(0.0))).collect::<Vec<_>>()
+      };
+      let ticks = match path.next(&prices) {
+        Some(ticks) => ticks,
+        None => {
+          println!("simulator binance {} price path finished", self.market);
+          return
+        }
+      };
+      for (symbol, price, volume) in ticks.iter() {
+        self.tick(symbol, *price, *volume).await;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use redis::AsyncCommands;
+  use rsa::pkcs8::{EncodePrivateKey, LineEnding};
+
+  use crate::config::binance::futures::config as Config;
+  use crate::repositories::binance::futures::account::*;
+  use crate::repositories::binance::futures::orders::*;
+
+  // the repositories talk http to the simulator, redis, postgres and nats come from .env
+  async fn serve() -> (Ctx, Simulator) {
+    Env::load();
+    let private_key = RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 2048).unwrap();
+    std::env::set_var("BINANCE_FUTURES_ACCOUNT_API_KEY", "account");
+    std::env::set_var("BINANCE_FUTURES_ACCOUNT_API_SECRET", "secret");
+    std::env::set_var("BINANCE_FUTURES_TRADE_API_KEY", "trade");
+    std::env::set_var("BINANCE_FUTURES_TRADE_API_SECRET", private_key.to_pkcs8_pem(LineEnding::LF).unwrap().as_str());
+
+    let mut engine = Engine::new("futures");
+    engine.listing("BTCUSDT", Listing {
+      base_asset: "BTC".to_string(),
+      quote_asset: "USDT".to_string(),
+      tick_size: 0.1,
+      step_size: 0.001,
+      notional: 5.0,
+    });
+    engine.deposit("USDT", 1000.0);
+    engine.tick("BTCUSDT", 100.0, 1.0, Utc::now().timestamp_millis());
+    let simulator = Simulator::new("futures", engine);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    std::env::set_var("BINANCE_FUTURES_API_ENDPOINT", format!("http://{}", listener.local_addr().unwrap()));
+    let router = rest::router(simulator.clone());
+    tokio::spawn(async move {
+      axum::serve(listener, router).await
+    });
+
+    let rdb = Rdb::new(1).await.expect("bad rdb connection");
+    let rmq = Rmq::new(1).await.expect("bad rmq connection");
+    let pool = Pool::new(1).expect("bad pool connection");
+    let nats = Nats::new().await.expect("bad nats connection");
+    (Ctx::new(rdb, rmq, pool, nats), simulator)
+  }
+
+  #[tokio::test]
+  #[ignore = "needs the redis, postgres and nats of .env"]
+  async fn futures_order_submit_and_account_flush_run_against_simulator() {
+    let (ctx, simulator) = serve().await;
+
+    let order_id = OrdersRepository::submit(ctx.clone(), "BTCUSDT", "LONG", "BUY", 95.0, 1.0).await.unwrap();
+    assert_eq!(simulator.engine.lock().await.order("BTCUSDT", order_id).unwrap().status, "NEW");
+
+    simulator.tick("BTCUSDT", 94.9, 1.0).await;
+    let engine = simulator.engine.lock().await;
+    assert_eq!(engine.order("BTCUSDT", order_id).unwrap().status, "FILLED");
+    assert!(engine.positions().iter().any(|(symbol, side, position)| symbol == "BTCUSDT" && side == "LONG" && position.quantity == 1.0));
+    let (free, locked) = engine.balance("USDT");
+    drop(engine);
+
+    AccountRepository::flush(ctx.clone()).await.unwrap();
+    let mut rdb = ctx.rdb.lock().await.clone();
+    let balance: String = rdb.hget(format!("{}:USDT", Config::REDIS_KEY_BALANCE), "balance").await.unwrap();
+    let free_balance: String = rdb.hget(format!("{}:USDT", Config::REDIS_KEY_BALANCE), "free").await.unwrap();
+    assert_eq!(balance.parse::<f64>().unwrap(), free + locked);
+    assert_eq!(free_balance.parse::<f64>().unwrap(), free);
+  }
+}
@@ -0,0 +1,664 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::json;
+
+use crate::common::*;
+
+// enough history for a 1500 bar kline request on the minute timeframe
+const CANDLES_SIZE: usize = 1500;
+const BOOK_LEVELS: i64 = 20;
+
+pub struct Listing {
+  pub base_asset: String,
+  pub quote_asset: String,
+  pub tick_size: f64,
+  pub step_size: f64,
+  pub notional: f64,
+}
+
+#[derive(Clone, Copy)]
+pub struct Candle {
+  pub timestamp: i64,
+  pub open: f64,
+  pub high: f64,
+  pub low: f64,
+  pub close: f64,
+  pub volume: f64,
+  pub quota: f64,
+}
+
+#[derive(Clone)]
+pub struct SimOrder {
+  pub symbol: String,
+  pub order_id: i64,
+  pub order_type: String,
+  pub side: String,
+  pub position_side: String,
+  pub price: f64,
+  pub avg_price: f64,
+  pub quantity: f64,
+  pub executed_quantity: f64,
+  pub status: String,
+  pub time: i64,
+  pub update_time: i64,
+}
+
+#[derive(Clone, Default)]
+pub struct SimPosition {
+  pub quantity: f64,
+  pub entry_price: f64,
+  pub margin: f64,
+  pub update_time: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rejection {
+  pub code: i64,
+  pub message: String,
+}
+
+impl Rejection {
+  pub fn new(code: i64, message: &str) -> Self {
+    Self {
+      code: code,
+      message: message.to_string(),
+    }
+  }
+}
+
+pub struct Engine {
+  pub market: &'static str,
+  pub leverage: f64,
+  listings: HashMap<String, Listing>,
+  prices: HashMap<String, f64>,
+  candles: HashMap<String, VecDeque<Candle>>,
+  books: HashMap<String, (i64, Vec<String>, Vec<String>)>,
+  balances: HashMap<String, (f64, f64)>,
+  positions: HashMap<(String, String), SimPosition>,
+  orders: Vec<SimOrder>,
+  next_order_id: i64,
+  events: Vec<serde_json::Value>,
+}
+
+fn aligned(value: f64, step: f64) -> bool {
+  let steps = value / step;
+  (steps - steps.round()).abs() < 1e-6
+}
+
+impl Engine {
+  pub fn new(market: &'static str) -> Self {
+    Self {
+      market: market,
+      leverage: 10.0,
+      listings: HashMap::new(),
+      prices: HashMap::new(),
+      candles: HashMap::new(),
+      books: HashMap::new(),
+      balances: HashMap::new(),
+      positions: HashMap::new(),
+      orders: Vec::new(),
+      next_order_id: 1,
+      events: Vec::new(),
+    }
+  }
+
+  pub fn is_futures(&self) -> bool {
+    self.market == "futures"
+  }
+
+  pub fn listing(&mut self, symbol: &str, listing: Listing) {
+    self.listings.insert(symbol.to_string(), listing);
+  }
+
+  pub fn listings(&self) -> Vec<(&String, &Listing)> {
+    let mut items = self.listings.iter().collect::<Vec<_>>();
+    items.sort_by(|a, b| a.0.cmp(b.0));
+    items
+  }
+
+  pub fn deposit(&mut self, asset: &str, amount: f64) {
+    self.balances.entry(asset.to_string()).or_insert((0.0, 0.0)).0 += amount;
+  }
+
+  pub fn balance(&self, asset: &str) -> (f64, f64) {
+    self.balances.get(asset).copied().unwrap_or((0.0, 0.0))
+  }
+
+  pub fn balances(&self) -> Vec<(String, f64, f64)> {
+    let mut items = self.balances.iter().map(|(asset, (free, locked))| (asset.clone(), *free, *locked)).collect::<Vec<_>>();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items
+  }
+
+  pub fn positions(&self) -> Vec<(String, String, SimPosition)> {
+    let mut items = self.positions.iter().map(|((symbol, side), position)| (symbol.clone(), side.clone(), position.clone())).collect::<Vec<_>>();
+    items.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+    items
+  }
+
+  pub fn price(&self, symbol: &str) -> Option<f64> {
+    self.prices.get(symbol).copied()
+  }
+
+  pub fn candles(&self, symbol: &str) -> Vec<Candle> {
+    match self.candles.get(symbol) {
+      Some(items) => items.iter().copied().collect(),
+      None => Vec::new(),
+    }
+  }
+
+  pub fn klines(&self, symbol: &str, interval: Interval, endtime: i64, limit: usize) -> Vec<Candle> {
+    let mut klines: Vec<Candle> = Vec::new();
+    for candle in self.candles(symbol).iter() {
+      if endtime > 0 && candle.timestamp > endtime {
+        break
+      }
+      let timestamp = interval.align(candle.timestamp);
+      match klines.last_mut() {
+        Some(kline) if kline.timestamp == timestamp => {
+          kline.high = kline.high.max(candle.high);
+          kline.low = kline.low.min(candle.low);
+          kline.close = candle.close;
+          kline.volume += candle.volume;
+          kline.quota += candle.quota;
+        }
+        _ => klines.push(Candle { timestamp: timestamp, ..*candle }),
+      }
+    }
+    let skip = klines.len().saturating_sub(limit);
+    klines.split_off(skip)
+  }
+
+  // synthetic book, one lot per tick around the last price
+  pub fn depth(&self, symbol: &str, limit: usize) -> Option<(i64, Vec<(String, String)>, Vec<(String, String)>)> {
+    let (update_id, asks, bids) = self.books.get(symbol)?;
+    let level = |price: &String| (price.clone(), "1".to_string());
+    Some((
+      *update_id,
+      asks.iter().take(limit).map(level).collect(),
+      bids.iter().take(limit).map(level).collect(),
+    ))
+  }
+
+  pub fn orders(&self, symbol: &str, start_time: i64, limit: usize) -> Vec<SimOrder> {
+    let items = self.orders.iter()
+      .filter(|order| order.symbol == symbol && order.time >= start_time)
+      .cloned()
+      .collect::<Vec<_>>();
+    let skip = items.len().saturating_sub(limit);
+    items[skip..].to_vec()
+  }
+
+  pub fn order(&self, symbol: &str, order_id: i64) -> Result<SimOrder, Rejection> {
+    match self.orders.iter().find(|order| order.symbol == symbol && order.order_id == order_id) {
+      Some(order) => Ok(order.clone()),
+      None => Err(Rejection::new(-2013, "Order does not exist.")),
+    }
+  }
+
+  pub fn drain(&mut self) -> Vec<serde_json::Value> {
+    std::mem::take(&mut self.events)
+  }
+
+  // advances the market, returns the depth diff as (first, last, prev update id, asks, bids)
+  pub fn tick(&mut self, symbol: &str, price: f64, volume: f64, timestamp: i64) -> Option<(i64, i64, i64, Vec<(String, String)>, Vec<(String, String)>)> {
+    let tick_size = self.listings.get(symbol)?.tick_size;
+    let price = ((price / tick_size).round() * tick_size * 1e8).round() / 1e8;
+    self.prices.insert(symbol.to_string(), price);
+
+    let candles = self.candles.entry(symbol.to_string()).or_default();
+    let minute = Interval::Minute1.align(timestamp);
+    match candles.back_mut() {
+      Some(candle) if candle.timestamp == minute => {
+        candle.high = candle.high.max(price);
+        candle.low = candle.low.min(price);
+        candle.close = price;
+        candle.volume += volume;
+        candle.quota += volume * price;
+      }
+      _ => {
+        candles.push_back(Candle {
+          timestamp: minute,
+          open: price,
+          high: price,
+          low: price,
+          close: price,
+          volume: volume,
+          quota: volume * price,
+        });
+        if candles.len() > CANDLES_SIZE {
+          candles.pop_front();
+        }
+      }
+    }
+
+    let diff = self.rebook(symbol, price, tick_size);
+
+    let matched = self.orders.iter()
+      .enumerate()
+      .filter(|(_, order)| order.symbol == symbol && order.status == "NEW")
+      .filter(|(_, order)| (order.side == "BUY" && price <= order.price) || (order.side == "SELL" && price >= order.price))
+      .map(|(i, _)| i)
+      .collect::<Vec<_>>();
+    for i in matched {
+      let fill_price = self.orders[i].price;
+      self.fill(i, fill_price, timestamp);
+    }
+
+    Some(diff)
+  }
+
+  fn rebook(&mut self, symbol: &str, price: f64, tick_size: f64) -> (i64, i64, i64, Vec<(String, String)>, Vec<(String, String)>) {
+    let base = (price / tick_size).round();
+    let format = |steps: f64| format!("{}", (steps * tick_size * 1e8).round() / 1e8);
+    let asks = (1..=BOOK_LEVELS).map(|i| format(base + i as f64)).collect::<Vec<_>>();
+    let bids = (1..=BOOK_LEVELS).map(|i| format(base - i as f64)).filter(|price| price.parse::<f64>().unwrap_or(0.0) > 0.0).collect::<Vec<_>>();
+
+    let (last_update_id, last_asks, last_bids) = self.books.remove(symbol).unwrap_or((0, Vec::new(), Vec::new()));
+    let changes = |last: &Vec<String>, next: &Vec<String>| {
+      let mut items = last.iter().filter(|price| !next.contains(price)).map(|price| (price.clone(), "0".to_string())).collect::<Vec<_>>();
+      items.extend(next.iter().map(|price| (price.clone(), "1".to_string())));
+      items
+    };
+    let ask_changes = changes(&last_asks, &asks);
+    let bid_changes = changes(&last_bids, &bids);
+
+    let update_id = last_update_id + 1;
+    self.books.insert(symbol.to_string(), (update_id, asks, bids));
+    (update_id, update_id, last_update_id, ask_changes, bid_changes)
+  }
+
+  fn is_open(side: &str, position_side: &str) -> bool {
+    match position_side {
+      "SHORT" => side == "SELL",
+      _ => side == "BUY",
+    }
+  }
+
+  pub fn submit(
+    &mut self,
+    symbol: &str,
+    order_type: &str,
+    side: &str,
+    position_side: &str,
+    price: f64,
+    quantity: f64,
+    timestamp: i64,
+  ) -> Result<SimOrder, Rejection> {
+    let listing = match self.listings.get(symbol) {
+      Some(listing) => listing,
+      None => return Err(Rejection::new(-1121, "Invalid symbol.")),
+    };
+    if side != "BUY" && side != "SELL" {
+      return Err(Rejection::new(-1117, "Invalid side."))
+    }
+    if order_type != "LIMIT" && order_type != "MARKET" {
+      return Err(Rejection::new(-1116, "Invalid orderType."))
+    }
+    if self.is_futures() && position_side != "LONG" && position_side != "SHORT" {
+      return Err(Rejection::new(-4061, "Order's position side does not match user's setting."))
+    }
+    let last_price = match self.prices.get(symbol) {
+      Some(price) => *price,
+      None => return Err(Rejection::new(-1121, "Invalid symbol.")),
+    };
+    let price = if order_type == "MARKET" { last_price } else { price };
+
+    if quantity <= 0.0 || !aligned(quantity, listing.step_size) {
+      if self.is_futures() {
+        return Err(Rejection::new(-1111, "Precision is over the maximum defined for this asset."))
+      }
+      return Err(Rejection::new(-1013, "Filter failure: LOT_SIZE"))
+    }
+    if price <= 0.0 || !aligned(price, listing.tick_size) {
+      if self.is_futures() {
+        return Err(Rejection::new(-4014, "Price not increased by tick size."))
+      }
+      return Err(Rejection::new(-1013, "Filter failure: PRICE_FILTER"))
+    }
+
+    let is_open = !self.is_futures() || Self::is_open(side, position_side);
+    if is_open && price * quantity < listing.notional {
+      if self.is_futures() {
+        return Err(Rejection::new(-4164, &format!("Order's notional must be no smaller than {} (unless you choose reduce only).", listing.notional)))
+      }
+      return Err(Rejection::new(-1013, "Filter failure: NOTIONAL"))
+    }
+
+    let base_asset = listing.base_asset.clone();
+    let quote_asset = listing.quote_asset.clone();
+
+    // funds are locked at the limit price and released on fill or cancel
+    let (asset, amount) = if self.is_futures() {
+      if is_open {
+        (quote_asset, price * quantity / self.leverage)
+      } else {
+        let position = self.positions.get(&(symbol.to_string(), position_side.to_string())).cloned().unwrap_or_default();
+        let pending = self.orders.iter()
+          .filter(|order| order.symbol == symbol && order.status == "NEW" && order.position_side == position_side && !Self::is_open(&order.side, &order.position_side))
+          .map(|order| order.quantity)
+          .sum::<f64>();
+        if position.quantity - pending < quantity - 1e-9 {
+          return Err(Rejection::new(-2022, "ReduceOnly Order is rejected."))
+        }
+        (quote_asset, 0.0)
+      }
+    } else if side == "BUY" {
+      (quote_asset, price * quantity)
+    } else {
+      (base_asset, quantity)
+    };
+
+    let (free, locked) = self.balance(&asset);
+    if free < amount {
+      if self.is_futures() {
+        return Err(Rejection::new(-2019, "Margin is insufficient."))
+      }
+      return Err(Rejection::new(-2010, "Account has insufficient balance for requested action."))
+    }
+    self.balances.insert(asset, (free - amount, locked + amount));
+
+    let order = SimOrder {
+      symbol: symbol.to_string(),
+      order_id: self.next_order_id,
+      order_type: order_type.to_string(),
+      side: side.to_string(),
+      position_side: if self.is_futures() { position_side.to_string() } else { "".to_string() },
+      price: price,
+      avg_price: 0.0,
+      quantity: quantity,
+      executed_quantity: 0.0,
+      status: "NEW".to_string(),
+      time: timestamp,
+      update_time: timestamp,
+    };
+    self.next_order_id += 1;
+    self.orders.push(order);
+    let i = self.orders.len() - 1;
+    self.report(i, timestamp);
+
+    // marketable orders take the last price right away
+    if (side == "BUY" && last_price <= price) || (side == "SELL" && last_price >= price) {
+      self.fill(i, last_price, timestamp);
+    }
+
+    Ok(self.orders[i].clone())
+  }
+
+  pub fn cancel(&mut self, symbol: &str, order_id: i64, timestamp: i64) -> Result<SimOrder, Rejection> {
+    let i = match self.orders.iter().position(|order| order.symbol == symbol && order.order_id == order_id && order.status == "NEW") {
+      Some(i) => i,
+      None => return Err(Rejection::new(-2011, "Unknown order sent.")),
+    };
+    let order = self.orders[i].clone();
+    let listing = self.listings.get(symbol).unwrap();
+    let (asset, amount) = if self.is_futures() {
+      let amount = if Self::is_open(&order.side, &order.position_side) { order.price * order.quantity / self.leverage } else { 0.0 };
+      (listing.quote_asset.clone(), amount)
+    } else if order.side == "BUY" {
+      (listing.quote_asset.clone(), order.price * order.quantity)
+    } else {
+      (listing.base_asset.clone(), order.quantity)
+    };
+    let (free, locked) = self.balance(&asset);
+    self.balances.insert(asset, (free + amount, locked - amount));
+
+    self.orders[i].status = "CANCELED".to_string();
+    self.orders[i].update_time = timestamp;
+    self.report(i, timestamp);
+    Ok(self.orders[i].clone())
+  }
+
+  fn fill(&mut self, i: usize, price: f64, timestamp: i64) {
+    let order = self.orders[i].clone();
+    let listing = self.listings.get(&order.symbol).unwrap();
+    let base_asset = listing.base_asset.clone();
+    let quote_asset = listing.quote_asset.clone();
+
+    if self.is_futures() {
+      let key = (order.symbol.clone(), order.position_side.clone());
+      let mut position = self.positions.get(&key).cloned().unwrap_or_default();
+      let (free, locked) = self.balance(&quote_asset);
+      if Self::is_open(&order.side, &order.position_side) {
+        let reserved = order.price * order.quantity / self.leverage;
+        let margin = price * order.quantity / self.leverage;
+        position.entry_price = (position.entry_price * position.quantity + price * order.quantity) / (position.quantity + order.quantity);
+        position.quantity += order.quantity;
+        position.margin += margin;
+        self.balances.insert(quote_asset.clone(), (free + reserved - margin, locked - reserved + margin));
+      } else {
+        let quantity = order.quantity.min(position.quantity);
+        let profit = match order.position_side.as_str() {
+          "SHORT" => (position.entry_price - price) * quantity,
+          _ => (price - position.entry_price) * quantity,
+        };
+        let released = if position.quantity > 0.0 { position.margin * quantity / position.quantity } else { 0.0 };
+        position.quantity -= quantity;
+        position.margin -= released;
+        if position.quantity <= 1e-12 {
+          position = SimPosition::default();
+        }
+        self.balances.insert(quote_asset.clone(), (free + released + profit, locked - released));
+      }
+      position.update_time = timestamp;
+      self.positions.insert(key, position);
+    } else if order.side == "BUY" {
+      let reserved = order.price * order.quantity;
+      let (free, locked) = self.balance(&quote_asset);
+      self.balances.insert(quote_asset.clone(), (free + reserved - price * order.quantity, locked - reserved));
+      self.deposit(&base_asset, order.quantity);
+    } else {
+      let (free, locked) = self.balance(&base_asset);
+      self.balances.insert(base_asset.clone(), (free, locked - order.quantity));
+      self.deposit(&quote_asset, price * order.quantity);
+    }
+
+    let filled = &mut self.orders[i];
+    filled.executed_quantity = filled.quantity;
+    filled.avg_price = price;
+    filled.status = "FILLED".to_string();
+    filled.update_time = timestamp;
+    self.report(i, timestamp);
+    self.account(&order.symbol, &base_asset, &quote_asset, timestamp);
+  }
+
+  fn report(&mut self, i: usize, timestamp: i64) {
+    let order = &self.orders[i];
+    let event = if self.is_futures() {
+      json!({
+        "e": "ORDER_TRADE_UPDATE",
+        "E": timestamp,
+        "T": timestamp,
+        "o": {
+          "s": order.symbol,
+          "i": order.order_id,
+          "o": order.order_type,
+          "ps": order.position_side,
+          "S": order.side,
+          "p": order.price.to_string(),
+          "ap": order.avg_price.to_string(),
+          "sp": "0",
+          "q": order.quantity.to_string(),
+          "z": order.executed_quantity.to_string(),
+          "T": order.update_time,
+          "wt": "CONTRACT_PRICE",
+          "pP": false,
+          "R": !Self::is_open(&order.side, &order.position_side),
+          "cp": false,
+          "X": order.status,
+        },
+      })
+    } else {
+      json!({
+        "e": "executionReport",
+        "E": timestamp,
+        "s": order.symbol,
+        "i": order.order_id,
+        "o": order.order_type,
+        "S": order.side,
+        "p": order.price.to_string(),
+        "q": order.quantity.to_string(),
+        "z": order.executed_quantity.to_string(),
+        "X": order.status,
+        "T": order.update_time,
+      })
+    };
+    self.events.push(event);
+  }
+
+  fn account(&mut self, symbol: &str, base_asset: &str, quote_asset: &str, timestamp: i64) {
+    let event = if self.is_futures() {
+      let (free, locked) = self.balance(quote_asset);
+      let positions = ["LONG", "SHORT"].iter().map(|side| {
+        let position = self.positions.get(&(symbol.to_string(), side.to_string())).cloned().unwrap_or_default();
+        let quantity = if *side == "SHORT" { -position.quantity } else { position.quantity };
+        json!({
+          "s": symbol,
+          "pa": quantity.to_string(),
+          "ep": position.entry_price.to_string(),
+          "mt": "cross",
+          "ps": side,
+        })
+      }).collect::<Vec<_>>();
+      json!({
+        "e": "ACCOUNT_UPDATE",
+        "E": timestamp,
+        "T": timestamp,
+        "a": {
+          "m": "ORDER",
+          "B": [{"a": quote_asset, "wb": (free + locked).to_string(), "cw": (free + locked).to_string()}],
+          "P": positions,
+        },
+      })
+    } else {
+      let balances = [base_asset, quote_asset].iter().map(|asset| {
+        let (free, locked) = self.balance(asset);
+        json!({"a": asset, "f": free.to_string(), "l": locked.to_string()})
+      }).collect::<Vec<_>>();
+      json!({
+        "e": "outboundAccountPosition",
+        "E": timestamp,
+        "u": timestamp,
+        "B": balances,
+      })
+    };
+    self.events.push(event);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn engine(market: &'static str) -> Engine {
+    let mut engine = Engine::new(market);
+    engine.listing("BTCUSDT", Listing {
+      base_asset: "BTC".to_string(),
+      quote_asset: "USDT".to_string(),
+      tick_size: 0.1,
+      step_size: 0.001,
+      notional: 5.0,
+    });
+    engine.deposit("USDT", 1000.0);
+    engine.tick("BTCUSDT", 100.0, 1.0, 60000);
+    engine
+  }
+
+  #[test]
+  fn spot_limit_order_fills_when_price_crosses() {
+    let mut engine = engine("spot");
+    let order = engine.submit("BTCUSDT", "LIMIT", "BUY", "", 95.0, 1.0, 60000).unwrap();
+    assert_eq!(order.status, "NEW");
+    assert_eq!(engine.balance("USDT"), (905.0, 95.0));
+
+    engine.tick("BTCUSDT", 96.0, 1.0, 120000);
+    assert_eq!(engine.order("BTCUSDT", order.order_id).unwrap().status, "NEW");
+
+    engine.tick("BTCUSDT", 94.9, 1.0, 180000);
+    let order = engine.order("BTCUSDT", order.order_id).unwrap();
+    assert_eq!(order.status, "FILLED");
+    assert_eq!(order.executed_quantity, 1.0);
+    assert_eq!(engine.balance("USDT"), (905.0, 0.0));
+    assert_eq!(engine.balance("BTC"), (1.0, 0.0));
+  }
+
+  #[test]
+  fn spot_cancel_releases_funds() {
+    let mut engine = engine("spot");
+    let order = engine.submit("BTCUSDT", "LIMIT", "BUY", "", 90.0, 2.0, 60000).unwrap();
+    engine.cancel("BTCUSDT", order.order_id, 61000).unwrap();
+    assert_eq!(engine.balance("USDT"), (1000.0, 0.0));
+    assert_eq!(engine.cancel("BTCUSDT", order.order_id, 62000).unwrap_err().code, -2011);
+  }
+
+  #[test]
+  fn spot_rejects_like_binance() {
+    let mut engine = engine("spot");
+    assert_eq!(engine.submit("ETHUSDT", "LIMIT", "BUY", "", 90.0, 1.0, 60000).unwrap_err().code, -1121);
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "", 90.05, 1.0, 60000).unwrap_err(), Rejection::new(-1013, "Filter failure: PRICE_FILTER"));
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "", 90.0, 0.0001, 60000).unwrap_err(), Rejection::new(-1013, "Filter failure: LOT_SIZE"));
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "", 90.0, 0.01, 60000).unwrap_err(), Rejection::new(-1013, "Filter failure: NOTIONAL"));
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "", 90.0, 20.0, 60000).unwrap_err().code, -2010);
+    assert_eq!(engine.order("BTCUSDT", 42).unwrap_err().code, -2013);
+  }
+
+  #[test]
+  fn futures_round_trip_realizes_profit() {
+    let mut engine = engine("futures");
+    let open = engine.submit("BTCUSDT", "LIMIT", "BUY", "LONG", 100.0, 1.0, 60000).unwrap();
+    assert_eq!(open.status, "FILLED");
+    let position = engine.positions().into_iter().find(|(_, side, _)| side == "LONG").unwrap().2;
+    assert_eq!(position.quantity, 1.0);
+    assert_eq!(position.entry_price, 100.0);
+    assert_eq!(engine.balance("USDT"), (990.0, 10.0));
+
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "SELL", "LONG", 110.0, 2.0, 60000).unwrap_err().code, -2022);
+    let close = engine.submit("BTCUSDT", "LIMIT", "SELL", "LONG", 110.0, 1.0, 60000).unwrap();
+    assert_eq!(close.status, "NEW");
+
+    engine.tick("BTCUSDT", 110.0, 1.0, 120000);
+    assert_eq!(engine.order("BTCUSDT", close.order_id).unwrap().status, "FILLED");
+    assert_eq!(engine.balance("USDT"), (1010.0, 0.0));
+
+    let events = engine.drain();
+    assert!(events.iter().any(|event| event["e"] == "ACCOUNT_UPDATE"));
+    assert_eq!(events.iter().filter(|event| event["e"] == "ORDER_TRADE_UPDATE").count(), 4);
+  }
+
+  #[test]
+  fn futures_rejects_like_binance() {
+    let mut engine = engine("futures");
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "BOTH", 90.0, 1.0, 60000).unwrap_err().code, -4061);
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "LONG", 90.05, 1.0, 60000).unwrap_err().code, -4014);
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "LONG", 90.0, 0.01, 60000).unwrap_err().code, -4164);
+    assert_eq!(engine.submit("BTCUSDT", "LIMIT", "BUY", "LONG", 90.0, 200.0, 60000).unwrap_err().code, -2019);
+  }
+
+  #[test]
+  fn klines_aggregate_minutes() {
+    let mut engine = engine("spot");
+    for (i, price) in [101.0, 99.0, 102.0, 100.5].iter().enumerate() {
+      engine.tick("BTCUSDT", *price, 1.0, 61000 + i as i64 * 60000);
+    }
+    let klines = engine.klines("BTCUSDT", Interval::Minute5, 0, 10);
+    assert_eq!(klines.len(), 1);
+    assert_eq!(klines[0].timestamp, 0);
+    assert_eq!(klines[0].open, 100.0);
+    assert_eq!(klines[0].high, 102.0);
+    assert_eq!(klines[0].low, 99.0);
+    assert_eq!(klines[0].close, 100.5);
+    assert_eq!(klines[0].volume, 5.0);
+  }
+
+  #[test]
+  fn depth_diffs_chain_update_ids() {
+    let mut engine = engine("futures");
+    let (first, last, prev, asks, _) = engine.tick("BTCUSDT", 100.5, 1.0, 120000).unwrap();
+    assert_eq!((first, last, prev), (2, 2, 1));
+    assert!(asks.contains(&("100.1".to_string(), "0".to_string())));
+    let (update_id, asks, bids) = engine.depth("BTCUSDT", 5).unwrap();
+    assert_eq!(update_id, 2);
+    assert_eq!(asks[0].0, "100.6");
+    assert_eq!(bids[0].0, "100.4");
+  }
+}
@@ -0,0 +1,57 @@
+use std::collections::VecDeque;
+
+// scripted lines of `symbol,price[,volume]`, one line per tick, or a seeded random walk
+pub enum PricePath {
+  Replay(VecDeque<(String, f64, f64)>),
+  Walk(u64, f64),
+}
+
+impl PricePath {
+  pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut ticks = VecDeque::new();
+    for line in content.lines() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with("#") {
+        continue
+      }
+      let values = line.split(",").map(|value| value.trim()).collect::<Vec<_>>();
+      if values.len() < 2 {
+        return Err(Box::from(format!("price path line {line:} not valid")))
+      }
+      let volume = match values.get(2) {
+        Some(value) => value.parse::<f64>()?,
+        None => 1.0,
+      };
+      ticks.push_back((values[0].to_string(), values[1].parse::<f64>()?, volume));
+    }
+    Ok(Self::Replay(ticks))
+  }
+
+  pub fn walk(seed: u64, volatility: f64) -> Self {
+    Self::Walk(seed.max(1), volatility)
+  }
+
+  pub fn next(&mut self, prices: &[(String, f64)]) -> Option<Vec<(String, f64, f64)>> {
+    match self {
+      Self::Replay(ticks) => ticks.pop_front().map(|tick| vec![tick]),
+      Self::Walk(seed, volatility) => {
+        let mut ticks = Vec::new();
+        for (symbol, price) in prices.iter() {
+          let change = (Self::random(seed) - 0.5) * 2.0 * *volatility;
+          let volume = Self::random(seed) * 10.0;
+          ticks.push((symbol.clone(), price * (1.0 + change), volume));
+        }
+        Some(ticks)
+      }
+    }
+  }
+
+  // xorshift64, reproducible for a given seed
+  fn random(seed: &mut u64) -> f64 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 7;
+    *seed ^= *seed << 17;
+    (*seed >> 11) as f64 / (1u64 << 53) as f64
+  }
+}
@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+
+use axum::{
+  extract::State,
+  http::{HeaderMap, Method, StatusCode, Uri},
+  response::{IntoResponse, Response},
+  Json,
+  Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::prelude::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use url::form_urlencoded;
+
+use crate::common::*;
+use crate::simulator::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Security {
+  Public,
+  Stream,
+  Signed,
+}
+
+struct Request {
+  method: Method,
+  endpoint: String,
+  params: HashMap<String, String>,
+}
+
+impl Request {
+  fn param(&self, key: &str) -> &str {
+    self.params.get(key).map(|value| value.as_str()).unwrap_or("")
+  }
+
+  fn required(&self, key: &str) -> Result<&str, Rejection> {
+    match self.param(key) {
+      "" => Err(Rejection::new(-1102, &format!("Mandatory parameter '{key:}' was not sent, was empty/null, or malformed."))),
+      value => Ok(value),
+    }
+  }
+
+  fn number<T: std::str::FromStr>(&self, key: &str) -> Result<T, Rejection> {
+    match self.required(key)?.parse::<T>() {
+      Ok(value) => Ok(value),
+      Err(_) => Err(Rejection::new(-1100, &format!("Illegal characters found in parameter '{key:}'."))),
+    }
+  }
+}
+
+pub fn router(simulator: Simulator) -> Router {
+  Router::new().fallback(handle).with_state(simulator)
+}
+
+async fn handle(
+  State(simulator): State<Simulator>,
+  method: Method,
+  uri: Uri,
+  headers: HeaderMap,
+  body: String,
+) -> Response {
+  let prefixes: &[&str] = match simulator.market {
+    "spot" => &["/api/v3/"],
+    _ => &["/fapi/v1/", "/fapi/v2/"],
+  };
+  let endpoint = match prefixes.iter().find_map(|prefix| uri.path().strip_prefix(prefix)) {
+    Some(endpoint) => endpoint.to_string(),
+    None => return reject(StatusCode::NOT_FOUND, Rejection::new(-1000, "Unknown endpoint."), 0),
+  };
+
+  let query = uri.query().unwrap_or("").to_string();
+  let mut params = HashMap::new();
+  for (key, value) in form_urlencoded::parse(query.as_bytes()).chain(form_urlencoded::parse(body.as_bytes())) {
+    params.insert(key.to_string(), value.to_string());
+  }
+  let request = Request {
+    method: method,
+    endpoint: endpoint,
+    params: params,
+  };
+
+  let limit = request.param("limit").parse::<i64>().unwrap_or(500);
+  let (security, weight) = route(&simulator, &request, limit);
+  let used = match charge(&simulator, weight).await {
+    Ok(used) => used,
+    Err(retry_after) => {
+      let used = simulator.weight.lock().await.1;
+      let rejection = Rejection::new(-1003, &format!("Too many requests; current limit of IP is {} requests per minute.", simulator.weight_limit));
+      let mut response = reject(StatusCode::TOO_MANY_REQUESTS, rejection, used);
+      response.headers_mut().insert("retry-after", retry_after.into());
+      return response
+    }
+  };
+
+  if let Err((status_code, rejection)) = authorize(&simulator, &request, &headers, &query, &body, security) {
+    return reject(status_code, rejection, used)
+  }
+
+  match dispatch(&simulator, &request).await {
+    Ok(content) => {
+      let mut response = Json(content).into_response();
+      response.headers_mut().insert("x-mbx-used-weight-1m", used.into());
+      response
+    }
+    Err(rejection) if rejection.code == -1000 => reject(StatusCode::NOT_FOUND, rejection, used),
+    Err(rejection) => reject(StatusCode::BAD_REQUEST, rejection, used),
+  }
+}
+
+fn reject(status_code: StatusCode, rejection: Rejection, used: i64) -> Response {
+  let mut response = (status_code, Json(json!({"code": rejection.code, "msg": rejection.message}))).into_response();
+  response.headers_mut().insert("x-mbx-used-weight-1m", used.into());
+  response
+}
+
+fn route(simulator: &Simulator, request: &Request, limit: i64) -> (Security, i64) {
+  let is_spot = simulator.market == "spot";
+  match request.endpoint.as_str() {
+    "time" | "ping" => (Security::Public, 1),
+    "exchangeInfo" => (Security::Public, if is_spot { 20 } else { 1 }),
+    "klines" if is_spot => (Security::Public, 2),
+    "klines" => (Security::Public, match limit {
+      0..=99 => 1,
+      100..=499 => 2,
+      500..=1000 => 5,
+      _ => 10,
+    }),
+    "depth" => (Security::Public, match limit {
+      0..=50 => 2,
+      51..=100 => 5,
+      101..=500 => 10,
+      _ => 20,
+    }),
+    "listenKey" | "userDataStream" => (Security::Stream, 1),
+    "order" if request.method == Method::GET && is_spot => (Security::Signed, 4),
+    "order" => (Security::Signed, 1),
+    "allOrders" | "account" if is_spot => (Security::Signed, 20),
+    "allOrders" | "account" => (Security::Signed, 5),
+    _ => (Security::Public, 1),
+  }
+}
+
+// per minute ip weight, the retry delay in seconds when the limit is reached
+async fn charge(simulator: &Simulator, weight: i64) -> Result<i64, i64> {
+  let timestamp = Utc::now().timestamp();
+  let mut bucket = simulator.weight.lock().await;
+  if bucket.0 != timestamp / 60 {
+    *bucket = (timestamp / 60, 0);
+  }
+  if bucket.1 + weight > simulator.weight_limit {
+    return Err(60 - timestamp % 60)
+  }
+  bucket.1 += weight;
+  Ok(bucket.1)
+}
+
+fn authorize(
+  simulator: &Simulator,
+  request: &Request,
+  headers: &HeaderMap,
+  query: &str,
+  body: &str,
+  security: Security,
+) -> Result<(), (StatusCode, Rejection)> {
+  if security == Security::Public {
+    return Ok(())
+  }
+
+  let api_key = headers.get("X-MBX-APIKEY").and_then(|value| value.to_str().ok()).unwrap_or("");
+  if api_key.is_empty() {
+    return Err((StatusCode::UNAUTHORIZED, Rejection::new(-2014, "API-key format invalid.")))
+  }
+  if api_key != simulator.account_key && api_key != simulator.trade_key {
+    return Err((StatusCode::UNAUTHORIZED, Rejection::new(-2015, "Invalid API-key, IP, or permissions for action.")))
+  }
+  if security == Security::Stream {
+    return Ok(())
+  }
+
+  let payload = match body.is_empty() {
+    true => query.to_string(),
+    false if query.is_empty() => body.to_string(),
+    false => format!("{query:}{body:}"),
+  };
+  let (payload, _) = payload.rsplit_once("&signature=").unwrap_or((payload.as_str(), ""));
+  let signature = request.param("signature");
+  if signature.is_empty() {
+    return Err((StatusCode::BAD_REQUEST, Rejection::new(-1102, "Mandatory parameter 'signature' was not sent, was empty/null, or malformed.")))
+  }
+  if !verify(simulator, api_key, payload, signature) {
+    return Err((StatusCode::BAD_REQUEST, Rejection::new(-1022, "Signature for this request is not valid.")))
+  }
+
+  let timestamp = match request.param("timestamp").parse::<i64>() {
+    Ok(timestamp) => timestamp,
+    Err(_) => return Err((StatusCode::BAD_REQUEST, Rejection::new(-1102, "Mandatory parameter 'timestamp' was not sent, was empty/null, or malformed."))),
+  };
+  let recv_window = request.param("recvWindow").parse::<i64>().unwrap_or(5000);
+  let now = Utc::now().timestamp_millis();
+  if timestamp > now + 1000 || now - timestamp > recv_window {
+    return Err((StatusCode::BAD_REQUEST, Rejection::new(-1021, "Timestamp for this request is outside of the recvWindow.")))
+  }
+
+  Ok(())
+}
+
+fn verify(simulator: &Simulator, api_key: &str, payload: &str, signature: &str) -> bool {
+  if api_key == simulator.account_key {
+    type HmacSha256 = Hmac<Sha256>;
+    let mut mac = match HmacSha256::new_from_slice(simulator.account_secret.as_bytes()) {
+      Ok(mac) => mac,
+      Err(_) => return false,
+    };
+    mac.update(payload.as_bytes());
+    return match hex::decode(signature) {
+      Ok(signature) => mac.verify_slice(&signature).is_ok(),
+      Err(_) => false,
+    }
+  }
+
+  let public_key = match &simulator.trade_public_key {
+    Some(public_key) => public_key,
+    None => return false,
+  };
+  let signature = match general_purpose::STANDARD.decode(signature) {
+    Ok(signature) => signature,
+    Err(_) => return false,
+  };
+  public_key.verify(
+    rsa::pkcs1v15::Pkcs1v15Sign::new::<rsa::sha2::Sha256>(),
+    &Sha256::digest(payload.as_bytes()),
+    &signature,
+  ).is_ok()
+}
+
+async fn dispatch(simulator: &Simulator, request: &Request) -> Result<serde_json::Value, Rejection> {
+  let is_futures = simulator.market == "futures";
+  let timestamp = Utc::now().timestamp_millis();
+  match (request.method.clone(), request.endpoint.as_str()) {
+    (Method::GET, "ping") => Ok(json!({})),
+    (Method::GET, "time") => Ok(json!({"serverTime": timestamp})),
+    (Method::GET, "exchangeInfo") => {
+      let engine = simulator.engine.lock().await;
+      let symbols = engine.listings().iter().map(|(symbol, listing)| {
+        let mut info = json!({
+          "symbol": symbol,
+          "baseAsset": listing.base_asset,
+          "quoteAsset": listing.quote_asset,
+          "status": "TRADING",
+          "filters": [
+            {"filterType": "PRICE_FILTER", "minPrice": listing.tick_size.to_string(), "maxPrice": "1000000", "tickSize": listing.tick_size.to_string()},
+            {"filterType": "LOT_SIZE", "minQty": listing.step_size.to_string(), "maxQty": "100000", "stepSize": listing.step_size.to_string()},
+            {"filterType": "MARKET_LOT_SIZE", "minQty": listing.step_size.to_string(), "maxQty": "10000", "stepSize": listing.step_size.to_string()},
+            {"filterType": "MAX_NUM_ORDERS", "limit": 200},
+            {"filterType": "MAX_NUM_ALGO_ORDERS", "limit": 10},
+          ],
+        });
+        if is_futures {
+          info["contractType"] = json!("PERPETUAL");
+          info["filters"].as_array_mut().unwrap().push(json!({"filterType": "MIN_NOTIONAL", "notional": listing.notional.to_string()}));
+          info["filters"].as_array_mut().unwrap().push(json!({"filterType": "PERCENT_PRICE", "multiplierUp": "1.05", "multiplierDown": "0.95", "multiplierDecimal": "4"}));
+        } else {
+          info["isSpotTradingAllowed"] = json!(true);
+          info["isMarginTradingAllowed"] = json!(false);
+          info["filters"].as_array_mut().unwrap().push(json!({"filterType": "NOTIONAL", "minNotional": listing.notional.to_string(), "maxNotional": "9000000"}));
+        }
+        info
+      }).collect::<Vec<_>>();
+      Ok(json!({"timezone": "UTC", "serverTime": timestamp, "symbols": symbols}))
+    }
+    (Method::GET, "klines") => {
+      let symbol = request.required("symbol")?;
+      let interval = match Interval::parse(request.required("interval")?) {
+        Ok(interval) => interval,
+        Err(_) => return Err(Rejection::new(-1120, "Invalid interval.")),
+      };
+      let endtime = request.param("endTime").parse::<i64>().unwrap_or(0);
+      let limit = request.param("limit").parse::<usize>().unwrap_or(500).min(1500);
+      let engine = simulator.engine.lock().await;
+      if engine.price(symbol).is_none() {
+        return Err(Rejection::new(-1121, "Invalid symbol."))
+      }
+      let klines = engine.klines(symbol, interval, endtime, limit).iter().map(|kline| {
+        json!([
+          kline.timestamp,
+          kline.open.to_string(),
+          kline.high.to_string(),
+          kline.low.to_string(),
+          kline.close.to_string(),
+          kline.volume.to_string(),
+          interval.next(kline.timestamp) - 1,
+          kline.quota.to_string(),
+          1,
+          "0",
+          "0",
+          "0",
+        ])
+      }).collect::<Vec<_>>();
+      Ok(json!(klines))
+    }
+    (Method::GET, "depth") => {
+      let symbol = request.required("symbol")?;
+      let limit = request.param("limit").parse::<usize>().unwrap_or(100);
+      let engine = simulator.engine.lock().await;
+      match engine.depth(symbol, limit) {
+        Some((update_id, asks, bids)) => Ok(json!({
+          "lastUpdateId": update_id,
+          "E": timestamp,
+          "T": timestamp,
+          "asks": asks.iter().map(|(price, quantity)| vec![price, quantity]).collect::<Vec<_>>(),
+          "bids": bids.iter().map(|(price, quantity)| vec![price, quantity]).collect::<Vec<_>>(),
+        })),
+        None => Err(Rejection::new(-1121, "Invalid symbol.")),
+      }
+    }
+    (Method::POST, "order") => {
+      let symbol = request.required("symbol")?;
+      let order_type = request.required("type")?;
+      let side = request.required("side")?;
+      let position_side = if is_futures { request.param("positionSide") } else { "" };
+      let price = if order_type == "LIMIT" { request.number::<f64>("price")? } else { 0.0 };
+      let quantity = request.number::<f64>("quantity")?;
+      let mut engine = simulator.engine.lock().await;
+      let order = engine.submit(symbol, order_type, side, position_side, price, quantity, timestamp)?;
+      simulator.publish(engine.drain());
+      Ok(render(is_futures, &order))
+    }
+    (Method::GET, "order") => {
+      let symbol = request.required("symbol")?;
+      let order_id = request.number::<i64>("orderId")?;
+      let engine = simulator.engine.lock().await;
+      Ok(render(is_futures, &engine.order(symbol, order_id)?))
+    }
+    (Method::DELETE, "order") => {
+      let symbol = request.required("symbol")?;
+      let order_id = request.number::<i64>("orderId")?;
+      let mut engine = simulator.engine.lock().await;
+      let order = engine.cancel(symbol, order_id, timestamp)?;
+      simulator.publish(engine.drain());
+      Ok(render(is_futures, &order))
+    }
+    (Method::GET, "allOrders") => {
+      let symbol = request.required("symbol")?;
+      let start_time = request.param("startTime").parse::<i64>().unwrap_or(0);
+      let limit = request.param("limit").parse::<usize>().unwrap_or(500).min(1000);
+      let engine = simulator.engine.lock().await;
+      let orders = engine.orders(symbol, start_time, limit).iter().map(|order| render(is_futures, order)).collect::<Vec<_>>();
+      Ok(json!(orders))
+    }
+    (Method::GET, "account") => {
+      let engine = simulator.engine.lock().await;
+      if !is_futures {
+        let balances = engine.balances().iter().map(|(asset, free, locked)| {
+          json!({"asset": asset, "free": free.to_string(), "locked": locked.to_string()})
+        }).collect::<Vec<_>>();
+        return Ok(json!({"canTrade": true, "updateTime": timestamp, "balances": balances}))
+      }
+      let positions = engine.positions();
+      let assets = engine.balances().iter().map(|(asset, free, locked)| {
+        let unrealized_profit = positions.iter().map(|(symbol, side, position)| {
+          let price = engine.price(symbol).unwrap_or(position.entry_price);
+          let quote_asset = engine.listings().iter().find(|(s, _)| *s == symbol).map(|(_, listing)| listing.quote_asset.clone()).unwrap_or_default();
+          if &quote_asset != asset {
+            return 0.0
+          }
+          match side.as_str() {
+            "SHORT" => (position.entry_price - price) * position.quantity,
+            _ => (price - position.entry_price) * position.quantity,
+          }
+        }).sum::<f64>();
+        json!({
+          "asset": asset,
+          "walletBalance": (free + locked).to_string(),
+          "availableBalance": free.to_string(),
+          "unrealizedProfit": unrealized_profit.to_string(),
+          "marginBalance": (free + locked + unrealized_profit).to_string(),
+          "initialMargin": locked.to_string(),
+          "maintMargin": (locked / 2.0).to_string(),
+        })
+      }).collect::<Vec<_>>();
+      let positions = positions.iter().map(|(symbol, side, position)| {
+        let quantity = if side == "SHORT" { -position.quantity } else { position.quantity };
+        let notional = engine.price(symbol).unwrap_or(position.entry_price) * quantity;
+        json!({
+          "symbol": symbol,
+          "positionSide": side,
+          "isolated": false,
+          "leverage": engine.leverage.to_string(),
+          "maxNotional": "1000000",
+          "notional": notional.to_string(),
+          "entryPrice": position.entry_price.to_string(),
+          "positionAmt": quantity.to_string(),
+          "updateTime": position.update_time,
+        })
+      }).collect::<Vec<_>>();
+      Ok(json!({"canTrade": true, "assets": assets, "positions": positions}))
+    }
+    (Method::POST, "listenKey") | (Method::POST, "userDataStream") => {
+      let listen_key = xid::new().to_string();
+      simulator.listen_keys.lock().await.insert(listen_key.clone());
+      Ok(json!({"listenKey": listen_key}))
+    }
+    (Method::PUT, "listenKey") | (Method::PUT, "userDataStream") => Ok(json!({})),
+    (Method::DELETE, "listenKey") | (Method::DELETE, "userDataStream") => {
+      simulator.listen_keys.lock().await.remove(request.param("listenKey"));
+      Ok(json!({}))
+    }
+    _ => Err(Rejection::new(-1000, "Unknown endpoint.")),
+  }
+}
+
+fn render(is_futures: bool, order: &SimOrder) -> serde_json::Value {
+  if is_futures {
+    return json!({
+      "symbol": order.symbol,
+      "orderId": order.order_id,
+      "type": order.order_type,
+      "positionSide": order.position_side,
+      "side": order.side,
+      "price": order.price.to_string(),
+      "avgPrice": order.avg_price.to_string(),
+      "stopPrice": "0",
+      "origQty": order.quantity.to_string(),
+      "executedQty": order.executed_quantity.to_string(),
+      "workingType": "CONTRACT_PRICE",
+      "priceProtect": false,
+      "reduceOnly": (order.side == "SELL") == (order.position_side == "LONG"),
+      "closePosition": false,
+      "timeInForce": "GTC",
+      "time": order.time,
+      "updateTime": order.update_time,
+      "status": order.status,
+    })
+  }
+  json!({
+    "symbol": order.symbol,
+    "orderId": order.order_id,
+    "type": order.order_type,
+    "side": order.side,
+    "price": order.price.to_string(),
+    "stopPrice": "0",
+    "origQty": order.quantity.to_string(),
+    "executedQty": order.executed_quantity.to_string(),
+    "timeInForce": "GTC",
+    "transactTime": order.update_time,
+    "time": order.time,
+    "updateTime": order.update_time,
+    "status": order.status,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn simulator() -> Simulator {
+    let mut engine = Engine::new("spot");
+    engine.listing("BTCUSDT", Listing {
+      base_asset: "BTC".to_string(),
+      quote_asset: "USDT".to_string(),
+      tick_size: 0.1,
+      step_size: 0.001,
+      notional: 5.0,
+    });
+    engine.deposit("USDT", 1000.0);
+    engine.tick("BTCUSDT", 100.0, 1.0, Utc::now().timestamp_millis());
+    let mut simulator = Simulator::new("spot", engine);
+    simulator.account_key = "key".to_string();
+    simulator.account_secret = "secret".to_string();
+    simulator.trade_key = "".to_string();
+    simulator.trade_public_key = None;
+    simulator
+  }
+
+  fn sign(query: &str) -> String {
+    type HmacSha256 = Hmac<Sha256>;
+    let mut mac = HmacSha256::new_from_slice(b"secret").unwrap();
+    mac.update(query.as_bytes());
+    format!("{}&signature={}", query, hex::encode(mac.finalize().into_bytes()))
+  }
+
+  async fn call(simulator: &Simulator, method: Method, uri: &str, api_key: &str) -> (StatusCode, serde_json::Value) {
+    let mut headers = HeaderMap::new();
+    if !api_key.is_empty() {
+      headers.insert("X-MBX-APIKEY", api_key.parse().unwrap());
+    }
+    let response = handle(State(simulator.clone()), method, uri.parse().unwrap(), headers, "".to_string()).await;
+    let status_code = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status_code, serde_json::from_slice(&body).unwrap())
+  }
+
+  #[tokio::test]
+  async fn signed_order_round_trip() {
+    let simulator = simulator();
+    let query = format!("symbol=BTCUSDT&side=BUY&type=LIMIT&price=95&quantity=1&timeInForce=GTC&timestamp={}", Utc::now().timestamp_millis());
+    let (status_code, order) = call(&simulator, Method::POST, &format!("/api/v3/order?{}", sign(&query)), "key").await;
+    assert_eq!(status_code, StatusCode::OK);
+    assert_eq!(order["status"], "NEW");
+    assert_eq!(order["origQty"], "1");
+
+    let mut events = simulator.events.subscribe();
+    simulator.tick("BTCUSDT", 94.0, 1.0).await;
+    let event: serde_json::Value = serde_json::from_str(&events.recv().await.unwrap()).unwrap();
+    assert_eq!(event["e"], "executionReport");
+    assert_eq!(event["X"], "FILLED");
+
+    let query = format!("timestamp={}", Utc::now().timestamp_millis());
+    let (_, account) = call(&simulator, Method::GET, &format!("/api/v3/account?{}", sign(&query)), "key").await;
+    let balances = account["balances"].as_array().unwrap();
+    assert!(balances.contains(&json!({"asset": "BTC", "free": "1", "locked": "0"})));
+  }
+
+  #[tokio::test]
+  async fn rejects_bad_requests_like_binance() {
+    let simulator = simulator();
+    let timestamp = Utc::now().timestamp_millis();
+
+    let (status_code, error) = call(&simulator, Method::GET, "/api/v3/account", "").await;
+    assert_eq!((status_code, error["code"].clone()), (StatusCode::UNAUTHORIZED, json!(-2014)));
+
+    let (_, error) = call(&simulator, Method::GET, "/api/v3/account", "other").await;
+    assert_eq!(error["code"], -2015);
+
+    let (_, error) = call(&simulator, Method::GET, &format!("/api/v3/account?timestamp={}&signature=00", timestamp), "key").await;
+    assert_eq!(error["code"], -1022);
+
+    let query = format!("timestamp={}", timestamp - 60000);
+    let (_, error) = call(&simulator, Method::GET, &format!("/api/v3/account?{}", sign(&query)), "key").await;
+    assert_eq!(error["code"], -1021);
+
+    let query = format!("symbol=BTCUSDT&orderId=7&timestamp={}", timestamp);
+    let (_, error) = call(&simulator, Method::DELETE, &format!("/api/v3/order?{}", sign(&query)), "key").await;
+    assert_eq!(error["code"], -2011);
+  }
+
+  #[tokio::test]
+  async fn enforces_weight_limit() {
+    let mut simulator = simulator();
+    simulator.weight_limit = 40;
+    let (status_code, _) = call(&simulator, Method::GET, "/api/v3/exchangeInfo", "").await;
+    assert_eq!(status_code, StatusCode::OK);
+    let (status_code, _) = call(&simulator, Method::GET, "/api/v3/exchangeInfo", "").await;
+    assert_eq!(status_code, StatusCode::OK);
+    let (status_code, error) = call(&simulator, Method::GET, "/api/v3/exchangeInfo", "").await;
+    assert_eq!(status_code, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(error["code"], -1003);
+  }
+}
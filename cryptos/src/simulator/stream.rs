@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use chrono::prelude::Utc;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::common::*;
+use crate::simulator::*;
+
+#[derive(Deserialize)]
+struct StreamRequest {
+  method: String,
+  #[serde(default)]
+  params: Vec<String>,
+  id: serde_json::Value,
+}
+
+pub async fn serve(simulator: Simulator, listener: TcpListener) -> Result<(), Box<dyn std::error::Error>> {
+  loop {
+    let (socket, _) = listener.accept().await?;
+    let simulator = simulator.clone();
+    tokio::spawn(async move {
+      if let Err(err) = connection(simulator, socket).await {
+        println!("simulator stream error: {}", err);
+      }
+    });
+  }
+}
+
+async fn connection(simulator: Simulator, socket: TcpStream) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+  let mut uri = String::new();
+  let callback = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+    uri = request.uri().to_string();
+    Ok(response)
+  };
+  let mut socket = tokio_tungstenite::accept_hdr_async(socket, callback).await?;
+
+  if let Some(listen_key) = uri.strip_prefix("/ws/") {
+    if !simulator.listen_keys.lock().await.contains(listen_key) {
+      let error = json!({"error": {"code": -1125, "msg": "This listenKey does not exist."}});
+      socket.send(Message::text(error.to_string())).await?;
+      return Ok(())
+    }
+    let mut events = simulator.events.subscribe();
+    loop {
+      tokio::select! {
+        message = socket.next() => match message {
+          Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+          _ => (),
+        },
+        event = events.recv() => match event {
+          Ok(event) => socket.send(Message::text(event)).await?,
+          Err(RecvError::Lagged(_)) => (),
+          Err(RecvError::Closed) => return Ok(()),
+        },
+      }
+    }
+  }
+
+  let mut streams: HashSet<String> = HashSet::new();
+  if let Some((_, names)) = uri.split_once("streams=") {
+    streams.extend(names.split("/").filter(|name| !name.is_empty()).map(|name| name.to_string()));
+  }
+
+  let mut ticks = simulator.ticks.subscribe();
+  loop {
+    tokio::select! {
+      message = socket.next() => match message {
+        Some(Ok(Message::Text(content))) => {
+          let request = match serde_json::from_str::<StreamRequest>(content.as_str()) {
+            Ok(request) => request,
+            Err(_) => {
+              let error = json!({"error": {"code": 2, "msg": "Invalid request"}});
+              socket.send(Message::text(error.to_string())).await?;
+              continue
+            }
+          };
+          let result = match request.method.as_str() {
+            "SUBSCRIBE" => {
+              streams.extend(request.params);
+              serde_json::Value::Null
+            }
+            "UNSUBSCRIBE" => {
+              request.params.iter().for_each(|name| { streams.remove(name); });
+              serde_json::Value::Null
+            }
+            "LIST_SUBSCRIPTIONS" => json!(streams.iter().collect::<Vec<_>>()),
+            _ => serde_json::Value::Null,
+          };
+          socket.send(Message::text(json!({"result": result, "id": request.id}).to_string())).await?;
+        }
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+        _ => (),
+      },
+      tick = ticks.recv() => match tick {
+        Ok((symbol, diff)) => {
+          for frame in frames(&simulator, &streams, &symbol, diff).await {
+            socket.send(Message::text(frame)).await?;
+          }
+        }
+        Err(RecvError::Lagged(_)) => (),
+        Err(RecvError::Closed) => return Ok(()),
+      },
+    }
+  }
+}
+
+async fn frames(simulator: &Simulator, streams: &HashSet<String>, symbol: &str, mut diff: serde_json::Value) -> Vec<String> {
+  let prefix = format!("{}@", symbol.to_lowercase());
+  let timestamp = Utc::now().timestamp_millis();
+  let engine = simulator.engine.lock().await;
+
+  let mut frames = Vec::new();
+  for stream in streams.iter() {
+    let name = match stream.strip_prefix(&prefix) {
+      Some(name) => name,
+      None => continue,
+    };
+    let data = if name == "miniTicker" {
+      let kline = match engine.klines(symbol, Interval::Day1, 0, 1).pop() {
+        Some(kline) => kline,
+        None => continue,
+      };
+      json!({
+        "e": "24hrMiniTicker",
+        "E": timestamp,
+        "s": symbol,
+        "c": kline.close.to_string(),
+        "o": kline.open.to_string(),
+        "h": kline.high.to_string(),
+        "l": kline.low.to_string(),
+        "v": kline.volume.to_string(),
+        "q": kline.quota.to_string(),
+      })
+    } else if name.starts_with("depth") {
+      diff["e"] = json!("depthUpdate");
+      diff["E"] = json!(timestamp);
+      diff["s"] = json!(symbol);
+      if !engine.is_futures() {
+        diff.as_object_mut().unwrap().remove("pu");
+      }
+      diff.clone()
+    } else if let Some(interval) = name.strip_prefix("kline_") {
+      let interval = match Interval::parse(interval) {
+        Ok(interval) => interval,
+        Err(_) => continue,
+      };
+      let kline = match engine.klines(symbol, interval, 0, 1).pop() {
+        Some(kline) => kline,
+        None => continue,
+      };
+      json!({
+        "e": "kline",
+        "E": timestamp,
+        "s": symbol,
+        "k": {
+          "t": kline.timestamp,
+          "T": interval.next(kline.timestamp) - 1,
+          "s": symbol,
+          "i": interval.as_str(),
+          "o": kline.open.to_string(),
+          "c": kline.close.to_string(),
+          "h": kline.high.to_string(),
+          "l": kline.low.to_string(),
+          "v": kline.volume.to_string(),
+          "q": kline.quota.to_string(),
+          "x": false,
+        },
+      })
+    } else {
+      continue
+    };
+    frames.push(json!({"stream": stream, "data": data}).to_string());
+  }
+  frames
+}